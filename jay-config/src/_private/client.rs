@@ -8,15 +8,19 @@ use {
             logging, Config, ConfigEntry, ConfigEntryGen, VERSION,
         },
         exec::Command,
-        input::{acceleration::AccelProfile, capability::Capability, InputDevice, Seat},
+        input::{
+            acceleration::AccelProfile, capability::Capability, InputDevice, Seat,
+            ShortcutsInhibitRequest,
+        },
         keyboard::Keymap,
         logging::LogLevel,
         theme::{colors::Colorable, sized::Resizable, Color},
         timer::Timer,
         video::{
             connector_type::{ConnectorType, CON_UNKNOWN},
-            Connector, DrmDevice, Mode,
+            Connector, DrmDevice, Mode, VrrMode,
         },
+        window::Window,
         Axis, Direction, ModifiedKeySym, PciId, Workspace,
     },
     std::{
@@ -36,6 +40,7 @@ pub(crate) struct Client {
     srv_unref: unsafe extern "C" fn(data: *const u8),
     srv_handler: unsafe extern "C" fn(data: *const u8, msg: *const u8, size: usize),
     key_handlers: RefCell<HashMap<(Seat, ModifiedKeySym), Rc<dyn Fn()>>>,
+    swipe_handlers: RefCell<HashMap<(Seat, u32, Direction), Rc<dyn Fn()>>>,
     timer_handlers: RefCell<HashMap<Timer, Rc<dyn Fn()>>>,
     response: RefCell<Vec<Response>>,
     on_new_seat: RefCell<Option<Rc<dyn Fn(Seat)>>>,
@@ -47,6 +52,15 @@ pub(crate) struct Client {
     on_new_drm_device: RefCell<Option<Rc<dyn Fn(DrmDevice)>>>,
     on_del_drm_device: RefCell<Option<Rc<dyn Fn(DrmDevice)>>>,
     on_idle: RefCell<Option<Rc<dyn Fn()>>>,
+    on_shortcuts_inhibit_request: RefCell<Option<Rc<dyn Fn(&ShortcutsInhibitRequest) -> bool>>>,
+    on_new_window: RefCell<Option<Rc<dyn Fn(Window)>>>,
+    on_activation_request: RefCell<Option<Rc<dyn Fn(Window) -> bool>>>,
+    on_window_focused: RefCell<Option<Rc<dyn Fn(Seat, Option<Window>)>>>,
+    on_window_title_changed: RefCell<Option<Rc<dyn Fn(Window)>>>,
+    on_window_fullscreen_changed: RefCell<Option<Rc<dyn Fn(Window, bool)>>>,
+    on_workspace_created: RefCell<Option<Rc<dyn Fn(Workspace, Connector)>>>,
+    on_workspace_destroyed: RefCell<Option<Rc<dyn Fn(Workspace, Connector)>>>,
+    on_workspace_shown: RefCell<Option<Rc<dyn Fn(Workspace, Connector)>>>,
     bufs: RefCell<Vec<Vec<u8>>>,
     reload: Cell<bool>,
 }
@@ -124,6 +138,7 @@ pub unsafe extern "C" fn init(
         srv_unref,
         srv_handler,
         key_handlers: Default::default(),
+        swipe_handlers: Default::default(),
         timer_handlers: Default::default(),
         response: Default::default(),
         on_new_seat: Default::default(),
@@ -135,6 +150,15 @@ pub unsafe extern "C" fn init(
         on_new_drm_device: Default::default(),
         on_del_drm_device: Default::default(),
         on_idle: Default::default(),
+        on_shortcuts_inhibit_request: Default::default(),
+        on_new_window: Default::default(),
+        on_activation_request: Default::default(),
+        on_window_focused: Default::default(),
+        on_window_title_changed: Default::default(),
+        on_window_fullscreen_changed: Default::default(),
+        on_workspace_created: Default::default(),
+        on_workspace_destroyed: Default::default(),
+        on_workspace_shown: Default::default(),
         bufs: Default::default(),
         reload: Cell::new(false),
     });
@@ -231,6 +255,21 @@ impl Client {
         }
     }
 
+    pub fn unbind_swipe(&self, seat: Seat, fingers: u32, direction: Direction) {
+        let deregister = self
+            .swipe_handlers
+            .borrow_mut()
+            .remove(&(seat, fingers, direction))
+            .is_some();
+        if deregister {
+            self.send(&ClientMessage::RemoveSwipeBinding {
+                seat,
+                fingers,
+                direction,
+            })
+        }
+    }
+
     fn with_response<F: FnOnce()>(&self, f: F) -> Response {
         f();
         self.response.borrow_mut().pop().unwrap_or(Response::None)
@@ -297,6 +336,193 @@ impl Client {
         self.send(&ClientMessage::SetDefaultWorkspaceCapture { capture });
     }
 
+    pub fn set_activation_switches_workspace(&self, switch: bool) {
+        self.send(&ClientMessage::SetActivationSwitchesWorkspace { switch });
+    }
+
+    pub fn set_shortcuts_inhibit_escape(&self, seat: Seat, escape: Option<ModifiedKeySym>) {
+        self.send(&ClientMessage::SeatSetShortcutsInhibitEscape { seat, escape });
+    }
+
+    pub fn on_shortcuts_inhibit_request<F: Fn(&ShortcutsInhibitRequest) -> bool + 'static>(
+        &self,
+        f: F,
+    ) {
+        *self.on_shortcuts_inhibit_request.borrow_mut() = Some(Rc::new(f));
+        self.send(&ClientMessage::SetShortcutsInhibitHook { enabled: true });
+    }
+
+    pub fn on_new_window<F: Fn(Window) + 'static>(&self, f: F) {
+        *self.on_new_window.borrow_mut() = Some(Rc::new(f));
+    }
+
+    pub fn on_activation_request<F: Fn(Window) -> bool + 'static>(&self, f: F) {
+        *self.on_activation_request.borrow_mut() = Some(Rc::new(f));
+        self.send(&ClientMessage::SetActivationHook { enabled: true });
+    }
+
+    pub fn on_window_focused<F: Fn(Seat, Option<Window>) + 'static>(&self, f: F) {
+        *self.on_window_focused.borrow_mut() = Some(Rc::new(f));
+    }
+
+    pub fn on_window_title_changed<F: Fn(Window) + 'static>(&self, f: F) {
+        *self.on_window_title_changed.borrow_mut() = Some(Rc::new(f));
+    }
+
+    pub fn on_window_fullscreen_changed<F: Fn(Window, bool) + 'static>(&self, f: F) {
+        *self.on_window_fullscreen_changed.borrow_mut() = Some(Rc::new(f));
+    }
+
+    pub fn on_workspace_created<F: Fn(Workspace, Connector) + 'static>(&self, f: F) {
+        *self.on_workspace_created.borrow_mut() = Some(Rc::new(f));
+    }
+
+    pub fn on_workspace_destroyed<F: Fn(Workspace, Connector) + 'static>(&self, f: F) {
+        *self.on_workspace_destroyed.borrow_mut() = Some(Rc::new(f));
+    }
+
+    pub fn on_workspace_shown<F: Fn(Workspace, Connector) + 'static>(&self, f: F) {
+        *self.on_workspace_shown.borrow_mut() = Some(Rc::new(f));
+    }
+
+    pub fn window_title(&self, window: Window) -> String {
+        let res = self.send_with_response(&ClientMessage::GetWindowTitle { window });
+        get_response!(res, String::new(), GetWindowTitle { title });
+        title
+    }
+
+    pub fn window_app_id(&self, window: Window) -> String {
+        let res = self.send_with_response(&ClientMessage::GetWindowAppId { window });
+        get_response!(res, String::new(), GetWindowAppId { app_id });
+        app_id
+    }
+
+    pub fn window_x_class(&self, window: Window) -> String {
+        let res = self.send_with_response(&ClientMessage::GetWindowXClass { window });
+        get_response!(res, String::new(), GetWindowXClass { class });
+        class
+    }
+
+    pub fn window_x_instance(&self, window: Window) -> String {
+        let res = self.send_with_response(&ClientMessage::GetWindowXInstance { window });
+        get_response!(res, String::new(), GetWindowXInstance { instance });
+        instance
+    }
+
+    pub fn window_x_role(&self, window: Window) -> String {
+        let res = self.send_with_response(&ClientMessage::GetWindowXRole { window });
+        get_response!(res, String::new(), GetWindowXRole { role });
+        role
+    }
+
+    pub fn window_pid(&self, window: Window) -> Option<i32> {
+        let res = self.send_with_response(&ClientMessage::GetWindowPid { window });
+        get_response!(res, None, GetWindowPid { pid });
+        pid
+    }
+
+    pub fn window_is_xwayland(&self, window: Window) -> bool {
+        let res = self.send_with_response(&ClientMessage::GetWindowIsXwayland { window });
+        get_response!(res, false, GetWindowIsXwayland { xwayland });
+        xwayland
+    }
+
+    pub fn window_exists(&self, window: Window) -> bool {
+        let res = self.send_with_response(&ClientMessage::WindowExists { window });
+        get_response!(res, false, WindowExists { exists });
+        exists
+    }
+
+    pub fn window_workspace(&self, window: Window) -> Workspace {
+        let res = self.send_with_response(&ClientMessage::GetWindowWorkspace { window });
+        get_response!(res, Workspace(0), GetWindowWorkspace { workspace });
+        workspace
+    }
+
+    pub fn window_floating(&self, window: Window) -> bool {
+        let res = self.send_with_response(&ClientMessage::GetWindowFloating { window });
+        get_response!(res, false, GetFloating { floating });
+        floating
+    }
+
+    pub fn window_fullscreen(&self, window: Window) -> bool {
+        let res = self.send_with_response(&ClientMessage::GetWindowFullscreen { window });
+        get_response!(res, false, GetFullscreen { fullscreen });
+        fullscreen
+    }
+
+    pub fn hide_window(&self, window: Window) {
+        self.send(&ClientMessage::HideWindow { window });
+    }
+
+    pub fn window_hidden(&self, window: Window) -> bool {
+        let res = self.send_with_response(&ClientMessage::GetWindowHidden { window });
+        get_response!(res, false, GetWindowHidden { hidden });
+        hidden
+    }
+
+    pub fn toggle_scratchpad(&self, seat: Seat, name: &str) {
+        self.send(&ClientMessage::ToggleScratchpad { seat, name });
+    }
+
+    pub fn focus_window(&self, window: Window, seat: Seat) {
+        self.send(&ClientMessage::FocusWindow { window, seat });
+    }
+
+    pub fn close_window(&self, window: Window) {
+        self.send(&ClientMessage::CloseWindow { window });
+    }
+
+    pub fn set_workspace_output(&self, workspace: Workspace, connector: Connector) {
+        self.send(&ClientMessage::SetWorkspaceOutput {
+            workspace,
+            connector,
+        });
+    }
+
+    pub fn move_workspace_to_output(&self, workspace: Workspace, direction: Direction) {
+        self.send(&ClientMessage::MoveWorkspaceToOutput {
+            workspace,
+            direction,
+        });
+    }
+
+    pub fn workspace_windows(&self, workspace: Workspace) -> Vec<Window> {
+        let res = self.send_with_response(&ClientMessage::GetWorkspaceWindows { workspace });
+        get_response!(res, vec![], GetWindows { windows });
+        windows
+    }
+
+    pub fn get_focused_window(&self, seat: Seat) -> Option<Window> {
+        let res = self.send_with_response(&ClientMessage::GetFocusedWindow { seat });
+        get_response!(res, None, GetFocusedWindow { window });
+        window
+    }
+
+    pub fn set_window_workspace(&self, window: Window, workspace: Workspace) {
+        self.send(&ClientMessage::SetWindowWorkspace { window, workspace });
+    }
+
+    pub fn set_window_floating(&self, window: Window, floating: bool) {
+        self.send(&ClientMessage::SetWindowFloating { window, floating });
+    }
+
+    pub fn set_window_float_size(&self, window: Window, width: i32, height: i32) {
+        self.send(&ClientMessage::SetWindowFloatSize {
+            window,
+            width,
+            height,
+        });
+    }
+
+    pub fn set_window_fullscreen(&self, window: Window, fullscreen: bool) {
+        self.send(&ClientMessage::SetWindowFullscreen { window, fullscreen });
+    }
+
+    pub fn set_window_sticky(&self, window: Window, sticky: bool) {
+        self.send(&ClientMessage::SetWindowSticky { window, sticky });
+    }
+
     pub fn set_workspace_capture(&self, workspace: Workspace, capture: bool) {
         self.send(&ClientMessage::SetWorkspaceCapture { workspace, capture });
     }
@@ -546,6 +772,35 @@ impl Client {
         }
     }
 
+    pub fn connector_modes(&self, connector: Connector) -> Vec<Mode> {
+        let res = self.send_with_response(&ClientMessage::ConnectorModes { connector });
+        get_response!(res, vec![], ConnectorModes { modes });
+        modes
+    }
+
+    pub fn connector_set_mode(&self, connector: Connector, mode: Mode) {
+        self.send(&ClientMessage::ConnectorSetMode { connector, mode });
+    }
+
+    pub fn connector_set_vrr_mode(&self, connector: Connector, mode: VrrMode) {
+        self.send(&ClientMessage::ConnectorSetVrrMode { connector, mode });
+    }
+
+    pub fn connector_set_gamma(
+        &self,
+        connector: Connector,
+        red: &[u16],
+        green: &[u16],
+        blue: &[u16],
+    ) {
+        self.send(&ClientMessage::ConnectorSetGamma {
+            connector,
+            red: red.to_vec(),
+            green: green.to_vec(),
+            blue: blue.to_vec(),
+        });
+    }
+
     pub fn connector_size(&self, connector: Connector) -> (i32, i32) {
         let res = self.send_with_response(&ClientMessage::ConnectorSize { connector });
         get_response!(res, (0, 0), ConnectorSize { width, height });
@@ -625,6 +880,10 @@ impl Client {
         self.send(&ClientMessage::SetDragLockEnabled { device, enabled })
     }
 
+    pub fn set_input_device_connector(&self, device: InputDevice, connector: Connector) {
+        self.send(&ClientMessage::SetInputDeviceConnector { device, connector })
+    }
+
     pub fn device_name(&self, device: InputDevice) -> String {
         let res = self.send_with_response(&ClientMessage::GetDeviceName { device });
         get_response!(res, String::new(), GetDeviceName { name });
@@ -682,6 +941,36 @@ impl Client {
         }
     }
 
+    pub fn bind_swipe<F: Fn() + 'static>(
+        &self,
+        seat: Seat,
+        fingers: u32,
+        direction: Direction,
+        f: F,
+    ) {
+        let register = {
+            let mut sh = self.swipe_handlers.borrow_mut();
+            let f = Rc::new(f);
+            match sh.entry((seat, fingers, direction)) {
+                Entry::Occupied(mut o) => {
+                    *o.get_mut() = f;
+                    false
+                }
+                Entry::Vacant(v) => {
+                    v.insert(f);
+                    true
+                }
+            }
+        };
+        if register {
+            self.send(&ClientMessage::AddSwipeBinding {
+                seat,
+                fingers,
+                direction,
+            });
+        }
+    }
+
     pub fn log(&self, level: LogLevel, msg: &str, file: Option<&str>, line: Option<u32>) {
         self.send(&ClientMessage::Log {
             level,
@@ -775,6 +1064,85 @@ impl Client {
                     handler();
                 }
             }
+            ServerMessage::InvokeSwipe {
+                seat,
+                fingers,
+                direction,
+            } => {
+                let handler = self
+                    .swipe_handlers
+                    .borrow_mut()
+                    .get(&(seat, fingers, direction))
+                    .cloned();
+                if let Some(handler) = handler {
+                    handler();
+                }
+            }
+            ServerMessage::ShortcutsInhibitRequest { request } => {
+                let handler = self.on_shortcuts_inhibit_request.borrow().clone();
+                if let Some(handler) = handler {
+                    let allow = handler(&request);
+                    self.send(&ClientMessage::ShortcutsInhibitDecision { allow });
+                }
+            }
+            ServerMessage::ActivationRequest { window } => {
+                let handler = self.on_activation_request.borrow().clone();
+                if let Some(handler) = handler {
+                    let allow = handler(window);
+                    self.send(&ClientMessage::ActivationDecision { allow });
+                }
+            }
+            ServerMessage::NewWindow { window } => {
+                let handler = self.on_new_window.borrow().clone();
+                if let Some(handler) = handler {
+                    handler(window);
+                }
+            }
+            ServerMessage::WindowFocused { seat, window } => {
+                let handler = self.on_window_focused.borrow().clone();
+                if let Some(handler) = handler {
+                    handler(seat, window);
+                }
+            }
+            ServerMessage::WindowTitleChanged { window } => {
+                let handler = self.on_window_title_changed.borrow().clone();
+                if let Some(handler) = handler {
+                    handler(window);
+                }
+            }
+            ServerMessage::WindowFullscreenChanged { window, fullscreen } => {
+                let handler = self.on_window_fullscreen_changed.borrow().clone();
+                if let Some(handler) = handler {
+                    handler(window, fullscreen);
+                }
+            }
+            ServerMessage::WorkspaceCreated {
+                workspace,
+                connector,
+            } => {
+                let handler = self.on_workspace_created.borrow().clone();
+                if let Some(handler) = handler {
+                    handler(workspace, connector);
+                }
+            }
+            ServerMessage::WorkspaceDestroyed {
+                workspace,
+                connector,
+            } => {
+                let handler = self.on_workspace_destroyed.borrow().clone();
+                if let Some(handler) = handler {
+                    handler(workspace, connector);
+                }
+            }
+            ServerMessage::WorkspaceShown {
+                workspace,
+                connector,
+            } => {
+                let handler = self.on_workspace_shown.borrow().clone();
+                if let Some(handler) = handler {
+                    handler(workspace, connector);
+                }
+            }
         }
     }
 
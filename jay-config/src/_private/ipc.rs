@@ -1,12 +1,16 @@
 use {
     crate::{
-        input::{acceleration::AccelProfile, capability::Capability, InputDevice, Seat},
+        input::{
+            acceleration::AccelProfile, capability::Capability, InputDevice, Seat,
+            ShortcutsInhibitRequest,
+        },
         keyboard::{mods::Modifiers, syms::KeySym, Keymap},
         logging::LogLevel,
         theme::{colors::Colorable, sized::Resizable, Color},
         timer::Timer,
-        video::{connector_type::ConnectorType, Connector, DrmDevice},
-        Axis, Direction, PciId, Workspace,
+        video::{connector_type::ConnectorType, Connector, DrmDevice, Mode, VrrMode},
+        window::Window,
+        Axis, Direction, ModifiedKeySym, PciId, Workspace,
     },
     bincode::{BorrowDecode, Decode, Encode},
     std::time::Duration,
@@ -56,6 +60,43 @@ pub enum ServerMessage {
     },
     Idle,
     DevicesEnumerated,
+    InvokeSwipe {
+        seat: Seat,
+        fingers: u32,
+        direction: Direction,
+    },
+    ShortcutsInhibitRequest {
+        request: ShortcutsInhibitRequest,
+    },
+    NewWindow {
+        window: Window,
+    },
+    WindowFocused {
+        seat: Seat,
+        window: Option<Window>,
+    },
+    WindowTitleChanged {
+        window: Window,
+    },
+    WindowFullscreenChanged {
+        window: Window,
+        fullscreen: bool,
+    },
+    WorkspaceCreated {
+        workspace: Workspace,
+        connector: Connector,
+    },
+    WorkspaceDestroyed {
+        workspace: Workspace,
+        connector: Connector,
+    },
+    WorkspaceShown {
+        workspace: Workspace,
+        connector: Connector,
+    },
+    ActivationRequest {
+        window: Window,
+    },
 }
 
 #[derive(Encode, BorrowDecode, Debug)]
@@ -330,6 +371,141 @@ pub enum ClientMessage<'a> {
     GetWorkspaceCapture {
         workspace: Workspace,
     },
+    SetInputDeviceConnector {
+        device: InputDevice,
+        connector: Connector,
+    },
+    AddSwipeBinding {
+        seat: Seat,
+        fingers: u32,
+        direction: Direction,
+    },
+    RemoveSwipeBinding {
+        seat: Seat,
+        fingers: u32,
+        direction: Direction,
+    },
+    ConnectorModes {
+        connector: Connector,
+    },
+    ConnectorSetMode {
+        connector: Connector,
+        mode: Mode,
+    },
+    ConnectorSetVrrMode {
+        connector: Connector,
+        mode: VrrMode,
+    },
+    SetActivationSwitchesWorkspace {
+        switch: bool,
+    },
+    ConnectorSetGamma {
+        connector: Connector,
+        red: Vec<u16>,
+        green: Vec<u16>,
+        blue: Vec<u16>,
+    },
+    SeatSetShortcutsInhibitEscape {
+        seat: Seat,
+        escape: Option<ModifiedKeySym>,
+    },
+    SetShortcutsInhibitHook {
+        enabled: bool,
+    },
+    ShortcutsInhibitDecision {
+        allow: bool,
+    },
+    GetWindowTitle {
+        window: Window,
+    },
+    GetWindowAppId {
+        window: Window,
+    },
+    GetWindowXClass {
+        window: Window,
+    },
+    GetWindowXInstance {
+        window: Window,
+    },
+    GetWindowXRole {
+        window: Window,
+    },
+    GetWindowPid {
+        window: Window,
+    },
+    GetWindowIsXwayland {
+        window: Window,
+    },
+    SetWindowWorkspace {
+        window: Window,
+        workspace: Workspace,
+    },
+    SetWindowFloating {
+        window: Window,
+        floating: bool,
+    },
+    SetWindowFloatSize {
+        window: Window,
+        width: i32,
+        height: i32,
+    },
+    SetWindowFullscreen {
+        window: Window,
+        fullscreen: bool,
+    },
+    SetWindowSticky {
+        window: Window,
+        sticky: bool,
+    },
+    WindowExists {
+        window: Window,
+    },
+    GetWindowWorkspace {
+        window: Window,
+    },
+    GetWindowFloating {
+        window: Window,
+    },
+    GetWindowFullscreen {
+        window: Window,
+    },
+    FocusWindow {
+        window: Window,
+        seat: Seat,
+    },
+    CloseWindow {
+        window: Window,
+    },
+    GetWorkspaceWindows {
+        workspace: Workspace,
+    },
+    GetFocusedWindow {
+        seat: Seat,
+    },
+    SetWorkspaceOutput {
+        workspace: Workspace,
+        connector: Connector,
+    },
+    MoveWorkspaceToOutput {
+        workspace: Workspace,
+        direction: Direction,
+    },
+    HideWindow {
+        window: Window,
+    },
+    GetWindowHidden {
+        window: Window,
+    },
+    ToggleScratchpad {
+        seat: Seat,
+        name: &'a str,
+    },
+    SetActivationHook {
+        enabled: bool,
+    },
+    ActivationDecision {
+        allow: bool,
+    },
 }
 
 #[derive(Encode, Decode, Debug)]
@@ -432,6 +608,45 @@ pub enum Response {
     GetWorkspaceCapture {
         capture: bool,
     },
+    ConnectorModes {
+        modes: Vec<Mode>,
+    },
+    GetWindowTitle {
+        title: String,
+    },
+    GetWindowAppId {
+        app_id: String,
+    },
+    GetWindowXClass {
+        class: String,
+    },
+    GetWindowXInstance {
+        instance: String,
+    },
+    GetWindowXRole {
+        role: String,
+    },
+    GetWindowPid {
+        pid: Option<i32>,
+    },
+    GetWindowIsXwayland {
+        xwayland: bool,
+    },
+    WindowExists {
+        exists: bool,
+    },
+    GetWindowWorkspace {
+        workspace: Workspace,
+    },
+    GetWindows {
+        windows: Vec<Window>,
+    },
+    GetFocusedWindow {
+        window: Option<Window>,
+    },
+    GetWindowHidden {
+        hidden: bool,
+    },
 }
 
 #[derive(Encode, Decode, Debug)]
@@ -7,6 +7,7 @@ use {
     crate::{
         input::{acceleration::AccelProfile, capability::Capability},
         keyboard::Keymap,
+        video::Connector,
         Axis, Direction, ModifiedKeySym, Workspace,
     },
     bincode::{Decode, Encode},
@@ -104,6 +105,17 @@ impl InputDevice {
     pub fn set_drag_lock_enabled(self, enabled: bool) {
         get!().set_input_drag_lock_enabled(self, enabled);
     }
+
+    /// Maps this input device to a connector.
+    ///
+    /// The connector should be connected.
+    ///
+    /// This should be used for touch screens and graphics tablets. Absolute input from
+    /// such devices is mapped to the area of the connector. By default, such input is
+    /// mapped to the output that currently contains the cursor of the seat.
+    pub fn set_connector(self, connector: Connector) {
+        get!().set_input_device_connector(self, connector);
+    }
 }
 
 /// A seat.
@@ -162,6 +174,38 @@ impl Seat {
         get!().unbind(self, mod_sym)
     }
 
+    /// Sets the key combination that restores compositor shortcuts while an application
+    /// inhibits them.
+    ///
+    /// Applications such as virtual machines and remote-desktop clients can ask the
+    /// compositor to forward all keys to them while they have the keyboard focus. While
+    /// such a request is active, only this key combination is handled by the compositor.
+    /// Pressing it re-enables all shortcuts until the application's window is focused again.
+    ///
+    /// By default, there is no escape combination.
+    pub fn set_shortcuts_inhibit_escape<T: Into<ModifiedKeySym>>(self, mod_sym: T) {
+        get!().set_shortcuts_inhibit_escape(self, Some(mod_sym.into()))
+    }
+
+    /// Removes the escape combination set with `set_shortcuts_inhibit_escape`.
+    pub fn unset_shortcuts_inhibit_escape(self) {
+        get!().set_shortcuts_inhibit_escape(self, None)
+    }
+
+    /// Creates a compositor-wide touchpad swipe binding.
+    ///
+    /// The closure is invoked when the user completes a swipe with the specified number
+    /// of fingers in the specified direction. While a binding exists for a finger count,
+    /// swipes with that number of fingers are no longer forwarded to applications.
+    pub fn bind_swipe<F: Fn() + 'static>(self, fingers: u32, direction: Direction, f: F) {
+        get!().bind_swipe(self, fingers, direction, f)
+    }
+
+    /// Unbinds a swipe binding.
+    pub fn unbind_swipe(self, fingers: u32, direction: Direction) {
+        get!().unbind_swipe(self, fingers, direction)
+    }
+
     /// Moves the keyboard focus of the seat in the specified direction.
     pub fn focus(self, direction: Direction) {
         get!().focus(self, direction)
@@ -240,6 +284,15 @@ impl Seat {
         get!().close(self);
     }
 
+    /// Toggles the scratchpad windows with the given app id.
+    ///
+    /// If such a window is visible on the current workspace of the seat, it is hidden
+    /// again. Otherwise a hidden window with this app id is shown floating and centered
+    /// on the current output of the seat.
+    pub fn toggle_scratchpad(self, name: &str) {
+        get!().toggle_scratchpad(self, name);
+    }
+
     /// Returns whether the currently focused window is floating.
     pub fn get_floating(self) -> bool {
         get!().get_floating(self)
@@ -322,3 +375,24 @@ pub fn on_new_seat<F: Fn(Seat) + 'static>(f: F) {
 pub fn on_new_input_device<F: Fn(InputDevice) + 'static>(f: F) {
     get!().on_new_input_device(f)
 }
+
+/// A request by an application to inhibit the shortcuts of a seat.
+#[derive(Encode, Decode, Clone, Debug)]
+pub struct ShortcutsInhibitRequest {
+    /// The seat whose shortcuts would be inhibited.
+    pub seat: Seat,
+    /// The app-id of the window that made the request or an empty string.
+    pub app_id: String,
+    /// The process id of the application.
+    pub pid: i32,
+    /// The process name of the application.
+    pub comm: String,
+}
+
+/// Sets a closure that decides whether an application may inhibit shortcuts.
+///
+/// The closure is invoked once for each request and should return `true` to allow it. If
+/// no closure is set, all requests are allowed.
+pub fn on_shortcuts_inhibit_request<F: Fn(&ShortcutsInhibitRequest) -> bool + 'static>(f: F) {
+    get!().on_shortcuts_inhibit_request(f)
+}
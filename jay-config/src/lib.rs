@@ -41,7 +41,7 @@
 )]
 
 use {
-    crate::keyboard::ModifiedKeySym,
+    crate::{keyboard::ModifiedKeySym, video::Connector, window::Window},
     bincode::{Decode, Encode},
     std::fmt::{Debug, Display, Formatter},
 };
@@ -59,9 +59,10 @@ pub mod status;
 pub mod theme;
 pub mod timer;
 pub mod video;
+pub mod window;
 
 /// A planar direction.
-#[derive(Encode, Decode, Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Encode, Decode, Copy, Clone, Debug, Hash, Eq, PartialEq)]
 pub enum Direction {
     Left,
     Down,
@@ -128,6 +129,19 @@ pub fn toggle_default_workspace_capture() {
     get.set_default_workspace_capture(!get.get_default_workspace_capture());
 }
 
+/// Sets whether windows that request activation switch to their workspace.
+///
+/// Applications request activation when they want to be focused, for example when they
+/// were started from another application. If this is `false`, windows on invisible
+/// workspaces are only marked as urgent. Requests that are not backed by recent user
+/// input always mark the window as urgent. Use [`window::on_activation_request`] to
+/// decide per window.
+///
+/// The default is `true`.
+pub fn set_activation_switches_workspace(switch: bool) {
+    get!().set_activation_switches_workspace(switch)
+}
+
 /// A workspace.
 #[derive(Encode, Decode, Copy, Clone, Debug, Hash, Eq, PartialEq)]
 pub struct Workspace(pub u64);
@@ -155,6 +169,27 @@ impl Workspace {
         let get = get!();
         get.set_workspace_capture(self, !get.get_workspace_capture(self));
     }
+
+    /// Returns the windows on this workspace.
+    pub fn windows(self) -> Vec<Window> {
+        get!(vec![]).workspace_windows(self)
+    }
+
+    /// Moves this workspace to the output of the connector.
+    ///
+    /// The output becomes the preferred output of the workspace. If the output is
+    /// disconnected, the workspace moves to another output and returns once the output
+    /// is connected again. The preference is kept even if the workspace is destroyed.
+    pub fn set_output(self, connector: Connector) {
+        get!().set_workspace_output(self, connector)
+    }
+
+    /// Moves this workspace to the adjacent output in the given direction.
+    ///
+    /// The output becomes the preferred output of the workspace.
+    pub fn move_to_output(self, direction: Direction) {
+        get!().move_workspace_to_output(self, direction)
+    }
 }
 
 /// Returns the workspace with the given name.
@@ -187,6 +222,23 @@ pub fn on_idle<F: Fn() + 'static>(f: F) {
     get!().on_idle(f)
 }
 
+/// Sets the callback to be called when a workspace is created on an output.
+pub fn on_workspace_created<F: Fn(Workspace, Connector) + 'static>(f: F) {
+    get!().on_workspace_created(f)
+}
+
+/// Sets the callback to be called when an empty workspace is destroyed.
+///
+/// Workspaces are destroyed when they become invisible while containing no windows.
+pub fn on_workspace_destroyed<F: Fn(Workspace, Connector) + 'static>(f: F) {
+    get!().on_workspace_destroyed(f)
+}
+
+/// Sets the callback to be called when a workspace is shown on an output.
+pub fn on_workspace_shown<F: Fn(Workspace, Connector) + 'static>(f: F) {
+    get!().on_workspace_shown(f)
+}
+
 /// Sets the callback to be called when all devices have been enumerated.
 ///
 /// This callback is only invoked once during the lifetime of the compositor. This is a
@@ -253,6 +253,14 @@ pub mod colors {
         ///
         /// Default: `#772831`.
         const 13 => CAPTURED_FOCUSED_TITLE_BACKGROUND_COLOR,
+        /// The title background color of an unfocused window that requested attention.
+        ///
+        /// Default: `#900000`.
+        const 14 => URGENT_TITLE_BACKGROUND_COLOR,
+        /// The title text color of an unfocused window that requested attention.
+        ///
+        /// Default: `#ffffff`.
+        const 15 => URGENT_TITLE_TEXT_COLOR,
     }
 }
 
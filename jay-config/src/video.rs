@@ -21,7 +21,7 @@ use {
 /// - width in pixels
 /// - height in pixels
 /// - refresh rate in mhz.
-#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[derive(Encode, Decode, Copy, Clone, Debug, Hash, Eq, PartialEq)]
 pub struct Mode {
     pub(crate) width: i32,
     pub(crate) height: i32,
@@ -29,6 +29,15 @@ pub struct Mode {
 }
 
 impl Mode {
+    #[doc(hidden)]
+    pub fn new(width: i32, height: i32, refresh_millihz: u32) -> Self {
+        Self {
+            width,
+            height,
+            refresh_millihz,
+        }
+    }
+
     /// Returns the width of the mode.
     pub fn width(&self) -> i32 {
         self.width
@@ -55,6 +64,20 @@ impl Mode {
     }
 }
 
+/// The variable refresh rate (VRR) mode of a connector.
+///
+/// VRR is only used if the connected monitor supports it.
+#[derive(Encode, Decode, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum VrrMode {
+    /// VRR is never enabled.
+    Never,
+    /// VRR is always enabled.
+    Always,
+    /// VRR is enabled while the visible workspace of the connector contains a fullscreen
+    /// window.
+    FullscreenOnly,
+}
+
 /// A connector that is potentially connected to an output device.
 ///
 /// A connector is the part that sticks out of your graphics card. A graphics card usually
@@ -112,6 +135,25 @@ impl Connector {
         get!(Mode::zeroed()).connector_mode(self)
     }
 
+    /// Returns the modes supported by the currently connected monitor.
+    pub fn modes(self) -> Vec<Mode> {
+        if !self.exists() {
+            return vec![];
+        }
+        get!(vec![]).connector_modes(self)
+    }
+
+    /// Sets the mode of the connector.
+    ///
+    /// The mode must be one of the modes returned by `modes()`.
+    pub fn set_mode(self, mode: Mode) {
+        if !self.exists() {
+            log::warn!("set_mode called on a connector that does not exist");
+            return;
+        }
+        get!().connector_set_mode(self, mode);
+    }
+
     /// Returns the logical width of the connector.
     ///
     /// The returned value will be different from `mode().width()` if the scale is not 1.
@@ -148,6 +190,55 @@ impl Connector {
         get!().connector_set_position(self, x, y);
     }
 
+    /// Sets the VRR mode of the connector.
+    ///
+    /// By default, VRR is never enabled.
+    pub fn set_vrr_mode(self, mode: VrrMode) {
+        if !self.exists() {
+            log::warn!("set_vrr_mode called on a connector that does not exist");
+            return;
+        }
+        get!().connector_set_vrr_mode(self, mode);
+    }
+
+    /// Sets the gamma ramps of the connector.
+    ///
+    /// Each ramp maps the intensity of its color channel to the intensity sent to the
+    /// display. The ramps must have the same length and are interpolated to the size
+    /// supported by the hardware. Empty ramps restore the identity mapping.
+    ///
+    /// Clients using the wlr-gamma-control protocol, e.g. gammastep, take precedence over
+    /// this setting while they are running.
+    pub fn set_gamma(self, red: &[u16], green: &[u16], blue: &[u16]) {
+        if !self.exists() {
+            log::warn!("set_gamma called on a connector that does not exist");
+            return;
+        }
+        get!().connector_set_gamma(self, red, green, blue);
+    }
+
+    /// Restores the identity gamma mapping of the connector.
+    pub fn reset_gamma(self) {
+        self.set_gamma(&[], &[], &[]);
+    }
+
+    /// Tints the connector as if it were lit by a light source of the given color
+    /// temperature in Kelvin.
+    ///
+    /// 6500K is neutral. Lower values produce warmer colors.
+    pub fn set_color_temperature(self, kelvin: u32) {
+        let [r, g, b] = color_temperature_factors(kelvin);
+        let ramp = |f: f64| -> Vec<u16> {
+            (0..GAMMA_RAMP_SIZE)
+                .map(|i| {
+                    let v = i as f64 / (GAMMA_RAMP_SIZE - 1) as f64 * f;
+                    (v * u16::MAX as f64).round() as u16
+                })
+                .collect()
+        };
+        self.set_gamma(&ramp(r), &ramp(g), &ramp(b));
+    }
+
     /// Enables or disables the connector.
     ///
     /// By default, all connectors are enabled.
@@ -160,6 +251,36 @@ impl Connector {
     }
 }
 
+const GAMMA_RAMP_SIZE: usize = 256;
+
+/// Returns the RGB factors of the white point of a black body with the given temperature,
+/// normalized to 6500K.
+fn color_temperature_factors(kelvin: u32) -> [f64; 3] {
+    fn white_point(kelvin: u32) -> [f64; 3] {
+        // Approximation by Tanner Helland of the blackbody color in sRGB.
+        let t = kelvin.clamp(1000, 40000) as f64 / 100.0;
+        let r = match t <= 66.0 {
+            true => 255.0,
+            false => 329.698727446 * (t - 60.0).powf(-0.1332047592),
+        };
+        let g = match t <= 66.0 {
+            true => 99.4708025861 * t.ln() - 161.1195681661,
+            false => 288.1221695283 * (t - 60.0).powf(-0.0755148492),
+        };
+        let b = if t >= 66.0 {
+            255.0
+        } else if t <= 19.0 {
+            0.0
+        } else {
+            138.5177312231 * (t - 10.0).ln() - 305.0447927307
+        };
+        [r, g, b].map(|v| v.clamp(0.0, 255.0) / 255.0)
+    }
+    let wp = white_point(kelvin);
+    let neutral = white_point(6500);
+    [0, 1, 2].map(|i| (wp[i] / neutral[i]).min(1.0))
+}
+
 /// Returns all available DRM devices.
 pub fn drm_devices() -> Vec<DrmDevice> {
     get!().drm_devices()
@@ -0,0 +1,199 @@
+//! Tools for inspecting and manipulating windows.
+
+use {
+    crate::{input::Seat, Workspace},
+    bincode::{Decode, Encode},
+};
+
+/// A toplevel window.
+///
+/// Windows are created by the compositor. Use `on_new_window` to be notified about them.
+#[derive(Encode, Decode, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct Window(pub u64);
+
+impl Window {
+    /// Returns whether the window still exists.
+    pub fn exists(self) -> bool {
+        get!(false).window_exists(self)
+    }
+
+    /// Returns the title of the window.
+    pub fn title(self) -> String {
+        get!(String::new()).window_title(self)
+    }
+
+    /// Returns the app id of the window.
+    ///
+    /// For X windows, this is the class of the window.
+    pub fn app_id(self) -> String {
+        get!(String::new()).window_app_id(self)
+    }
+
+    /// Returns the `WM_CLASS` class of the window.
+    ///
+    /// This is empty for Wayland windows.
+    pub fn x_class(self) -> String {
+        get!(String::new()).window_x_class(self)
+    }
+
+    /// Returns the `WM_CLASS` instance of the window.
+    ///
+    /// This is empty for Wayland windows.
+    pub fn x_instance(self) -> String {
+        get!(String::new()).window_x_instance(self)
+    }
+
+    /// Returns the `WM_WINDOW_ROLE` of the window.
+    ///
+    /// This is empty for Wayland windows.
+    pub fn x_role(self) -> String {
+        get!(String::new()).window_x_role(self)
+    }
+
+    /// Returns the PID of the process that owns the window, if known.
+    pub fn pid(self) -> Option<i32> {
+        get!(None).window_pid(self)
+    }
+
+    /// Returns whether the window is an X window.
+    pub fn is_xwayland(self) -> bool {
+        get!(false).window_is_xwayland(self)
+    }
+
+    /// Returns the workspace the window is on.
+    pub fn workspace(self) -> Workspace {
+        get!(Workspace(0)).window_workspace(self)
+    }
+
+    /// Moves the window to a workspace.
+    ///
+    /// If the workspace doesn't exist, it is created on the output of the window.
+    pub fn set_workspace(self, workspace: Workspace) {
+        get!().set_window_workspace(self, workspace)
+    }
+
+    /// Returns whether the window is floating.
+    pub fn floating(self) -> bool {
+        get!(false).window_floating(self)
+    }
+
+    /// Sets whether the window is floating.
+    pub fn set_floating(self, floating: bool) {
+        get!().set_window_floating(self, floating)
+    }
+
+    /// Toggles whether the window is floating.
+    pub fn toggle_floating(self) {
+        let get = get!();
+        get.set_window_floating(self, !get.window_floating(self));
+    }
+
+    /// Sets the size the window has while it is floating.
+    ///
+    /// If the window is currently floating, it is resized and centered on its output.
+    pub fn set_float_size(self, width: i32, height: i32) {
+        get!().set_window_float_size(self, width, height)
+    }
+
+    /// Returns whether the window is fullscreen.
+    pub fn fullscreen(self) -> bool {
+        get!(false).window_fullscreen(self)
+    }
+
+    /// Sets whether the window is fullscreen.
+    pub fn set_fullscreen(self, fullscreen: bool) {
+        get!().set_window_fullscreen(self, fullscreen)
+    }
+
+    /// Toggles whether the window is fullscreen.
+    pub fn toggle_fullscreen(self) {
+        let get = get!();
+        get.set_window_fullscreen(self, !get.window_fullscreen(self));
+    }
+
+    /// Sets whether the window is sticky.
+    ///
+    /// Sticky windows follow the visible workspace of their output. This only has an
+    /// effect while the window is floating.
+    pub fn set_sticky(self, sticky: bool) {
+        get!().set_window_sticky(self, sticky)
+    }
+
+    /// Gives the keyboard focus of the seat to the window.
+    ///
+    /// If the window is on a workspace that is not currently visible, the workspace is
+    /// shown first.
+    pub fn focus(self, seat: Seat) {
+        get!().focus_window(self, seat)
+    }
+
+    /// Hides the window by moving it to the scratchpad.
+    ///
+    /// Hidden windows are identified by their app id and can be shown again with
+    /// [`Seat::toggle_scratchpad`]. Windows that minimize themselves are hidden in the same
+    /// way.
+    pub fn hide(self) {
+        get!().hide_window(self)
+    }
+
+    /// Returns whether the window is hidden in the scratchpad.
+    pub fn hidden(self) -> bool {
+        get!(false).window_hidden(self)
+    }
+
+    /// Requests the window to be closed.
+    pub fn close(self) {
+        get!().close_window(self)
+    }
+}
+
+/// Returns the window that has the keyboard focus of the seat, if any.
+pub fn get_focused_window(seat: Seat) -> Option<Window> {
+    get!(None).get_focused_window(seat)
+}
+
+/// Sets a callback to be called when a new window is mapped.
+///
+/// The callback is invoked once per window, before the window is placed in the tree.
+/// Changes of the workspace, floating and fullscreen state of the window are applied by
+/// the initial placement. This can be used to implement window rules:
+///
+/// ```rust,ignore
+/// on_new_window(|w| {
+///     if w.app_id() == "pavucontrol" {
+///         w.set_floating(true);
+///         w.set_float_size(800, 600);
+///     }
+/// });
+/// ```
+pub fn on_new_window<F: Fn(Window) + 'static>(f: F) {
+    get!().on_new_window(f)
+}
+
+/// Sets a closure that decides whether a window may take the keyboard focus when it
+/// requests activation.
+///
+/// The closure is only invoked for requests that are backed by recent user input and
+/// should return `true` to allow the activation. Otherwise the window is only marked
+/// as urgent. If no closure is set, all such requests are allowed.
+pub fn on_activation_request<F: Fn(Window) -> bool + 'static>(f: F) {
+    get!().on_activation_request(f)
+}
+
+/// Sets a callback to be called when the keyboard focus of a seat moves to a different
+/// window.
+///
+/// The window is `None` if the seat no longer has a window focused.
+pub fn on_window_focused<F: Fn(Seat, Option<Window>) + 'static>(f: F) {
+    get!().on_window_focused(f)
+}
+
+/// Sets a callback to be called when the title of a window changes.
+pub fn on_window_title_changed<F: Fn(Window) + 'static>(f: F) {
+    get!().on_window_title_changed(f)
+}
+
+/// Sets a callback to be called when a window enters or leaves fullscreen.
+pub fn on_window_fullscreen_changed<F: Fn(Window, bool) + 'static>(f: F) {
+    get!().on_window_fullscreen_changed(f)
+}
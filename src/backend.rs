@@ -4,6 +4,7 @@ use {
         fixed::Fixed,
         ifs::wl_seat::wl_pointer::{CONTINUOUS, FINGER, HORIZONTAL_SCROLL, VERTICAL_SCROLL, WHEEL},
         render::Framebuffer,
+        utils::clonecell::UnsafeCellCloneSafe,
         video::drm::{ConnectorType, DrmError, DrmVersion},
     },
     std::{
@@ -12,12 +13,16 @@ use {
         fmt::{Debug, Display, Formatter},
         rc::Rc,
     },
+    thiserror::Error,
     uapi::c,
 };
 
 linear_ids!(ConnectorIds, ConnectorId);
 linear_ids!(InputDeviceIds, InputDeviceId);
 linear_ids!(DrmDeviceIds, DrmDeviceId);
+linear_ids!(TabletToolIds, TabletToolId);
+
+unsafe impl UnsafeCellCloneSafe for TabletToolId {}
 
 pub trait Backend {
     fn run(self: Rc<Self>) -> SpawnedFuture<Result<(), Box<dyn Error>>>;
@@ -42,6 +47,30 @@ pub trait Backend {
     fn supports_presentation_feedback(&self) -> bool {
         false
     }
+
+    /// Checks whether the connectors can be configured as described without changing
+    /// the current configuration. Connectors not listed keep their current state.
+    fn test_connector_configs(&self, configs: &[ConnectorConfig]) -> Result<(), ConnectorError> {
+        let _ = configs;
+        Ok(())
+    }
+}
+
+/// The desired state of a connector.
+#[derive(Copy, Clone, Debug)]
+pub struct ConnectorConfig {
+    pub connector: ConnectorId,
+    pub enabled: bool,
+    /// The mode of the connector or `None` to keep the current mode.
+    pub mode: Option<Mode>,
+}
+
+#[derive(Debug, Error)]
+pub enum ConnectorError {
+    #[error("The connector does not support mode {0:?}")]
+    UnsupportedMode(Mode),
+    #[error("The connector configuration was rejected")]
+    Rejected(#[source] Box<dyn Error>),
 }
 
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
@@ -51,7 +80,44 @@ pub struct Mode {
     pub refresh_rate_millihz: u32,
 }
 
-#[derive(Clone, Debug)]
+/// A gamma lookup table with the same number of entries for each channel.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GammaLut {
+    pub red: Vec<u16>,
+    pub green: Vec<u16>,
+    pub blue: Vec<u16>,
+}
+
+impl GammaLut {
+    /// Linearly interpolates the table to `size` entries per channel.
+    pub fn resample(&self, size: usize) -> GammaLut {
+        let resample = |ramp: &[u16]| {
+            if ramp.len() == size || ramp.is_empty() {
+                return ramp.to_vec();
+            }
+            let mut res = Vec::with_capacity(size);
+            for i in 0..size {
+                let pos = match size {
+                    1 => 0.0,
+                    _ => i as f64 * (ramp.len() - 1) as f64 / (size - 1) as f64,
+                };
+                let lo = pos.floor() as usize;
+                let hi = (lo + 1).min(ramp.len() - 1);
+                let frac = pos - lo as f64;
+                let v = ramp[lo] as f64 * (1.0 - frac) + ramp[hi] as f64 * frac;
+                res.push(v.round() as u16);
+            }
+            res
+        };
+        GammaLut {
+            red: resample(&self.red),
+            green: resample(&self.green),
+            blue: resample(&self.blue),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct MonitorInfo {
     pub modes: Vec<Mode>,
     pub manufacturer: String,
@@ -81,7 +147,15 @@ pub trait Connector {
     fn on_change(&self, cb: Rc<dyn Fn()>);
     fn damage(&self);
     fn drm_dev(&self) -> Option<DrmDeviceId>;
-    fn set_enabled(&self, enabled: bool);
+    fn set_enabled(&self, enabled: bool) -> Result<(), ConnectorError>;
+    fn set_mode(&self, mode: Mode) -> Result<(), ConnectorError>;
+    fn set_vrr_enabled(&self, enabled: bool);
+    fn set_tearing_allowed(&self, allowed: bool);
+    /// Returns the number of entries of the gamma lookup table or 0 if the gamma cannot
+    /// be changed.
+    fn gamma_size(&self) -> u32;
+    /// Sets the gamma lookup table. `None` restores the identity mapping.
+    fn set_gamma_lut(&self, lut: Option<Rc<GammaLut>>);
 }
 
 #[derive(Debug)]
@@ -119,6 +193,92 @@ pub trait InputDevice {
     fn set_tap_enabled(&self, enabled: bool);
     fn set_drag_enabled(&self, enabled: bool);
     fn set_drag_lock_enabled(&self, enabled: bool);
+
+    fn tablet_info(&self) -> Option<Box<TabletInit>> {
+        None
+    }
+
+    fn tablet_pad_info(&self) -> Option<Box<TabletPadInit>> {
+        None
+    }
+}
+
+#[derive(Debug)]
+pub struct TabletInit {
+    pub name: String,
+    pub vid: u32,
+    pub pid: u32,
+    pub path: String,
+    /// Tablets and pads with the same group belong to the same physical device.
+    pub group: usize,
+}
+
+#[derive(Debug)]
+pub struct TabletPadInit {
+    pub path: String,
+    pub group: usize,
+    pub buttons: u32,
+    pub groups: Vec<TabletPadGroupInit>,
+}
+
+#[derive(Debug)]
+pub struct TabletPadGroupInit {
+    pub buttons: Vec<u32>,
+    pub rings: Vec<u32>,
+    pub strips: Vec<u32>,
+    pub modes: u32,
+    pub mode: u32,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TabletToolType {
+    Pen,
+    Eraser,
+    Brush,
+    Pencil,
+    Airbrush,
+    Finger,
+    Mouse,
+    Lens,
+}
+
+#[derive(Debug, Copy, Clone, Default)]
+pub struct TabletToolCapabilities {
+    pub pressure: bool,
+    pub distance: bool,
+    pub tilt: bool,
+    pub rotation: bool,
+    pub slider: bool,
+    pub wheel: bool,
+}
+
+#[derive(Debug)]
+pub struct TabletToolInit {
+    pub id: TabletToolId,
+    pub type_: TabletToolType,
+    pub hardware_serial: u64,
+    pub hardware_id_wacom: u64,
+    pub capabilities: TabletToolCapabilities,
+}
+
+#[derive(Debug, Default)]
+pub struct TabletToolChanges {
+    pub proximity: Option<bool>,
+    pub down: Option<bool>,
+    /// The position normalized to the range `[0, 1]`.
+    pub pos: Option<(f64, f64)>,
+    /// The pressure in the range `[0, 1]`.
+    pub pressure: Option<f64>,
+    /// The distance in the range `[0, 1]`.
+    pub distance: Option<f64>,
+    /// The tilt in degrees.
+    pub tilt: Option<(f64, f64)>,
+    /// The rotation in degrees.
+    pub rotation: Option<f64>,
+    /// The slider position in the range `[-1, 1]`.
+    pub slider: Option<f64>,
+    /// The wheel rotation in degrees and in clicks.
+    pub wheel: Option<(f64, i32)>,
 }
 
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
@@ -209,6 +369,108 @@ pub enum InputEvent {
     AxisFrame {
         time_usec: u64,
     },
+
+    TouchDown {
+        time_usec: u64,
+        id: i32,
+        x_normed: f64,
+        y_normed: f64,
+    },
+    TouchUp {
+        time_usec: u64,
+        id: i32,
+    },
+    TouchMotion {
+        time_usec: u64,
+        id: i32,
+        x_normed: f64,
+        y_normed: f64,
+    },
+    TouchCancel {
+        id: i32,
+    },
+    TouchFrame,
+
+    TabletToolAdded {
+        init: Rc<TabletToolInit>,
+    },
+    TabletToolChanged {
+        time_usec: u64,
+        id: TabletToolId,
+        changes: Box<TabletToolChanges>,
+    },
+    TabletToolRemoved {
+        id: TabletToolId,
+    },
+    TabletToolButton {
+        time_usec: u64,
+        id: TabletToolId,
+        button: u32,
+        state: KeyState,
+    },
+    TabletPadButton {
+        time_usec: u64,
+        button: u32,
+        state: KeyState,
+    },
+    TabletPadModeSwitch {
+        time_usec: u64,
+        group: u32,
+        mode: u32,
+    },
+    TabletPadRing {
+        time_usec: u64,
+        ring: u32,
+        finger: bool,
+        /// The angle in degrees or `None` if the interaction stopped.
+        angle: Option<f64>,
+    },
+    TabletPadStrip {
+        time_usec: u64,
+        strip: u32,
+        finger: bool,
+        /// The position in the range `[0, 1]` or `None` if the interaction stopped.
+        position: Option<f64>,
+    },
+
+    SwipeBegin {
+        time_usec: u64,
+        finger_count: u32,
+    },
+    SwipeUpdate {
+        time_usec: u64,
+        dx: Fixed,
+        dy: Fixed,
+    },
+    SwipeEnd {
+        time_usec: u64,
+        cancelled: bool,
+    },
+    PinchBegin {
+        time_usec: u64,
+        finger_count: u32,
+    },
+    PinchUpdate {
+        time_usec: u64,
+        dx: Fixed,
+        dy: Fixed,
+        /// The absolute scale compared to the start of the gesture.
+        scale: Fixed,
+        /// The relative rotation in degrees.
+        rotation: Fixed,
+    },
+    PinchEnd {
+        time_usec: u64,
+        cancelled: bool,
+    },
+    HoldBegin {
+        time_usec: u64,
+        finger_count: u32,
+    },
+    HoldEnd {
+        time_usec: u64,
+        cancelled: bool,
+    },
 }
 
 pub enum DrmEvent {
@@ -2,7 +2,8 @@ use {
     crate::{
         async_engine::SpawnedFuture,
         backend::{
-            Backend, Connector, ConnectorEvent, ConnectorId, ConnectorKernelId, DrmDeviceId,
+            Backend, Connector, ConnectorError, ConnectorEvent, ConnectorId, ConnectorKernelId,
+            DrmDeviceId, GammaLut, Mode,
         },
         video::drm::ConnectorType,
     },
@@ -53,7 +54,27 @@ impl Connector for DummyOutput {
         None
     }
 
-    fn set_enabled(&self, _enabled: bool) {
+    fn set_enabled(&self, _enabled: bool) -> Result<(), ConnectorError> {
+        Ok(())
+    }
+
+    fn set_mode(&self, _mode: Mode) -> Result<(), ConnectorError> {
+        Ok(())
+    }
+
+    fn set_vrr_enabled(&self, _enabled: bool) {
+        // nothing
+    }
+
+    fn set_tearing_allowed(&self, _allowed: bool) {
+        // nothing
+    }
+
+    fn gamma_size(&self) -> u32 {
+        0
+    }
+
+    fn set_gamma_lut(&self, _lut: Option<Rc<GammaLut>>) {
         // nothing
     }
 }
@@ -6,8 +6,9 @@ use {
     crate::{
         async_engine::SpawnedFuture,
         backend::{
-            Backend, InputDevice, InputDeviceAccelProfile, InputDeviceCapability, InputDeviceId,
-            InputEvent, KeyState, TransformMatrix,
+            Backend, ConnectorConfig, ConnectorError, InputDevice, InputDeviceAccelProfile,
+            InputDeviceCapability, InputDeviceId, InputEvent, KeyState, TabletInit,
+            TabletPadGroupInit, TabletPadInit, TabletToolId, TransformMatrix,
         },
         backends::metal::video::{MetalDrmDeviceData, MetalRenderContext, PendingDrmDevice},
         dbus::{DbusError, SignalHandler},
@@ -41,6 +42,7 @@ use {
             gbm::GbmError,
         },
     },
+    bstr::ByteSlice,
     std::{
         any::Any,
         cell::{Cell, RefCell},
@@ -194,6 +196,11 @@ impl Backend for MetalBackend {
         true
     }
 
+    fn test_connector_configs(&self, configs: &[ConnectorConfig]) -> Result<(), ConnectorError> {
+        self.test_connector_configs_(configs)
+            .map_err(|e| ConnectorError::Rejected(Box::new(e)))
+    }
+
     fn import_environment(&self) -> bool {
         true
     }
@@ -287,6 +294,9 @@ struct MetalInputDevice {
     // state
     pressed_keys: SmallMap<u32, (), 5>,
     pressed_buttons: SmallMap<u32, (), 2>,
+    touch_points: SmallMap<i32, (), 2>,
+    tablet_tools: CopyHashMap<usize, TabletToolId>,
+    tablet_pad_modes: SmallMap<u32, u32, 1>,
 
     // config
     left_handed: Cell<Option<bool>>,
@@ -372,6 +382,16 @@ impl MetalInputDevice {
                 state: KeyState::Released,
             });
         }
+        let touch_points = self.touch_points.take();
+        if !touch_points.is_empty() {
+            for (id, _) in touch_points {
+                self.event(InputEvent::TouchCancel { id });
+            }
+            self.event(InputEvent::TouchFrame);
+        }
+        for (_, id) in self.tablet_tools.clear() {
+            self.event(InputEvent::TabletToolRemoved { id });
+        }
     }
 }
 
@@ -465,6 +485,52 @@ impl InputDevice for MetalInputDevice {
             dev.device().set_drag_lock_enabled(enabled);
         }
     }
+
+    fn tablet_info(&self) -> Option<Box<TabletInit>> {
+        let dev = self.inputdev.get()?;
+        let dev = dev.device();
+        if !dev.has_cap(LIBINPUT_DEVICE_CAP_TABLET_TOOL) {
+            return None;
+        }
+        Some(Box::new(TabletInit {
+            name: dev.name(),
+            vid: dev.vendor(),
+            pid: dev.product(),
+            path: self.devnode.to_bytes().as_bstr().to_string(),
+            group: dev.device_group(),
+        }))
+    }
+
+    fn tablet_pad_info(&self) -> Option<Box<TabletPadInit>> {
+        let dev = self.inputdev.get()?;
+        let dev = dev.device();
+        if !dev.has_cap(LIBINPUT_DEVICE_CAP_TABLET_PAD) {
+            return None;
+        }
+        let buttons = dev.pad_num_buttons();
+        let strips = dev.pad_num_strips();
+        let rings = dev.pad_num_rings();
+        let mut groups = vec![];
+        for n in 0..dev.pad_num_mode_groups() {
+            let group = match dev.pad_mode_group(n) {
+                Some(g) => g,
+                _ => continue,
+            };
+            groups.push(TabletPadGroupInit {
+                buttons: (0..buttons).filter(|b| group.has_button(*b)).collect(),
+                rings: (0..rings).filter(|r| group.has_ring(*r)).collect(),
+                strips: (0..strips).filter(|s| group.has_strip(*s)).collect(),
+                modes: group.num_modes(),
+                mode: group.mode(),
+            });
+        }
+        Some(Box::new(TabletPadInit {
+            path: self.devnode.to_bytes().as_bstr().to_string(),
+            group: dev.device_group(),
+            buttons,
+            groups,
+        }))
+    }
 }
 
 impl MetalInputDevice {
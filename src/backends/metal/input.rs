@@ -1,14 +1,23 @@
 use {
     crate::{
-        backend::{AxisSource, InputEvent, KeyState, ScrollAxis},
-        backends::metal::MetalBackend,
+        backend::{
+            AxisSource, InputEvent, KeyState, ScrollAxis, TabletToolCapabilities,
+            TabletToolChanges, TabletToolId, TabletToolInit, TabletToolType,
+        },
+        backends::metal::{MetalBackend, MetalInputDevice},
         fixed::Fixed,
         libinput::{
             consts::{
                 LIBINPUT_BUTTON_STATE_PRESSED, LIBINPUT_KEY_STATE_PRESSED,
                 LIBINPUT_POINTER_AXIS_SCROLL_HORIZONTAL, LIBINPUT_POINTER_AXIS_SCROLL_VERTICAL,
+                LIBINPUT_TABLET_PAD_RING_SOURCE_FINGER, LIBINPUT_TABLET_PAD_STRIP_SOURCE_FINGER,
+                LIBINPUT_TABLET_TOOL_PROXIMITY_STATE_IN, LIBINPUT_TABLET_TOOL_TIP_DOWN,
+                LIBINPUT_TABLET_TOOL_TYPE_AIRBRUSH, LIBINPUT_TABLET_TOOL_TYPE_BRUSH,
+                LIBINPUT_TABLET_TOOL_TYPE_ERASER, LIBINPUT_TABLET_TOOL_TYPE_LENS,
+                LIBINPUT_TABLET_TOOL_TYPE_MOUSE, LIBINPUT_TABLET_TOOL_TYPE_PENCIL,
+                LIBINPUT_TABLET_TOOL_TYPE_TOTEM,
             },
-            event::LibInputEvent,
+            event::{LibInputEvent, LibInputEventTabletTool},
         },
         utils::{bitflags::BitflagsExt, errorfmt::ErrorFmt},
     },
@@ -91,6 +100,26 @@ impl MetalBackend {
             c::LIBINPUT_EVENT_POINTER_SCROLL_CONTINUOUS => {
                 self.handle_pointer_axis(event, AxisSource::Continuous)
             }
+            c::LIBINPUT_EVENT_TOUCH_DOWN => self.handle_touch_down(event),
+            c::LIBINPUT_EVENT_TOUCH_UP => self.handle_touch_up(event),
+            c::LIBINPUT_EVENT_TOUCH_MOTION => self.handle_touch_motion(event),
+            c::LIBINPUT_EVENT_TOUCH_CANCEL => self.handle_touch_cancel(event),
+            c::LIBINPUT_EVENT_TOUCH_FRAME => self.handle_touch_frame(event),
+            c::LIBINPUT_EVENT_TABLET_TOOL_AXIS => self.handle_tablet_tool_axis(event),
+            c::LIBINPUT_EVENT_TABLET_TOOL_PROXIMITY => self.handle_tablet_tool_proximity(event),
+            c::LIBINPUT_EVENT_TABLET_TOOL_TIP => self.handle_tablet_tool_tip(event),
+            c::LIBINPUT_EVENT_TABLET_TOOL_BUTTON => self.handle_tablet_tool_button(event),
+            c::LIBINPUT_EVENT_TABLET_PAD_BUTTON => self.handle_tablet_pad_button(event),
+            c::LIBINPUT_EVENT_TABLET_PAD_RING => self.handle_tablet_pad_ring(event),
+            c::LIBINPUT_EVENT_TABLET_PAD_STRIP => self.handle_tablet_pad_strip(event),
+            c::LIBINPUT_EVENT_GESTURE_SWIPE_BEGIN => self.handle_gesture_swipe_begin(event),
+            c::LIBINPUT_EVENT_GESTURE_SWIPE_UPDATE => self.handle_gesture_swipe_update(event),
+            c::LIBINPUT_EVENT_GESTURE_SWIPE_END => self.handle_gesture_swipe_end(event),
+            c::LIBINPUT_EVENT_GESTURE_PINCH_BEGIN => self.handle_gesture_pinch_begin(event),
+            c::LIBINPUT_EVENT_GESTURE_PINCH_UPDATE => self.handle_gesture_pinch_update(event),
+            c::LIBINPUT_EVENT_GESTURE_PINCH_END => self.handle_gesture_pinch_end(event),
+            c::LIBINPUT_EVENT_GESTURE_HOLD_BEGIN => self.handle_gesture_hold_begin(event),
+            c::LIBINPUT_EVENT_GESTURE_HOLD_END => self.handle_gesture_hold_end(event),
             _ => {}
         }
     }
@@ -203,4 +232,282 @@ impl MetalBackend {
             dy_unaccelerated: Fixed::from_f64(dy_unaccelerated),
         });
     }
+
+    fn handle_gesture_swipe_begin(self: &Rc<Self>, event: LibInputEvent) {
+        let (event, dev) = unpack!(self, event, gesture_event);
+        dev.event(InputEvent::SwipeBegin {
+            time_usec: event.time_usec(),
+            finger_count: event.finger_count(),
+        });
+    }
+
+    fn handle_gesture_swipe_update(self: &Rc<Self>, event: LibInputEvent) {
+        let (event, dev) = unpack!(self, event, gesture_event);
+        dev.event(InputEvent::SwipeUpdate {
+            time_usec: event.time_usec(),
+            dx: Fixed::from_f64(event.dx()),
+            dy: Fixed::from_f64(event.dy()),
+        });
+    }
+
+    fn handle_gesture_swipe_end(self: &Rc<Self>, event: LibInputEvent) {
+        let (event, dev) = unpack!(self, event, gesture_event);
+        dev.event(InputEvent::SwipeEnd {
+            time_usec: event.time_usec(),
+            cancelled: event.cancelled(),
+        });
+    }
+
+    fn handle_gesture_pinch_begin(self: &Rc<Self>, event: LibInputEvent) {
+        let (event, dev) = unpack!(self, event, gesture_event);
+        dev.event(InputEvent::PinchBegin {
+            time_usec: event.time_usec(),
+            finger_count: event.finger_count(),
+        });
+    }
+
+    fn handle_gesture_pinch_update(self: &Rc<Self>, event: LibInputEvent) {
+        let (event, dev) = unpack!(self, event, gesture_event);
+        dev.event(InputEvent::PinchUpdate {
+            time_usec: event.time_usec(),
+            dx: Fixed::from_f64(event.dx()),
+            dy: Fixed::from_f64(event.dy()),
+            scale: Fixed::from_f64(event.scale()),
+            rotation: Fixed::from_f64(event.angle_delta()),
+        });
+    }
+
+    fn handle_gesture_pinch_end(self: &Rc<Self>, event: LibInputEvent) {
+        let (event, dev) = unpack!(self, event, gesture_event);
+        dev.event(InputEvent::PinchEnd {
+            time_usec: event.time_usec(),
+            cancelled: event.cancelled(),
+        });
+    }
+
+    fn handle_gesture_hold_begin(self: &Rc<Self>, event: LibInputEvent) {
+        let (event, dev) = unpack!(self, event, gesture_event);
+        dev.event(InputEvent::HoldBegin {
+            time_usec: event.time_usec(),
+            finger_count: event.finger_count(),
+        });
+    }
+
+    fn handle_gesture_hold_end(self: &Rc<Self>, event: LibInputEvent) {
+        let (event, dev) = unpack!(self, event, gesture_event);
+        dev.event(InputEvent::HoldEnd {
+            time_usec: event.time_usec(),
+            cancelled: event.cancelled(),
+        });
+    }
+
+    fn handle_touch_down(self: &Rc<Self>, event: LibInputEvent) {
+        let (event, dev) = unpack!(self, event, touch_event);
+        if dev.touch_points.insert(event.seat_slot(), ()).is_some() {
+            return;
+        }
+        dev.event(InputEvent::TouchDown {
+            time_usec: event.time_usec(),
+            id: event.seat_slot(),
+            x_normed: event.x_normed(),
+            y_normed: event.y_normed(),
+        });
+    }
+
+    fn handle_touch_up(self: &Rc<Self>, event: LibInputEvent) {
+        let (event, dev) = unpack!(self, event, touch_event);
+        if dev.touch_points.remove(&event.seat_slot()).is_none() {
+            return;
+        }
+        dev.event(InputEvent::TouchUp {
+            time_usec: event.time_usec(),
+            id: event.seat_slot(),
+        });
+    }
+
+    fn handle_touch_motion(self: &Rc<Self>, event: LibInputEvent) {
+        let (event, dev) = unpack!(self, event, touch_event);
+        dev.event(InputEvent::TouchMotion {
+            time_usec: event.time_usec(),
+            id: event.seat_slot(),
+            x_normed: event.x_normed(),
+            y_normed: event.y_normed(),
+        });
+    }
+
+    fn handle_touch_cancel(self: &Rc<Self>, event: LibInputEvent) {
+        let (event, dev) = unpack!(self, event, touch_event);
+        if dev.touch_points.remove(&event.seat_slot()).is_none() {
+            return;
+        }
+        dev.event(InputEvent::TouchCancel {
+            id: event.seat_slot(),
+        });
+    }
+
+    fn handle_touch_frame(self: &Rc<Self>, event: LibInputEvent) {
+        let (_, dev) = unpack!(self, event, touch_event);
+        dev.event(InputEvent::TouchFrame);
+    }
+
+    fn tablet_tool_id(
+        self: &Rc<Self>,
+        dev: &MetalInputDevice,
+        event: &LibInputEventTabletTool,
+    ) -> TabletToolId {
+        let tool = event.tool();
+        if let Some(id) = dev.tablet_tools.get(&tool.raw()) {
+            return id;
+        }
+        let id = self.state.tablet_tool_ids.next();
+        dev.tablet_tools.set(tool.raw(), id);
+        let type_ = match tool.ty() {
+            LIBINPUT_TABLET_TOOL_TYPE_ERASER => TabletToolType::Eraser,
+            LIBINPUT_TABLET_TOOL_TYPE_BRUSH => TabletToolType::Brush,
+            LIBINPUT_TABLET_TOOL_TYPE_PENCIL => TabletToolType::Pencil,
+            LIBINPUT_TABLET_TOOL_TYPE_AIRBRUSH => TabletToolType::Airbrush,
+            LIBINPUT_TABLET_TOOL_TYPE_MOUSE => TabletToolType::Mouse,
+            LIBINPUT_TABLET_TOOL_TYPE_LENS => TabletToolType::Lens,
+            LIBINPUT_TABLET_TOOL_TYPE_TOTEM => TabletToolType::Finger,
+            _ => TabletToolType::Pen,
+        };
+        dev.event(InputEvent::TabletToolAdded {
+            init: Rc::new(TabletToolInit {
+                id,
+                type_,
+                hardware_serial: tool.serial(),
+                hardware_id_wacom: tool.tool_id(),
+                capabilities: TabletToolCapabilities {
+                    pressure: tool.has_pressure(),
+                    distance: tool.has_distance(),
+                    tilt: tool.has_tilt(),
+                    rotation: tool.has_rotation(),
+                    slider: tool.has_slider(),
+                    wheel: tool.has_wheel(),
+                },
+            }),
+        });
+        id
+    }
+
+    fn tablet_tool_changes(event: &LibInputEventTabletTool) -> TabletToolChanges {
+        let mut changes = TabletToolChanges::default();
+        if event.x_has_changed() || event.y_has_changed() {
+            changes.pos = Some((event.x_normed(), event.y_normed()));
+        }
+        if event.pressure_has_changed() {
+            changes.pressure = Some(event.pressure());
+        }
+        if event.distance_has_changed() {
+            changes.distance = Some(event.distance());
+        }
+        if event.tilt_x_has_changed() || event.tilt_y_has_changed() {
+            changes.tilt = Some((event.tilt_x(), event.tilt_y()));
+        }
+        if event.rotation_has_changed() {
+            changes.rotation = Some(event.rotation());
+        }
+        if event.slider_has_changed() {
+            changes.slider = Some(event.slider_position());
+        }
+        if event.wheel_has_changed() {
+            changes.wheel = Some((event.wheel_delta(), event.wheel_delta_discrete()));
+        }
+        changes
+    }
+
+    fn handle_tablet_tool_axis(self: &Rc<Self>, event: LibInputEvent) {
+        let (event, dev) = unpack!(self, event, tablet_tool_event);
+        let id = self.tablet_tool_id(&dev, &event);
+        dev.event(InputEvent::TabletToolChanged {
+            time_usec: event.time_usec(),
+            id,
+            changes: Box::new(Self::tablet_tool_changes(&event)),
+        });
+    }
+
+    fn handle_tablet_tool_proximity(self: &Rc<Self>, event: LibInputEvent) {
+        let (event, dev) = unpack!(self, event, tablet_tool_event);
+        let id = self.tablet_tool_id(&dev, &event);
+        let mut changes = Self::tablet_tool_changes(&event);
+        changes.proximity =
+            Some(event.proximity_state() == LIBINPUT_TABLET_TOOL_PROXIMITY_STATE_IN);
+        dev.event(InputEvent::TabletToolChanged {
+            time_usec: event.time_usec(),
+            id,
+            changes: Box::new(changes),
+        });
+    }
+
+    fn handle_tablet_tool_tip(self: &Rc<Self>, event: LibInputEvent) {
+        let (event, dev) = unpack!(self, event, tablet_tool_event);
+        let id = self.tablet_tool_id(&dev, &event);
+        let mut changes = Self::tablet_tool_changes(&event);
+        changes.down = Some(event.tip_state() == LIBINPUT_TABLET_TOOL_TIP_DOWN);
+        dev.event(InputEvent::TabletToolChanged {
+            time_usec: event.time_usec(),
+            id,
+            changes: Box::new(changes),
+        });
+    }
+
+    fn handle_tablet_tool_button(self: &Rc<Self>, event: LibInputEvent) {
+        let (event, dev) = unpack!(self, event, tablet_tool_event);
+        let id = self.tablet_tool_id(&dev, &event);
+        let state = match event.button_state() == LIBINPUT_BUTTON_STATE_PRESSED {
+            true => KeyState::Pressed,
+            false => KeyState::Released,
+        };
+        dev.event(InputEvent::TabletToolButton {
+            time_usec: event.time_usec(),
+            id,
+            button: event.button(),
+            state,
+        });
+    }
+
+    fn handle_tablet_pad_button(self: &Rc<Self>, event: LibInputEvent) {
+        let (event, dev) = unpack!(self, event, tablet_pad_event);
+        let time_usec = event.time_usec();
+        let group = event.mode_group();
+        let (group, mode) = (group.index(), event.mode());
+        if dev.tablet_pad_modes.insert(group, mode) != Some(mode) {
+            dev.event(InputEvent::TabletPadModeSwitch {
+                time_usec,
+                group,
+                mode,
+            });
+        }
+        let state = match event.button_state() == LIBINPUT_BUTTON_STATE_PRESSED {
+            true => KeyState::Pressed,
+            false => KeyState::Released,
+        };
+        dev.event(InputEvent::TabletPadButton {
+            time_usec,
+            button: event.button_number(),
+            state,
+        });
+    }
+
+    fn handle_tablet_pad_ring(self: &Rc<Self>, event: LibInputEvent) {
+        let (event, dev) = unpack!(self, event, tablet_pad_event);
+        let angle = event.ring_position();
+        dev.event(InputEvent::TabletPadRing {
+            time_usec: event.time_usec(),
+            ring: event.ring_number(),
+            finger: event.ring_source() == LIBINPUT_TABLET_PAD_RING_SOURCE_FINGER,
+            angle: if angle < 0.0 { None } else { Some(angle) },
+        });
+    }
+
+    fn handle_tablet_pad_strip(self: &Rc<Self>, event: LibInputEvent) {
+        let (event, dev) = unpack!(self, event, tablet_pad_event);
+        let position = event.strip_position();
+        dev.event(InputEvent::TabletPadStrip {
+            time_usec: event.time_usec(),
+            strip: event.strip_number(),
+            finger: event.strip_source() == LIBINPUT_TABLET_PAD_STRIP_SOURCE_FINGER,
+            position: if position < 0.0 { None } else { Some(position) },
+        });
+    }
 }
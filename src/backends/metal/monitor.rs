@@ -289,6 +289,9 @@ impl MetalBackend {
             name: Default::default(),
             pressed_keys: Default::default(),
             pressed_buttons: Default::default(),
+            touch_points: Default::default(),
+            tablet_tools: Default::default(),
+            tablet_pad_modes: Default::default(),
             left_handed: Default::default(),
             accel_profile: Default::default(),
             accel_speed: Default::default(),
@@ -2,28 +2,36 @@ use {
     crate::{
         async_engine::{Phase, SpawnedFuture},
         backend::{
-            BackendDrmDevice, BackendEvent, Connector, ConnectorEvent, ConnectorId,
-            ConnectorKernelId, DrmDeviceId, HardwareCursor, MonitorInfo,
+            BackendDrmDevice, BackendEvent, Connector, ConnectorConfig, ConnectorError,
+            ConnectorEvent, ConnectorId, ConnectorKernelId, DrmDeviceId, GammaLut, HardwareCursor,
+            Mode, MonitorInfo,
         },
         backends::metal::{MetalBackend, MetalError},
         edid::Descriptor,
         format::{Format, ARGB8888, XRGB8888},
-        ifs::wp_presentation_feedback::{KIND_HW_COMPLETION, KIND_VSYNC},
-        render::{Framebuffer, RenderContext, RenderResult, Texture},
+        ifs::{
+            wl_buffer::WlBuffer,
+            wl_surface::{SurfaceNodeId, WlSurface},
+            wp_presentation_feedback::{KIND_HW_COMPLETION, KIND_VSYNC, KIND_ZERO_COPY},
+        },
+        rect::{Rect, Region, RegionBuilder},
+        render::{DrawOp, Framebuffer, RenderContext, RenderResult, Texture},
         state::State,
+        tree::OutputNode,
         udev::UdevDevice,
         utils::{
             asyncevent::AsyncEvent, bitflags::BitflagsExt, clonecell::CloneCell,
             copyhashmap::CopyHashMap, debug_fn::debug_fn, errorfmt::ErrorFmt, numcell::NumCell,
-            oserror::OsError, syncqueue::SyncQueue,
+            oserror::OsError, rc_eq::rc_eq, syncqueue::SyncQueue,
         },
         video::{
             drm::{
-                drm_mode_modeinfo, Change, ConnectorStatus, ConnectorType, DrmBlob, DrmConnector,
-                DrmCrtc, DrmEncoder, DrmError, DrmEvent, DrmFramebuffer, DrmMaster, DrmModeInfo,
-                DrmObject, DrmPlane, DrmProperty, DrmPropertyDefinition, DrmPropertyType,
-                DrmVersion, PropBlob, DRM_CLIENT_CAP_ATOMIC, DRM_MODE_ATOMIC_ALLOW_MODESET,
-                DRM_MODE_ATOMIC_NONBLOCK, DRM_MODE_PAGE_FLIP_EVENT,
+                drm_color_lut, drm_mode_modeinfo, Change, ConnectorStatus, ConnectorType, DrmBlob,
+                DrmConnector, DrmCrtc, DrmEncoder, DrmError, DrmEvent, DrmFramebuffer, DrmMaster,
+                DrmModeInfo, DrmObject, DrmPlane, DrmProperty, DrmPropertyDefinition,
+                DrmPropertyType, DrmVersion, PropBlob, DRM_CLIENT_CAP_ATOMIC,
+                DRM_MODE_ATOMIC_ALLOW_MODESET, DRM_MODE_ATOMIC_NONBLOCK, DRM_MODE_PAGE_FLIP_ASYNC,
+                DRM_MODE_PAGE_FLIP_EVENT,
             },
             gbm::{GbmDevice, GBM_BO_USE_LINEAR, GBM_BO_USE_RENDERING, GBM_BO_USE_SCANOUT},
             ModifiedFormat, INVALID_MODIFIER,
@@ -33,6 +41,7 @@ use {
     bstr::{BString, ByteSlice},
     std::{
         cell::{Cell, RefCell},
+        cmp::Reverse,
         ffi::CString,
         fmt::{Debug, Formatter},
         mem,
@@ -138,6 +147,8 @@ pub struct ConnectorDisplayData {
 
     pub connector_type: ConnectorType,
     pub connector_type_id: u32,
+
+    pub vrr_capable: bool,
 }
 
 impl ConnectorDisplayData {
@@ -192,6 +203,88 @@ pub struct MetalConnector {
     pub cursor_buffers: CloneCell<Option<Rc<[RenderBuffer; 2]>>>,
     pub cursor_front_buffer: NumCell<usize>,
     pub cursor_swap_buffer: Cell<bool>,
+
+    pub vrr_enabled: Cell<bool>,
+
+    pub gamma_lut: CloneCell<Option<Rc<GammaLut>>>,
+    pub gamma_changed: Cell<bool>,
+
+    pub tearing_allowed: Cell<bool>,
+    pub async_flip_supported: Cell<bool>,
+    pub last_flip_async: Cell<bool>,
+    pub last_flip_zero_copy: Cell<bool>,
+
+    pub overlay_planes: RefCell<Vec<Rc<MetalPlane>>>,
+    pub overlays: RefCell<Vec<Overlay>>,
+
+    pub client_fbs: RefCell<Vec<Rc<ClientFramebuffer>>>,
+    pub scanout_presented: RefCell<Vec<Rc<ClientFramebuffer>>>,
+    pub scanout_retired: RefCell<Vec<Rc<ClientFramebuffer>>>,
+}
+
+pub struct ClientFramebuffer {
+    pub buffer: Rc<WlBuffer>,
+    /// `None` if the buffer cannot be imported.
+    pub fb: Option<Rc<DrmFramebuffer>>,
+    pub primary_ok: Cell<Option<bool>>,
+}
+
+impl Debug for ClientFramebuffer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientFramebuffer").finish_non_exhaustive()
+    }
+}
+
+const MAX_CLIENT_FRAMEBUFFERS: usize = 8;
+
+const MAX_OVERLAY_PLANES: usize = 2;
+
+#[derive(Debug, Clone)]
+pub struct Overlay {
+    pub plane: Rc<MetalPlane>,
+    pub surface: SurfaceNodeId,
+    pub rect: Rect,
+    pub fb: Rc<ClientFramebuffer>,
+}
+
+impl Overlay {
+    fn same_as(&self, other: &Overlay) -> bool {
+        rc_eq(&self.plane, &other.plane) && self.rect == other.rect && rc_eq(&self.fb, &other.fb)
+    }
+
+    fn apply(&self, crtc: &MetalCrtc, changes: &mut Change) {
+        let plane = &self.plane;
+        let fb = self.fb.fb.as_ref().unwrap();
+        let (width, height) = self.fb.buffer.rect.size();
+        changes.change_object(plane.id, |c| {
+            c.change(plane.fb_id, fb.id().0 as _);
+            c.change(plane.crtc_id.id, crtc.id.0 as _);
+            c.change(plane.crtc_x.id, self.rect.x1() as _);
+            c.change(plane.crtc_y.id, self.rect.y1() as _);
+            c.change(plane.crtc_w.id, self.rect.width() as _);
+            c.change(plane.crtc_h.id, self.rect.height() as _);
+            c.change(plane.src_x.id, 0);
+            c.change(plane.src_y.id, 0);
+            c.change(plane.src_w.id, (width as u64) << 16);
+            c.change(plane.src_h.id, (height as u64) << 16);
+        });
+    }
+}
+
+fn disable_plane(plane: &MetalPlane, changes: &mut Change) {
+    changes.change_object(plane.id, |c| {
+        c.change(plane.fb_id, 0);
+        c.change(plane.crtc_id.id, 0);
+    });
+}
+
+fn overlays_cover_same(a: &[Overlay], b: &[Overlay]) -> bool {
+    a.len() == b.len()
+        && a.iter().zip(b.iter()).all(|(a, b)| {
+            a.surface == b.surface
+                && a.rect == b.rect
+                && a.fb.buffer.format.has_alpha == b.fb.buffer.format.has_alpha
+        })
 }
 
 #[derive(Debug)]
@@ -318,6 +411,16 @@ impl MetalConnector {
             && self.primary_plane.get().is_some()
     }
 
+    fn restore_configuration(&self, dev: &Rc<MetalDrmDeviceData>) {
+        if let Err(e) = self.backend.handle_drm_change_(dev, true) {
+            dev.unprocessed_change.set(true);
+            log::error!(
+                "Could not restore the connector configuration: {}",
+                ErrorFmt(e)
+            );
+        }
+    }
+
     fn send_event(&self, event: ConnectorEvent) {
         self.events.push(event);
         if let Some(oc) = self.on_change.on_change.get() {
@@ -329,12 +432,168 @@ impl MetalConnector {
         self.present_trigger.trigger();
     }
 
+    fn import_client_buffer(&self, buffer: &WlBuffer) -> Option<Rc<DrmFramebuffer>> {
+        let dmabuf = buffer.dmabuf()?;
+        match self.master.add_fb(dmabuf) {
+            Ok(fb) => Some(Rc::new(fb)),
+            Err(e) => {
+                log::debug!("Could not import client buffer: {}", ErrorFmt(e));
+                None
+            }
+        }
+    }
+
+    fn client_framebuffer(&self, buffer: &Rc<WlBuffer>) -> Option<Rc<ClientFramebuffer>> {
+        let mut cache = self.client_fbs.borrow_mut();
+        cache.retain(|cf| !cf.buffer.destroyed());
+        let cf = match cache.iter().find(|cf| rc_eq(&cf.buffer, buffer)) {
+            Some(cf) => cf.clone(),
+            _ => {
+                let cf = Rc::new(ClientFramebuffer {
+                    buffer: buffer.clone(),
+                    fb: self.import_client_buffer(buffer),
+                    primary_ok: Cell::new(None),
+                });
+                if cache.len() >= MAX_CLIENT_FRAMEBUFFERS {
+                    cache.remove(0);
+                }
+                cache.push(cf.clone());
+                cf
+            }
+        };
+        cf.fb.as_ref()?;
+        Some(cf)
+    }
+
+    fn prepare_direct_scanout(
+        &self,
+        node: &OutputNode,
+        plane: &MetalPlane,
+    ) -> Option<(Rc<WlSurface>, Rc<ClientFramebuffer>)> {
+        let (surface, buffer) = node.direct_scanout_candidate()?;
+        if !plane.formats.contains_key(&buffer.format.drm) {
+            return None;
+        }
+        let cf = self.client_framebuffer(&buffer)?;
+        if cf.primary_ok.get().is_none() {
+            let fb = cf.fb.as_ref().unwrap();
+            let mut changes = self.master.change();
+            changes.change_object(plane.id, |c| {
+                c.change(plane.fb_id, fb.id().0 as _);
+            });
+            let res = changes.test(0);
+            if let Err(e) = &res {
+                log::debug!("Plane does not accept client buffer: {}", ErrorFmt(e));
+            }
+            cf.primary_ok.set(Some(res.is_ok()));
+        }
+        if cf.primary_ok.get() != Some(true) {
+            return None;
+        }
+        Some((surface, cf))
+    }
+
+    /// The surfaces of the returned overlays must not be drawn into the primary plane.
+    fn assign_overlays(
+        &self,
+        node: &OutputNode,
+        fb: &Framebuffer,
+        crtc: &MetalCrtc,
+    ) -> Vec<Overlay> {
+        let planes = self.overlay_planes.borrow();
+        let mut overlays = vec![];
+        if planes.is_empty() {
+            return overlays;
+        }
+        // Captures read the framebuffer which does not contain the offloaded surfaces.
+        if !node.screencasts.is_empty() || !node.global.pending_captures.is_empty() {
+            return overlays;
+        }
+        let ops = fb.record(
+            node,
+            &self.state,
+            Some(node.global.pos.get()),
+            node.preferred_scale.get(),
+            !self.cursor_enabled.get(),
+        );
+        let mut candidates = vec![];
+        for (idx, op) in ops.iter().enumerate() {
+            let draw = match op {
+                DrawOp::Surface(d) if d.plain => d,
+                _ => continue,
+            };
+            let occluded = ops[idx + 1..].iter().any(|op| {
+                let rect = match op {
+                    DrawOp::Fill(r) => r,
+                    DrawOp::Surface(d) => &d.rect,
+                };
+                rect.intersects(&draw.rect)
+            });
+            if !occluded && draw.buffer.dmabuf().is_some() {
+                candidates.push(draw);
+            }
+        }
+        candidates.sort_by_key(|d| Reverse(d.rect.width() as i64 * d.rect.height() as i64));
+        let old = self.overlays.borrow();
+        for draw in candidates {
+            let plane = match planes.get(overlays.len()) {
+                Some(p) => p,
+                _ => break,
+            };
+            if !plane.formats.contains_key(&draw.buffer.format.drm) {
+                continue;
+            }
+            let fb = match self.client_framebuffer(&draw.buffer) {
+                Some(fb) => fb,
+                _ => continue,
+            };
+            let overlay = Overlay {
+                plane: plane.clone(),
+                surface: draw.surface,
+                rect: draw.rect,
+                fb,
+            };
+            let new = || overlays.iter().chain(Some(&overlay));
+            let committed =
+                old.len() > overlays.len() && new().zip(old.iter()).all(|(a, b)| a.same_as(b));
+            if !committed {
+                let mut changes = self.master.change();
+                for overlay in new() {
+                    overlay.apply(crtc, &mut changes);
+                }
+                for old in old.iter().skip(overlays.len() + 1) {
+                    disable_plane(&old.plane, &mut changes);
+                }
+                if let Err(e) = changes.test(0) {
+                    log::debug!(
+                        "Overlay plane does not accept client buffer: {}",
+                        ErrorFmt(e)
+                    );
+                    continue;
+                }
+            }
+            overlays.push(overlay);
+        }
+        overlays
+    }
+
     pub fn present(&self) {
         let crtc = match self.crtc.get() {
             Some(crtc) => crtc,
             _ => return,
         };
-        if (!self.has_damage.get() && !self.cursor_changed.get()) || !self.can_present.get() {
+        // VRR_ENABLED does not require a modeset. Once it is set, the kernel delays each
+        // vblank until the next page flip, i.e. until the next client commit that caused
+        // damage, up to the minimum refresh rate of the monitor.
+        let vrr_enabled = self.vrr_enabled.get() && self.display.borrow().vrr_capable;
+        let vrr_changed = match &crtc.vrr_enabled {
+            Some(p) => p.value.get() != vrr_enabled,
+            _ => false,
+        };
+        let gamma_changed = self.gamma_changed.get() && crtc.gamma_lut.is_some();
+        if (!self.has_damage.get() && !self.cursor_changed.get() && !vrr_changed && !gamma_changed)
+            || !self.can_present.get()
+        {
             return;
         }
         if !crtc.active.value.get() {
@@ -350,22 +609,79 @@ impl MetalConnector {
         };
         let cursor = self.cursor_plane.get();
         let mut changes = self.master.change();
-        if self.has_damage.get() {
+        let node = self.state.root.outputs.get(&self.connector_id);
+        let direct_scanout = match (self.has_damage.get(), &node) {
+            (true, Some(node)) => self.prepare_direct_scanout(node, &plane),
+            _ => None,
+        };
+        let mut overlays = vec![];
+        if let Some((surface, ds)) = &direct_scanout {
+            let node = node.as_ref().unwrap();
+            // The scanout buffers are not updated while the client buffer is shown.
+            // Accumulate the damage so that they are fully repainted afterwards.
+            let mut damage = node.take_damage();
+            let old_overlays = self.overlays.borrow();
+            if !old_overlays.is_empty() {
+                let rects: Vec<_> = old_overlays.iter().map(|o| o.rect).collect();
+                damage = damage.union(&Region::from_rects(&rects));
+            }
+            for buffer in buffers.iter() {
+                buffer.add_damage(&damage);
+            }
+            for fr in surface.frame_requests.borrow_mut().drain(..) {
+                fr.send_done();
+                let _ = fr.client.remove_obj(&*fr);
+            }
+            let mut rr = self.render_result.borrow_mut();
+            rr.presentation_feedbacks
+                .extend(surface.presentation_feedback.borrow_mut().drain(..));
+            let fb = ds.fb.as_ref().unwrap();
+            changes.change_object(plane.id, |c| {
+                c.change(plane.fb_id, fb.id().0 as _);
+            });
+        } else if self.has_damage.get() {
             if !self.backend.check_render_context(&self.dev) {
                 return;
             }
             let buffer = &buffers[self.next_buffer.fetch_add(1) % buffers.len()];
-            if let Some(node) = self.state.root.outputs.get(&self.connector_id) {
-                let mut rr = self.render_result.borrow_mut();
+            if let Some(node) = &node {
                 let render_fb = buffer.render_fb();
+                overlays = self.assign_overlays(node, &render_fb, &crtc);
+                let mut damage = node.take_damage();
+                let old_overlays = self.overlays.borrow();
+                if overlays_cover_same(&old_overlays, &overlays) {
+                    // Damage below opaque overlays is not visible.
+                    let hidden: Vec<_> = overlays
+                        .iter()
+                        .filter(|o| !o.fb.buffer.format.has_alpha)
+                        .map(|o| o.rect)
+                        .collect();
+                    damage = damage.subtract(&Region::from_rects(&hidden));
+                } else {
+                    let rects: Vec<_> = old_overlays
+                        .iter()
+                        .chain(overlays.iter())
+                        .map(|o| o.rect)
+                        .collect();
+                    damage = damage.union(&Region::from_rects(&rects));
+                }
+                drop(old_overlays);
+                for buffer in buffers.iter() {
+                    buffer.add_damage(&damage);
+                }
+                let damage = buffer.take_damage();
+                let offloaded: Vec<_> = overlays.iter().map(|o| o.surface).collect();
+                let mut rr = self.render_result.borrow_mut();
                 render_fb.render(
-                    &*node,
+                    &**node,
                     &self.state,
                     Some(node.global.pos.get()),
                     true,
                     &mut rr,
                     node.preferred_scale.get(),
                     !self.cursor_enabled.get(),
+                    Some(&damage),
+                    &offloaded,
                 );
                 if let Some(tex) = &buffer.dev_tex {
                     buffer.dev_fb.copy_texture(&self.state, tex, 0, 0, false);
@@ -413,7 +729,72 @@ impl MetalConnector {
                 });
             }
         }
-        if let Err(e) = changes.commit(DRM_MODE_ATOMIC_NONBLOCK | DRM_MODE_PAGE_FLIP_EVENT, 0) {
+        let primary_changed = self.has_damage.get();
+        let mut overlays_changed = false;
+        if primary_changed {
+            let old_overlays = self.overlays.borrow();
+            for (idx, overlay) in overlays.iter().enumerate() {
+                if !old_overlays.get(idx).is_some_and(|o| o.same_as(overlay)) {
+                    overlay.apply(&crtc, &mut changes);
+                    overlays_changed = true;
+                }
+            }
+            for old in old_overlays.iter().skip(overlays.len()) {
+                disable_plane(&old.plane, &mut changes);
+                overlays_changed = true;
+            }
+        }
+        if vrr_changed {
+            if let Some(p) = &crtc.vrr_enabled {
+                changes.change_object(crtc.id, |c| {
+                    c.change(p.id, vrr_enabled as _);
+                });
+            }
+        }
+        let mut gamma_blob = None;
+        if gamma_changed {
+            if let Some(p) = &crtc.gamma_lut {
+                match crtc.create_gamma_blob(self.gamma_lut.get().as_deref()) {
+                    Ok(blob) => {
+                        let id = blob.as_ref().map(|b| b.id()).unwrap_or(DrmBlob::NONE);
+                        changes.change_object(crtc.id, |c| {
+                            c.change(p.id, id.0 as _);
+                        });
+                        gamma_blob = Some(blob);
+                    }
+                    Err(e) => log::error!("Could not create gamma blob: {}", ErrorFmt(e)),
+                }
+            }
+        }
+        // Async flips can only change the framebuffer of the primary plane. Cursor,
+        // overlay, VRR, and gamma changes are therefore always submitted with a regular
+        // flip.
+        let flags = DRM_MODE_ATOMIC_NONBLOCK | DRM_MODE_PAGE_FLIP_EVENT;
+        let mut flip_async = self.tearing_allowed.get()
+            && self.async_flip_supported.get()
+            && primary_changed
+            && !overlays_changed
+            && !self.cursor_changed.get()
+            && !vrr_changed
+            && !gamma_changed;
+        let mut res = match flip_async {
+            true => changes.commit(flags | DRM_MODE_PAGE_FLIP_ASYNC, 0),
+            false => changes.commit(flags, 0),
+        };
+        if flip_async {
+            if let Err(e) = &res {
+                if !matches!(e, DrmError::Atomic(OsError(c::EACCES))) {
+                    log::warn!(
+                        "Driver rejected async page flip, falling back to vsync: {}",
+                        ErrorFmt(e)
+                    );
+                    self.async_flip_supported.set(false);
+                    flip_async = false;
+                    res = changes.commit(flags, 0);
+                }
+            }
+        }
+        if let Err(e) = res {
             match e {
                 DrmError::Atomic(OsError(c::EACCES)) => {
                     log::debug!("Could not perform atomic commit, likely because we're no longer the DRM master");
@@ -424,6 +805,28 @@ impl MetalConnector {
             self.can_present.set(false);
             self.has_damage.set(false);
             self.cursor_changed.set(false);
+            self.last_flip_async.set(flip_async);
+            if primary_changed {
+                self.last_flip_zero_copy.set(direct_scanout.is_some());
+                let presented: Vec<_> = direct_scanout
+                    .map(|(_, ds)| ds)
+                    .into_iter()
+                    .chain(overlays.iter().map(|o| o.fb.clone()))
+                    .collect();
+                for cf in &presented {
+                    cf.buffer.scanout_begin();
+                }
+                let old = self.scanout_presented.replace(presented);
+                self.scanout_retired.borrow_mut().extend(old);
+                *self.overlays.borrow_mut() = overlays;
+            }
+            if let Some(p) = &crtc.vrr_enabled {
+                p.value.set(vrr_enabled);
+            }
+            self.gamma_changed.set(false);
+            if let Some(blob) = gamma_blob {
+                crtc.set_gamma_blob(blob);
+            }
         }
     }
 }
@@ -460,17 +863,81 @@ impl Connector for MetalConnector {
         Some(self.dev.id)
     }
 
-    fn set_enabled(&self, enabled: bool) {
+    fn set_enabled(&self, enabled: bool) -> Result<(), ConnectorError> {
         if self.enabled.replace(enabled) != enabled {
             if self.display.borrow_mut().connection == ConnectorStatus::Connected {
                 if let Some(dev) = self.backend.device_holder.drm_devices.get(&self.dev.devnum) {
                     if let Err(e) = self.backend.handle_drm_change_(&dev, true) {
-                        dev.unprocessed_change.set(true);
-                        log::error!("Could not dis/enable connector: {}", ErrorFmt(e));
+                        log::error!("Could not dis/enable connector: {}", ErrorFmt(&e));
+                        self.enabled.set(!enabled);
+                        self.restore_configuration(&dev);
+                        return Err(ConnectorError::Rejected(Box::new(e)));
                     }
                 }
             }
         }
+        Ok(())
+    }
+
+    fn set_mode(&self, mode: Mode) -> Result<(), ConnectorError> {
+        let mut dd = self.display.borrow_mut();
+        let new_mode = match dd.modes.iter().find(|m| m.to_backend() == mode) {
+            Some(m) => m.clone(),
+            _ => return Err(ConnectorError::UnsupportedMode(mode)),
+        };
+        if let Some(old_mode) = &dd.mode {
+            if modes_equal(old_mode, &new_mode) {
+                return Ok(());
+            }
+        }
+        let old_refresh = mem::replace(&mut dd.refresh, refresh_nsec(&new_mode));
+        let old_mode = dd.mode.replace(Rc::new(new_mode));
+        let connected = dd.connection == ConnectorStatus::Connected;
+        drop(dd);
+        if !self.enabled.get() || !connected {
+            return Ok(());
+        }
+        if let Some(dev) = self.backend.device_holder.drm_devices.get(&self.dev.devnum) {
+            if let Err(e) = self.backend.handle_drm_change_(&dev, true) {
+                log::error!("Could not change mode of connector: {}", ErrorFmt(&e));
+                {
+                    let mut dd = self.display.borrow_mut();
+                    dd.refresh = old_refresh;
+                    dd.mode = old_mode;
+                }
+                self.restore_configuration(&dev);
+                return Err(ConnectorError::Rejected(Box::new(e)));
+            }
+        }
+        if self.connect_sent.get() {
+            self.send_event(ConnectorEvent::ModeChanged(mode));
+        }
+        Ok(())
+    }
+
+    fn set_vrr_enabled(&self, enabled: bool) {
+        if self.vrr_enabled.replace(enabled) != enabled && self.can_present.get() {
+            self.schedule_present();
+        }
+    }
+
+    fn set_tearing_allowed(&self, allowed: bool) {
+        self.tearing_allowed.set(allowed);
+    }
+
+    fn gamma_size(&self) -> u32 {
+        match self.crtc.get() {
+            Some(crtc) if crtc.gamma_lut.is_some() => crtc.gamma_lut_size,
+            _ => 0,
+        }
+    }
+
+    fn set_gamma_lut(&self, lut: Option<Rc<GammaLut>>) {
+        self.gamma_lut.set(lut);
+        self.gamma_changed.set(true);
+        if self.can_present.get() {
+            self.schedule_present();
+        }
     }
 }
 
@@ -487,8 +954,38 @@ pub struct MetalCrtc {
     pub active: MutableProperty<bool>,
     pub mode_id: MutableProperty<DrmBlob>,
     pub out_fence_ptr: DrmProperty,
+    pub vrr_enabled: Option<MutableProperty<bool>>,
+    pub gamma_lut: Option<MutableProperty<DrmBlob>>,
+    pub gamma_lut_size: u32,
 
     pub mode_blob: CloneCell<Option<Rc<PropBlob>>>,
+    pub gamma_blob: CloneCell<Option<Rc<PropBlob>>>,
+}
+
+impl MetalCrtc {
+    fn create_gamma_blob(&self, lut: Option<&GammaLut>) -> Result<Option<PropBlob>, DrmError> {
+        let lut = match lut {
+            Some(lut) if self.gamma_lut_size > 0 => lut.resample(self.gamma_lut_size as usize),
+            _ => return Ok(None),
+        };
+        let entries: Vec<_> = (0..lut.red.len())
+            .map(|i| drm_color_lut {
+                red: lut.red[i],
+                green: lut.green[i],
+                blue: lut.blue[i],
+                reserved: 0,
+            })
+            .collect();
+        self.master.create_blob(&entries[..]).map(Some)
+    }
+
+    fn set_gamma_blob(&self, blob: Option<PropBlob>) {
+        if let Some(p) = &self.gamma_lut {
+            p.value
+                .set(blob.as_ref().map(|b| b.id()).unwrap_or(DrmBlob::NONE));
+        }
+        self.gamma_blob.set(blob.map(Rc::new));
+    }
 }
 
 #[derive(Debug)]
@@ -590,6 +1087,18 @@ fn create_connector(
         cursor_changed: Cell::new(false),
         cursor_front_buffer: Default::default(),
         cursor_swap_buffer: Cell::new(false),
+        vrr_enabled: Cell::new(false),
+        gamma_lut: Default::default(),
+        gamma_changed: Cell::new(false),
+        tearing_allowed: Cell::new(false),
+        async_flip_supported: Cell::new(true),
+        last_flip_async: Cell::new(false),
+        last_flip_zero_copy: Cell::new(false),
+        overlay_planes: Default::default(),
+        overlays: Default::default(),
+        client_fbs: Default::default(),
+        scanout_presented: Default::default(),
+        scanout_retired: Default::default(),
     });
     let futures = ConnectorFutures {
         present: backend
@@ -619,10 +1128,7 @@ fn create_connector_display_data(
     let mut manufacturer = String::new();
     let mut serial_number = String::new();
     let mode = info.modes.first().cloned().map(Rc::new);
-    let refresh = mode
-        .as_ref()
-        .map(|m| 1_000_000_000_000u64 / (m.refresh_rate_millihz() as u64))
-        .unwrap_or(0) as u32;
+    let refresh = mode.as_deref().map(refresh_nsec).unwrap_or(0);
     let connector_type = ConnectorType::from_drm(info.connector_type);
     let connector_name = debug_fn(|f| write!(f, "{}-{}", connector_type, info.connector_type_id));
     'fetch_edid: {
@@ -704,6 +1210,10 @@ fn create_connector_display_data(
         subpixel: info.subpixel,
         connector_type,
         connector_type_id: info.connector_type_id,
+        vrr_capable: match props.get("vrr_capable") {
+            Ok(p) => p.value.get() == 1,
+            _ => false,
+        },
     })
 }
 
@@ -748,7 +1258,17 @@ fn create_crtc(
         active: props.get("ACTIVE")?.map(|v| v == 1),
         mode_id: props.get("MODE_ID")?.map(|v| DrmBlob(v as u32)),
         out_fence_ptr: props.get("OUT_FENCE_PTR")?.id,
+        vrr_enabled: props.get("VRR_ENABLED").ok().map(|p| p.map(|v| v == 1)),
+        gamma_lut: props
+            .get("GAMMA_LUT")
+            .ok()
+            .map(|p| p.map(|v| DrmBlob(v as u32))),
+        gamma_lut_size: props
+            .get("GAMMA_LUT_SIZE")
+            .map(|p| p.value.get() as u32)
+            .unwrap_or(0),
         mode_blob: Default::default(),
+        gamma_blob: Default::default(),
     })
 }
 
@@ -981,6 +1501,14 @@ impl MetalBackend {
             };
             let mut old = c.display.borrow_mut();
             mem::swap(old.deref_mut(), &mut dd);
+            if old.is_same_monitor(&dd) {
+                if let Some(mode) = &dd.mode {
+                    if old.modes.iter().any(|m| modes_equal(m, mode)) {
+                        old.mode = Some(mode.clone());
+                        old.refresh = dd.refresh;
+                    }
+                }
+            }
             if c.connect_sent.get() {
                 if !c.enabled.get()
                     || old.connection != ConnectorStatus::Connected
@@ -1177,6 +1705,12 @@ impl MetalBackend {
             c.mode_id
                 .value
                 .set(DrmBlob(get(&props, c.mode_id.id)? as _));
+            if let Some(vrr) = &c.vrr_enabled {
+                vrr.value.set(get(&props, vrr.id)? != 0);
+            }
+            if let Some(gamma) = &c.gamma_lut {
+                gamma.value.set(DrmBlob(get(&props, gamma.id)? as _));
+            }
         }
         for c in dev.dev.planes.values() {
             let props = collect_untyped_properties(master, c.id)?;
@@ -1195,6 +1729,8 @@ impl MetalBackend {
             connector.can_present.set(true);
             connector.has_damage.set(true);
             connector.cursor_changed.set(true);
+            // Another session might have changed the gamma while we were inactive.
+            connector.gamma_changed.set(true);
         }
         if dev.unprocessed_change.get() {
             return self.handle_drm_change_(dev, false);
@@ -1253,7 +1789,13 @@ impl MetalBackend {
             _ => return,
         };
         connector.can_present.set(true);
-        if connector.has_damage.get() || connector.cursor_changed.get() {
+        for cf in connector.scanout_retired.take() {
+            cf.buffer.scanout_end();
+        }
+        if connector.has_damage.get()
+            || connector.cursor_changed.get()
+            || connector.gamma_changed.get()
+        {
             connector.schedule_present();
         }
         let dd = connector.display.borrow_mut();
@@ -1262,6 +1804,13 @@ impl MetalBackend {
             let mut rr = connector.render_result.borrow_mut();
             if let Some(g) = &global {
                 let refresh = dd.refresh;
+                let mut flags = match connector.last_flip_async.get() {
+                    true => KIND_HW_COMPLETION,
+                    false => KIND_VSYNC | KIND_HW_COMPLETION,
+                };
+                if connector.last_flip_zero_copy.get() {
+                    flags |= KIND_ZERO_COPY;
+                }
                 let bindings = g.node.global.bindings.borrow_mut();
                 for fb in rr.presentation_feedbacks.drain(..) {
                     if let Some(bindings) = bindings.get(&fb.client.id) {
@@ -1269,13 +1818,7 @@ impl MetalBackend {
                             fb.send_sync_output(binding);
                         }
                     }
-                    fb.send_presented(
-                        tv_sec as _,
-                        tv_usec * 1000,
-                        refresh,
-                        sequence as _,
-                        KIND_VSYNC | KIND_HW_COMPLETION,
-                    );
+                    fb.send_presented(tv_sec as _, tv_usec * 1000, refresh, sequence as _, flags);
                     let _ = fb.client.remove_obj(&*fb);
                 }
             } else {
@@ -1315,6 +1858,16 @@ impl MetalBackend {
             connector.primary_plane.set(None);
             connector.cursor_plane.set(None);
             connector.cursor_enabled.set(false);
+            connector.overlay_planes.borrow_mut().clear();
+            connector.overlays.borrow_mut().clear();
+            // The planes are disabled by the same commit that resets the crtc.
+            for cf in connector.scanout_presented.take() {
+                cf.buffer.scanout_end();
+            }
+            for cf in connector.scanout_retired.take() {
+                cf.buffer.scanout_end();
+            }
+            connector.last_flip_zero_copy.set(false);
             connector.crtc.set(None);
             let dd = connector.display.borrow_mut();
             dd.crtc_id.value.set(DrmCrtc::NONE);
@@ -1333,7 +1886,15 @@ impl MetalBackend {
                 c.change(crtc.active.id, 0);
                 c.change(crtc.mode_id.id, 0);
                 c.change(crtc.out_fence_ptr, 0);
-            })
+                if let Some(vrr) = &crtc.vrr_enabled {
+                    vrr.value.set(false);
+                    c.change(vrr.id, 0);
+                }
+                if let Some(gamma) = &crtc.gamma_lut {
+                    c.change(gamma.id, 0);
+                }
+            });
+            crtc.set_gamma_blob(None);
         }
     }
 
@@ -1407,6 +1968,9 @@ impl MetalBackend {
                 if let Some(pp) = connector.cursor_plane.get() {
                     preserve.planes.insert(pp.id);
                 }
+                for pp in connector.overlay_planes.borrow().iter() {
+                    preserve.planes.insert(pp.id);
+                }
                 if let Some(crtc) = connector.crtc.get() {
                     preserve.crtcs.insert(crtc.id);
                 }
@@ -1485,6 +2049,120 @@ impl MetalBackend {
         Ok(())
     }
 
+    pub fn test_connector_configs_(&self, configs: &[ConnectorConfig]) -> Result<(), MetalError> {
+        let ctx = match self.ctx.get() {
+            Some(ctx) => ctx,
+            _ => return Ok(()),
+        };
+        for dev in self.device_holder.drm_devices.lock().values() {
+            self.test_drm_device(dev, configs, &ctx)?;
+        }
+        Ok(())
+    }
+
+    fn test_drm_device(
+        &self,
+        dev: &Rc<MetalDrmDeviceData>,
+        configs: &[ConnectorConfig],
+        ctx: &MetalRenderContext,
+    ) -> Result<(), MetalError> {
+        let mut changes = dev.dev.master.change();
+        for plane in dev.dev.planes.values() {
+            changes.change_object(plane.id, |c| {
+                c.change(plane.crtc_id.id, 0);
+                c.change(plane.fb_id, 0);
+            })
+        }
+        for crtc in dev.dev.crtcs.values() {
+            changes.change_object(crtc.id, |c| {
+                c.change(crtc.active.id, 0);
+                c.change(crtc.mode_id.id, 0);
+            })
+        }
+        let mut used_crtcs = AHashSet::new();
+        let mut used_planes = AHashSet::new();
+        let mut mode_blobs = vec![];
+        let mut buffers = vec![];
+        for connector in dev.connectors.lock().values() {
+            let dd = connector.display.borrow_mut();
+            changes.change_object(connector.id, |c| {
+                c.change(dd.crtc_id.id, 0);
+            });
+            let config = configs
+                .iter()
+                .find(|c| c.connector == connector.connector_id);
+            let enabled = match config {
+                Some(c) => c.enabled,
+                _ => connector.enabled.get(),
+            };
+            if !enabled || dd.connection != ConnectorStatus::Connected {
+                continue;
+            }
+            let mode = match config.and_then(|c| c.mode) {
+                Some(mode) => match dd.modes.iter().find(|m| m.to_backend() == mode) {
+                    Some(m) => m.clone(),
+                    _ => return Err(MetalError::NoModeForConnector),
+                },
+                _ => match &dd.mode {
+                    Some(m) => (**m).clone(),
+                    _ => return Err(MetalError::NoModeForConnector),
+                },
+            };
+            let crtc = match dd.crtcs.values().find(|c| !used_crtcs.contains(&c.id)) {
+                Some(c) => c.clone(),
+                _ => return Err(MetalError::NoCrtcForConnector),
+            };
+            used_crtcs.insert(crtc.id);
+            let primary_plane = crtc.possible_planes.values().find(|p| {
+                p.ty == PlaneType::Primary
+                    && !used_planes.contains(&p.id)
+                    && p.formats.contains_key(&XRGB8888.drm)
+            });
+            let primary_plane = match primary_plane {
+                Some(p) => p.clone(),
+                _ => return Err(MetalError::NoPrimaryPlaneForConnector),
+            };
+            used_planes.insert(primary_plane.id);
+            let mode_blob = mode.create_blob(&connector.master)?;
+            let buffer = self.create_scanout_buffer(
+                &connector.dev,
+                &ModifiedFormat {
+                    format: XRGB8888,
+                    modifier: INVALID_MODIFIER,
+                },
+                mode.hdisplay as _,
+                mode.vdisplay as _,
+                ctx,
+                false,
+            )?;
+            changes.change_object(connector.id, |c| {
+                c.change(dd.crtc_id.id, crtc.id.0 as _);
+            });
+            changes.change_object(crtc.id, |c| {
+                c.change(crtc.active.id, 1);
+                c.change(crtc.mode_id.id, mode_blob.id().0 as _);
+            });
+            changes.change_object(primary_plane.id, |c| {
+                c.change(primary_plane.fb_id, buffer.drm.id().0 as _);
+                c.change(primary_plane.crtc_id.id, crtc.id.0 as _);
+                c.change(primary_plane.crtc_x.id, 0);
+                c.change(primary_plane.crtc_y.id, 0);
+                c.change(primary_plane.crtc_w.id, mode.hdisplay as _);
+                c.change(primary_plane.crtc_h.id, mode.vdisplay as _);
+                c.change(primary_plane.src_x.id, 0);
+                c.change(primary_plane.src_y.id, 0);
+                c.change(primary_plane.src_w.id, (mode.hdisplay as u64) << 16);
+                c.change(primary_plane.src_h.id, (mode.vdisplay as u64) << 16);
+            });
+            mode_blobs.push(mode_blob);
+            buffers.push(buffer);
+        }
+        if let Err(e) = changes.test(DRM_MODE_ATOMIC_ALLOW_MODESET) {
+            return Err(MetalError::Modeset(e));
+        }
+        Ok(())
+    }
+
     fn can_use_current_drm_mode(&self, dev: &Rc<MetalDrmDeviceData>) -> bool {
         let mut used_crtcs = AHashSet::new();
         let mut used_planes = AHashSet::new();
@@ -1649,12 +2327,15 @@ impl MetalBackend {
 
             (Some(dev_tex), render_tex, Some(render_fb))
         };
+        let mut damage = RegionBuilder::default();
+        damage.add(Rect::new_sized(0, 0, width, height).unwrap());
         Ok(RenderBuffer {
             drm: drm_fb,
             dev_fb,
             dev_tex,
             render_tex,
             render_fb,
+            damage: RefCell::new(damage),
         })
     }
 
@@ -1680,19 +2361,38 @@ impl MetalBackend {
             _ => return Err(MetalError::NoModeForConnector),
         };
         let mode_blob = mode.create_blob(&connector.master)?;
+        let gamma_blob = match &crtc.gamma_lut {
+            Some(_) => Some(crtc.create_gamma_blob(connector.gamma_lut.get().as_deref())?),
+            _ => None,
+        };
         changes.change_object(connector.id, |c| {
             c.change(dd.crtc_id.id, crtc.id.0 as _);
         });
+        let vrr_enabled = connector.vrr_enabled.get() && dd.vrr_capable;
         changes.change_object(crtc.id, |c| {
             c.change(crtc.active.id, 1);
             c.change(crtc.mode_id.id, mode_blob.id().0 as _);
+            if let Some(vrr) = &crtc.vrr_enabled {
+                c.change(vrr.id, vrr_enabled as _);
+            }
+            if let (Some(gamma), Some(blob)) = (&crtc.gamma_lut, &gamma_blob) {
+                let id = blob.as_ref().map(|b| b.id()).unwrap_or(DrmBlob::NONE);
+                c.change(gamma.id, id.0 as _);
+            }
         });
         connector.crtc.set(Some(crtc.clone()));
         dd.crtc_id.value.set(crtc.id);
         crtc.connector.set(Some(connector.clone()));
         crtc.active.value.set(true);
         crtc.mode_id.value.set(mode_blob.id());
+        if let Some(vrr) = &crtc.vrr_enabled {
+            vrr.value.set(vrr_enabled);
+        }
         crtc.mode_blob.set(Some(Rc::new(mode_blob)));
+        if let Some(blob) = gamma_blob {
+            crtc.set_gamma_blob(blob);
+        }
+        connector.gamma_changed.set(false);
         Ok(())
     }
 
@@ -1747,6 +2447,17 @@ impl MetalBackend {
                 break;
             }
         }
+        if cursor_plane.is_none() {
+            for plane in crtc.possible_planes.values() {
+                if plane.ty == PlaneType::Overlay
+                    && !plane.assigned.get()
+                    && plane.formats.contains_key(&ARGB8888.drm)
+                {
+                    cursor_plane = Some(plane.clone());
+                    break;
+                }
+            }
+        }
         let mut cursor_buffers = None;
         if cursor_plane.is_some() {
             let res = self.create_scanout_buffers(
@@ -1805,6 +2516,17 @@ impl MetalBackend {
         }
         connector.cursor_plane.set(cursor_plane);
         connector.cursor_enabled.set(false);
+        let mut overlay_planes = vec![];
+        for plane in crtc.possible_planes.values() {
+            if overlay_planes.len() >= MAX_OVERLAY_PLANES {
+                break;
+            }
+            if plane.ty == PlaneType::Overlay && !plane.assigned.get() {
+                plane.assigned.set(true);
+                overlay_planes.push(plane.clone());
+            }
+        }
+        *connector.overlay_planes.borrow_mut() = overlay_planes;
         Ok(())
     }
 
@@ -1842,6 +2564,8 @@ pub struct RenderBuffer {
     // ctx = render
     // buffer location = render
     render_fb: Option<Rc<Framebuffer>>,
+    // the damage accumulated since this buffer was last rendered to
+    damage: RefCell<RegionBuilder>,
 }
 
 impl RenderBuffer {
@@ -1850,6 +2574,24 @@ impl RenderBuffer {
             .clone()
             .unwrap_or_else(|| self.dev_fb.clone())
     }
+
+    fn add_damage(&self, damage: &Region) {
+        let mut builder = self.damage.borrow_mut();
+        for rect in damage.iter() {
+            builder.add(*rect);
+        }
+    }
+
+    fn take_damage(&self) -> Rc<Region> {
+        let mut builder = self.damage.borrow_mut();
+        let damage = builder.get();
+        builder.clear();
+        damage
+    }
+}
+
+fn refresh_nsec(mode: &DrmModeInfo) -> u32 {
+    (1_000_000_000_000u64 / (mode.refresh_rate_millihz() as u64)) as u32
 }
 
 fn modes_equal(a: &DrmModeInfo, b: &DrmModeInfo) -> bool {
@@ -2,10 +2,10 @@ use {
     crate::{
         async_engine::{Phase, SpawnedFuture},
         backend::{
-            AxisSource, Backend, BackendDrmDevice, BackendEvent, Connector, ConnectorEvent,
-            ConnectorId, ConnectorKernelId, DrmDeviceId, DrmEvent, InputDevice,
-            InputDeviceAccelProfile, InputDeviceCapability, InputDeviceId, InputEvent, KeyState,
-            Mode, MonitorInfo, ScrollAxis, TransformMatrix, AXIS_120,
+            AxisSource, Backend, BackendDrmDevice, BackendEvent, Connector, ConnectorError,
+            ConnectorEvent, ConnectorId, ConnectorKernelId, DrmDeviceId, DrmEvent, GammaLut,
+            InputDevice, InputDeviceAccelProfile, InputDeviceCapability, InputDeviceId, InputEvent,
+            KeyState, Mode, MonitorInfo, ScrollAxis, TransformMatrix, AXIS_120,
         },
         fixed::Fixed,
         format::XRGB8888,
@@ -719,6 +719,8 @@ impl XBackend {
         image.last_serial.set(serial);
 
         if let Some(node) = self.state.root.outputs.get(&output.id) {
+            // The images are always redrawn completely.
+            node.take_damage();
             let mut rr = self.render_result.borrow_mut();
             let fb = image.fb.get();
             fb.render(
@@ -729,6 +731,8 @@ impl XBackend {
                 rr.deref_mut(),
                 node.preferred_scale.get(),
                 true,
+                None,
+                &[],
             );
             for fr in rr.frame_requests.drain(..) {
                 fr.send_done();
@@ -1031,7 +1035,27 @@ impl Connector for XOutput {
         Some(self.backend.drm_device_id)
     }
 
-    fn set_enabled(&self, _enabled: bool) {
+    fn set_enabled(&self, _enabled: bool) -> Result<(), ConnectorError> {
+        Ok(())
+    }
+
+    fn set_mode(&self, _mode: Mode) -> Result<(), ConnectorError> {
+        Ok(())
+    }
+
+    fn set_vrr_enabled(&self, _enabled: bool) {
+        // nothing
+    }
+
+    fn set_tearing_allowed(&self, _allowed: bool) {
+        // nothing
+    }
+
+    fn gamma_size(&self) -> u32 {
+        0
+    }
+
+    fn set_gamma_lut(&self, _lut: Option<Rc<GammaLut>>) {
         // nothing
     }
 }
@@ -3,7 +3,7 @@ use {
         client::{Client, ClientError},
         ifs::{
             ipc::{
-                wl_data_source::WlDataSource,
+                wl_data_source::WlDataSource, zwlr_data_control_source_v1::ZwlrDataControlSourceV1,
                 zwp_primary_selection_source_v1::ZwpPrimarySelectionSourceV1,
             },
             jay_output::JayOutput,
@@ -21,6 +21,7 @@ use {
             },
             xdg_positioner::XdgPositioner,
             xdg_wm_base::XdgWmBase,
+            zwlr_output_head_v1::ZwlrOutputHeadV1,
         },
         object::{Object, ObjectId},
         utils::{
@@ -30,7 +31,8 @@ use {
         wire::{
             JayOutputId, JayScreencastId, JayWorkspaceId, WlBufferId, WlDataSourceId, WlOutputId,
             WlPointerId, WlRegionId, WlRegistryId, WlSeatId, WlSurfaceId, XdgPositionerId,
-            XdgSurfaceId, XdgToplevelId, XdgWmBaseId, ZwpPrimarySelectionSourceV1Id,
+            XdgSurfaceId, XdgToplevelId, XdgWmBaseId, ZwlrDataControlSourceV1Id,
+            ZwlrOutputHeadV1Id, ZwpPrimarySelectionSourceV1Id,
         },
     },
     std::{cell::RefCell, mem, rc::Rc},
@@ -47,6 +49,8 @@ pub struct Objects {
     pub wl_data_source: CopyHashMap<WlDataSourceId, Rc<WlDataSource>>,
     pub zwp_primary_selection_source:
         CopyHashMap<ZwpPrimarySelectionSourceV1Id, Rc<ZwpPrimarySelectionSourceV1>>,
+    pub zwlr_data_control_source:
+        CopyHashMap<ZwlrDataControlSourceV1Id, Rc<ZwlrDataControlSourceV1>>,
     pub xdg_positioners: CopyHashMap<XdgPositionerId, Rc<XdgPositioner>>,
     pub regions: CopyHashMap<WlRegionId, Rc<WlRegion>>,
     pub buffers: CopyHashMap<WlBufferId, Rc<WlBuffer>>,
@@ -56,6 +60,7 @@ pub struct Objects {
     pub xdg_wm_bases: CopyHashMap<XdgWmBaseId, Rc<XdgWmBase>>,
     pub seats: CopyHashMap<WlSeatId, Rc<WlSeat>>,
     pub screencasts: CopyHashMap<JayScreencastId, Rc<JayScreencast>>,
+    pub output_heads: CopyHashMap<ZwlrOutputHeadV1Id, Rc<ZwlrOutputHeadV1>>,
     ids: RefCell<Vec<usize>>,
 }
 
@@ -74,6 +79,7 @@ impl Objects {
             xdg_toplevel: Default::default(),
             wl_data_source: Default::default(),
             zwp_primary_selection_source: Default::default(),
+            zwlr_data_control_source: Default::default(),
             xdg_positioners: Default::default(),
             regions: Default::default(),
             buffers: Default::default(),
@@ -83,6 +89,7 @@ impl Objects {
             xdg_wm_bases: Default::default(),
             seats: Default::default(),
             screencasts: Default::default(),
+            output_heads: Default::default(),
             ids: RefCell::new(vec![]),
         }
     }
@@ -105,6 +112,7 @@ impl Objects {
         self.xdg_toplevel.clear();
         self.wl_data_source.clear();
         self.zwp_primary_selection_source.clear();
+        self.zwlr_data_control_source.clear();
         self.xdg_positioners.clear();
         self.regions.clear();
         self.buffers.clear();
@@ -114,6 +122,7 @@ impl Objects {
         self.seats.clear();
         self.pointers.clear();
         self.screencasts.clear();
+        self.output_heads.clear();
     }
 
     pub fn id<T>(&self, client_data: &Client) -> Result<T, ClientError>
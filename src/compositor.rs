@@ -27,7 +27,7 @@ use {
         tasks::{self, idle},
         tree::{
             container_layout, container_render_data, float_layout, float_titles,
-            output_render_data, DisplayNode, NodeIds, OutputNode, WorkspaceNode,
+            output_render_data, DisplayNode, NodeIds, OutputNode, VrrMode, WorkspaceNode,
         },
         user_session::import_environment,
         utils::{
@@ -140,7 +140,9 @@ fn start_compositor2(
         connector_ids: Default::default(),
         root: Rc::new(DisplayNode::new(node_ids.next())),
         workspaces: Default::default(),
+        workspace_outputs: Default::default(),
         dummy_output: Default::default(),
+        scratchpad: Default::default(),
         node_ids,
         backend_events: AsyncQueue::new(),
         seat_ids: Default::default(),
@@ -150,6 +152,7 @@ fn start_compositor2(
         tree_changed_sent: Cell::new(false),
         config: Default::default(),
         input_device_ids: Default::default(),
+        tablet_tool_ids: Default::default(),
         input_device_handlers: Default::default(),
         theme: Default::default(),
         pending_container_layout: Default::default(),
@@ -157,6 +160,7 @@ fn start_compositor2(
         pending_output_render_data: Default::default(),
         pending_float_layout: Default::default(),
         pending_float_titles: Default::default(),
+        pending_workspace_events: Default::default(),
         dbus: Dbus::new(&engine, &ring, &run_toplevel),
         fdcloser: FdCloser::new(),
         logger,
@@ -171,6 +175,7 @@ fn start_compositor2(
             timeout_changed: Default::default(),
             inhibitors: Default::default(),
             inhibitors_changed: Default::default(),
+            notifications: Default::default(),
         },
         run_args,
         xwayland: XWaylandState {
@@ -198,7 +203,13 @@ fn start_compositor2(
         testers: Default::default(),
         render_ctx_watchers: Default::default(),
         workspace_watchers: Default::default(),
+        output_managers: Default::default(),
+        foreign_toplevel_managers: Default::default(),
+        workspace_managers: Default::default(),
+        toplevels: Default::default(),
         default_workspace_capture: Cell::new(true),
+        activation_tokens: Default::default(),
+        activation_switches_workspace: Cell::new(true),
     });
     state.tracker.register(ClientId::from_raw(0));
     create_dummy_output(&state);
@@ -280,6 +291,7 @@ fn start_global_event_handlers(
     vec![
         eng.spawn(tasks::handle_backend_events(state.clone())),
         eng.spawn(tasks::handle_slow_clients(state.clone())),
+        eng.spawn(tasks::handle_workspace_events(state.clone())),
         eng.spawn(tasks::handle_hardware_cursor_tick(state.clone())),
         eng.spawn2(Phase::Layout, container_layout(state.clone())),
         eng.spawn2(Phase::PostLayout, container_render_data(state.clone())),
@@ -365,6 +377,13 @@ fn create_dummy_output(state: &Rc<State>) {
                 name: "Dummy".to_string(),
                 drm_dev: None,
                 async_event: Default::default(),
+                enabled: Cell::new(true),
+                monitor_info: Default::default(),
+                pending_position: Cell::new(None),
+                pending_scale: Cell::new(None),
+                vrr_mode: Cell::new(VrrMode::Never),
+                gamma_lut: Default::default(),
+                gamma_control: Default::default(),
             }),
             0,
             &backend::Mode {
@@ -394,6 +413,7 @@ fn create_dummy_output(state: &Rc<State>) {
         hardware_cursor: Default::default(),
         update_render_data_scheduled: Cell::new(false),
         screencasts: Default::default(),
+        damage: Default::default(),
     });
     let dummy_workspace = Rc::new(WorkspaceNode {
         id: state.node_ids.next(),
@@ -416,6 +436,24 @@ fn create_dummy_output(state: &Rc<State>) {
         dummy_output.workspaces.add_last(dummy_workspace.clone()),
     ));
     dummy_output.show_workspace(&dummy_workspace);
+    let scratchpad = Rc::new(WorkspaceNode {
+        id: state.node_ids.next(),
+        is_dummy: true,
+        output: CloneCell::new(dummy_output.clone()),
+        position: Default::default(),
+        container: Default::default(),
+        stacked: Default::default(),
+        seat_state: Default::default(),
+        name: "scratchpad".to_string(),
+        output_link: Default::default(),
+        visible: Default::default(),
+        fullscreen: Default::default(),
+        visible_on_desired_output: Default::default(),
+        desired_output: CloneCell::new(dummy_output.global.output_id.clone()),
+        jay_workspaces: Default::default(),
+        capture: Cell::new(false),
+    });
+    state.scratchpad.set(Some(scratchpad));
     state.dummy_output.set(Some(dummy_output));
 }
 
@@ -5,9 +5,11 @@ use crate::it::test_config::TEST_CONFIG_ENTRY;
 use {
     crate::{
         backend::{ConnectorId, DrmDeviceId, InputDeviceId},
+        client::PidInfo,
         config::handler::ConfigProxyHandler,
         ifs::wl_seat::SeatId,
         state::State,
+        tree::NodeId,
         utils::{
             clonecell::CloneCell, numcell::NumCell, oserror::OsError, ptr_ext::PtrExt,
             unlink_on_drop::UnlinkOnDrop, xrd::xrd,
@@ -19,9 +21,11 @@ use {
             ipc::{InitMessage, ServerMessage, V1InitMessage},
             ConfigEntry, VERSION,
         },
-        input::{InputDevice, Seat},
+        input::{InputDevice, Seat, ShortcutsInhibitRequest},
         keyboard::ModifiedKeySym,
         video::{Connector, DrmDevice},
+        window::Window,
+        Direction,
     },
     libloading::Library,
     std::{cell::Cell, mem, ptr, rc::Rc},
@@ -46,6 +50,22 @@ pub struct ConfigProxy {
     handler: CloneCell<Option<Rc<ConfigProxyHandler>>>,
 }
 
+/// A workspace change that is reported to the config after the tree has been updated.
+pub enum WorkspaceEvent {
+    Created {
+        name: String,
+        connector: ConnectorId,
+    },
+    Destroyed {
+        name: String,
+        connector: ConnectorId,
+    },
+    Shown {
+        name: String,
+        connector: ConnectorId,
+    },
+}
+
 impl ConfigProxy {
     fn send(&self, msg: &ServerMessage) {
         if let Some(handler) = self.handler.get() {
@@ -70,6 +90,14 @@ impl ConfigProxy {
         });
     }
 
+    pub fn invoke_swipe(&self, seat: SeatId, fingers: u32, direction: Direction) {
+        self.send(&ServerMessage::InvokeSwipe {
+            seat: Seat(seat.raw() as _),
+            fingers,
+            direction,
+        });
+    }
+
     pub fn new_drm_dev(&self, dev: DrmDeviceId) {
         self.send(&ServerMessage::NewDrmDev {
             device: DrmDevice(dev.raw() as _),
@@ -88,6 +116,54 @@ impl ConfigProxy {
         });
     }
 
+    pub fn new_window(&self, node: NodeId) {
+        self.send(&ServerMessage::NewWindow {
+            window: Window(node.raw() as _),
+        });
+    }
+
+    pub fn window_focused(&self, seat: SeatId, node: Option<NodeId>) {
+        self.send(&ServerMessage::WindowFocused {
+            seat: Seat(seat.raw() as _),
+            window: node.map(|node| Window(node.raw() as _)),
+        });
+    }
+
+    pub fn window_title_changed(&self, node: NodeId) {
+        self.send(&ServerMessage::WindowTitleChanged {
+            window: Window(node.raw() as _),
+        });
+    }
+
+    pub fn window_fullscreen_changed(&self, node: NodeId, fullscreen: bool) {
+        self.send(&ServerMessage::WindowFullscreenChanged {
+            window: Window(node.raw() as _),
+            fullscreen,
+        });
+    }
+
+    pub fn workspace_event(&self, event: &WorkspaceEvent) {
+        let handler = match self.handler.get() {
+            Some(handler) => handler,
+            _ => return,
+        };
+        let msg = match event {
+            WorkspaceEvent::Created { name, connector } => ServerMessage::WorkspaceCreated {
+                workspace: handler.workspace_by_name(name),
+                connector: Connector(connector.raw() as _),
+            },
+            WorkspaceEvent::Destroyed { name, connector } => ServerMessage::WorkspaceDestroyed {
+                workspace: handler.workspace_by_name(name),
+                connector: Connector(connector.raw() as _),
+            },
+            WorkspaceEvent::Shown { name, connector } => ServerMessage::WorkspaceShown {
+                workspace: handler.workspace_by_name(name),
+                connector: Connector(connector.raw() as _),
+            },
+        };
+        handler.send(&msg);
+    }
+
     pub fn del_connector(&self, connector: ConnectorId) {
         self.send(&ServerMessage::DelConnector {
             device: Connector(connector.raw() as _),
@@ -133,6 +209,48 @@ impl ConfigProxy {
     pub fn idle(&self) {
         self.send(&ServerMessage::Idle);
     }
+
+    /// Asks the config whether a client may inhibit the shortcuts of a seat.
+    pub fn shortcuts_inhibit_allowed(
+        &self,
+        seat: SeatId,
+        app_id: &str,
+        pid_info: &PidInfo,
+    ) -> bool {
+        let handler = match self.handler.get() {
+            Some(handler) => handler,
+            _ => return true,
+        };
+        if !handler.shortcuts_inhibit_hook.get() {
+            return true;
+        }
+        handler.shortcuts_inhibit_decision.take();
+        handler.send(&ServerMessage::ShortcutsInhibitRequest {
+            request: ShortcutsInhibitRequest {
+                seat: Seat(seat.raw() as _),
+                app_id: app_id.to_string(),
+                pid: pid_info.pid,
+                comm: pid_info.comm.clone(),
+            },
+        });
+        handler.shortcuts_inhibit_decision.take().unwrap_or(true)
+    }
+
+    /// Asks the config whether a window may take the focus after requesting activation.
+    pub fn activation_allowed(&self, node: NodeId) -> bool {
+        let handler = match self.handler.get() {
+            Some(handler) => handler,
+            _ => return true,
+        };
+        if !handler.activation_hook.get() {
+            return true;
+        }
+        handler.activation_decision.take();
+        handler.send(&ServerMessage::ActivationRequest {
+            window: Window(node.raw() as _),
+        });
+        handler.activation_decision.take().unwrap_or(true)
+    }
 }
 
 impl Drop for ConfigProxy {
@@ -174,6 +292,10 @@ impl ConfigProxy {
             timer_ids: NumCell::new(1),
             timers_by_name: Default::default(),
             timers_by_id: Default::default(),
+            shortcuts_inhibit_hook: Cell::new(false),
+            shortcuts_inhibit_decision: Cell::new(None),
+            activation_hook: Cell::new(false),
+            activation_decision: Cell::new(None),
         });
         let init_msg =
             bincode::encode_to_vec(&InitMessage::V1(V1InitMessage {}), bincode_ops()).unwrap();
@@ -2,16 +2,22 @@ use {
     crate::{
         async_engine::SpawnedFuture,
         backend::{
-            self, ConnectorId, DrmDeviceId, InputDeviceAccelProfile, InputDeviceCapability,
-            InputDeviceId,
+            self, ConnectorError, ConnectorId, DrmDeviceId, GammaLut, InputDeviceAccelProfile,
+            InputDeviceCapability, InputDeviceId,
         },
         compositor::MAX_EXTENTS,
         config::ConfigProxy,
-        ifs::wl_seat::{SeatId, WlSeatGlobal},
+        ifs::{
+            wl_seat::{SeatId, WlSeatGlobal},
+            xdg_activation_token_v1::XDG_ACTIVATION_TOKEN,
+        },
         scale::Scale,
         state::{ConnectorData, DeviceHandlerData, DrmDevData, OutputData, State},
         theme::{Color, ThemeSized, DEFAULT_FONT},
-        tree::{ContainerNode, ContainerSplit, FloatNode, Node, NodeVisitorBase, OutputNode},
+        tree::{
+            self, ContainerNode, ContainerSplit, FloatNode, Node, NodeId, NodeVisitorBase,
+            OutputNode, ToplevelNode,
+        },
         utils::{
             copyhashmap::CopyHashMap,
             debug_fn::debug_fn,
@@ -36,11 +42,12 @@ use {
             },
             InputDevice, Seat,
         },
-        keyboard::{mods::Modifiers, syms::KeySym, Keymap},
+        keyboard::{mods::Modifiers, syms::KeySym, Keymap, ModifiedKeySym},
         logging::LogLevel,
         theme::{colors::Colorable, sized::Resizable},
         timer::Timer as JayTimer,
-        video::{Connector, DrmDevice},
+        video::{Connector, DrmDevice, Mode, VrrMode},
+        window::Window,
         Axis, Direction, Workspace,
     },
     libloading::Library,
@@ -69,6 +76,12 @@ pub(super) struct ConfigProxyHandler {
     pub timer_ids: NumCell<u64>,
     pub timers_by_name: CopyHashMap<Rc<String>, Rc<TimerData>>,
     pub timers_by_id: CopyHashMap<u64, Rc<TimerData>>,
+
+    pub shortcuts_inhibit_hook: Cell<bool>,
+    pub shortcuts_inhibit_decision: Cell<Option<bool>>,
+
+    pub activation_hook: Cell<bool>,
+    pub activation_decision: Cell<Option<bool>>,
 }
 
 pub(super) struct TimerData {
@@ -228,6 +241,7 @@ impl ConfigProxyHandler {
             config.destroy();
             for seat in self.state.globals.seats.lock().values() {
                 seat.clear_shortcuts();
+                seat.clear_swipe_bindings();
             }
         }
         config.configure(true);
@@ -427,6 +441,18 @@ impl ConfigProxyHandler {
         }
     }
 
+    fn get_window(&self, window: Window) -> Result<Rc<dyn ToplevelNode>, CphError> {
+        let tl = self
+            .state
+            .toplevels
+            .get(&NodeId(window.0 as _))
+            .and_then(|tl| tl.upgrade());
+        match tl {
+            Some(tl) => Ok(tl),
+            _ => Err(CphError::WindowDoesNotExist(window)),
+        }
+    }
+
     fn get_seat(&self, seat: Seat) -> Result<Rc<WlSeatGlobal>, CphError> {
         let seats = self.state.globals.seats.lock();
         for seat_global in seats.values() {
@@ -459,7 +485,7 @@ impl ConfigProxyHandler {
             Some(self.get_seat(seat)?)
         };
         let dev = self.get_device_handler_data(device)?;
-        dev.seat.set(seat);
+        dev.set_seat(seat);
         Ok(())
     }
 
@@ -512,6 +538,17 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_set_input_device_connector(
+        &self,
+        device: InputDevice,
+        connector: Connector,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        let connector = self.get_connector(connector)?;
+        dev.connector.set(Some(connector.connector.id()));
+        Ok(())
+    }
+
     fn handle_set_drag_lock_enabled(
         &self,
         device: InputDevice,
@@ -532,7 +569,7 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
-    fn handle_get_workspace(&self, name: &str) {
+    pub fn workspace_by_name(&self, name: &str) -> Workspace {
         let name = Rc::new(name.to_owned());
         let ws = match self.workspaces_by_name.get(&name) {
             Some(w) => w,
@@ -543,9 +580,63 @@ impl ConfigProxyHandler {
                 ws
             }
         };
-        self.respond(Response::GetWorkspace {
-            workspace: Workspace(ws),
-        });
+        Workspace(ws)
+    }
+
+    fn handle_get_workspace(&self, name: &str) {
+        let workspace = self.workspace_by_name(name);
+        self.respond(Response::GetWorkspace { workspace });
+    }
+
+    fn handle_set_workspace_output(
+        &self,
+        workspace: Workspace,
+        connector: Connector,
+    ) -> Result<(), CphError> {
+        let name = self.get_workspace(workspace)?;
+        let output = self.get_output(connector)?;
+        match self.state.workspaces.get(name.as_str()) {
+            Some(ws) => self.state.move_workspace(&ws, &output.node),
+            _ => {
+                let output_id = output.node.global.output_id.clone();
+                self.state
+                    .workspace_outputs
+                    .set(name.to_string(), output_id);
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_move_workspace_to_output(
+        &self,
+        workspace: Workspace,
+        direction: Direction,
+    ) -> Result<(), CphError> {
+        let name = self.get_workspace(workspace)?;
+        if let Some(ws) = self.state.workspaces.get(name.as_str()) {
+            let output = ws.output.get();
+            if let Some(target) = self.state.output_in_direction(&output, direction.into()) {
+                self.state.move_workspace(&ws, &target);
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_get_workspace_windows(&self, workspace: Workspace) -> Result<(), CphError> {
+        let name = self.get_workspace(workspace)?;
+        let mut windows = vec![];
+        for tl in self.state.toplevels.lock().values() {
+            if let Some(tl) = tl.upgrade() {
+                if let Some(ws) = tl.tl_data().workspace.get() {
+                    if !ws.is_dummy && ws.name == *name {
+                        windows.push(Window(tl.node_id().raw() as _));
+                    }
+                }
+            }
+        }
+        windows.sort_by_key(|w| w.0);
+        self.respond(Response::GetWindows { windows });
+        Ok(())
     }
 
     fn handle_get_workspace_capture(&self, workspace: Workspace) -> Result<(), CphError> {
@@ -558,6 +649,222 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_get_window_title(&self, window: Window) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        let title = tl.tl_data().title.borrow().clone();
+        self.respond(Response::GetWindowTitle { title });
+        Ok(())
+    }
+
+    fn handle_get_window_app_id(&self, window: Window) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        let app_id = tl.tl_data().app_id.borrow().clone();
+        self.respond(Response::GetWindowAppId { app_id });
+        Ok(())
+    }
+
+    fn handle_get_window_x_class(&self, window: Window) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        let class = match tl.tl_xwindow_info() {
+            Some(info) => info.class.borrow().as_ref().map(|c| c.to_string()),
+            _ => None,
+        };
+        self.respond(Response::GetWindowXClass {
+            class: class.unwrap_or_default(),
+        });
+        Ok(())
+    }
+
+    fn handle_get_window_x_instance(&self, window: Window) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        let instance = match tl.tl_xwindow_info() {
+            Some(info) => info.instance.borrow().as_ref().map(|c| c.to_string()),
+            _ => None,
+        };
+        self.respond(Response::GetWindowXInstance {
+            instance: instance.unwrap_or_default(),
+        });
+        Ok(())
+    }
+
+    fn handle_get_window_x_role(&self, window: Window) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        let role = match tl.tl_xwindow_info() {
+            Some(info) => info.role.borrow().as_ref().map(|c| c.to_string()),
+            _ => None,
+        };
+        self.respond(Response::GetWindowXRole {
+            role: role.unwrap_or_default(),
+        });
+        Ok(())
+    }
+
+    fn handle_get_window_pid(&self, window: Window) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        let pid = match tl.tl_xwindow_info() {
+            Some(info) => info.pid.get().map(|pid| pid as i32),
+            _ => tl.tl_data().client.as_ref().map(|c| c.pid_info.pid),
+        };
+        self.respond(Response::GetWindowPid { pid });
+        Ok(())
+    }
+
+    fn handle_get_window_is_xwayland(&self, window: Window) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        self.respond(Response::GetWindowIsXwayland {
+            xwayland: tl.tl_xwindow_info().is_some(),
+        });
+        Ok(())
+    }
+
+    fn handle_window_exists(&self, window: Window) {
+        let exists = self.get_window(window).is_ok();
+        self.respond(Response::WindowExists { exists });
+    }
+
+    fn handle_get_window_workspace(&self, window: Window) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        let workspace = match tl.tl_data().workspace.get() {
+            Some(ws) if !ws.is_dummy => self.workspace_by_name(&ws.name),
+            _ => Workspace(0),
+        };
+        self.respond(Response::GetWindowWorkspace { workspace });
+        Ok(())
+    }
+
+    fn handle_get_window_floating(&self, window: Window) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        self.respond(Response::GetFloating {
+            floating: tl.tl_data().is_floating.get(),
+        });
+        Ok(())
+    }
+
+    fn handle_get_window_fullscreen(&self, window: Window) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        self.respond(Response::GetFullscreen {
+            fullscreen: tl.tl_data().is_fullscreen.get(),
+        });
+        Ok(())
+    }
+
+    fn handle_focus_window(&self, window: Window, seat: Seat) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        let seat = self.get_seat(seat)?;
+        if self.state.is_hidden(&*tl) {
+            self.state.show_hidden_toplevel(&seat, tl);
+            return Ok(());
+        }
+        if let Some(ws) = tl.tl_data().workspace.get() {
+            if !ws.visible.get() {
+                self.state.show_workspace(&seat, &ws.name);
+            }
+        }
+        seat.focus_toplevel(tl);
+        Ok(())
+    }
+
+    fn handle_close_window(&self, window: Window) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        tl.tl_close();
+        Ok(())
+    }
+
+    fn handle_get_focused_window(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        let window = seat
+            .get_focused_toplevel()
+            .map(|tl| Window(tl.node_id().raw() as _));
+        self.respond(Response::GetFocusedWindow { window });
+        Ok(())
+    }
+
+    fn handle_set_window_workspace(
+        &self,
+        window: Window,
+        workspace: Workspace,
+    ) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        let name = self.get_workspace(workspace)?;
+        if let Some(rules) = tl.tl_data().pending_rules.borrow_mut().as_mut() {
+            rules.workspace = Some(name);
+            return Ok(());
+        }
+        let workspace = match self.state.workspaces.get(name.deref()) {
+            Some(ws) => ws,
+            _ => match tl.tl_data().workspace.get() {
+                Some(ws) => ws.output.get().create_workspace(name.deref()),
+                _ => return Ok(()),
+            },
+        };
+        self.state.move_toplevel_to_workspace(tl, &workspace);
+        Ok(())
+    }
+
+    fn handle_set_window_floating(&self, window: Window, floating: bool) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        if let Some(rules) = tl.tl_data().pending_rules.borrow_mut().as_mut() {
+            rules.floating = Some(floating);
+            return Ok(());
+        }
+        self.state.set_toplevel_floating(tl, floating);
+        Ok(())
+    }
+
+    fn handle_set_window_float_size(
+        &self,
+        window: Window,
+        width: i32,
+        height: i32,
+    ) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        if width <= 0 || height <= 0 {
+            return Err(CphError::InvalidFloatSize(width, height));
+        }
+        self.state.set_toplevel_float_size(tl, width, height);
+        Ok(())
+    }
+
+    fn handle_set_window_fullscreen(
+        &self,
+        window: Window,
+        fullscreen: bool,
+    ) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        if let Some(rules) = tl.tl_data().pending_rules.borrow_mut().as_mut() {
+            rules.fullscreen = fullscreen;
+            return Ok(());
+        }
+        tl.tl_set_fullscreen(fullscreen);
+        Ok(())
+    }
+
+    fn handle_hide_window(&self, window: Window) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        self.state.hide_toplevel(tl);
+        Ok(())
+    }
+
+    fn handle_get_window_hidden(&self, window: Window) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        self.respond(Response::GetWindowHidden {
+            hidden: self.state.is_hidden(&*tl),
+        });
+        Ok(())
+    }
+
+    fn handle_toggle_scratchpad(&self, seat: Seat, name: &str) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        self.state.toggle_scratchpad(&seat, name);
+        Ok(())
+    }
+
+    fn handle_set_window_sticky(&self, window: Window, sticky: bool) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        tl.tl_data().sticky.set(sticky);
+        Ok(())
+    }
+
     fn handle_set_workspace_capture(
         &self,
         workspace: Workspace,
@@ -578,6 +885,10 @@ impl ConfigProxyHandler {
         });
     }
 
+    fn handle_set_activation_switches_workspace(&self, switch: bool) {
+        self.state.activation_switches_workspace.set(switch);
+    }
+
     fn handle_set_default_workspace_capture(&self, capture: bool) {
         self.state.default_workspace_capture.set(capture);
     }
@@ -653,6 +964,80 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_connector_modes(&self, connector: Connector) -> Result<(), CphError> {
+        let connector = self.get_output(connector)?;
+        let modes = connector
+            .monitor_info
+            .modes
+            .iter()
+            .map(|m| Mode::new(m.width, m.height, m.refresh_rate_millihz))
+            .collect();
+        self.respond(Response::ConnectorModes { modes });
+        Ok(())
+    }
+
+    fn handle_connector_set_mode(&self, connector: Connector, mode: Mode) -> Result<(), CphError> {
+        let output = self.get_output(connector)?;
+        let mode = backend::Mode {
+            width: mode.width(),
+            height: mode.height(),
+            refresh_rate_millihz: mode.refresh_rate(),
+        };
+        if !output.monitor_info.modes.contains(&mode) {
+            return Err(CphError::UnsupportedMode(connector, mode));
+        }
+        output.connector.connector.set_mode(mode)?;
+        Ok(())
+    }
+
+    fn handle_connector_set_vrr_mode(
+        &self,
+        connector: Connector,
+        mode: VrrMode,
+    ) -> Result<(), CphError> {
+        let connector = self.get_connector(connector)?;
+        let mode = match mode {
+            VrrMode::Never => tree::VrrMode::Never,
+            VrrMode::Always => tree::VrrMode::Always,
+            VrrMode::FullscreenOnly => tree::VrrMode::Fullscreen,
+        };
+        connector.vrr_mode.set(mode);
+        if let Some(output) = self.state.outputs.get(&connector.connector.id()) {
+            output.node.update_vrr();
+        }
+        Ok(())
+    }
+
+    fn handle_connector_set_gamma(
+        &self,
+        connector: Connector,
+        red: Vec<u16>,
+        green: Vec<u16>,
+        blue: Vec<u16>,
+    ) -> Result<(), CphError> {
+        let connector = self.get_connector(connector)?;
+        if red.len() != green.len() || red.len() != blue.len() {
+            return Err(CphError::InvalidGammaRamps);
+        }
+        let lut = match red.is_empty() {
+            true => None,
+            false => Some(Rc::new(GammaLut { red, green, blue })),
+        };
+        connector.gamma_lut.set(lut);
+        connector.update_gamma();
+        Ok(())
+    }
+
+    fn handle_seat_set_shortcuts_inhibit_escape(
+        &self,
+        seat: Seat,
+        escape: Option<ModifiedKeySym>,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_shortcuts_inhibit_escape(escape);
+        Ok(())
+    }
+
     fn handle_set_cursor_size(&self, seat: Seat, size: i32) -> Result<(), CphError> {
         let seat = self.get_seat(seat)?;
         if size < 0 {
@@ -728,18 +1113,7 @@ impl ConfigProxyHandler {
         if x < 0 || y < 0 || x > MAX_EXTENTS || y > MAX_EXTENTS {
             return Err(CphError::InvalidConnectorPosition(x, y));
         }
-        let old_pos = connector.node.global.pos.get();
         connector.node.set_position(x, y);
-        let seats = self.state.globals.seats.lock();
-        for seat in seats.values() {
-            if seat.get_output().id == connector.node.id {
-                let seat_pos = seat.position();
-                seat.set_position(
-                    seat_pos.0.round_down() + x - old_pos.x1(),
-                    seat_pos.1.round_down() + y - old_pos.y1(),
-                );
-            }
-        }
         Ok(())
     }
 
@@ -749,7 +1123,7 @@ impl ConfigProxyHandler {
         enabled: bool,
     ) -> Result<(), CphError> {
         let connector = self.get_connector(connector)?;
-        connector.connector.set_enabled(enabled);
+        connector.set_enabled(enabled)?;
         Ok(())
     }
 
@@ -852,6 +1226,28 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_add_swipe_binding(
+        &self,
+        seat: Seat,
+        fingers: u32,
+        direction: Direction,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.add_swipe_binding(fingers, direction);
+        Ok(())
+    }
+
+    fn handle_remove_swipe_binding(
+        &self,
+        seat: Seat,
+        fingers: u32,
+        direction: Direction,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.remove_swipe_binding(fingers, direction);
+        Ok(())
+    }
+
     fn handle_get_input_devices(&self, seat: Option<Seat>) {
         let id = seat.map(|s| SeatId::from_raw(s.0 as _));
         let matches = |dhd: &DeviceHandlerData| {
@@ -891,12 +1287,16 @@ impl ConfigProxyHandler {
         &self,
         prog: &str,
         args: Vec<String>,
-        env: Vec<(String, String)>,
+        mut env: Vec<(String, String)>,
     ) -> Result<(), CphError> {
         let forker = match self.state.forker.get() {
             Some(f) => f,
             _ => return Err(CphError::NoForker),
         };
+        if env.iter().all(|(k, _)| k != XDG_ACTIVATION_TOKEN) {
+            let token = self.state.create_activation_token(None, None);
+            env.push((XDG_ACTIVATION_TOKEN.to_string(), token));
+        }
         forker.spawn(prog.to_string(), args, env, None);
         Ok(())
     }
@@ -1048,6 +1448,8 @@ impl ConfigProxyHandler {
             FOCUSED_TITLE_TEXT_COLOR => &colors.focused_title_text,
             FOCUSED_INACTIVE_TITLE_TEXT_COLOR => &colors.focused_inactive_title_text,
             BAR_STATUS_TEXT_COLOR => &colors.bar_text,
+            URGENT_TITLE_BACKGROUND_COLOR => &colors.urgent_title_background,
+            URGENT_TITLE_TEXT_COLOR => &colors.urgent_title_text,
             _ => return Err(CphError::UnknownColor(colorable.0)),
         };
         Ok(colorable)
@@ -1296,6 +1698,139 @@ impl ConfigProxyHandler {
             ClientMessage::GetWorkspaceCapture { workspace } => self
                 .handle_get_workspace_capture(workspace)
                 .wrn("get_workspace_capture")?,
+            ClientMessage::SetInputDeviceConnector { device, connector } => self
+                .handle_set_input_device_connector(device, connector)
+                .wrn("set_input_device_connector")?,
+            ClientMessage::AddSwipeBinding {
+                seat,
+                fingers,
+                direction,
+            } => self
+                .handle_add_swipe_binding(seat, fingers, direction)
+                .wrn("add_swipe_binding")?,
+            ClientMessage::RemoveSwipeBinding {
+                seat,
+                fingers,
+                direction,
+            } => self
+                .handle_remove_swipe_binding(seat, fingers, direction)
+                .wrn("remove_swipe_binding")?,
+            ClientMessage::ConnectorModes { connector } => self
+                .handle_connector_modes(connector)
+                .wrn("connector_modes")?,
+            ClientMessage::ConnectorSetMode { connector, mode } => self
+                .handle_connector_set_mode(connector, mode)
+                .wrn("connector_set_mode")?,
+            ClientMessage::ConnectorSetVrrMode { connector, mode } => self
+                .handle_connector_set_vrr_mode(connector, mode)
+                .wrn("connector_set_vrr_mode")?,
+            ClientMessage::SetActivationSwitchesWorkspace { switch } => {
+                self.handle_set_activation_switches_workspace(switch)
+            }
+            ClientMessage::ConnectorSetGamma {
+                connector,
+                red,
+                green,
+                blue,
+            } => self
+                .handle_connector_set_gamma(connector, red, green, blue)
+                .wrn("connector_set_gamma")?,
+            ClientMessage::SeatSetShortcutsInhibitEscape { seat, escape } => self
+                .handle_seat_set_shortcuts_inhibit_escape(seat, escape)
+                .wrn("seat_set_shortcuts_inhibit_escape")?,
+            ClientMessage::SetShortcutsInhibitHook { enabled } => {
+                self.shortcuts_inhibit_hook.set(enabled)
+            }
+            ClientMessage::ShortcutsInhibitDecision { allow } => {
+                self.shortcuts_inhibit_decision.set(Some(allow))
+            }
+            ClientMessage::GetWindowTitle { window } => self
+                .handle_get_window_title(window)
+                .wrn("get_window_title")?,
+            ClientMessage::GetWindowAppId { window } => self
+                .handle_get_window_app_id(window)
+                .wrn("get_window_app_id")?,
+            ClientMessage::GetWindowXClass { window } => self
+                .handle_get_window_x_class(window)
+                .wrn("get_window_x_class")?,
+            ClientMessage::GetWindowXInstance { window } => self
+                .handle_get_window_x_instance(window)
+                .wrn("get_window_x_instance")?,
+            ClientMessage::GetWindowXRole { window } => self
+                .handle_get_window_x_role(window)
+                .wrn("get_window_x_role")?,
+            ClientMessage::GetWindowPid { window } => {
+                self.handle_get_window_pid(window).wrn("get_window_pid")?
+            }
+            ClientMessage::GetWindowIsXwayland { window } => self
+                .handle_get_window_is_xwayland(window)
+                .wrn("get_window_is_xwayland")?,
+            ClientMessage::SetWindowWorkspace { window, workspace } => self
+                .handle_set_window_workspace(window, workspace)
+                .wrn("set_window_workspace")?,
+            ClientMessage::SetWindowFloating { window, floating } => self
+                .handle_set_window_floating(window, floating)
+                .wrn("set_window_floating")?,
+            ClientMessage::SetWindowFloatSize {
+                window,
+                width,
+                height,
+            } => self
+                .handle_set_window_float_size(window, width, height)
+                .wrn("set_window_float_size")?,
+            ClientMessage::SetWindowFullscreen { window, fullscreen } => self
+                .handle_set_window_fullscreen(window, fullscreen)
+                .wrn("set_window_fullscreen")?,
+            ClientMessage::SetWindowSticky { window, sticky } => self
+                .handle_set_window_sticky(window, sticky)
+                .wrn("set_window_sticky")?,
+            ClientMessage::WindowExists { window } => self.handle_window_exists(window),
+            ClientMessage::GetWindowWorkspace { window } => self
+                .handle_get_window_workspace(window)
+                .wrn("get_window_workspace")?,
+            ClientMessage::GetWindowFloating { window } => self
+                .handle_get_window_floating(window)
+                .wrn("get_window_floating")?,
+            ClientMessage::GetWindowFullscreen { window } => self
+                .handle_get_window_fullscreen(window)
+                .wrn("get_window_fullscreen")?,
+            ClientMessage::FocusWindow { window, seat } => {
+                self.handle_focus_window(window, seat).wrn("focus_window")?
+            }
+            ClientMessage::CloseWindow { window } => {
+                self.handle_close_window(window).wrn("close_window")?
+            }
+            ClientMessage::GetWorkspaceWindows { workspace } => self
+                .handle_get_workspace_windows(workspace)
+                .wrn("get_workspace_windows")?,
+            ClientMessage::GetFocusedWindow { seat } => self
+                .handle_get_focused_window(seat)
+                .wrn("get_focused_window")?,
+            ClientMessage::SetWorkspaceOutput {
+                workspace,
+                connector,
+            } => self
+                .handle_set_workspace_output(workspace, connector)
+                .wrn("set_workspace_output")?,
+            ClientMessage::MoveWorkspaceToOutput {
+                workspace,
+                direction,
+            } => self
+                .handle_move_workspace_to_output(workspace, direction)
+                .wrn("move_workspace_to_output")?,
+            ClientMessage::HideWindow { window } => {
+                self.handle_hide_window(window).wrn("hide_window")?
+            }
+            ClientMessage::GetWindowHidden { window } => self
+                .handle_get_window_hidden(window)
+                .wrn("get_window_hidden")?,
+            ClientMessage::ToggleScratchpad { seat, name } => self
+                .handle_toggle_scratchpad(seat, name)
+                .wrn("toggle_scratchpad")?,
+            ClientMessage::SetActivationHook { enabled } => self.activation_hook.set(enabled),
+            ClientMessage::ActivationDecision { allow } => {
+                self.activation_decision.set(Some(allow))
+            }
         }
         Ok(())
     }
@@ -1327,6 +1862,8 @@ enum CphError {
     OutputDoesNotExist(Connector),
     #[error("{0}x{1} is not a valid connector position")]
     InvalidConnectorPosition(i32, i32),
+    #[error("Connector {0:?} does not support mode {1:?}")]
+    UnsupportedMode(Connector, backend::Mode),
     #[error("Keymap {0:?} does not exist")]
     KeymapDoesNotExist(Keymap),
     #[error("Seat {0:?} does not exist")]
@@ -1353,6 +1890,14 @@ enum CphError {
     ScaleTooLarge(f64),
     #[error("Tried to set a negative cursor size")]
     NegativeCursorSize,
+    #[error("The gamma ramps have different lengths")]
+    InvalidGammaRamps,
+    #[error("Window {0:?} does not exist")]
+    WindowDoesNotExist(Window),
+    #[error("{0}x{1} is not a valid float size")]
+    InvalidFloatSize(i32, i32),
+    #[error(transparent)]
+    ConnectorError(#[from] ConnectorError),
 }
 
 trait WithRequestName {
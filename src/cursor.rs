@@ -71,6 +71,28 @@ pub struct ServerCursors {
     pub resize_top_right: ServerCursorTemplate,
     pub resize_bottom_left: ServerCursorTemplate,
     pub resize_bottom_right: ServerCursorTemplate,
+    pub context_menu: ServerCursorTemplate,
+    pub help: ServerCursorTemplate,
+    pub progress: ServerCursorTemplate,
+    pub wait: ServerCursorTemplate,
+    pub cell: ServerCursorTemplate,
+    pub crosshair: ServerCursorTemplate,
+    pub text: ServerCursorTemplate,
+    pub vertical_text: ServerCursorTemplate,
+    pub alias: ServerCursorTemplate,
+    pub copy: ServerCursorTemplate,
+    pub move_: ServerCursorTemplate,
+    pub no_drop: ServerCursorTemplate,
+    pub not_allowed: ServerCursorTemplate,
+    pub grab: ServerCursorTemplate,
+    pub grabbing: ServerCursorTemplate,
+    pub resize_top_right_bottom_left: ServerCursorTemplate,
+    pub resize_top_left_bottom_right: ServerCursorTemplate,
+    pub col_resize: ServerCursorTemplate,
+    pub row_resize: ServerCursorTemplate,
+    pub all_scroll: ServerCursorTemplate,
+    pub zoom_in: ServerCursorTemplate,
+    pub zoom_out: ServerCursorTemplate,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, FromPrimitive)]
@@ -83,6 +105,32 @@ pub enum KnownCursor {
     ResizeTopRight,
     ResizeBottomLeft,
     ResizeBottomRight,
+    ResizeLeft,
+    ResizeRight,
+    ResizeTop,
+    ResizeBottom,
+    ContextMenu,
+    Help,
+    Progress,
+    Wait,
+    Cell,
+    Crosshair,
+    Text,
+    VerticalText,
+    Alias,
+    Copy,
+    Move,
+    NoDrop,
+    NotAllowed,
+    Grab,
+    Grabbing,
+    ResizeTopRightBottomLeft,
+    ResizeTopLeftBottomRight,
+    ColResize,
+    RowResize,
+    AllScroll,
+    ZoomIn,
+    ZoomOut,
 }
 
 impl ServerCursors {
@@ -98,21 +146,42 @@ impl ServerCursors {
         let theme = xcursor_theme.as_ref().map(|theme| BStr::new(theme.bytes()));
 
         let load =
-            |name: &str| ServerCursorTemplate::load(name, theme, &scales, &sizes, &paths, ctx);
+            |names: &[&str]| ServerCursorTemplate::load(names, theme, &scales, &sizes, &paths, ctx);
         Ok(Some(Self {
-            default: load("left_ptr")?,
-            pointer: load("hand2")?,
-            // default: load("left_ptr_watch")?,
-            resize_right: load("right_side")?,
-            resize_left: load("left_side")?,
-            resize_top: load("top_side")?,
-            resize_bottom: load("bottom_side")?,
-            resize_top_bottom: load("v_double_arrow")?,
-            resize_left_right: load("h_double_arrow")?,
-            resize_top_left: load("top_left_corner")?,
-            resize_top_right: load("top_right_corner")?,
-            resize_bottom_left: load("bottom_left_corner")?,
-            resize_bottom_right: load("bottom_right_corner")?,
+            default: load(&["left_ptr", "default"])?,
+            pointer: load(&["hand2", "pointer"])?,
+            resize_right: load(&["right_side", "e-resize"])?,
+            resize_left: load(&["left_side", "w-resize"])?,
+            resize_top: load(&["top_side", "n-resize"])?,
+            resize_bottom: load(&["bottom_side", "s-resize"])?,
+            resize_top_bottom: load(&["v_double_arrow", "ns-resize"])?,
+            resize_left_right: load(&["h_double_arrow", "ew-resize"])?,
+            resize_top_left: load(&["top_left_corner", "nw-resize"])?,
+            resize_top_right: load(&["top_right_corner", "ne-resize"])?,
+            resize_bottom_left: load(&["bottom_left_corner", "sw-resize"])?,
+            resize_bottom_right: load(&["bottom_right_corner", "se-resize"])?,
+            context_menu: load(&["context-menu"])?,
+            help: load(&["help", "question_arrow"])?,
+            progress: load(&["progress", "left_ptr_watch"])?,
+            wait: load(&["wait", "watch"])?,
+            cell: load(&["cell", "plus"])?,
+            crosshair: load(&["crosshair", "cross"])?,
+            text: load(&["text", "xterm"])?,
+            vertical_text: load(&["vertical-text"])?,
+            alias: load(&["alias", "dnd-link"])?,
+            copy: load(&["copy", "dnd-copy"])?,
+            move_: load(&["move", "dnd-move"])?,
+            no_drop: load(&["no-drop", "dnd-no-drop"])?,
+            not_allowed: load(&["not-allowed", "crossed_circle"])?,
+            grab: load(&["grab", "openhand"])?,
+            grabbing: load(&["grabbing", "closedhand"])?,
+            resize_top_right_bottom_left: load(&["nesw-resize", "fd_double_arrow"])?,
+            resize_top_left_bottom_right: load(&["nwse-resize", "bd_double_arrow"])?,
+            col_resize: load(&["col-resize", "sb_h_double_arrow"])?,
+            row_resize: load(&["row-resize", "sb_v_double_arrow"])?,
+            all_scroll: load(&["all-scroll", "fleur"])?,
+            zoom_in: load(&["zoom-in"])?,
+            zoom_out: load(&["zoom-out"])?,
         }))
     }
 }
@@ -129,14 +198,21 @@ enum ServerCursorTemplateVariant {
 
 impl ServerCursorTemplate {
     fn load(
-        name: &str,
+        names: &[&str],
         theme: Option<&BStr>,
         scales: &[Scale],
         sizes: &[u32],
         paths: &[BString],
         ctx: &Rc<RenderContext>,
     ) -> Result<Self, CursorError> {
-        match open_cursor(name, theme, scales, sizes, paths) {
+        let mut res = Err(CursorError::NotFound);
+        for name in names {
+            res = open_cursor(name, theme, scales, sizes, paths);
+            if res.is_ok() {
+                break;
+            }
+        }
+        match res {
             Ok(cs) => {
                 if cs.images.len() == 1 {
                     let mut sizes = SmallMapMut::new();
@@ -177,7 +253,7 @@ impl ServerCursorTemplate {
                 }
             }
             Err(e) => {
-                log::warn!("Could not load cursor {}: {}", name, ErrorFmt(e));
+                log::warn!("Could not load cursor {}: {}", names[0], ErrorFmt(e));
                 let empty: [Cell<u8>; 4] = unsafe { MaybeUninit::zeroed().assume_init() };
                 let mut img_sizes = SmallMapMut::new();
                 for scale in scales {
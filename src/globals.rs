@@ -3,9 +3,12 @@ use {
         backend::Backend,
         client::Client,
         ifs::{
+            ext_idle_notifier_v1::ExtIdleNotifierV1Global,
             ext_session_lock_manager_v1::ExtSessionLockManagerV1Global,
+            ext_workspace_manager_v1::ExtWorkspaceManagerV1Global,
             ipc::{
                 wl_data_device_manager::WlDataDeviceManagerGlobal,
+                zwlr_data_control_manager_v1::ZwlrDataControlManagerV1Global,
                 zwp_primary_selection_device_manager_v1::ZwpPrimarySelectionDeviceManagerV1Global,
             },
             jay_compositor::JayCompositorGlobal,
@@ -14,8 +17,19 @@ use {
             wl_output::WlOutputGlobal,
             wl_registry::WlRegistry,
             wl_seat::{
+                tablet::zwp_tablet_manager_v2::ZwpTabletManagerV2Global,
+                text_input::{
+                    zwp_input_method_manager_v2::ZwpInputMethodManagerV2Global,
+                    zwp_text_input_manager_v3::ZwpTextInputManagerV3Global,
+                },
+                wp_cursor_shape_manager_v1::WpCursorShapeManagerV1Global,
+                zwlr_virtual_pointer_manager_v1::ZwlrVirtualPointerManagerV1Global,
+                zwp_keyboard_shortcuts_inhibit_manager_v1::ZwpKeyboardShortcutsInhibitManagerV1Global,
                 zwp_pointer_constraints_v1::ZwpPointerConstraintsV1Global,
-                zwp_relative_pointer_manager_v1::ZwpRelativePointerManagerV1Global, WlSeatGlobal,
+                zwp_pointer_gestures_v1::ZwpPointerGesturesV1Global,
+                zwp_relative_pointer_manager_v1::ZwpRelativePointerManagerV1Global,
+                zwp_virtual_keyboard_manager_v1::ZwpVirtualKeyboardManagerV1Global,
+                WlSeatGlobal,
             },
             wl_shm::WlShmGlobal,
             wl_subcompositor::WlSubcompositorGlobal,
@@ -24,8 +38,12 @@ use {
             wp_presentation::WpPresentationGlobal,
             wp_tearing_control_manager_v1::WpTearingControlManagerV1Global,
             wp_viewporter::WpViewporterGlobal,
+            xdg_activation_v1::XdgActivationV1Global,
             xdg_wm_base::XdgWmBaseGlobal,
+            zwlr_foreign_toplevel_manager_v1::ZwlrForeignToplevelManagerV1Global,
+            zwlr_gamma_control_manager_v1::ZwlrGammaControlManagerV1Global,
             zwlr_layer_shell_v1::ZwlrLayerShellV1Global,
+            zwlr_output_manager_v1::ZwlrOutputManagerV1Global,
             zwlr_screencopy_manager_v1::ZwlrScreencopyManagerV1Global,
             zwp_idle_inhibit_manager_v1::ZwpIdleInhibitManagerV1Global,
             zxdg_decoration_manager_v1::ZxdgDecorationManagerV1Global,
@@ -145,17 +163,31 @@ impl Globals {
         add_singleton!(ZxdgDecorationManagerV1Global);
         add_singleton!(OrgKdeKwinServerDecorationManagerGlobal);
         add_singleton!(ZwpPrimarySelectionDeviceManagerV1Global);
+        add_singleton!(ZwlrDataControlManagerV1Global);
         add_singleton!(ZwlrLayerShellV1Global);
+        add_singleton!(ZwlrOutputManagerV1Global);
+        add_singleton!(ZwlrForeignToplevelManagerV1Global);
         add_singleton!(ZxdgOutputManagerV1Global);
         add_singleton!(JayCompositorGlobal);
         add_singleton!(ZwlrScreencopyManagerV1Global);
         add_singleton!(ZwpRelativePointerManagerV1Global);
+        add_singleton!(ZwpPointerGesturesV1Global);
         add_singleton!(ExtSessionLockManagerV1Global);
+        add_singleton!(ExtWorkspaceManagerV1Global);
+        add_singleton!(XdgActivationV1Global);
+        add_singleton!(ZwlrGammaControlManagerV1Global);
+        add_singleton!(ZwpKeyboardShortcutsInhibitManagerV1Global);
         add_singleton!(WpViewporterGlobal);
         add_singleton!(WpFractionalScaleManagerV1Global);
         add_singleton!(ZwpPointerConstraintsV1Global);
         add_singleton!(XwaylandShellV1Global);
         add_singleton!(WpTearingControlManagerV1Global);
+        add_singleton!(ZwpTabletManagerV2Global);
+        add_singleton!(ZwpTextInputManagerV3Global);
+        add_singleton!(ZwpInputMethodManagerV2Global);
+        add_singleton!(ZwpVirtualKeyboardManagerV1Global);
+        add_singleton!(ZwlrVirtualPointerManagerV1Global);
+        add_singleton!(WpCursorShapeManagerV1Global);
     }
 
     pub fn add_backend_singletons(&self, backend: &Rc<dyn Backend>) {
@@ -166,6 +198,7 @@ impl Globals {
         }
         if backend.supports_idle() {
             add_singleton!(ZwpIdleInhibitManagerV1Global);
+            add_singleton!(ExtIdleNotifierV1Global);
         }
         if backend.supports_presentation_feedback() {
             add_singleton!(WpPresentationGlobal);
@@ -1,5 +1,10 @@
+pub mod ext_idle_notification_v1;
+pub mod ext_idle_notifier_v1;
 pub mod ext_session_lock_manager_v1;
 pub mod ext_session_lock_v1;
+pub mod ext_workspace_group_handle_v1;
+pub mod ext_workspace_handle_v1;
+pub mod ext_workspace_manager_v1;
 pub mod ipc;
 pub mod jay_compositor;
 pub mod jay_idle;
@@ -32,9 +37,20 @@ pub mod wp_presentation;
 pub mod wp_presentation_feedback;
 pub mod wp_tearing_control_manager_v1;
 pub mod wp_viewporter;
+pub mod xdg_activation_token_v1;
+pub mod xdg_activation_v1;
 pub mod xdg_positioner;
 pub mod xdg_wm_base;
+pub mod zwlr_foreign_toplevel_handle_v1;
+pub mod zwlr_foreign_toplevel_manager_v1;
+pub mod zwlr_gamma_control_manager_v1;
+pub mod zwlr_gamma_control_v1;
 pub mod zwlr_layer_shell_v1;
+pub mod zwlr_output_configuration_head_v1;
+pub mod zwlr_output_configuration_v1;
+pub mod zwlr_output_head_v1;
+pub mod zwlr_output_manager_v1;
+pub mod zwlr_output_mode_v1;
 pub mod zwlr_screencopy_frame_v1;
 pub mod zwlr_screencopy_manager_v1;
 pub mod zwp_idle_inhibit_manager_v1;
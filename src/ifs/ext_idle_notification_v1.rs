@@ -0,0 +1,133 @@
+use {
+    crate::{
+        async_engine::SpawnedFuture,
+        client::{Client, ClientError},
+        leaks::Tracker,
+        object::Object,
+        time::Time,
+        utils::{
+            asyncevent::AsyncEvent,
+            buffd::{MsgParser, MsgParserError},
+            errorfmt::ErrorFmt,
+        },
+        wire::{ext_idle_notification_v1::*, ExtIdleNotificationV1Id},
+    },
+    futures_util::{future, select, FutureExt},
+    std::{cell::Cell, rc::Rc, time::Duration},
+    thiserror::Error,
+};
+
+pub struct ExtIdleNotificationV1 {
+    pub id: ExtIdleNotificationV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub timeout: Duration,
+    /// Set when input occurred since the task last ran.
+    pub input: Cell<bool>,
+    /// Set when the last idle inhibitor was removed since the task last ran.
+    pub restart: Cell<bool>,
+    pub change: AsyncEvent,
+    task: Cell<Option<SpawnedFuture<()>>>,
+}
+
+impl ExtIdleNotificationV1 {
+    pub fn new(id: ExtIdleNotificationV1Id, client: &Rc<Client>, timeout: Duration) -> Self {
+        Self {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+            timeout,
+            input: Cell::new(false),
+            restart: Cell::new(false),
+            change: Default::default(),
+            task: Cell::new(None),
+        }
+    }
+
+    pub fn install(self: &Rc<Self>) {
+        let task = self.client.state.eng.spawn(self.clone().run());
+        self.task.set(Some(task));
+        self.client.state.idle.add_notification(self);
+    }
+
+    async fn run(self: Rc<Self>) {
+        let state = &self.client.state;
+        let mut last_input = Time::now_unchecked();
+        let mut idle = false;
+        loop {
+            let inhibited = state.idle.inhibitors.len() > 0;
+            let elapsed = Time::now_unchecked() - last_input;
+            if !idle && !inhibited && elapsed >= self.timeout {
+                self.client.event(Idled { self_id: self.id });
+                idle = true;
+            }
+            let mut timeout = match idle || inhibited {
+                true => future::Either::Left(future::pending()),
+                false => {
+                    let remaining = (self.timeout - elapsed).as_millis() as u64;
+                    future::Either::Right(state.wheel.timeout(remaining))
+                }
+            }
+            .fuse();
+            select! {
+                res = timeout => {
+                    if let Err(e) = res {
+                        log::error!("Could not wait for idle timeout: {}", ErrorFmt(e));
+                        return;
+                    }
+                },
+                _ = self.change.triggered().fuse() => { },
+            }
+            if self.input.replace(false) {
+                last_input = Time::now_unchecked();
+                if idle {
+                    self.client.event(Resumed { self_id: self.id });
+                    idle = false;
+                }
+            }
+            if self.restart.replace(false) && !idle {
+                last_input = Time::now_unchecked();
+            }
+        }
+    }
+
+    fn detach(&self) {
+        self.client.state.idle.remove_notification(self);
+        self.task.take();
+    }
+
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), ExtIdleNotificationV1Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.detach();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    ExtIdleNotificationV1;
+
+    DESTROY => destroy,
+}
+
+impl Object for ExtIdleNotificationV1 {
+    fn num_requests(&self) -> u32 {
+        DESTROY + 1
+    }
+
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
+
+simple_add_obj!(ExtIdleNotificationV1);
+
+#[derive(Debug, Error)]
+pub enum ExtIdleNotificationV1Error {
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ExtIdleNotificationV1Error, ClientError);
+efrom!(ExtIdleNotificationV1Error, MsgParserError);
@@ -0,0 +1,113 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        globals::{Global, GlobalName},
+        ifs::ext_idle_notification_v1::ExtIdleNotificationV1,
+        leaks::Tracker,
+        object::Object,
+        utils::buffd::{MsgParser, MsgParserError},
+        wire::{ext_idle_notifier_v1::*, ExtIdleNotifierV1Id},
+    },
+    std::{rc::Rc, time::Duration},
+    thiserror::Error,
+};
+
+pub struct ExtIdleNotifierV1Global {
+    pub name: GlobalName,
+}
+
+impl ExtIdleNotifierV1Global {
+    pub fn new(name: GlobalName) -> Self {
+        Self { name }
+    }
+
+    fn bind_(
+        self: Rc<Self>,
+        id: ExtIdleNotifierV1Id,
+        client: &Rc<Client>,
+        _version: u32,
+    ) -> Result<(), ExtIdleNotifierV1Error> {
+        let obj = Rc::new(ExtIdleNotifierV1 {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+        });
+        track!(client, obj);
+        client.add_client_obj(&obj)?;
+        Ok(())
+    }
+}
+
+global_base!(
+    ExtIdleNotifierV1Global,
+    ExtIdleNotifierV1,
+    ExtIdleNotifierV1Error
+);
+
+impl Global for ExtIdleNotifierV1Global {
+    fn singleton(&self) -> bool {
+        true
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+}
+
+simple_add_global!(ExtIdleNotifierV1Global);
+
+pub struct ExtIdleNotifierV1 {
+    pub id: ExtIdleNotifierV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+}
+
+impl ExtIdleNotifierV1 {
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), ExtIdleNotifierV1Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn get_idle_notification(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), ExtIdleNotifierV1Error> {
+        let req: GetIdleNotification = self.client.parse(self, parser)?;
+        let _seat = self.client.lookup(req.seat)?;
+        let notification = Rc::new(ExtIdleNotificationV1::new(
+            req.id,
+            &self.client,
+            Duration::from_millis(req.timeout as u64),
+        ));
+        track!(self.client, notification);
+        self.client.add_client_obj(&notification)?;
+        notification.install();
+        Ok(())
+    }
+}
+
+object_base! {
+    ExtIdleNotifierV1;
+
+    DESTROY => destroy,
+    GET_IDLE_NOTIFICATION => get_idle_notification,
+}
+
+impl Object for ExtIdleNotifierV1 {
+    fn num_requests(&self) -> u32 {
+        GET_IDLE_NOTIFICATION + 1
+    }
+}
+
+simple_add_obj!(ExtIdleNotifierV1);
+
+#[derive(Debug, Error)]
+pub enum ExtIdleNotifierV1Error {
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ExtIdleNotifierV1Error, ClientError);
+efrom!(ExtIdleNotifierV1Error, MsgParserError);
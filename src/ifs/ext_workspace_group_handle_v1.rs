@@ -0,0 +1,137 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::{
+            ext_workspace_handle_v1::ExtWorkspaceHandleV1,
+            ext_workspace_manager_v1::{ExtWorkspaceManagerV1, WorkspaceAction},
+        },
+        leaks::Tracker,
+        object::Object,
+        tree::{OutputNode, OutputNodeId},
+        utils::{
+            buffd::{MsgParser, MsgParserError},
+            clonecell::CloneCell,
+        },
+        wire::{ext_workspace_group_handle_v1::*, ExtWorkspaceGroupHandleV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+const CAP_CREATE_WORKSPACE: u32 = 1;
+
+pub struct ExtWorkspaceGroupHandleV1 {
+    pub id: ExtWorkspaceGroupHandleV1Id,
+    pub client: Rc<Client>,
+    pub manager: Rc<ExtWorkspaceManagerV1>,
+    pub output_id: OutputNodeId,
+    pub output: CloneCell<Option<Rc<OutputNode>>>,
+    pub destroyed: Cell<bool>,
+    pub tracker: Tracker<Self>,
+}
+
+impl ExtWorkspaceGroupHandleV1 {
+    pub fn send_capabilities(&self) {
+        self.client.event(Capabilities {
+            self_id: self.id,
+            capabilities: CAP_CREATE_WORKSPACE,
+        });
+    }
+
+    pub fn send_output_enter(&self, output: &OutputNode) {
+        let bindings = output.global.bindings.borrow_mut();
+        if let Some(bindings) = bindings.get(&self.client.id) {
+            for binding in bindings.values() {
+                self.client.event(OutputEnter {
+                    self_id: self.id,
+                    output: binding.id,
+                });
+            }
+        }
+    }
+
+    pub fn send_workspace_enter(&self, workspace: &ExtWorkspaceHandleV1) {
+        if !self.destroyed.get() && !workspace.destroyed.get() {
+            self.client.event(WorkspaceEnter {
+                self_id: self.id,
+                workspace: workspace.id,
+            });
+        }
+    }
+
+    pub fn send_workspace_leave(&self, workspace: &ExtWorkspaceHandleV1) {
+        if !self.destroyed.get() && !workspace.destroyed.get() {
+            self.client.event(WorkspaceLeave {
+                self_id: self.id,
+                workspace: workspace.id,
+            });
+        }
+    }
+
+    pub fn send_removed(&self) {
+        if let Some(output) = self.output.take() {
+            if !self.destroyed.get() {
+                let bindings = output.global.bindings.borrow_mut();
+                if let Some(bindings) = bindings.get(&self.client.id) {
+                    for binding in bindings.values() {
+                        self.client.event(OutputLeave {
+                            self_id: self.id,
+                            output: binding.id,
+                        });
+                    }
+                }
+                self.client.event(Removed { self_id: self.id });
+            }
+        }
+    }
+
+    fn create_workspace(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), ExtWorkspaceGroupHandleV1Error> {
+        let req: CreateWorkspace = self.client.parse(self, parser)?;
+        if let Some(output) = self.output.get() {
+            self.manager
+                .pending
+                .borrow_mut()
+                .push(WorkspaceAction::Create(output, req.workspace.to_string()));
+        }
+        Ok(())
+    }
+
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), ExtWorkspaceGroupHandleV1Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.destroyed.set(true);
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    ExtWorkspaceGroupHandleV1;
+
+    CREATE_WORKSPACE => create_workspace,
+    DESTROY => destroy,
+}
+
+impl Object for ExtWorkspaceGroupHandleV1 {
+    fn num_requests(&self) -> u32 {
+        DESTROY + 1
+    }
+
+    fn break_loops(&self) {
+        self.output.take();
+    }
+}
+
+simple_add_obj!(ExtWorkspaceGroupHandleV1);
+
+#[derive(Debug, Error)]
+pub enum ExtWorkspaceGroupHandleV1Error {
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ExtWorkspaceGroupHandleV1Error, MsgParserError);
+efrom!(ExtWorkspaceGroupHandleV1Error, ClientError);
@@ -0,0 +1,210 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::{
+            ext_workspace_group_handle_v1::ExtWorkspaceGroupHandleV1,
+            ext_workspace_manager_v1::{ExtWorkspaceManagerV1, WorkspaceAction},
+        },
+        leaks::Tracker,
+        object::Object,
+        tree::{WorkspaceNode, WorkspaceNodeId},
+        utils::{
+            buffd::{MsgParser, MsgParserError},
+            clonecell::CloneCell,
+            rc_eq::rc_eq,
+        },
+        wire::{ext_workspace_handle_v1::*, ExtWorkspaceHandleV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+const STATE_ACTIVE: u32 = 1;
+const STATE_URGENT: u32 = 2;
+const STATE_HIDDEN: u32 = 4;
+
+const CAP_ACTIVATE: u32 = 1;
+const CAP_REMOVE: u32 = 4;
+
+pub struct ExtWorkspaceHandleV1 {
+    pub id: ExtWorkspaceHandleV1Id,
+    pub client: Rc<Client>,
+    pub manager: Rc<ExtWorkspaceManagerV1>,
+    pub workspace_id: WorkspaceNodeId,
+    pub workspace: CloneCell<Option<Rc<WorkspaceNode>>>,
+    pub group: CloneCell<Option<Rc<ExtWorkspaceGroupHandleV1>>>,
+    pub coordinate: Cell<Option<u32>>,
+    pub sent_state: Cell<Option<u32>>,
+    pub destroyed: Cell<bool>,
+    pub tracker: Tracker<Self>,
+}
+
+impl ExtWorkspaceHandleV1 {
+    pub fn send_id(&self, id: &str) {
+        self.client.event(Id {
+            self_id: self.id,
+            id,
+        });
+    }
+
+    pub fn send_name(&self, name: &str) {
+        self.client.event(Name {
+            self_id: self.id,
+            name,
+        });
+    }
+
+    pub fn send_capabilities(&self, ws: &WorkspaceNode) {
+        let capabilities = match ws.is_dummy {
+            true => 0,
+            false => CAP_ACTIVATE | CAP_REMOVE,
+        };
+        self.client.event(Capabilities {
+            self_id: self.id,
+            capabilities,
+        });
+    }
+
+    pub fn send_removed(&self) {
+        self.workspace.take();
+        if !self.destroyed.get() {
+            self.client.event(Removed { self_id: self.id });
+        }
+    }
+
+    /// Removes the workspace from its group. Returns whether it was in a group.
+    pub fn leave_group(&self) -> bool {
+        match self.group.take() {
+            Some(group) => {
+                group.send_workspace_leave(self);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Sends the group and position of the workspace if they changed.
+    pub fn update(&self, group: &Rc<ExtWorkspaceGroupHandleV1>, coordinate: u32) -> bool {
+        let mut changed = false;
+        let same_group = match self.group.get() {
+            Some(g) => rc_eq(&g, group),
+            _ => false,
+        };
+        if !same_group {
+            self.leave_group();
+            group.send_workspace_enter(self);
+            self.group.set(Some(group.clone()));
+            changed = true;
+        }
+        if self.coordinate.replace(Some(coordinate)) != Some(coordinate) {
+            if !self.destroyed.get() {
+                self.client.event(Coordinates {
+                    self_id: self.id,
+                    coordinates: &[coordinate],
+                });
+            }
+            changed = true;
+        }
+        changed
+    }
+
+    /// Sends the state of the workspace if it changed.
+    pub fn update_state(&self) -> bool {
+        let ws = match self.workspace.get() {
+            Some(ws) => ws,
+            _ => return false,
+        };
+        let mut state = 0;
+        if ws.visible.get() {
+            state |= STATE_ACTIVE;
+        }
+        if ws.is_urgent() {
+            state |= STATE_URGENT;
+        }
+        if ws.is_dummy || ws.output.get().is_dummy {
+            state |= STATE_HIDDEN;
+        }
+        if self.sent_state.replace(Some(state)) == Some(state) {
+            return false;
+        }
+        if !self.destroyed.get() {
+            self.client.event(State {
+                self_id: self.id,
+                state,
+            });
+        }
+        true
+    }
+
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), ExtWorkspaceHandleV1Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.destroyed.set(true);
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn activate(&self, parser: MsgParser<'_, '_>) -> Result<(), ExtWorkspaceHandleV1Error> {
+        let _req: Activate = self.client.parse(self, parser)?;
+        if let Some(ws) = self.workspace.get() {
+            self.manager
+                .pending
+                .borrow_mut()
+                .push(WorkspaceAction::Activate(ws));
+        }
+        Ok(())
+    }
+
+    fn deactivate(&self, parser: MsgParser<'_, '_>) -> Result<(), ExtWorkspaceHandleV1Error> {
+        let _req: Deactivate = self.client.parse(self, parser)?;
+        Ok(())
+    }
+
+    fn assign(&self, parser: MsgParser<'_, '_>) -> Result<(), ExtWorkspaceHandleV1Error> {
+        let _req: Assign = self.client.parse(self, parser)?;
+        Ok(())
+    }
+
+    fn remove(&self, parser: MsgParser<'_, '_>) -> Result<(), ExtWorkspaceHandleV1Error> {
+        let _req: Remove = self.client.parse(self, parser)?;
+        if let Some(ws) = self.workspace.get() {
+            self.manager
+                .pending
+                .borrow_mut()
+                .push(WorkspaceAction::Remove(ws));
+        }
+        Ok(())
+    }
+}
+
+object_base! {
+    ExtWorkspaceHandleV1;
+
+    DESTROY => destroy,
+    ACTIVATE => activate,
+    DEACTIVATE => deactivate,
+    ASSIGN => assign,
+    REMOVE => remove,
+}
+
+impl Object for ExtWorkspaceHandleV1 {
+    fn num_requests(&self) -> u32 {
+        REMOVE + 1
+    }
+
+    fn break_loops(&self) {
+        self.workspace.take();
+        self.group.take();
+    }
+}
+
+simple_add_obj!(ExtWorkspaceHandleV1);
+
+#[derive(Debug, Error)]
+pub enum ExtWorkspaceHandleV1Error {
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ExtWorkspaceHandleV1Error, MsgParserError);
+efrom!(ExtWorkspaceHandleV1Error, ClientError);
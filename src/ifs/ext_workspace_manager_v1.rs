@@ -0,0 +1,333 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        globals::{Global, GlobalName},
+        ifs::{
+            ext_workspace_group_handle_v1::ExtWorkspaceGroupHandleV1,
+            ext_workspace_handle_v1::ExtWorkspaceHandleV1,
+        },
+        leaks::Tracker,
+        object::Object,
+        tree::{OutputNode, OutputNodeId, WorkspaceNode, WorkspaceNodeId},
+        utils::{
+            buffd::{MsgParser, MsgParserError},
+            clonecell::CloneCell,
+            copyhashmap::CopyHashMap,
+            rc_eq::rc_eq,
+        },
+        wire::{ext_workspace_manager_v1::*, ExtWorkspaceManagerV1Id},
+    },
+    ahash::AHashMap,
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+    thiserror::Error,
+};
+
+pub struct ExtWorkspaceManagerV1Global {
+    pub name: GlobalName,
+}
+
+impl ExtWorkspaceManagerV1Global {
+    pub fn new(name: GlobalName) -> Self {
+        Self { name }
+    }
+
+    fn bind_(
+        self: Rc<Self>,
+        id: ExtWorkspaceManagerV1Id,
+        client: &Rc<Client>,
+        _version: u32,
+    ) -> Result<(), ExtWorkspaceManagerV1Error> {
+        let obj = Rc::new(ExtWorkspaceManagerV1 {
+            id,
+            client: client.clone(),
+            groups: Default::default(),
+            workspaces: Default::default(),
+            pending: Default::default(),
+            stopped: Cell::new(false),
+            tracker: Default::default(),
+        });
+        track!(client, obj);
+        client.add_client_obj(&obj)?;
+        client
+            .state
+            .workspace_managers
+            .set((client.id, id), obj.clone());
+        obj.update_()?;
+        Ok(())
+    }
+}
+
+global_base!(
+    ExtWorkspaceManagerV1Global,
+    ExtWorkspaceManagerV1,
+    ExtWorkspaceManagerV1Error
+);
+
+impl Global for ExtWorkspaceManagerV1Global {
+    fn singleton(&self) -> bool {
+        true
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+}
+
+simple_add_global!(ExtWorkspaceManagerV1Global);
+
+/// A request that is applied when the client commits.
+pub enum WorkspaceAction {
+    Activate(Rc<WorkspaceNode>),
+    Create(Rc<OutputNode>, String),
+    Remove(Rc<WorkspaceNode>),
+}
+
+pub struct ExtWorkspaceManagerV1 {
+    pub id: ExtWorkspaceManagerV1Id,
+    pub client: Rc<Client>,
+    pub groups: CopyHashMap<OutputNodeId, Rc<ExtWorkspaceGroupHandleV1>>,
+    pub workspaces: CopyHashMap<WorkspaceNodeId, Rc<ExtWorkspaceHandleV1>>,
+    pub pending: RefCell<Vec<WorkspaceAction>>,
+    pub stopped: Cell<bool>,
+    pub tracker: Tracker<Self>,
+}
+
+impl ExtWorkspaceManagerV1 {
+    /// Sends the changes of the workspace layout to the client.
+    pub fn update(self: &Rc<Self>) {
+        if let Err(e) = self.update_() {
+            self.client.error(e);
+        }
+    }
+
+    fn update_(self: &Rc<Self>) -> Result<(), ClientError> {
+        if self.stopped.get() {
+            return Ok(());
+        }
+        let state = &self.client.state;
+        let mut changed = false;
+        let outputs: Vec<_> = state.root.outputs.lock().values().cloned().collect();
+        let mut workspaces: AHashMap<_, _> = state
+            .workspaces
+            .lock()
+            .values()
+            .map(|ws| (ws.id, ws.clone()))
+            .collect();
+        if let Some(ws) = state.scratchpad.get() {
+            workspaces.insert(ws.id, ws);
+        }
+        let handles: Vec<_> = self.workspaces.lock().values().cloned().collect();
+        for handle in handles {
+            if !workspaces.contains_key(&handle.workspace_id) {
+                self.workspaces.remove(&handle.workspace_id);
+                handle.leave_group();
+                handle.send_removed();
+                changed = true;
+            }
+        }
+        let groups: Vec<_> = self.groups.lock().values().cloned().collect();
+        for group in groups {
+            if !outputs.iter().any(|o| o.id == group.output_id) {
+                self.groups.remove(&group.output_id);
+                for handle in self.workspaces.lock().values() {
+                    if let Some(g) = handle.group.get() {
+                        if rc_eq(&g, &group) {
+                            handle.leave_group();
+                        }
+                    }
+                }
+                group.send_removed();
+                changed = true;
+            }
+        }
+        for output in &outputs {
+            if !self.groups.contains(&output.id) {
+                self.announce_group(output)?;
+                changed = true;
+            }
+        }
+        for ws in workspaces.values() {
+            if !self.workspaces.contains(&ws.id) {
+                self.announce_workspace(ws)?;
+                changed = true;
+            }
+        }
+        for output in &outputs {
+            let group = match self.groups.get(&output.id) {
+                Some(g) => g,
+                _ => continue,
+            };
+            for (idx, ws) in output.workspaces.iter().enumerate() {
+                if let Some(handle) = self.workspaces.get(&ws.id) {
+                    changed |= handle.update(&group, idx as u32);
+                }
+            }
+        }
+        for handle in self.workspaces.lock().values() {
+            if let Some(ws) = handle.workspace.get() {
+                if ws.output.get().is_dummy {
+                    changed |= handle.leave_group();
+                }
+            }
+            changed |= handle.update_state();
+        }
+        if changed {
+            self.send_done();
+        }
+        Ok(())
+    }
+
+    fn announce_group(self: &Rc<Self>, output: &Rc<OutputNode>) -> Result<(), ClientError> {
+        let group = Rc::new(ExtWorkspaceGroupHandleV1 {
+            id: self.client.new_id()?,
+            client: self.client.clone(),
+            manager: self.clone(),
+            output_id: output.id,
+            output: CloneCell::new(Some(output.clone())),
+            destroyed: Cell::new(false),
+            tracker: Default::default(),
+        });
+        track!(self.client, group);
+        self.client.add_server_obj(&group);
+        self.client.event(WorkspaceGroup {
+            self_id: self.id,
+            workspace_group: group.id,
+        });
+        group.send_capabilities();
+        group.send_output_enter(output);
+        self.groups.set(output.id, group);
+        Ok(())
+    }
+
+    fn announce_workspace(self: &Rc<Self>, ws: &Rc<WorkspaceNode>) -> Result<(), ClientError> {
+        let handle = Rc::new(ExtWorkspaceHandleV1 {
+            id: self.client.new_id()?,
+            client: self.client.clone(),
+            manager: self.clone(),
+            workspace_id: ws.id,
+            workspace: CloneCell::new(Some(ws.clone())),
+            group: Default::default(),
+            coordinate: Cell::new(None),
+            sent_state: Cell::new(None),
+            destroyed: Cell::new(false),
+            tracker: Default::default(),
+        });
+        track!(self.client, handle);
+        self.client.add_server_obj(&handle);
+        self.client.event(Workspace {
+            self_id: self.id,
+            workspace: handle.id,
+        });
+        handle.send_id(&ws.name);
+        handle.send_name(&ws.name);
+        handle.send_capabilities(ws);
+        self.workspaces.set(ws.id, handle);
+        Ok(())
+    }
+
+    fn send_done(&self) {
+        self.client.event(Done { self_id: self.id });
+    }
+
+    fn send_finished(&self) {
+        self.client.event(Finished { self_id: self.id });
+    }
+
+    fn detach(&self) {
+        self.client
+            .state
+            .workspace_managers
+            .remove(&(self.client.id, self.id));
+    }
+
+    fn commit(&self, parser: MsgParser<'_, '_>) -> Result<(), ExtWorkspaceManagerV1Error> {
+        let _req: Commit = self.client.parse(self, parser)?;
+        let state = &self.client.state;
+        let actions = self.pending.take();
+        for action in actions {
+            match action {
+                WorkspaceAction::Activate(ws) => {
+                    let output = ws.output.get();
+                    let seat = {
+                        let seats = state.globals.lock_seats();
+                        seats
+                            .values()
+                            .find(|s| s.get_output().id == output.id)
+                            .or_else(|| seats.values().next())
+                            .cloned()
+                    };
+                    if let Some(seat) = seat {
+                        state.show_workspace(&seat, &ws.name);
+                    }
+                }
+                WorkspaceAction::Create(output, name) => {
+                    if output.is_dummy || state.workspaces.contains(&name) {
+                        continue;
+                    }
+                    output.create_workspace(&name);
+                    output.schedule_update_render_data();
+                    state.tree_changed();
+                }
+                WorkspaceAction::Remove(ws) => {
+                    if ws.is_dummy || ws.visible.get() || !ws.is_empty() {
+                        continue;
+                    }
+                    if state.workspaces.get(&ws.name).map(|w| w.id) != Some(ws.id) {
+                        continue;
+                    }
+                    let output = ws.output.get();
+                    output.destroy_workspace(&ws);
+                    output.schedule_update_render_data();
+                    state.tree_changed();
+                    state.workspaces_changed();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn stop(&self, parser: MsgParser<'_, '_>) -> Result<(), ExtWorkspaceManagerV1Error> {
+        let _req: Stop = self.client.parse(self, parser)?;
+        if !self.stopped.replace(true) {
+            self.detach();
+            self.send_finished();
+            self.client.remove_obj(self)?;
+        }
+        Ok(())
+    }
+}
+
+object_base! {
+    ExtWorkspaceManagerV1;
+
+    COMMIT => commit,
+    STOP => stop,
+}
+
+impl Object for ExtWorkspaceManagerV1 {
+    fn num_requests(&self) -> u32 {
+        STOP + 1
+    }
+
+    fn break_loops(&self) {
+        self.detach();
+        self.groups.clear();
+        self.workspaces.clear();
+        self.pending.take();
+    }
+}
+
+simple_add_obj!(ExtWorkspaceManagerV1);
+
+#[derive(Debug, Error)]
+pub enum ExtWorkspaceManagerV1Error {
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ExtWorkspaceManagerV1Error, MsgParserError);
+efrom!(ExtWorkspaceManagerV1Error, ClientError);
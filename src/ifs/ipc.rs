@@ -1,10 +1,24 @@
 use {
     crate::{
         client::{Client, ClientError, ClientId, WaylandObject},
-        ifs::wl_seat::{WlSeatError, WlSeatGlobal},
+        ifs::{
+            ipc::{
+                wl_data_device::ClipboardIpc, wl_data_source::WlDataSource,
+                zwlr_data_control_offer_v1::ZwlrDataControlOfferV1,
+                zwlr_data_control_source_v1::ZwlrDataControlSourceV1,
+                zwp_primary_selection_device_v1::PrimarySelectionIpc,
+                zwp_primary_selection_source_v1::ZwpPrimarySelectionSourceV1,
+            },
+            wl_seat::{WlSeatError, WlSeatGlobal},
+        },
         utils::{
-            bitflags::BitflagsExt, clonecell::CloneCell, numcell::NumCell, smallmap::SmallMap,
+            bitflags::BitflagsExt,
+            clonecell::{CloneCell, UnsafeCellCloneSafe},
+            copyhashmap::CopyHashMap,
+            numcell::NumCell,
+            smallmap::SmallMap,
         },
+        wire::ZwlrDataControlOfferV1Id,
     },
     ahash::AHashSet,
     std::{
@@ -20,6 +34,10 @@ pub mod wl_data_device;
 pub mod wl_data_device_manager;
 pub mod wl_data_offer;
 pub mod wl_data_source;
+pub mod zwlr_data_control_device_v1;
+pub mod zwlr_data_control_manager_v1;
+pub mod zwlr_data_control_offer_v1;
+pub mod zwlr_data_control_source_v1;
 pub mod zwp_primary_selection_device_manager_v1;
 pub mod zwp_primary_selection_device_v1;
 pub mod zwp_primary_selection_offer_v1;
@@ -113,6 +131,11 @@ pub struct SourceData<T: IpcVtable> {
     role: Cell<Role>,
     shared: CloneCell<Rc<SharedState>>,
     pub is_xwm: bool,
+    /// The data-control source this source forwards to, if it was created by a
+    /// clipboard manager.
+    pub data_control: CloneCell<Option<Rc<ZwlrDataControlSourceV1>>>,
+    data_control_offers:
+        CopyHashMap<(ClientId, ZwlrDataControlOfferV1Id), Rc<ZwlrDataControlOfferV1>>,
 }
 
 struct SharedState {
@@ -148,6 +171,8 @@ impl<T: IpcVtable> SourceData<T> {
             role: Cell::new(Role::Selection),
             shared: Default::default(),
             is_xwm,
+            data_control: Default::default(),
+            data_control_offers: Default::default(),
         }
     }
 }
@@ -191,6 +216,9 @@ pub fn detach_seat<T: IpcVtable>(src: &Rc<T::Source>) {
     let data = T::get_source_data(src);
     data.seat.set(None);
     cancel_offers::<T>(src);
+    for (_, offer) in data.data_control_offers.clear() {
+        offer.source.take();
+    }
     if !data.state.get().contains(SOURCE_STATE_FINISHED) {
         T::send_cancelled(src);
     }
@@ -254,6 +282,9 @@ pub fn add_data_source_mime_type<T: IpcVtable>(src: &T::Source, mime_type: &str)
             // let data = T::get_offer_data(&offer);
             // data.client.flush();
         }
+        for offer in data.data_control_offers.lock().values() {
+            offer.send_offer(mime_type);
+        }
     }
 }
 
@@ -306,6 +337,8 @@ fn break_source_loops<T: IpcVtable>(src: &T::Source) {
     if data.offer_client.get() == data.client.id {
         data.offers.take();
     }
+    data.data_control.take();
+    data.data_control_offers.clear();
     destroy_data_source::<T>(src);
 }
 
@@ -330,3 +363,58 @@ pub fn receive_data_offer<T: IpcVtable>(offer: &T::Offer, mime_type: &str, fd: R
         // data.client.flush();
     }
 }
+
+/// A source of the selection or the primary selection of a seat.
+#[derive(Clone)]
+pub enum SelectionSource {
+    Clipboard(Rc<WlDataSource>),
+    Primary(Rc<ZwpPrimarySelectionSourceV1>),
+}
+
+unsafe impl UnsafeCellCloneSafe for SelectionSource {}
+
+impl SelectionSource {
+    fn with_data<R>(
+        &self,
+        clipboard: impl FnOnce(&SourceData<ClipboardIpc>) -> R,
+        primary: impl FnOnce(&SourceData<PrimarySelectionIpc>) -> R,
+    ) -> R {
+        match self {
+            SelectionSource::Clipboard(src) => clipboard(&src.data),
+            SelectionSource::Primary(src) => primary(&src.data),
+        }
+    }
+
+    pub fn for_each_mime_type(&self, mut f: impl FnMut(&str)) {
+        let mime_types = self.with_data(
+            |d| d.mime_types.borrow().clone(),
+            |d| d.mime_types.borrow().clone(),
+        );
+        for mime_type in &mime_types {
+            f(mime_type);
+        }
+    }
+
+    pub fn send_send(&self, mime_type: &str, fd: Rc<OwnedFd>) {
+        match self {
+            SelectionSource::Clipboard(src) => src.send_send(mime_type, fd),
+            SelectionSource::Primary(src) => src.send_send(mime_type, fd),
+        }
+    }
+
+    pub fn add_data_control_offer(&self, offer: &Rc<ZwlrDataControlOfferV1>) {
+        let key = (offer.client.id, offer.id);
+        self.with_data(
+            |d| d.data_control_offers.set(key, offer.clone()),
+            |d| d.data_control_offers.set(key, offer.clone()),
+        );
+    }
+
+    pub fn remove_data_control_offer(&self, offer: &ZwlrDataControlOfferV1) {
+        let key = (offer.client.id, offer.id);
+        self.with_data(
+            |d| d.data_control_offers.remove(&key),
+            |d| d.data_control_offers.remove(&key),
+        );
+    }
+}
@@ -111,6 +111,8 @@ impl WlDataSource {
                 .xwayland
                 .queue
                 .push(XWaylandEvent::ClipboardCancelSource(self.clone()));
+        } else if let Some(dc) = self.data.data_control.get() {
+            dc.send_cancelled();
         } else {
             self.data.client.event(Cancelled { self_id: self.id })
         }
@@ -128,6 +130,8 @@ impl WlDataSource {
                     mime_type.to_string(),
                     fd,
                 ));
+        } else if let Some(dc) = self.data.data_control.get() {
+            dc.send_send(mime_type, fd);
         } else {
             self.data.client.event(Send {
                 self_id: self.id,
@@ -0,0 +1,216 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::{
+            ipc::{
+                zwlr_data_control_offer_v1::ZwlrDataControlOfferV1,
+                zwlr_data_control_source_v1::ZwlrDataControlSourceV1, SelectionSource,
+            },
+            wl_seat::{WlSeatError, WlSeatGlobal},
+        },
+        leaks::Tracker,
+        object::Object,
+        utils::{
+            buffd::{MsgParser, MsgParserError},
+            clonecell::CloneCell,
+        },
+        wire::{
+            zwlr_data_control_device_v1::*, ZwlrDataControlDeviceV1Id, ZwlrDataControlOfferV1Id,
+            ZwlrDataControlSourceV1Id,
+        },
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+const USED_SOURCE: u32 = 1;
+
+pub const PRIMARY_SELECTION_SINCE: u32 = 2;
+
+pub struct ZwlrDataControlDeviceV1 {
+    pub id: ZwlrDataControlDeviceV1Id,
+    pub client: Rc<Client>,
+    pub version: u32,
+    pub seat: Rc<WlSeatGlobal>,
+    pub tracker: Tracker<Self>,
+    selection: CloneCell<Option<Rc<ZwlrDataControlOfferV1>>>,
+    primary_selection: CloneCell<Option<Rc<ZwlrDataControlOfferV1>>>,
+}
+
+impl ZwlrDataControlDeviceV1 {
+    pub fn new(
+        id: ZwlrDataControlDeviceV1Id,
+        client: &Rc<Client>,
+        version: u32,
+        seat: &Rc<WlSeatGlobal>,
+    ) -> Self {
+        Self {
+            id,
+            client: client.clone(),
+            version,
+            seat: seat.clone(),
+            tracker: Default::default(),
+            selection: Default::default(),
+            primary_selection: Default::default(),
+        }
+    }
+
+    /// Announces a new selection or primary selection of the seat.
+    pub fn send_selection(&self, source: Option<SelectionSource>, primary: bool) {
+        if primary && self.version < PRIMARY_SELECTION_SINCE {
+            return;
+        }
+        let offer = match source {
+            Some(src) => match self.create_offer(&src) {
+                Ok(offer) => Some(offer),
+                Err(e) => {
+                    self.client.error(e);
+                    return;
+                }
+            },
+            _ => None,
+        };
+        let id = offer
+            .as_ref()
+            .map(|o| o.id)
+            .unwrap_or(ZwlrDataControlOfferV1Id::NONE);
+        if primary {
+            self.client.event(PrimarySelection {
+                self_id: self.id,
+                id,
+            });
+            self.primary_selection.set(offer);
+        } else {
+            self.client.event(Selection {
+                self_id: self.id,
+                id,
+            });
+            self.selection.set(offer);
+        }
+    }
+
+    fn create_offer(
+        &self,
+        src: &SelectionSource,
+    ) -> Result<Rc<ZwlrDataControlOfferV1>, ClientError> {
+        let offer = Rc::new(ZwlrDataControlOfferV1 {
+            id: self.client.new_id()?,
+            client: self.client.clone(),
+            tracker: Default::default(),
+            source: CloneCell::new(Some(src.clone())),
+        });
+        track!(self.client, offer);
+        self.client.add_server_obj(&offer);
+        self.client.event(DataOffer {
+            self_id: self.id,
+            id: offer.id,
+        });
+        src.for_each_mime_type(|mime_type| offer.send_offer(mime_type));
+        src.add_data_control_offer(&offer);
+        Ok(offer)
+    }
+
+    pub fn send_finished(&self) {
+        self.client.event(Finished { self_id: self.id });
+    }
+
+    fn get_source(
+        &self,
+        id: ZwlrDataControlSourceV1Id,
+    ) -> Result<Option<Rc<ZwlrDataControlSourceV1>>, ZwlrDataControlDeviceV1Error> {
+        if id.is_none() {
+            return Ok(None);
+        }
+        Ok(Some(self.client.lookup(id)?))
+    }
+
+    fn used_source(&self) -> ZwlrDataControlDeviceV1Error {
+        self.client.protocol_error(
+            self,
+            USED_SOURCE,
+            "The source has already been used to set a selection",
+        );
+        ZwlrDataControlDeviceV1Error::UsedSource
+    }
+
+    fn set_selection(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwlrDataControlDeviceV1Error> {
+        let req: SetSelection = self.client.parse(self, parser)?;
+        let src = match self.get_source(req.source)? {
+            Some(src) => match src.create_clipboard_source() {
+                Some(src) => Some(src),
+                _ => return Err(self.used_source()),
+            },
+            _ => None,
+        };
+        self.seat.set_selection(src, None)?;
+        Ok(())
+    }
+
+    fn set_primary_selection(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), ZwlrDataControlDeviceV1Error> {
+        let req: SetPrimarySelection = self.client.parse(self, parser)?;
+        let src = match self.get_source(req.source)? {
+            Some(src) => match src.create_primary_selection_source() {
+                Some(src) => Some(src),
+                _ => return Err(self.used_source()),
+            },
+            _ => None,
+        };
+        self.seat.set_primary_selection(src, None)?;
+        Ok(())
+    }
+
+    fn detach(&self) {
+        self.seat.remove_data_control_device(self);
+        self.selection.take();
+        self.primary_selection.take();
+    }
+
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwlrDataControlDeviceV1Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.detach();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    ZwlrDataControlDeviceV1;
+
+    SET_SELECTION => set_selection,
+    DESTROY => destroy,
+    SET_PRIMARY_SELECTION => set_primary_selection,
+}
+
+impl Object for ZwlrDataControlDeviceV1 {
+    fn num_requests(&self) -> u32 {
+        if self.version < PRIMARY_SELECTION_SINCE {
+            DESTROY + 1
+        } else {
+            SET_PRIMARY_SELECTION + 1
+        }
+    }
+
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
+
+simple_add_obj!(ZwlrDataControlDeviceV1);
+
+#[derive(Debug, Error)]
+pub enum ZwlrDataControlDeviceV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error(transparent)]
+    WlSeatError(Box<WlSeatError>),
+    #[error("The source has already been used")]
+    UsedSource,
+}
+efrom!(ZwlrDataControlDeviceV1Error, ClientError);
+efrom!(ZwlrDataControlDeviceV1Error, MsgParserError);
+efrom!(ZwlrDataControlDeviceV1Error, WlSeatError);
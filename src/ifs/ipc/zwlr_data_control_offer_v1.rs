@@ -0,0 +1,82 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::ipc::SelectionSource,
+        leaks::Tracker,
+        object::Object,
+        utils::{
+            buffd::{MsgParser, MsgParserError},
+            clonecell::CloneCell,
+        },
+        wire::{zwlr_data_control_offer_v1::*, ZwlrDataControlOfferV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct ZwlrDataControlOfferV1 {
+    pub id: ZwlrDataControlOfferV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    /// The source of the selection. This is unset once the selection changes.
+    pub source: CloneCell<Option<SelectionSource>>,
+}
+
+impl ZwlrDataControlOfferV1 {
+    pub fn send_offer(&self, mime_type: &str) {
+        self.client.event(Offer {
+            self_id: self.id,
+            mime_type,
+        })
+    }
+
+    fn detach(&self) {
+        if let Some(src) = self.source.take() {
+            src.remove_data_control_offer(self);
+        }
+    }
+
+    fn receive(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwlrDataControlOfferV1Error> {
+        let req: Receive = self.client.parse(self, parser)?;
+        if let Some(src) = self.source.get() {
+            src.send_send(req.mime_type, req.fd);
+        }
+        Ok(())
+    }
+
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwlrDataControlOfferV1Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.detach();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    ZwlrDataControlOfferV1;
+
+    RECEIVE => receive,
+    DESTROY => destroy,
+}
+
+impl Object for ZwlrDataControlOfferV1 {
+    fn num_requests(&self) -> u32 {
+        DESTROY + 1
+    }
+
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
+
+simple_add_obj!(ZwlrDataControlOfferV1);
+
+#[derive(Debug, Error)]
+pub enum ZwlrDataControlOfferV1Error {
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwlrDataControlOfferV1Error, ClientError);
+efrom!(ZwlrDataControlOfferV1Error, MsgParserError);
@@ -0,0 +1,181 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::ipc::{
+            add_data_source_mime_type, destroy_data_source, wl_data_device::ClipboardIpc,
+            wl_data_source::WlDataSource, zwp_primary_selection_device_v1::PrimarySelectionIpc,
+            zwp_primary_selection_source_v1::ZwpPrimarySelectionSourceV1, SelectionSource,
+        },
+        leaks::Tracker,
+        object::Object,
+        utils::{
+            buffd::{MsgParser, MsgParserError},
+            clonecell::CloneCell,
+        },
+        wire::{
+            zwlr_data_control_source_v1::*, WlDataSourceId, ZwlrDataControlSourceV1Id,
+            ZwpPrimarySelectionSourceV1Id,
+        },
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+    thiserror::Error,
+    uapi::OwnedFd,
+};
+
+const INVALID_OFFER: u32 = 1;
+
+/// Installed as a selection via a regular data source that forwards to this object.
+pub struct ZwlrDataControlSourceV1 {
+    pub id: ZwlrDataControlSourceV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    mime_types: RefCell<Vec<String>>,
+    used: Cell<bool>,
+    destroyed: Cell<bool>,
+    proxy: CloneCell<Option<SelectionSource>>,
+}
+
+impl ZwlrDataControlSourceV1 {
+    pub fn new(id: ZwlrDataControlSourceV1Id, client: &Rc<Client>) -> Self {
+        Self {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+            mime_types: Default::default(),
+            used: Cell::new(false),
+            destroyed: Cell::new(false),
+            proxy: Default::default(),
+        }
+    }
+
+    /// Returns `false` if the source has already been used.
+    fn mark_used(&self) -> bool {
+        !self.used.replace(true)
+    }
+
+    pub fn create_clipboard_source(self: &Rc<Self>) -> Option<Rc<WlDataSource>> {
+        if !self.mark_used() {
+            return None;
+        }
+        let src = Rc::new(WlDataSource::new(WlDataSourceId::NONE, &self.client, false));
+        src.data.data_control.set(Some(self.clone()));
+        for mime_type in self.mime_types.borrow().iter() {
+            add_data_source_mime_type::<ClipboardIpc>(&src, mime_type);
+        }
+        self.proxy
+            .set(Some(SelectionSource::Clipboard(src.clone())));
+        Some(src)
+    }
+
+    pub fn create_primary_selection_source(
+        self: &Rc<Self>,
+    ) -> Option<Rc<ZwpPrimarySelectionSourceV1>> {
+        if !self.mark_used() {
+            return None;
+        }
+        let src = Rc::new(ZwpPrimarySelectionSourceV1::new(
+            ZwpPrimarySelectionSourceV1Id::NONE,
+            &self.client,
+            false,
+        ));
+        src.data.data_control.set(Some(self.clone()));
+        for mime_type in self.mime_types.borrow().iter() {
+            add_data_source_mime_type::<PrimarySelectionIpc>(&src, mime_type);
+        }
+        self.proxy.set(Some(SelectionSource::Primary(src.clone())));
+        Some(src)
+    }
+
+    pub fn send_send(&self, mime_type: &str, fd: Rc<OwnedFd>) {
+        if !self.destroyed.get() {
+            self.client.event(Send {
+                self_id: self.id,
+                mime_type,
+                fd,
+            });
+        }
+    }
+
+    pub fn send_cancelled(&self) {
+        if !self.destroyed.get() {
+            self.client.event(Cancelled { self_id: self.id });
+        }
+    }
+
+    fn detach(&self) {
+        self.destroyed.set(true);
+        match self.proxy.take() {
+            Some(SelectionSource::Clipboard(src)) => {
+                destroy_data_source::<ClipboardIpc>(&src);
+                src.data.data_control.take();
+            }
+            Some(SelectionSource::Primary(src)) => {
+                destroy_data_source::<PrimarySelectionIpc>(&src);
+                src.data.data_control.take();
+            }
+            None => {}
+        }
+    }
+
+    fn offer(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwlrDataControlSourceV1Error> {
+        let req: Offer = self.client.parse(self, parser)?;
+        if self.used.get() {
+            self.client.protocol_error(
+                self,
+                INVALID_OFFER,
+                "The source has already been used to set a selection",
+            );
+            return Err(ZwlrDataControlSourceV1Error::AlreadyUsed);
+        }
+        let mut mime_types = self.mime_types.borrow_mut();
+        if !mime_types.iter().any(|m| m == req.mime_type) {
+            mime_types.push(req.mime_type.to_string());
+        }
+        Ok(())
+    }
+
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwlrDataControlSourceV1Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.detach();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    ZwlrDataControlSourceV1;
+
+    OFFER => offer,
+    DESTROY => destroy,
+}
+
+impl Object for ZwlrDataControlSourceV1 {
+    fn num_requests(&self) -> u32 {
+        DESTROY + 1
+    }
+
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
+
+dedicated_add_obj!(
+    ZwlrDataControlSourceV1,
+    ZwlrDataControlSourceV1Id,
+    zwlr_data_control_source
+);
+
+#[derive(Debug, Error)]
+pub enum ZwlrDataControlSourceV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error("The source has already been used")]
+    AlreadyUsed,
+}
+efrom!(ZwlrDataControlSourceV1Error, ClientError);
+efrom!(ZwlrDataControlSourceV1Error, MsgParserError);
@@ -39,6 +39,8 @@ impl ZwpPrimarySelectionSourceV1 {
                 .xwayland
                 .queue
                 .push(XWaylandEvent::PrimarySelectionCancelSource(self.clone()));
+        } else if let Some(dc) = self.data.data_control.get() {
+            dc.send_cancelled();
         } else {
             self.data.client.event(Cancelled { self_id: self.id });
         }
@@ -56,6 +58,8 @@ impl ZwpPrimarySelectionSourceV1 {
                     mime_type.to_string(),
                     fd,
                 ));
+        } else if let Some(dc) = self.data.data_control.get() {
+            dc.send_send(mime_type, fd);
         } else {
             self.data.client.event(Send {
                 self_id: self.id,
@@ -11,6 +11,7 @@ use {
             buffd::{MsgParser, MsgParserError},
             clonecell::CloneCell,
             errorfmt::ErrorFmt,
+            numcell::NumCell,
         },
         video::dmabuf::DmaBuf,
         wire::{wl_buffer::*, WlBufferId},
@@ -41,6 +42,8 @@ pub struct WlBuffer {
     pub famebuffer: CloneCell<Option<Rc<Framebuffer>>>,
     width: i32,
     height: i32,
+    scanout_refs: NumCell<u32>,
+    release_deferred: Cell<bool>,
     pub tracker: Tracker<Self>,
 }
 
@@ -72,6 +75,8 @@ impl WlBuffer {
             dmabuf: Some(dmabuf),
             render_ctx_version: Cell::new(client.state.render_ctx_version.get()),
             storage: RefCell::new(Some(WlBufferStorage::Dmabuf(img.clone()))),
+            scanout_refs: Default::default(),
+            release_deferred: Cell::new(false),
             tracker: Default::default(),
         }
     }
@@ -109,6 +114,8 @@ impl WlBuffer {
             width,
             height,
             texture: CloneCell::new(None),
+            scanout_refs: Default::default(),
+            release_deferred: Cell::new(false),
             tracker: Default::default(),
             famebuffer: Default::default(),
         })
@@ -198,6 +205,37 @@ impl WlBuffer {
         Ok(())
     }
 
+    pub fn dmabuf(&self) -> Option<&DmaBuf> {
+        self.dmabuf.as_ref()
+    }
+
+    /// Delays `release` until the matching `scanout_end`.
+    pub fn scanout_begin(&self) {
+        self.scanout_refs.fetch_add(1);
+    }
+
+    pub fn scanout_end(&self) {
+        if self.scanout_refs.fetch_sub(1) == 1 && self.release_deferred.take() {
+            if !self.destroyed() {
+                self.send_release();
+            }
+        }
+    }
+
+    /// Releases the buffer once it is no longer used by the compositor.
+    pub fn release(&self) {
+        if self.scanout_refs.get() > 0 {
+            self.release_deferred.set(true);
+        } else if !self.destroyed() {
+            self.send_release();
+        }
+    }
+
+    /// Cancels a delayed release because the buffer has been attached again.
+    pub fn cancel_release(&self) {
+        self.release_deferred.set(false);
+    }
+
     pub fn send_release(&self) {
         self.client.event(Release { self_id: self.id })
     }
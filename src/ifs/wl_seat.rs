@@ -1,12 +1,27 @@
 mod event_handling;
+mod gesture;
 mod kb_owner;
 mod pointer_owner;
+pub mod tablet;
+pub mod text_input;
 pub mod wl_keyboard;
 pub mod wl_pointer;
 pub mod wl_touch;
+pub mod wp_cursor_shape_device_v1;
+pub mod wp_cursor_shape_manager_v1;
+pub mod zwlr_virtual_pointer_manager_v1;
+pub mod zwlr_virtual_pointer_v1;
+pub mod zwp_keyboard_shortcuts_inhibit_manager_v1;
+pub mod zwp_keyboard_shortcuts_inhibitor_v1;
 pub mod zwp_pointer_constraints_v1;
+pub mod zwp_pointer_gesture_hold_v1;
+pub mod zwp_pointer_gesture_pinch_v1;
+pub mod zwp_pointer_gesture_swipe_v1;
+pub mod zwp_pointer_gestures_v1;
 pub mod zwp_relative_pointer_manager_v1;
 pub mod zwp_relative_pointer_v1;
+pub mod zwp_virtual_keyboard_manager_v1;
+pub mod zwp_virtual_keyboard_v1;
 
 pub use event_handling::NodeSeatState;
 use {
@@ -21,19 +36,26 @@ use {
             ipc::{
                 wl_data_device::{ClipboardIpc, WlDataDevice},
                 wl_data_source::WlDataSource,
+                zwlr_data_control_device_v1::ZwlrDataControlDeviceV1,
                 zwp_primary_selection_device_v1::{
                     PrimarySelectionIpc, ZwpPrimarySelectionDeviceV1,
                 },
                 zwp_primary_selection_source_v1::ZwpPrimarySelectionSourceV1,
-                IpcError,
+                IpcError, SelectionSource,
             },
             wl_seat::{
                 kb_owner::KbOwnerHolder,
                 pointer_owner::PointerOwnerHolder,
+                tablet::TabletSeatData,
+                text_input::TextInputSeatData,
                 wl_keyboard::{WlKeyboard, WlKeyboardError, REPEAT_INFO_SINCE},
                 wl_pointer::WlPointer,
                 wl_touch::WlTouch,
+                zwp_keyboard_shortcuts_inhibitor_v1::ZwpKeyboardShortcutsInhibitorV1,
                 zwp_pointer_constraints_v1::{SeatConstraint, SeatConstraintStatus},
+                zwp_pointer_gesture_hold_v1::ZwpPointerGestureHoldV1,
+                zwp_pointer_gesture_pinch_v1::ZwpPointerGesturePinchV1,
+                zwp_pointer_gesture_swipe_v1::ZwpPointerGestureSwipeV1,
                 zwp_relative_pointer_v1::ZwpRelativePointerV1,
             },
             wl_surface::WlSurface,
@@ -44,7 +66,7 @@ use {
         state::State,
         tree::{
             generic_node_visitor, ContainerNode, ContainerSplit, Direction, FloatNode, FoundNode,
-            Node, OutputNode, WorkspaceNode,
+            Node, NodeId, OutputNode, ToplevelNode, WorkspaceNode,
         },
         utils::{
             asyncevent::AsyncEvent,
@@ -57,13 +79,17 @@ use {
             rc_eq::rc_eq,
         },
         wire::{
-            wl_seat::*, WlDataDeviceId, WlKeyboardId, WlPointerId, WlSeatId,
-            ZwpPrimarySelectionDeviceV1Id, ZwpRelativePointerV1Id,
+            wl_seat::*, WlDataDeviceId, WlKeyboardId, WlPointerId, WlSeatId, WlTouchId,
+            ZwlrDataControlDeviceV1Id, ZwpPointerGestureHoldV1Id, ZwpPointerGesturePinchV1Id,
+            ZwpPointerGestureSwipeV1Id, ZwpPrimarySelectionDeviceV1Id, ZwpRelativePointerV1Id,
         },
         xkbcommon::{XkbKeymap, XkbState},
     },
     ahash::{AHashMap, AHashSet},
-    jay_config::keyboard::mods::Modifiers,
+    jay_config::{
+        keyboard::{mods::Modifiers, ModifiedKeySym},
+        Direction as JayDirection,
+    },
     smallvec::SmallVec,
     std::{
         cell::{Cell, RefCell},
@@ -78,7 +104,6 @@ use {
 
 pub const POINTER: u32 = 1;
 const KEYBOARD: u32 = 2;
-#[allow(dead_code)]
 const TOUCH: u32 = 4;
 
 #[allow(dead_code)]
@@ -125,6 +150,7 @@ pub struct WlSeatGlobal {
     pointer_stack_modified: Cell<bool>,
     found_tree: RefCell<Vec<FoundNode>>,
     keyboard_node: CloneCell<Rc<dyn Node>>,
+    focused_window: Cell<Option<NodeId>>,
     pressed_keys: RefCell<AHashSet<u32>>,
     bindings: RefCell<AHashMap<ClientId, AHashMap<WlSeatId, Rc<WlSeat>>>>,
     data_devices: RefCell<AHashMap<ClientId, AHashMap<WlDataDeviceId, Rc<WlDataDevice>>>>,
@@ -134,9 +160,13 @@ pub struct WlSeatGlobal {
             AHashMap<ZwpPrimarySelectionDeviceV1Id, Rc<ZwpPrimarySelectionDeviceV1>>,
         >,
     >,
+    data_control_devices:
+        CopyHashMap<(ClientId, ZwlrDataControlDeviceV1Id), Rc<ZwlrDataControlDeviceV1>>,
     repeat_rate: Cell<(i32, i32)>,
-    kb_map: CloneCell<Rc<XkbKeymap>>,
-    kb_state: RefCell<XkbState>,
+    kb_state: Rc<RefCell<XkbState>>,
+    /// The keyboard state whose keymap was last sent to clients. This is either
+    /// `kb_state` or the state of a virtual keyboard.
+    kb_source: CloneCell<Rc<RefCell<XkbState>>>,
     cursor: CloneCell<Option<Rc<dyn Cursor>>>,
     tree_changed: Rc<AsyncEvent>,
     selection: CloneCell<Option<Rc<WlDataSource>>>,
@@ -147,6 +177,8 @@ pub struct WlSeatGlobal {
     kb_owner: KbOwnerHolder,
     dropped_dnd: RefCell<Option<DroppedDnd>>,
     shortcuts: CopyHashMap<(u32, u32), Modifiers>,
+    shortcuts_inhibitor: CloneCell<Option<Rc<ZwpKeyboardShortcutsInhibitorV1>>>,
+    shortcuts_inhibit_escape: Cell<Option<ModifiedKeySym>>,
     queue_link: Cell<Option<LinkedNode<Rc<Self>>>>,
     tree_changed_handler: Cell<Option<SpawnedFuture<()>>>,
     output: CloneCell<Rc<OutputNode>>,
@@ -155,6 +187,14 @@ pub struct WlSeatGlobal {
     cursor_size: Cell<u32>,
     hardware_cursor: Cell<bool>,
     constraint: CloneCell<Option<Rc<SeatConstraint>>>,
+    cursor_damage: Cell<Option<Rect>>,
+    touch_points: CopyHashMap<i32, Rc<WlSurface>>,
+    touch_frame_clients: CopyHashMap<ClientId, ()>,
+    tablet: TabletSeatData,
+    text_input: TextInputSeatData,
+    gesture_client: Cell<Option<ClientId>>,
+    bound_swipe: Cell<Option<(u32, f64, f64)>>,
+    swipe_bindings: CopyHashMap<(u32, JayDirection), ()>,
 }
 
 const CHANGE_CURSOR_MOVED: u32 = 1 << 0;
@@ -170,6 +210,7 @@ impl Drop for WlSeatGlobal {
 
 impl WlSeatGlobal {
     pub fn new(name: GlobalName, seat_name: &str, state: &Rc<State>) -> Rc<Self> {
+        let kb_state = Rc::new(RefCell::new(state.default_keymap.state().unwrap()));
         let slf = Rc::new(Self {
             id: state.seat_ids.next(),
             name,
@@ -184,13 +225,15 @@ impl WlSeatGlobal {
             pointer_stack_modified: Cell::new(false),
             found_tree: RefCell::new(vec![]),
             keyboard_node: CloneCell::new(state.root.clone()),
+            focused_window: Cell::new(None),
             pressed_keys: RefCell::new(Default::default()),
             bindings: Default::default(),
             data_devices: RefCell::new(Default::default()),
             primary_selection_devices: RefCell::new(Default::default()),
+            data_control_devices: Default::default(),
             repeat_rate: Cell::new((25, 250)),
-            kb_map: CloneCell::new(state.default_keymap.clone()),
-            kb_state: RefCell::new(state.default_keymap.state().unwrap()),
+            kb_state: kb_state.clone(),
+            kb_source: CloneCell::new(kb_state),
             cursor: Default::default(),
             tree_changed: Default::default(),
             selection: Default::default(),
@@ -201,6 +244,8 @@ impl WlSeatGlobal {
             kb_owner: Default::default(),
             dropped_dnd: RefCell::new(None),
             shortcuts: Default::default(),
+            shortcuts_inhibitor: Default::default(),
+            shortcuts_inhibit_escape: Cell::new(None),
             queue_link: Cell::new(None),
             tree_changed_handler: Cell::new(None),
             output: CloneCell::new(state.dummy_output.get().unwrap()),
@@ -209,6 +254,14 @@ impl WlSeatGlobal {
             cursor_size: Cell::new(DEFAULT_CURSOR_SIZE),
             hardware_cursor: Cell::new(state.globals.seats.len() == 0),
             constraint: Default::default(),
+            cursor_damage: Cell::new(None),
+            touch_points: Default::default(),
+            touch_frame_clients: Default::default(),
+            tablet: Default::default(),
+            text_input: Default::default(),
+            gesture_client: Cell::new(None),
+            bound_swipe: Cell::new(None),
+            swipe_bindings: Default::default(),
         });
         state.add_cursor_size(DEFAULT_CURSOR_SIZE);
         let seat = slf.clone();
@@ -297,6 +350,52 @@ impl WlSeatGlobal {
         }
     }
 
+    pub fn damage_cursor(&self) {
+        let extents = self.cursor_extents();
+        if let Some(old) = self.cursor_damage.replace(extents) {
+            self.state.damage_rect(old);
+        }
+        if let Some(new) = extents {
+            self.state.damage_rect(new);
+        }
+    }
+
+    fn cursor_extents(&self) -> Option<Rect> {
+        let (x, y) = self.get_position();
+        let (x, y) = (x.round_down(), y.round_down());
+        let mut extents = None::<Rect>;
+        let mut add = |rect: Rect| {
+            if !rect.is_empty() {
+                extents = Some(match extents {
+                    Some(e) => e.union(rect),
+                    _ => rect,
+                });
+            }
+        };
+        if let Some(cursor) = self.get_cursor() {
+            let scale = self.output.get().preferred_scale.get();
+            let mut rect = cursor.extents_at_scale(scale);
+            if scale != 1 {
+                let scale = scale.to_f64();
+                rect = Rect::new(
+                    (rect.x1() as f64 / scale).floor() as _,
+                    (rect.y1() as f64 / scale).floor() as _,
+                    (rect.x2() as f64 / scale).ceil() as _,
+                    (rect.y2() as f64 / scale).ceil() as _,
+                )
+                .unwrap();
+            }
+            add(rect.move_(x, y));
+        }
+        if let Some(dnd_icon) = self.dnd_icon() {
+            add(dnd_icon
+                .extents
+                .get()
+                .move_(x + dnd_icon.buf_x.get(), y + dnd_icon.buf_y.get()));
+        }
+        extents
+    }
+
     pub fn set_cursor_size(&self, size: u32) {
         let old = self.cursor_size.replace(size);
         if size != old {
@@ -345,45 +444,8 @@ impl WlSeatGlobal {
     }
 
     pub fn set_workspace(&self, ws: &Rc<WorkspaceNode>) {
-        let tl = match self.keyboard_node.get().node_toplevel() {
-            Some(tl) => tl,
-            _ => return,
-        };
-        if tl.tl_data().is_fullscreen.get() {
-            return;
-        }
-        let old_ws = match tl.tl_data().workspace.get() {
-            Some(ws) => ws,
-            _ => return,
-        };
-        if old_ws.id == ws.id {
-            return;
-        }
-        let cn = match tl
-            .tl_data()
-            .parent
-            .get()
-            .and_then(|p| p.node_into_containing_node())
-        {
-            Some(cn) => cn,
-            _ => return,
-        };
-        let kb_foci = collect_kb_foci(tl.clone().tl_into_node());
-        cn.cnode_remove_child2(tl.tl_as_node(), true);
-        if !ws.visible.get() {
-            for focus in kb_foci {
-                old_ws.clone().node_do_focus(&focus, Direction::Unspecified);
-            }
-        }
-        if tl.tl_data().is_floating.get() {
-            self.state.map_floating(
-                tl.clone(),
-                tl.tl_data().float_width.get(),
-                tl.tl_data().float_height.get(),
-                ws,
-            );
-        } else {
-            self.state.map_tiled_on(tl, ws);
+        if let Some(tl) = self.keyboard_node.get().node_toplevel() {
+            self.state.move_toplevel_to_workspace(tl, ws);
         }
     }
 
@@ -454,8 +516,12 @@ impl WlSeatGlobal {
                 return;
             }
         };
-        self.kb_map.set(keymap.clone());
         *self.kb_state.borrow_mut() = state;
+        self.kb_source.set(self.kb_state.clone());
+        self.send_keymap(keymap);
+    }
+
+    fn send_keymap(&self, keymap: &Rc<XkbKeymap>) {
         let bindings = self.bindings.borrow_mut();
         for (id, client) in bindings.iter() {
             for seat in client.values() {
@@ -472,6 +538,7 @@ impl WlSeatGlobal {
                 }
             }
         }
+        self.input_method_keymap_changed(keymap);
     }
 
     pub fn prepare_for_lock(self: &Rc<Self>) {
@@ -582,6 +649,24 @@ impl WlSeatGlobal {
         }
     }
 
+    fn keyboard_focus_changed(&self, node: &Rc<dyn Node>) {
+        let window = node
+            .clone()
+            .node_toplevel()
+            .map(|tl| tl.node_id())
+            .filter(|id| self.state.toplevels.contains(id));
+        if self.focused_window.replace(window) == window {
+            return;
+        }
+        if let Some(config) = self.state.config.get() {
+            config.window_focused(self.id, window);
+        }
+    }
+
+    pub fn get_focused_toplevel(&self) -> Option<Rc<dyn ToplevelNode>> {
+        self.keyboard_node.get().node_toplevel()
+    }
+
     pub fn get_floating(self: &Rc<Self>) -> Option<bool> {
         match self.keyboard_node.get().node_toplevel() {
             Some(tl) => Some(tl.tl_data().is_floating.get()),
@@ -590,30 +675,8 @@ impl WlSeatGlobal {
     }
 
     pub fn set_floating(self: &Rc<Self>, floating: bool) {
-        let tl = match self.keyboard_node.get().node_toplevel() {
-            Some(tl) => tl,
-            _ => return,
-        };
-        let data = tl.tl_data();
-        if data.is_fullscreen.get() {
-            return;
-        }
-        if data.is_floating.get() == floating {
-            return;
-        }
-        let parent = match data.parent.get() {
-            Some(p) => p,
-            _ => return,
-        };
-        if let Some(cn) = parent.node_into_containing_node() {
-            if !floating {
-                cn.cnode_remove_child2(tl.tl_as_node(), true);
-                self.state.map_tiled(tl);
-            } else if let Some(ws) = data.workspace.get() {
-                cn.cnode_remove_child2(tl.tl_as_node(), true);
-                let (width, height) = data.float_size(&ws);
-                self.state.map_floating(tl, width, height, &ws);
-            }
+        if let Some(tl) = self.keyboard_node.get().node_toplevel() {
+            self.state.set_toplevel_floating(tl, floating);
         }
     }
 
@@ -634,6 +697,7 @@ impl WlSeatGlobal {
                 }
             }
         }
+        self.input_method_repeat_info_changed(rate, delay);
     }
 
     pub fn close(self: &Rc<Self>) {
@@ -668,6 +732,27 @@ impl WlSeatGlobal {
         }
     }
 
+    pub fn add_data_control_device(&self, device: &Rc<ZwlrDataControlDeviceV1>) {
+        self.data_control_devices
+            .set((device.client.id, device.id), device.clone());
+        device.send_selection(self.selection.get().map(SelectionSource::Clipboard), false);
+        device.send_selection(
+            self.primary_selection.get().map(SelectionSource::Primary),
+            true,
+        );
+    }
+
+    pub fn remove_data_control_device(&self, device: &ZwlrDataControlDeviceV1) {
+        self.data_control_devices
+            .remove(&(device.client.id, device.id));
+    }
+
+    fn data_control_selection_changed(&self, source: Option<SelectionSource>, primary: bool) {
+        for device in self.data_control_devices.lock().values() {
+            device.send_selection(source.clone(), primary);
+        }
+    }
+
     fn set_selection_<T: ipc::IpcVtable>(
         self: &Rc<Self>,
         field: &CloneCell<Option<Rc<T::Source>>>,
@@ -726,7 +811,17 @@ impl WlSeatGlobal {
         if let Some(serial) = serial {
             self.selection_serial.set(serial);
         }
-        self.set_selection_::<ClipboardIpc>(&self.selection, selection)
+        self.set_selection_::<ClipboardIpc>(&self.selection, selection)?;
+        self.data_control_selection_changed(
+            self.selection.get().map(SelectionSource::Clipboard),
+            false,
+        );
+        Ok(())
+    }
+
+    /// Returns the client that has the keyboard focus.
+    pub fn keyboard_node_client(&self) -> Option<ClientId> {
+        self.keyboard_node.get().node_client_id()
     }
 
     pub fn may_modify_selection(&self, client: &Rc<Client>, serial: u32) -> bool {
@@ -760,7 +855,12 @@ impl WlSeatGlobal {
         if let Some(serial) = serial {
             self.primary_selection_serial.set(serial);
         }
-        self.set_selection_::<PrimarySelectionIpc>(&self.primary_selection, selection)
+        self.set_selection_::<PrimarySelectionIpc>(&self.primary_selection, selection)?;
+        self.data_control_selection_changed(
+            self.primary_selection.get().map(SelectionSource::Primary),
+            true,
+        );
+        Ok(())
     }
 
     pub fn reload_known_cursor(&self) {
@@ -769,6 +869,11 @@ impl WlSeatGlobal {
         }
     }
 
+    #[cfg(feature = "it")]
+    pub fn desired_known_cursor(&self) -> Option<KnownCursor> {
+        self.desired_known_cursor.get()
+    }
+
     pub fn set_known_cursor(&self, cursor: KnownCursor) {
         self.desired_known_cursor.set(Some(cursor));
         let cursors = match self.state.cursors.get() {
@@ -787,6 +892,32 @@ impl WlSeatGlobal {
             KnownCursor::ResizeTopRight => &cursors.resize_top_right,
             KnownCursor::ResizeBottomLeft => &cursors.resize_bottom_left,
             KnownCursor::ResizeBottomRight => &cursors.resize_bottom_right,
+            KnownCursor::ResizeLeft => &cursors.resize_left,
+            KnownCursor::ResizeRight => &cursors.resize_right,
+            KnownCursor::ResizeTop => &cursors.resize_top,
+            KnownCursor::ResizeBottom => &cursors.resize_bottom,
+            KnownCursor::ContextMenu => &cursors.context_menu,
+            KnownCursor::Help => &cursors.help,
+            KnownCursor::Progress => &cursors.progress,
+            KnownCursor::Wait => &cursors.wait,
+            KnownCursor::Cell => &cursors.cell,
+            KnownCursor::Crosshair => &cursors.crosshair,
+            KnownCursor::Text => &cursors.text,
+            KnownCursor::VerticalText => &cursors.vertical_text,
+            KnownCursor::Alias => &cursors.alias,
+            KnownCursor::Copy => &cursors.copy,
+            KnownCursor::Move => &cursors.move_,
+            KnownCursor::NoDrop => &cursors.no_drop,
+            KnownCursor::NotAllowed => &cursors.not_allowed,
+            KnownCursor::Grab => &cursors.grab,
+            KnownCursor::Grabbing => &cursors.grabbing,
+            KnownCursor::ResizeTopRightBottomLeft => &cursors.resize_top_right_bottom_left,
+            KnownCursor::ResizeTopLeftBottomRight => &cursors.resize_top_left_bottom_right,
+            KnownCursor::ColResize => &cursors.col_resize,
+            KnownCursor::RowResize => &cursors.row_resize,
+            KnownCursor::AllScroll => &cursors.all_scroll,
+            KnownCursor::ZoomIn => &cursors.zoom_in,
+            KnownCursor::ZoomOut => &cursors.zoom_out,
         };
         self.set_cursor2(Some(tpl.instantiate(self.cursor_size.get())));
     }
@@ -812,6 +943,7 @@ impl WlSeatGlobal {
         self.cursor.set(cursor.clone());
         self.state.hardware_tick_cursor.push(cursor);
         self.update_hardware_cursor();
+        self.damage_cursor();
     }
 
     pub fn dnd_icon(&self) -> Option<Rc<WlSurface>> {
@@ -843,6 +975,9 @@ impl WlSeatGlobal {
         self.bindings.borrow_mut().clear();
         self.data_devices.borrow_mut().clear();
         self.primary_selection_devices.borrow_mut().clear();
+        for (_, device) in self.data_control_devices.clear() {
+            device.send_finished();
+        }
         self.cursor.set(None);
         self.selection.set(None);
         self.primary_selection.set(None);
@@ -853,6 +988,13 @@ impl WlSeatGlobal {
         self.tree_changed_handler.set(None);
         self.output.set(self.state.dummy_output.get().unwrap());
         self.constraint.take();
+        self.shortcuts_inhibitor.take();
+        self.touch_points.clear();
+        self.touch_frame_clients.clear();
+        self.tablet.clear();
+        self.text_input.clear();
+        self.gesture_client.take();
+        self.bound_swipe.take();
     }
 
     pub fn id(&self) -> SeatId {
@@ -875,7 +1017,11 @@ impl WlSeatGlobal {
             client: client.clone(),
             pointers: Default::default(),
             relative_pointers: Default::default(),
+            swipe_gestures: Default::default(),
+            pinch_gestures: Default::default(),
+            hold_gestures: Default::default(),
             keyboards: Default::default(),
+            touches: Default::default(),
             version,
             tracker: Default::default(),
         });
@@ -920,7 +1066,11 @@ pub struct WlSeat {
     pub client: Rc<Client>,
     pointers: CopyHashMap<WlPointerId, Rc<WlPointer>>,
     relative_pointers: CopyHashMap<ZwpRelativePointerV1Id, Rc<ZwpRelativePointerV1>>,
+    swipe_gestures: CopyHashMap<ZwpPointerGestureSwipeV1Id, Rc<ZwpPointerGestureSwipeV1>>,
+    pinch_gestures: CopyHashMap<ZwpPointerGesturePinchV1Id, Rc<ZwpPointerGesturePinchV1>>,
+    hold_gestures: CopyHashMap<ZwpPointerGestureHoldV1Id, Rc<ZwpPointerGestureHoldV1>>,
     keyboards: CopyHashMap<WlKeyboardId, Rc<WlKeyboard>>,
+    touches: CopyHashMap<WlTouchId, Rc<WlTouch>>,
     version: u32,
     tracker: Tracker<Self>,
 }
@@ -931,7 +1081,7 @@ impl WlSeat {
     fn send_capabilities(self: &Rc<Self>) {
         self.client.event(Capabilities {
             self_id: self.id,
-            capabilities: POINTER | KEYBOARD,
+            capabilities: POINTER | KEYBOARD | TOUCH,
         })
     }
 
@@ -961,7 +1111,7 @@ impl WlSeat {
         track!(self.client, p);
         self.client.add_client_obj(&p)?;
         self.keyboards.set(req.id, p.clone());
-        let keymap = self.global.kb_map.get();
+        let keymap = self.global.kb_source.get().borrow().map().clone();
         p.send_keymap(
             wl_keyboard::XKB_V1,
             self.keymap_fd(&keymap)?,
@@ -1000,6 +1150,7 @@ impl WlSeat {
         let p = Rc::new(WlTouch::new(req.id, self));
         track!(self.client, p);
         self.client.add_client_obj(&p)?;
+        self.touches.set(req.id, p);
         Ok(())
     }
 
@@ -1049,7 +1200,11 @@ impl Object for WlSeat {
         }
         self.pointers.clear();
         self.relative_pointers.clear();
+        self.swipe_gestures.clear();
+        self.pinch_gestures.clear();
+        self.hold_gestures.clear();
         self.keyboards.clear();
+        self.touches.clear();
     }
 }
 
@@ -19,17 +19,18 @@ use {
                     AXIS_VALUE120_SINCE_VERSION, POINTER_FRAME_SINCE_VERSION, WHEEL_TILT,
                     WHEEL_TILT_SINCE_VERSION,
                 },
+                wl_touch::WlTouch,
                 zwp_pointer_constraints_v1::{ConstraintType, SeatConstraintStatus},
                 zwp_relative_pointer_v1::ZwpRelativePointerV1,
-                Dnd, SeatId, WlSeat, WlSeatGlobal, CHANGE_CURSOR_MOVED,
+                Dnd, SeatId, WlSeat, WlSeatGlobal, CHANGE_CURSOR_MOVED, CHANGE_TREE,
             },
             wl_surface::{xdg_surface::xdg_popup::XdgPopup, WlSurface},
         },
         state::DeviceHandlerData,
-        tree::{Direction, FloatNode, Node, ToplevelNode},
-        utils::{bitflags::BitflagsExt, clonecell::CloneCell, smallmap::SmallMap},
+        tree::{Direction, FloatNode, FoundNode, Node, NodeId, ToplevelNode},
+        utils::{bitflags::BitflagsExt, clonecell::CloneCell, rc_eq::rc_eq, smallmap::SmallMap},
         wire::WlDataOfferId,
-        xkbcommon::{ModifierState, XKB_KEY_DOWN, XKB_KEY_UP},
+        xkbcommon::{ModifierState, XkbState, XKB_KEY_DOWN, XKB_KEY_UP},
     },
     jay_config::keyboard::{
         mods::{Modifiers, CAPS, NUM},
@@ -37,7 +38,7 @@ use {
         ModifiedKeySym,
     },
     smallvec::SmallVec,
-    std::rc::Rc,
+    std::{cell::RefCell, rc::Rc},
 };
 
 #[derive(Default)]
@@ -46,6 +47,8 @@ pub struct NodeSeatState {
     kb_foci: SmallMap<SeatId, Rc<WlSeatGlobal>, 1>,
     pointer_grabs: SmallMap<SeatId, Rc<WlSeatGlobal>, 1>,
     dnd_targets: SmallMap<SeatId, Rc<WlSeatGlobal>, 1>,
+    touch_foci: SmallMap<SeatId, Rc<WlSeatGlobal>, 1>,
+    tablet_foci: SmallMap<SeatId, Rc<WlSeatGlobal>, 1>,
 }
 
 impl NodeSeatState {
@@ -83,6 +86,23 @@ impl NodeSeatState {
         self.dnd_targets.remove(&seat.id);
     }
 
+    fn add_touch_focus(&self, seat: &Rc<WlSeatGlobal>) {
+        self.touch_foci.insert(seat.id, seat.clone());
+    }
+
+    fn remove_touch_focus(&self, seat: &WlSeatGlobal) {
+        self.touch_foci.remove(&seat.id);
+        self.tablet_foci.remove(&seat.id);
+    }
+
+    pub(super) fn add_tablet_focus(&self, seat: &Rc<WlSeatGlobal>) {
+        self.tablet_foci.insert(seat.id, seat.clone());
+    }
+
+    pub(super) fn remove_tablet_focus(&self, seat: &WlSeatGlobal) {
+        self.tablet_foci.remove(&seat.id);
+    }
+
     pub fn is_active(&self) -> bool {
         self.kb_foci.len() > 0
     }
@@ -100,13 +120,17 @@ impl NodeSeatState {
     fn release_kb_focus2(&self, focus_last: bool) {
         self.release_kb_grab();
         while let Some((_, seat)) = self.kb_foci.pop() {
-            seat.keyboard_node.set(seat.state.root.clone());
+            let root: Rc<dyn Node> = seat.state.root.clone();
+            seat.keyboard_node.set(root.clone());
             // log::info!("keyboard_node = root");
             if focus_last {
                 seat.output
                     .get()
                     .node_do_focus(&seat, Direction::Unspecified);
             }
+            // Reported once the focus has settled so that the config does not see
+            // the intermediate root focus.
+            seat.keyboard_focus_changed(&seat.keyboard_node.get());
         }
     }
 
@@ -144,6 +168,12 @@ impl NodeSeatState {
             seat.pointer_stack_modified.set(true);
             seat.state.tree_changed();
         }
+        while let Some((_, seat)) = self.touch_foci.pop() {
+            seat.cancel_touch_points(node_id);
+        }
+        while let Some((_, seat)) = self.tablet_foci.pop() {
+            seat.tablet_node_removed(node_id);
+        }
         self.release_kb_focus2(focus_last);
     }
 
@@ -161,6 +191,7 @@ impl NodeSeatState {
         self.pointer_foci.remove(&seat.id);
         self.dnd_targets.remove(&seat.id);
         self.pointer_grabs.remove(&seat.id);
+        self.touch_foci.remove(&seat.id);
     }
 
     pub fn clear(&self) {
@@ -168,6 +199,8 @@ impl NodeSeatState {
         self.pointer_foci.clear();
         self.dnd_targets.clear();
         self.pointer_grabs.clear();
+        self.touch_foci.clear();
+        self.tablet_foci.clear();
     }
 }
 
@@ -202,11 +235,99 @@ impl WlSeatGlobal {
             InputEvent::Axis120 { dist, axis } => self.pointer_owner.axis_120(dist, axis),
             InputEvent::AxisPx { dist, axis } => self.pointer_owner.axis_px(dist, axis),
             InputEvent::AxisStop { axis } => self.pointer_owner.axis_stop(axis),
-            InputEvent::AxisFrame { time_usec } => self.pointer_owner.frame(dev, self, time_usec),
+            InputEvent::AxisFrame { time_usec } => {
+                self.pointer_owner
+                    .frame(dev.px_per_scroll_wheel.get(), self, time_usec)
+            }
+
+            InputEvent::TouchDown {
+                time_usec,
+                id,
+                x_normed,
+                y_normed,
+            } => self.touch_down_event(dev, time_usec, id, x_normed, y_normed),
+            InputEvent::TouchUp { time_usec, id } => self.touch_up_event(time_usec, id),
+            InputEvent::TouchMotion {
+                time_usec,
+                id,
+                x_normed,
+                y_normed,
+            } => self.touch_motion_event(dev, time_usec, id, x_normed, y_normed),
+            InputEvent::TouchCancel { id } => self.touch_cancel_event(id),
+            InputEvent::TouchFrame => self.touch_frame_event(),
+            InputEvent::TabletToolAdded { init } => self.tablet_tool_added(dev, init),
+            InputEvent::TabletToolChanged {
+                time_usec,
+                id,
+                changes,
+            } => self.tablet_tool_changed(dev, time_usec, id, &changes),
+            InputEvent::TabletToolRemoved { id } => self.tablet_tool_removed(id),
+            InputEvent::TabletToolButton {
+                time_usec,
+                id,
+                button,
+                state,
+            } => self.tablet_tool_button(time_usec, id, button, state),
+            InputEvent::TabletPadButton {
+                time_usec,
+                button,
+                state,
+            } => self.tablet_pad_button(dev, time_usec, button, state),
+            InputEvent::TabletPadModeSwitch {
+                time_usec,
+                group,
+                mode,
+            } => self.tablet_pad_mode_switch(dev, time_usec, group, mode),
+            InputEvent::TabletPadRing {
+                time_usec,
+                ring,
+                finger,
+                angle,
+            } => self.tablet_pad_ring(dev, time_usec, ring, finger, angle),
+            InputEvent::TabletPadStrip {
+                time_usec,
+                strip,
+                finger,
+                position,
+            } => self.tablet_pad_strip(dev, time_usec, strip, finger, position),
+            InputEvent::SwipeBegin {
+                time_usec,
+                finger_count,
+            } => self.swipe_begin_event(time_usec, finger_count),
+            InputEvent::SwipeUpdate { time_usec, dx, dy } => {
+                self.swipe_update_event(time_usec, dx, dy)
+            }
+            InputEvent::SwipeEnd {
+                time_usec,
+                cancelled,
+            } => self.swipe_end_event(time_usec, cancelled),
+            InputEvent::PinchBegin {
+                time_usec,
+                finger_count,
+            } => self.pinch_begin_event(time_usec, finger_count),
+            InputEvent::PinchUpdate {
+                time_usec,
+                dx,
+                dy,
+                scale,
+                rotation,
+            } => self.pinch_update_event(time_usec, dx, dy, scale, rotation),
+            InputEvent::PinchEnd {
+                time_usec,
+                cancelled,
+            } => self.pinch_end_event(time_usec, cancelled),
+            InputEvent::HoldBegin {
+                time_usec,
+                finger_count,
+            } => self.hold_begin_event(time_usec, finger_count),
+            InputEvent::HoldEnd {
+                time_usec,
+                cancelled,
+            } => self.hold_end_event(time_usec, cancelled),
         }
     }
 
-    fn connector_position_event(
+    pub(super) fn connector_position_event(
         self: &Rc<Self>,
         time_usec: u64,
         connector: ConnectorId,
@@ -232,7 +353,7 @@ impl WlSeatGlobal {
         self.set_new_position(time_usec, x, y);
     }
 
-    fn motion_event(
+    pub(super) fn motion_event(
         self: &Rc<Self>,
         time_usec: u64,
         dx: Fixed,
@@ -310,14 +431,184 @@ impl WlSeatGlobal {
         self.set_new_position(time_usec, x, y);
     }
 
-    fn button_event(self: &Rc<Self>, time_usec: u64, button: u32, state: KeyState) {
+    pub(super) fn button_event(self: &Rc<Self>, time_usec: u64, button: u32, state: KeyState) {
         self.state.for_each_seat_tester(|t| {
             t.send_button(self.id, time_usec, button, state);
         });
         self.pointer_owner.button(self, time_usec, button, state);
     }
 
+    /// Maps normalized device coordinates to the output the device is mapped to.
+    pub(super) fn absolute_position(
+        &self,
+        dev: &DeviceHandlerData,
+        x_normed: f64,
+        y_normed: f64,
+    ) -> (Fixed, Fixed) {
+        let output = match dev.connector.get() {
+            Some(c) => match self.state.outputs.get(&c) {
+                Some(o) => o.node.clone(),
+                _ => self.output.get(),
+            },
+            _ => self.output.get(),
+        };
+        let pos = output.global.pos.get();
+        let x = pos.x1() as f64 + x_normed * pos.width() as f64;
+        let y = pos.y1() as f64 + y_normed * pos.height() as f64;
+        (Fixed::from_f64(x), Fixed::from_f64(y))
+    }
+
+    pub(super) fn surface_at(&self, x: Fixed, y: Fixed) -> Option<(Rc<WlSurface>, Fixed, Fixed)> {
+        let x_int = x.round_down();
+        let y_int = y.round_down();
+        let found = {
+            let mut found_tree = self.found_tree.borrow_mut();
+            found_tree.push(FoundNode {
+                node: self.state.root.clone(),
+                x: x_int,
+                y: y_int,
+            });
+            self.state
+                .root
+                .node_find_tree_at(x_int, y_int, &mut found_tree);
+            let last = found_tree.pop();
+            found_tree.clear();
+            last?
+        };
+        let surface = found.node.node_into_surface()?;
+        Some((surface, x.apply_fract(found.x), y.apply_fract(found.y)))
+    }
+
+    fn touch_down_event(
+        self: &Rc<Self>,
+        dev: &DeviceHandlerData,
+        time_usec: u64,
+        id: i32,
+        x_normed: f64,
+        y_normed: f64,
+    ) {
+        let (x, y) = self.absolute_position(dev, x_normed, y_normed);
+        let (surface, x, y) = match self.surface_at(x, y) {
+            Some(s) => s,
+            _ => return,
+        };
+        if let Some(old) = self.touch_points.set(id, surface.clone()) {
+            self.touch_point_removed(&old);
+        }
+        surface.node_seat_state().add_touch_focus(self);
+        if let Some(node) = surface.get_focus_node(self.id) {
+            self.focus_node(node);
+        }
+        let serial = self.state.next_serial(Some(&surface.client));
+        let time = (time_usec / 1000) as u32;
+        self.for_each_touch(surface.client.id, |t| {
+            t.send_down(serial, time, surface.id, id, x, y)
+        });
+        self.touch_frame_clients.set(surface.client.id, ());
+    }
+
+    fn touch_up_event(&self, time_usec: u64, id: i32) {
+        let surface = match self.touch_points.remove(&id) {
+            Some(s) => s,
+            _ => return,
+        };
+        self.touch_point_removed(&surface);
+        let serial = self.state.next_serial(Some(&surface.client));
+        let time = (time_usec / 1000) as u32;
+        self.for_each_touch(surface.client.id, |t| t.send_up(serial, time, id));
+        self.touch_frame_clients.set(surface.client.id, ());
+    }
+
+    fn touch_motion_event(
+        &self,
+        dev: &DeviceHandlerData,
+        time_usec: u64,
+        id: i32,
+        x_normed: f64,
+        y_normed: f64,
+    ) {
+        let surface = match self.touch_points.get(&id) {
+            Some(s) => s,
+            _ => return,
+        };
+        let (x, y) = self.absolute_position(dev, x_normed, y_normed);
+        let surface_pos = surface.buffer_abs_pos.get();
+        let (x, y) = (x - surface_pos.x1(), y - surface_pos.y1());
+        let time = (time_usec / 1000) as u32;
+        self.for_each_touch(surface.client.id, |t| t.send_motion(time, id, x, y));
+        self.touch_frame_clients.set(surface.client.id, ());
+    }
+
+    fn touch_cancel_event(&self, id: i32) {
+        if let Some(surface) = self.touch_points.get(&id) {
+            self.cancel_touch_client(surface.client.id);
+        }
+    }
+
+    fn touch_frame_event(&self) {
+        for (client, _) in self.touch_frame_clients.clear() {
+            self.for_each_touch(client, |t| t.send_frame());
+        }
+    }
+
+    fn touch_point_removed(&self, surface: &WlSurface) {
+        let node_id = surface.node_id();
+        let touched = self
+            .touch_points
+            .lock()
+            .values()
+            .any(|s| s.node_id() == node_id);
+        if !touched {
+            surface.node_seat_state().remove_touch_focus(self);
+        }
+    }
+
+    /// wl_touch.cancel applies to all touch points of the client.
+    fn cancel_touch_client(&self, client: ClientId) {
+        let mut removed = vec![];
+        self.touch_points.lock().retain(|_, s| {
+            if s.client.id == client {
+                removed.push(s.clone());
+                false
+            } else {
+                true
+            }
+        });
+        if removed.is_empty() {
+            return;
+        }
+        for surface in &removed {
+            self.touch_point_removed(surface);
+        }
+        self.touch_frame_clients.remove(&client);
+        self.for_each_touch(client, |t| t.send_cancel());
+    }
+
+    fn cancel_touch_points(&self, node_id: NodeId) {
+        let client = self
+            .touch_points
+            .lock()
+            .values()
+            .find(|s| s.node_id() == node_id)
+            .map(|s| s.client.id);
+        if let Some(client) = client {
+            self.cancel_touch_client(client);
+        }
+    }
+
     fn key_event(&self, time_usec: u64, key: u32, key_state: KeyState) {
+        let kb_state = self.kb_state.clone();
+        self.key_event_with_state(time_usec, key, key_state, &kb_state, true);
+    }
+
+    pub(super) fn key_event_with_state(
+        &self,
+        time_usec: u64,
+        key: u32,
+        key_state: KeyState,
+        kb_state: &Rc<RefCell<XkbState>>,
+        to_input_method: bool,
+    ) {
         let (state, xkb_dir) = {
             let mut pk = self.pressed_keys.borrow_mut();
             match key_state {
@@ -335,15 +626,25 @@ impl WlSeatGlobal {
                 }
             }
         };
+        self.set_kb_source(kb_state);
         let mut shortcuts = SmallVec::<[_; 1]>::new();
+        let mut escaped = false;
         let new_mods;
         {
-            let mut kb_state = self.kb_state.borrow_mut();
+            let mut kb_state = kb_state.borrow_mut();
             if !self.state.lock.locked.get() && state == wl_keyboard::PRESSED {
                 let old_mods = kb_state.mods();
                 let keysyms = kb_state.unmodified_keysyms(key);
+                let inhibited = self.shortcuts_inhibitor.get().is_some();
+                let escape = self.shortcuts_inhibit_escape.get();
                 for &sym in keysyms {
                     let mods = old_mods.mods_effective & !(CAPS.0 | NUM.0);
+                    if inhibited {
+                        if let Some(escape) = escape {
+                            escaped |= (escape.mods.0, escape.sym.0) == (mods, sym);
+                        }
+                        continue;
+                    }
                     if let Some(mods) = self.shortcuts.get(&(mods, sym)) {
                         shortcuts.push(ModifiedKeySym {
                             mods,
@@ -358,20 +659,46 @@ impl WlSeatGlobal {
             t.send_key(self.id, time_usec, key, key_state);
         });
         let node = self.keyboard_node.get();
-        if shortcuts.is_empty() {
-            node.node_on_key(self, time_usec, key, state);
+        if escaped {
+            if let Some(inhibitor) = self.shortcuts_inhibitor.get() {
+                inhibitor.deactivate();
+            }
+        } else if shortcuts.is_empty() {
+            if !to_input_method || !self.input_method_key(time_usec, key, state) {
+                node.node_on_key(self, time_usec, key, state);
+            }
         } else if let Some(config) = self.state.config.get() {
             for shortcut in shortcuts {
                 config.invoke_shortcut(self.id(), &shortcut);
             }
         }
         if let Some(mods) = new_mods {
-            self.state.for_each_seat_tester(|t| {
-                t.send_modifiers(self.id, &mods);
-            });
-            node.node_on_mods(self, mods);
+            self.mods_event(mods);
         }
     }
+
+    pub(super) fn mods_event(&self, mods: ModifierState) {
+        self.state.for_each_seat_tester(|t| {
+            t.send_modifiers(self.id, &mods);
+        });
+        if !self.input_method_mods(mods) {
+            self.keyboard_node.get().node_on_mods(self, mods);
+        }
+    }
+
+    /// Makes the keymap of `kb_state` the keymap used by clients.
+    pub(super) fn set_kb_source(&self, kb_state: &Rc<RefCell<XkbState>>) {
+        if rc_eq(&self.kb_source.get(), kb_state) {
+            return;
+        }
+        self.kb_source.set(kb_state.clone());
+        let (keymap, mods) = {
+            let kb_state = kb_state.borrow();
+            (kb_state.map().clone(), kb_state.mods())
+        };
+        self.send_keymap(&keymap);
+        self.mods_event(mods);
+    }
 }
 
 impl WlSeatGlobal {
@@ -419,7 +746,7 @@ impl WlSeatGlobal {
         }
     }
 
-    fn for_each_seat<C>(&self, ver: u32, client: ClientId, mut f: C)
+    pub(super) fn for_each_seat<C>(&self, ver: u32, client: ClientId, mut f: C)
     where
         C: FnMut(&Rc<WlSeat>),
     {
@@ -457,6 +784,18 @@ impl WlSeatGlobal {
         })
     }
 
+    fn for_each_touch<C>(&self, client: ClientId, mut f: C)
+    where
+        C: FnMut(&Rc<WlTouch>),
+    {
+        self.for_each_seat(0, client, |seat| {
+            let touches = seat.touches.lock();
+            for touch in touches.values() {
+                f(touch);
+            }
+        })
+    }
+
     fn for_each_kb<C>(&self, ver: u32, client: ClientId, mut f: C)
     where
         C: FnMut(&Rc<WlKeyboard>),
@@ -537,12 +876,18 @@ impl WlSeatGlobal {
         self.pos_time_usec.set(time_usec);
         self.pos.set((x, y));
         self.update_hardware_cursor_position();
+        self.damage_cursor();
         self.changes.or_assign(CHANGE_CURSOR_MOVED);
         self.apply_changes();
     }
 
     pub fn clear_shortcuts(&self) {
         self.shortcuts.clear();
+        self.shortcuts_inhibit_escape.take();
+    }
+
+    pub fn set_shortcuts_inhibit_escape(&self, escape: Option<ModifiedKeySym>) {
+        self.shortcuts_inhibit_escape.set(escape);
     }
 
     pub fn add_shortcut(&self, mods: Modifiers, keysym: KeySym) {
@@ -559,7 +904,9 @@ impl WlSeatGlobal {
     }
 
     pub(super) fn apply_changes(self: &Rc<Self>) {
-        self.state.damage();
+        if self.changes.get().contains(CHANGE_TREE) {
+            self.state.damage();
+        }
         self.pointer_owner.apply_changes(self);
         self.changes.set(0);
     }
@@ -702,13 +1049,18 @@ impl WlSeatGlobal {
 impl WlSeatGlobal {
     pub fn unfocus_surface(&self, surface: &WlSurface) {
         let serial = surface.client.next_serial();
-        self.surface_kb_event(0, surface, |k| k.send_leave(serial, surface.id))
+        self.surface_kb_event(0, surface, |k| k.send_leave(serial, surface.id));
+        self.tablet_pads_unfocus_surface(surface);
+        self.text_input_unfocus_surface(surface);
+        if let Some(inhibitor) = surface.shortcuts_inhibitors.get(&self.id) {
+            inhibitor.deactivate();
+        }
     }
 }
 
 // Focus callbacks
 impl WlSeatGlobal {
-    pub fn focus_surface(&self, surface: &WlSurface) {
+    pub fn focus_surface(self: &Rc<Self>, surface: &Rc<WlSurface>) {
         let pressed_keys: Vec<_> = self.pressed_keys.borrow().iter().copied().collect();
         let serial = surface.client.next_serial();
         self.surface_kb_event(0, surface, |k| {
@@ -720,7 +1072,7 @@ impl WlSeatGlobal {
             mods_locked,
             group,
             ..
-        } = self.kb_state.borrow().mods();
+        } = self.kb_source.get().borrow().mods();
         let serial = surface.client.next_serial();
         self.surface_kb_event(0, surface, |k| {
             k.send_modifiers(serial, mods_depressed, mods_latched, mods_locked, group)
@@ -730,6 +1082,11 @@ impl WlSeatGlobal {
             self.offer_selection::<ClipboardIpc>(&self.selection, &surface.client);
             self.offer_selection::<PrimarySelectionIpc>(&self.primary_selection, &surface.client);
         }
+        self.tablet_pads_focus_surface(surface);
+        self.text_input_focus_surface(surface);
+        if let Some(inhibitor) = surface.shortcuts_inhibitors.get(&self.id) {
+            inhibitor.activate();
+        }
     }
 }
 
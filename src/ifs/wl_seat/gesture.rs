@@ -0,0 +1,203 @@
+use {
+    crate::{
+        client::ClientId,
+        fixed::Fixed,
+        ifs::{
+            wl_seat::{
+                zwp_pointer_gesture_hold_v1::ZwpPointerGestureHoldV1,
+                zwp_pointer_gesture_pinch_v1::ZwpPointerGesturePinchV1,
+                zwp_pointer_gesture_swipe_v1::ZwpPointerGestureSwipeV1, WlSeatGlobal,
+            },
+            wl_surface::WlSurface,
+        },
+    },
+    jay_config::Direction,
+    std::rc::Rc,
+};
+
+/// The distance that a bound swipe has to travel before it triggers the binding.
+const SWIPE_THRESHOLD: f64 = 50.0;
+
+const DIRECTIONS: [Direction; 4] = [
+    Direction::Left,
+    Direction::Down,
+    Direction::Up,
+    Direction::Right,
+];
+
+impl WlSeatGlobal {
+    pub fn clear_swipe_bindings(&self) {
+        self.swipe_bindings.clear();
+    }
+
+    pub fn add_swipe_binding(&self, fingers: u32, direction: Direction) {
+        self.swipe_bindings.set((fingers, direction), ());
+    }
+
+    pub fn remove_swipe_binding(&self, fingers: u32, direction: Direction) {
+        self.swipe_bindings.remove(&(fingers, direction));
+    }
+
+    fn has_swipe_binding(&self, fingers: u32) -> bool {
+        DIRECTIONS
+            .iter()
+            .any(|&d| self.swipe_bindings.contains(&(fingers, d)))
+    }
+
+    fn gesture_surface(&self) -> Option<Rc<WlSurface>> {
+        self.pointer_node().and_then(|n| n.node_into_surface())
+    }
+
+    fn for_each_swipe_gesture<C>(&self, client: ClientId, mut f: C)
+    where
+        C: FnMut(&Rc<ZwpPointerGestureSwipeV1>),
+    {
+        self.for_each_seat(0, client, |seat| {
+            let gestures = seat.swipe_gestures.lock();
+            for gesture in gestures.values() {
+                f(gesture);
+            }
+        })
+    }
+
+    fn for_each_pinch_gesture<C>(&self, client: ClientId, mut f: C)
+    where
+        C: FnMut(&Rc<ZwpPointerGesturePinchV1>),
+    {
+        self.for_each_seat(0, client, |seat| {
+            let gestures = seat.pinch_gestures.lock();
+            for gesture in gestures.values() {
+                f(gesture);
+            }
+        })
+    }
+
+    fn for_each_hold_gesture<C>(&self, client: ClientId, mut f: C)
+    where
+        C: FnMut(&Rc<ZwpPointerGestureHoldV1>),
+    {
+        self.for_each_seat(0, client, |seat| {
+            let gestures = seat.hold_gestures.lock();
+            for gesture in gestures.values() {
+                f(gesture);
+            }
+        })
+    }
+
+    pub(super) fn swipe_begin_event(&self, time_usec: u64, fingers: u32) {
+        if !self.state.lock.locked.get() && self.has_swipe_binding(fingers) {
+            self.bound_swipe.set(Some((fingers, 0.0, 0.0)));
+            return;
+        }
+        let surface = match self.gesture_surface() {
+            Some(s) => s,
+            _ => return,
+        };
+        let serial = surface.client.next_serial();
+        let time = (time_usec / 1000) as u32;
+        self.gesture_client.set(Some(surface.client.id));
+        self.for_each_swipe_gesture(surface.client.id, |g| {
+            g.send_begin(serial, time, &surface, fingers)
+        });
+    }
+
+    pub(super) fn swipe_update_event(&self, time_usec: u64, dx: Fixed, dy: Fixed) {
+        if let Some((fingers, x, y)) = self.bound_swipe.get() {
+            self.bound_swipe
+                .set(Some((fingers, x + dx.to_f64(), y + dy.to_f64())));
+            return;
+        }
+        if let Some(client) = self.gesture_client.get() {
+            let time = (time_usec / 1000) as u32;
+            self.for_each_swipe_gesture(client, |g| g.send_update(time, dx, dy));
+        }
+    }
+
+    pub(super) fn swipe_end_event(&self, time_usec: u64, cancelled: bool) {
+        if let Some((fingers, dx, dy)) = self.bound_swipe.take() {
+            if cancelled || dx.abs().max(dy.abs()) < SWIPE_THRESHOLD {
+                return;
+            }
+            let direction = match (dx.abs() > dy.abs(), dx < 0.0, dy < 0.0) {
+                (true, true, _) => Direction::Left,
+                (true, false, _) => Direction::Right,
+                (false, _, true) => Direction::Up,
+                (false, _, false) => Direction::Down,
+            };
+            if self.swipe_bindings.contains(&(fingers, direction)) {
+                if let Some(config) = self.state.config.get() {
+                    config.invoke_swipe(self.id(), fingers, direction);
+                }
+            }
+            return;
+        }
+        self.gesture_end(time_usec, |client, serial, time| {
+            self.for_each_swipe_gesture(client, |g| g.send_end(serial, time, cancelled));
+        });
+    }
+
+    pub(super) fn pinch_begin_event(&self, time_usec: u64, fingers: u32) {
+        let surface = match self.gesture_surface() {
+            Some(s) => s,
+            _ => return,
+        };
+        let serial = surface.client.next_serial();
+        let time = (time_usec / 1000) as u32;
+        self.gesture_client.set(Some(surface.client.id));
+        self.for_each_pinch_gesture(surface.client.id, |g| {
+            g.send_begin(serial, time, &surface, fingers)
+        });
+    }
+
+    pub(super) fn pinch_update_event(
+        &self,
+        time_usec: u64,
+        dx: Fixed,
+        dy: Fixed,
+        scale: Fixed,
+        rotation: Fixed,
+    ) {
+        if let Some(client) = self.gesture_client.get() {
+            let time = (time_usec / 1000) as u32;
+            self.for_each_pinch_gesture(client, |g| g.send_update(time, dx, dy, scale, rotation));
+        }
+    }
+
+    pub(super) fn pinch_end_event(&self, time_usec: u64, cancelled: bool) {
+        self.gesture_end(time_usec, |client, serial, time| {
+            self.for_each_pinch_gesture(client, |g| g.send_end(serial, time, cancelled));
+        });
+    }
+
+    pub(super) fn hold_begin_event(&self, time_usec: u64, fingers: u32) {
+        let surface = match self.gesture_surface() {
+            Some(s) => s,
+            _ => return,
+        };
+        let serial = surface.client.next_serial();
+        let time = (time_usec / 1000) as u32;
+        self.gesture_client.set(Some(surface.client.id));
+        self.for_each_hold_gesture(surface.client.id, |g| {
+            g.send_begin(serial, time, &surface, fingers)
+        });
+    }
+
+    pub(super) fn hold_end_event(&self, time_usec: u64, cancelled: bool) {
+        self.gesture_end(time_usec, |client, serial, time| {
+            self.for_each_hold_gesture(client, |g| g.send_end(serial, time, cancelled));
+        });
+    }
+
+    fn gesture_end<F>(&self, time_usec: u64, f: F)
+    where
+        F: FnOnce(ClientId, u32, u32),
+    {
+        let client_id = match self.gesture_client.take() {
+            Some(c) => c,
+            _ => return,
+        };
+        if let Ok(client) = self.state.clients.get(client_id) {
+            f(client_id, client.next_serial(), (time_usec / 1000) as u32);
+        }
+    }
+}
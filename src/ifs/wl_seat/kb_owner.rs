@@ -79,6 +79,7 @@ impl KbOwner for DefaultKbOwner {
         // log::info!("focus {}", node.node_id());
         node.clone().node_on_focus(seat);
         seat.keyboard_node.set(node.clone());
+        seat.keyboard_focus_changed(&node);
     }
 }
 
@@ -0,0 +1,607 @@
+pub mod zwp_tablet_manager_v2;
+pub mod zwp_tablet_pad_group_v2;
+pub mod zwp_tablet_pad_ring_v2;
+pub mod zwp_tablet_pad_strip_v2;
+pub mod zwp_tablet_pad_v2;
+pub mod zwp_tablet_seat_v2;
+pub mod zwp_tablet_tool_v2;
+pub mod zwp_tablet_v2;
+
+use {
+    crate::{
+        backend::{
+            InputDeviceId, KeyState, TabletInit, TabletPadInit, TabletToolChanges, TabletToolId,
+            TabletToolInit,
+        },
+        client::{ClientError, ClientId},
+        fixed::Fixed,
+        ifs::{
+            wl_seat::{
+                tablet::{
+                    zwp_tablet_pad_v2::ZwpTabletPadV2, zwp_tablet_seat_v2::ZwpTabletSeatV2,
+                    zwp_tablet_tool_v2::ZwpTabletToolV2, zwp_tablet_v2::ZwpTabletV2,
+                },
+                wl_pointer::{PRESSED, RELEASED},
+                WlSeatGlobal,
+            },
+            wl_surface::WlSurface,
+        },
+        state::DeviceHandlerData,
+        time::now_usec,
+        tree::{Node, NodeId},
+        utils::{clonecell::CloneCell, copyhashmap::CopyHashMap},
+        wire::ZwpTabletSeatV2Id,
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+type TabletSeatKey = (ClientId, ZwpTabletSeatV2Id);
+
+#[derive(Default)]
+pub struct TabletSeatData {
+    seats: CopyHashMap<TabletSeatKey, Rc<ZwpTabletSeatV2>>,
+    tablets: CopyHashMap<InputDeviceId, Rc<Tablet>>,
+    tools: CopyHashMap<TabletToolId, Rc<TabletTool>>,
+    pads: CopyHashMap<InputDeviceId, Rc<TabletPad>>,
+}
+
+impl TabletSeatData {
+    pub fn clear(&self) {
+        self.seats.clear();
+        for (_, tablet) in self.tablets.clear() {
+            tablet.bindings.clear();
+        }
+        for (_, tool) in self.tools.clear() {
+            tool.bindings.clear();
+            tool.node.take();
+        }
+        for (_, pad) in self.pads.clear() {
+            pad.bindings.clear();
+            pad.node.take();
+        }
+    }
+}
+
+pub struct Tablet {
+    dev: InputDeviceId,
+    init: Box<TabletInit>,
+    bindings: CopyHashMap<TabletSeatKey, Rc<ZwpTabletV2>>,
+}
+
+pub struct TabletTool {
+    id: TabletToolId,
+    tablet: Rc<Tablet>,
+    init: Rc<TabletToolInit>,
+    bindings: CopyHashMap<TabletSeatKey, Rc<ZwpTabletToolV2>>,
+    node: CloneCell<Option<Rc<WlSurface>>>,
+    pos: Cell<(Fixed, Fixed)>,
+    down: Cell<bool>,
+}
+
+pub struct TabletPad {
+    init: Box<TabletPadInit>,
+    modes: Vec<Cell<u32>>,
+    bindings: CopyHashMap<TabletSeatKey, Rc<ZwpTabletPadV2>>,
+    node: CloneCell<Option<Rc<WlSurface>>>,
+}
+
+impl WlSeatGlobal {
+    fn tablet_for_each_seat<F>(&self, mut f: F)
+    where
+        F: FnMut(&Rc<ZwpTabletSeatV2>) -> Result<(), ClientError>,
+    {
+        let seats: Vec<_> = self.tablet.seats.lock().values().cloned().collect();
+        for seat in seats {
+            if let Err(e) = f(&seat) {
+                seat.client.error(e);
+            }
+        }
+    }
+
+    fn tablet_tool_event<F>(&self, tool: &TabletTool, client: ClientId, mut f: F)
+    where
+        F: FnMut(&Rc<ZwpTabletToolV2>),
+    {
+        for ((c, _), binding) in tool.bindings.lock().iter() {
+            if *c == client {
+                f(binding);
+            }
+        }
+    }
+
+    fn tablet_pad_event<F>(&self, pad: &TabletPad, client: ClientId, mut f: F)
+    where
+        F: FnMut(&Rc<ZwpTabletPadV2>),
+    {
+        for ((c, _), binding) in pad.bindings.lock().iter() {
+            if *c == client {
+                f(binding);
+            }
+        }
+    }
+
+    fn tablet_add_seat(self: &Rc<Self>, seat: &Rc<ZwpTabletSeatV2>) -> Result<(), ClientError> {
+        self.tablet
+            .seats
+            .set((seat.client.id, seat.id), seat.clone());
+        let tablets: Vec<_> = self.tablet.tablets.lock().values().cloned().collect();
+        for tablet in &tablets {
+            seat.announce_tablet(tablet)?;
+        }
+        let tools: Vec<_> = self.tablet.tools.lock().values().cloned().collect();
+        for tool in &tools {
+            let binding = seat.announce_tool(tool)?;
+            if let Some(surface) = tool.node.get() {
+                if surface.client.id == seat.client.id {
+                    self.tablet_tool_proximity_in(tool, &binding, &surface);
+                }
+            }
+        }
+        let pads: Vec<_> = self.tablet.pads.lock().values().cloned().collect();
+        for pad in &pads {
+            let binding = seat.announce_pad(pad)?;
+            if let Some(surface) = pad.node.get() {
+                if surface.client.id == seat.client.id {
+                    self.tablet_pad_enter(pad, &binding, &surface);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn add_input_device(self: &Rc<Self>, dev: &DeviceHandlerData) {
+        let id = dev.device.id();
+        if let Some(init) = dev.device.tablet_info() {
+            let tablet = Rc::new(Tablet {
+                dev: id,
+                init,
+                bindings: Default::default(),
+            });
+            self.tablet.tablets.set(id, tablet.clone());
+            self.tablet_for_each_seat(|s| s.announce_tablet(&tablet));
+            let tools: Vec<_> = dev.tablet_tools.lock().values().cloned().collect();
+            for init in tools {
+                self.tablet_tool_added(dev, init);
+            }
+        }
+        if let Some(init) = dev.device.tablet_pad_info() {
+            let pad = Rc::new(TabletPad {
+                modes: init.groups.iter().map(|g| Cell::new(g.mode)).collect(),
+                init,
+                bindings: Default::default(),
+                node: Default::default(),
+            });
+            self.tablet.pads.set(id, pad.clone());
+            self.tablet_for_each_seat(|s| s.announce_pad(&pad).map(drop));
+            if let Some(surface) = self.keyboard_node.get().node_into_surface() {
+                self.tablet_pad_set_node(&pad, surface);
+            }
+        }
+    }
+
+    pub fn remove_input_device(&self, dev: &DeviceHandlerData) {
+        let id = dev.device.id();
+        if let Some(tablet) = self.tablet.tablets.remove(&id) {
+            let tools: Vec<_> = self
+                .tablet
+                .tools
+                .lock()
+                .values()
+                .filter(|t| t.tablet.dev == id)
+                .map(|t| t.id)
+                .collect();
+            for tool in tools {
+                self.tablet_tool_removed(tool);
+            }
+            for (_, binding) in tablet.bindings.clear() {
+                binding.send_removed();
+            }
+        }
+        if let Some(pad) = self.tablet.pads.remove(&id) {
+            self.tablet_pad_leave(&pad);
+            for (_, binding) in pad.bindings.clear() {
+                binding.send_removed();
+            }
+        }
+    }
+
+    pub(super) fn tablet_tool_added(&self, dev: &DeviceHandlerData, init: Rc<TabletToolInit>) {
+        if self.tablet.tools.contains(&init.id) {
+            return;
+        }
+        let tablet = match self.tablet.tablets.get(&dev.device.id()) {
+            Some(t) => t,
+            _ => return,
+        };
+        let tool = Rc::new(TabletTool {
+            id: init.id,
+            tablet,
+            init,
+            bindings: Default::default(),
+            node: Default::default(),
+            pos: Cell::new((Fixed(0), Fixed(0))),
+            down: Cell::new(false),
+        });
+        self.tablet.tools.set(tool.id, tool.clone());
+        self.tablet_for_each_seat(|s| s.announce_tool(&tool).map(drop));
+    }
+
+    pub(super) fn tablet_tool_removed(&self, id: TabletToolId) {
+        if let Some(tool) = self.tablet.tools.remove(&id) {
+            self.tablet_tool_leave(&tool, (now_usec() / 1000) as u32);
+            for (_, binding) in tool.bindings.clear() {
+                binding.send_removed();
+            }
+        }
+    }
+
+    pub(super) fn tablet_tool_changed(
+        self: &Rc<Self>,
+        dev: &DeviceHandlerData,
+        time_usec: u64,
+        id: TabletToolId,
+        changes: &TabletToolChanges,
+    ) {
+        let tool = match self.tablet.tools.get(&id) {
+            Some(t) => t,
+            _ => return,
+        };
+        let time = (time_usec / 1000) as u32;
+        if let Some((x, y)) = changes.pos {
+            tool.pos.set(self.absolute_position(dev, x, y));
+        }
+        if changes.proximity == Some(false) {
+            self.tablet_tool_leave(&tool, time);
+            tool.down.set(false);
+            return;
+        }
+        let (x, y) = tool.pos.get();
+        // While the tip is down, the surface that received the down event keeps receiving
+        // all events.
+        let node = match tool.node.get() {
+            Some(n) if tool.down.get() => Some(n),
+            _ => self.surface_at(x, y).map(|(s, _, _)| s),
+        };
+        let entered = self.tablet_tool_set_node(&tool, node.clone(), time);
+        if let Some(down) = changes.down {
+            tool.down.set(down);
+        }
+        let surface = match node {
+            Some(s) => s,
+            _ => return,
+        };
+        let mut serial = 0;
+        if changes.down == Some(true) {
+            if let Some(node) = surface.get_focus_node(self.id) {
+                self.focus_node(node);
+            }
+            serial = self.state.next_serial(Some(&surface.client));
+        }
+        let surface_pos = surface.buffer_abs_pos.get();
+        let (x, y) = (x - surface_pos.x1(), y - surface_pos.y1());
+        self.tablet_tool_event(&tool, surface.client.id, |t| {
+            if entered || changes.pos.is_some() {
+                t.send_motion(x, y);
+            }
+            if let Some(pressure) = changes.pressure {
+                t.send_pressure((pressure * 65535.0) as u32);
+            }
+            if let Some(distance) = changes.distance {
+                t.send_distance((distance * 65535.0) as u32);
+            }
+            if let Some((x, y)) = changes.tilt {
+                t.send_tilt(Fixed::from_f64(x), Fixed::from_f64(y));
+            }
+            if let Some(rotation) = changes.rotation {
+                t.send_rotation(Fixed::from_f64(rotation));
+            }
+            if let Some(slider) = changes.slider {
+                t.send_slider((slider * 65535.0) as i32);
+            }
+            if let Some((degrees, clicks)) = changes.wheel {
+                t.send_wheel(Fixed::from_f64(degrees), clicks);
+            }
+            match changes.down {
+                Some(true) => t.send_down(serial),
+                Some(false) => t.send_up(),
+                None => {}
+            }
+            t.send_frame(time);
+        });
+    }
+
+    pub(super) fn tablet_tool_button(
+        &self,
+        time_usec: u64,
+        id: TabletToolId,
+        button: u32,
+        state: KeyState,
+    ) {
+        let tool = match self.tablet.tools.get(&id) {
+            Some(t) => t,
+            _ => return,
+        };
+        let surface = match tool.node.get() {
+            Some(s) => s,
+            _ => return,
+        };
+        let state = match state {
+            KeyState::Released => RELEASED,
+            KeyState::Pressed => PRESSED,
+        };
+        let serial = self.state.next_serial(Some(&surface.client));
+        let time = (time_usec / 1000) as u32;
+        self.tablet_tool_event(&tool, surface.client.id, |t| {
+            t.send_button(serial, button, state);
+            t.send_frame(time);
+        });
+    }
+
+    /// Returns whether the tool entered a new surface.
+    fn tablet_tool_set_node(
+        self: &Rc<Self>,
+        tool: &TabletTool,
+        node: Option<Rc<WlSurface>>,
+        time: u32,
+    ) -> bool {
+        let surface = match node {
+            Some(s) => s,
+            _ => {
+                self.tablet_tool_leave(tool, time);
+                return false;
+            }
+        };
+        if let Some(old) = tool.node.get() {
+            if old.node_id() == surface.node_id() {
+                return false;
+            }
+        }
+        self.tablet_tool_leave(tool, time);
+        tool.node.set(Some(surface.clone()));
+        surface.node_seat_state().add_tablet_focus(self);
+        self.tablet_tool_event(tool, surface.client.id, |t| {
+            self.tablet_tool_proximity_in(tool, t, &surface);
+        });
+        true
+    }
+
+    fn tablet_tool_leave(&self, tool: &TabletTool, time: u32) {
+        if let Some(old) = tool.node.take() {
+            self.tablet_tool_event(tool, old.client.id, |t| {
+                t.send_proximity_out();
+                t.send_frame(time);
+            });
+            self.tablet_focus_removed(&old);
+        }
+    }
+
+    fn tablet_tool_proximity_in(
+        &self,
+        tool: &TabletTool,
+        binding: &ZwpTabletToolV2,
+        surface: &WlSurface,
+    ) {
+        let key = (binding.client.id, binding.seat.id);
+        if let Some(tablet) = tool.tablet.bindings.get(&key) {
+            let serial = self.state.next_serial(Some(&surface.client));
+            binding.send_proximity_in(serial, tablet.id, surface.id);
+        }
+    }
+
+    pub(super) fn tablet_pad_button(
+        &self,
+        dev: &DeviceHandlerData,
+        time_usec: u64,
+        button: u32,
+        state: KeyState,
+    ) {
+        let (pad, surface) = match self.tablet_pad_focus(dev) {
+            Some(p) => p,
+            _ => return,
+        };
+        let state = match state {
+            KeyState::Released => RELEASED,
+            KeyState::Pressed => PRESSED,
+        };
+        let time = (time_usec / 1000) as u32;
+        self.tablet_pad_event(&pad, surface.client.id, |p| {
+            p.send_button(time, button, state)
+        });
+    }
+
+    pub(super) fn tablet_pad_mode_switch(
+        &self,
+        dev: &DeviceHandlerData,
+        time_usec: u64,
+        group: u32,
+        mode: u32,
+    ) {
+        let pad = match self.tablet.pads.get(&dev.device.id()) {
+            Some(p) => p,
+            _ => return,
+        };
+        if let Some(m) = pad.modes.get(group as usize) {
+            m.set(mode);
+        }
+        let surface = match pad.node.get() {
+            Some(s) => s,
+            _ => return,
+        };
+        let serial = self.state.next_serial(Some(&surface.client));
+        let time = (time_usec / 1000) as u32;
+        self.tablet_pad_event(&pad, surface.client.id, |p| {
+            if let Some(group) = p.groups.get(&group) {
+                group.send_mode_switch(time, serial, mode);
+            }
+        });
+    }
+
+    pub(super) fn tablet_pad_ring(
+        &self,
+        dev: &DeviceHandlerData,
+        time_usec: u64,
+        ring: u32,
+        finger: bool,
+        angle: Option<f64>,
+    ) {
+        let (pad, surface) = match self.tablet_pad_focus(dev) {
+            Some(p) => p,
+            _ => return,
+        };
+        let time = (time_usec / 1000) as u32;
+        self.tablet_pad_event(&pad, surface.client.id, |p| {
+            if let Some(ring) = p.rings.get(&ring) {
+                if finger {
+                    ring.send_source(zwp_tablet_pad_ring_v2::SOURCE_FINGER);
+                }
+                match angle {
+                    Some(angle) => ring.send_angle(Fixed::from_f64(angle)),
+                    _ => ring.send_stop(),
+                }
+                ring.send_frame(time);
+            }
+        });
+    }
+
+    pub(super) fn tablet_pad_strip(
+        &self,
+        dev: &DeviceHandlerData,
+        time_usec: u64,
+        strip: u32,
+        finger: bool,
+        position: Option<f64>,
+    ) {
+        let (pad, surface) = match self.tablet_pad_focus(dev) {
+            Some(p) => p,
+            _ => return,
+        };
+        let time = (time_usec / 1000) as u32;
+        self.tablet_pad_event(&pad, surface.client.id, |p| {
+            if let Some(strip) = p.strips.get(&strip) {
+                if finger {
+                    strip.send_source(zwp_tablet_pad_strip_v2::SOURCE_FINGER);
+                }
+                match position {
+                    Some(position) => strip.send_position((position * 65535.0) as u32),
+                    _ => strip.send_stop(),
+                }
+                strip.send_frame(time);
+            }
+        });
+    }
+
+    fn tablet_pad_focus(&self, dev: &DeviceHandlerData) -> Option<(Rc<TabletPad>, Rc<WlSurface>)> {
+        let pad = self.tablet.pads.get(&dev.device.id())?;
+        let surface = pad.node.get()?;
+        Some((pad, surface))
+    }
+
+    pub(super) fn tablet_pads_focus_surface(self: &Rc<Self>, surface: &Rc<WlSurface>) {
+        let pads: Vec<_> = self.tablet.pads.lock().values().cloned().collect();
+        for pad in pads {
+            self.tablet_pad_set_node(&pad, surface.clone());
+        }
+    }
+
+    pub(super) fn tablet_pads_unfocus_surface(&self, surface: &WlSurface) {
+        let pads: Vec<_> = self.tablet.pads.lock().values().cloned().collect();
+        for pad in pads {
+            if let Some(node) = pad.node.get() {
+                if node.node_id() == surface.node_id() {
+                    self.tablet_pad_leave(&pad);
+                }
+            }
+        }
+    }
+
+    fn tablet_pad_set_node(self: &Rc<Self>, pad: &TabletPad, surface: Rc<WlSurface>) {
+        if let Some(old) = pad.node.get() {
+            if old.node_id() == surface.node_id() {
+                return;
+            }
+        }
+        self.tablet_pad_leave(pad);
+        pad.node.set(Some(surface.clone()));
+        surface.node_seat_state().add_tablet_focus(self);
+        self.tablet_pad_event(pad, surface.client.id, |p| {
+            self.tablet_pad_enter(pad, p, &surface);
+        });
+    }
+
+    fn tablet_pad_leave(&self, pad: &TabletPad) {
+        if let Some(old) = pad.node.take() {
+            let serial = self.state.next_serial(Some(&old.client));
+            self.tablet_pad_event(pad, old.client.id, |p| p.send_leave(serial, old.id));
+            self.tablet_focus_removed(&old);
+        }
+    }
+
+    fn tablet_pad_enter(&self, pad: &TabletPad, binding: &ZwpTabletPadV2, surface: &WlSurface) {
+        // Prefer the tablet that belongs to the same physical device as the pad.
+        let tablet = {
+            let tablets = self.tablet.tablets.lock();
+            tablets
+                .values()
+                .find(|t| t.init.group == pad.init.group)
+                .or_else(|| tablets.values().next())
+                .cloned()
+        };
+        let key = (binding.client.id, binding.seat.id);
+        let tablet = match tablet.and_then(|t| t.bindings.get(&key)) {
+            Some(t) => t,
+            _ => return,
+        };
+        let serial = self.state.next_serial(Some(&surface.client));
+        binding.send_enter(serial, tablet.id, surface.id);
+        let time = (now_usec() / 1000) as u32;
+        for (idx, mode) in pad.modes.iter().enumerate() {
+            if let Some(group) = binding.groups.get(&(idx as u32)) {
+                group.send_mode_switch(time, serial, mode.get());
+            }
+        }
+    }
+
+    fn tablet_focus_removed(&self, surface: &WlSurface) {
+        let node_id = surface.node_id();
+        let has_node = |node: Option<Rc<WlSurface>>| match node {
+            Some(n) => n.node_id() == node_id,
+            _ => false,
+        };
+        let focused = self
+            .tablet
+            .tools
+            .lock()
+            .values()
+            .any(|t| has_node(t.node.get()))
+            || self
+                .tablet
+                .pads
+                .lock()
+                .values()
+                .any(|p| has_node(p.node.get()));
+        if !focused {
+            surface.node_seat_state().remove_tablet_focus(self);
+        }
+    }
+
+    pub(super) fn tablet_node_removed(&self, node_id: NodeId) {
+        let is_node = |node: Option<Rc<WlSurface>>| match node {
+            Some(n) => n.node_id() == node_id,
+            _ => false,
+        };
+        let time = (now_usec() / 1000) as u32;
+        let tools: Vec<_> = self.tablet.tools.lock().values().cloned().collect();
+        for tool in tools {
+            if is_node(tool.node.get()) {
+                self.tablet_tool_leave(&tool, time);
+                tool.down.set(false);
+            }
+        }
+        let pads: Vec<_> = self.tablet.pads.lock().values().cloned().collect();
+        for pad in pads {
+            if is_node(pad.node.get()) {
+                self.tablet_pad_leave(&pad);
+            }
+        }
+    }
+}
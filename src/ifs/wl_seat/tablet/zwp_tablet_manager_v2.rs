@@ -0,0 +1,111 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        globals::{Global, GlobalName},
+        ifs::wl_seat::tablet::zwp_tablet_seat_v2::ZwpTabletSeatV2,
+        leaks::Tracker,
+        object::Object,
+        utils::buffd::{MsgParser, MsgParserError},
+        wire::{zwp_tablet_manager_v2::*, ZwpTabletManagerV2Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct ZwpTabletManagerV2Global {
+    pub name: GlobalName,
+}
+
+pub struct ZwpTabletManagerV2 {
+    pub id: ZwpTabletManagerV2Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+}
+
+impl ZwpTabletManagerV2Global {
+    pub fn new(name: GlobalName) -> Self {
+        Self { name }
+    }
+
+    fn bind_(
+        self: Rc<Self>,
+        id: ZwpTabletManagerV2Id,
+        client: &Rc<Client>,
+        _version: u32,
+    ) -> Result<(), ZwpTabletManagerV2Error> {
+        let obj = Rc::new(ZwpTabletManagerV2 {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+        });
+        track!(client, obj);
+        client.add_client_obj(&obj)?;
+        Ok(())
+    }
+}
+
+global_base!(
+    ZwpTabletManagerV2Global,
+    ZwpTabletManagerV2,
+    ZwpTabletManagerV2Error
+);
+
+impl Global for ZwpTabletManagerV2Global {
+    fn singleton(&self) -> bool {
+        true
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+}
+
+simple_add_global!(ZwpTabletManagerV2Global);
+
+impl ZwpTabletManagerV2 {
+    fn get_tablet_seat(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpTabletManagerV2Error> {
+        let req: GetTabletSeat = self.client.parse(self, parser)?;
+        let seat = self.client.lookup(req.seat)?;
+        let obj = Rc::new(ZwpTabletSeatV2 {
+            id: req.tablet_seat,
+            client: self.client.clone(),
+            seat: seat.global.clone(),
+            tracker: Default::default(),
+        });
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        seat.global.tablet_add_seat(&obj)?;
+        Ok(())
+    }
+
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpTabletManagerV2Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    ZwpTabletManagerV2;
+
+    GET_TABLET_SEAT => get_tablet_seat,
+    DESTROY => destroy,
+}
+
+impl Object for ZwpTabletManagerV2 {
+    fn num_requests(&self) -> u32 {
+        DESTROY + 1
+    }
+}
+
+simple_add_obj!(ZwpTabletManagerV2);
+
+#[derive(Debug, Error)]
+pub enum ZwpTabletManagerV2Error {
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwpTabletManagerV2Error, MsgParserError);
+efrom!(ZwpTabletManagerV2Error, ClientError);
@@ -0,0 +1,90 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::wl_seat::tablet::zwp_tablet_pad_v2::ZwpTabletPadV2,
+        leaks::Tracker,
+        object::Object,
+        utils::buffd::{MsgParser, MsgParserError},
+        wire::{zwp_tablet_pad_strip_v2::*, ZwpTabletPadStripV2Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub const SOURCE_FINGER: u32 = 1;
+
+pub struct ZwpTabletPadStripV2 {
+    pub id: ZwpTabletPadStripV2Id,
+    pub client: Rc<Client>,
+    pub pad: Rc<ZwpTabletPadV2>,
+    pub index: u32,
+    pub tracker: Tracker<Self>,
+}
+
+impl ZwpTabletPadStripV2 {
+    pub fn send_source(&self, source: u32) {
+        self.client.event(Source {
+            self_id: self.id,
+            source,
+        });
+    }
+
+    pub fn send_position(&self, position: u32) {
+        self.client.event(Position {
+            self_id: self.id,
+            position,
+        });
+    }
+
+    pub fn send_stop(&self) {
+        self.client.event(Stop { self_id: self.id });
+    }
+
+    pub fn send_frame(&self, time: u32) {
+        self.client.event(Frame {
+            self_id: self.id,
+            time,
+        });
+    }
+
+    fn set_feedback(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpTabletPadStripV2Error> {
+        let _req: SetFeedback = self.client.parse(self, parser)?;
+        Ok(())
+    }
+
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpTabletPadStripV2Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.pad.strips.remove(&self.index);
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    ZwpTabletPadStripV2;
+
+    SET_FEEDBACK => set_feedback,
+    DESTROY => destroy,
+}
+
+impl Object for ZwpTabletPadStripV2 {
+    fn num_requests(&self) -> u32 {
+        DESTROY + 1
+    }
+
+    fn break_loops(&self) {
+        self.pad.strips.remove(&self.index);
+    }
+}
+
+simple_add_obj!(ZwpTabletPadStripV2);
+
+#[derive(Debug, Error)]
+pub enum ZwpTabletPadStripV2Error {
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwpTabletPadStripV2Error, MsgParserError);
+efrom!(ZwpTabletPadStripV2Error, ClientError);
@@ -0,0 +1,136 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::wl_seat::tablet::{
+            zwp_tablet_pad_group_v2::ZwpTabletPadGroupV2,
+            zwp_tablet_pad_ring_v2::ZwpTabletPadRingV2,
+            zwp_tablet_pad_strip_v2::ZwpTabletPadStripV2, zwp_tablet_seat_v2::ZwpTabletSeatV2,
+            TabletPad,
+        },
+        leaks::Tracker,
+        object::Object,
+        utils::{
+            buffd::{MsgParser, MsgParserError},
+            copyhashmap::CopyHashMap,
+        },
+        wire::{zwp_tablet_pad_v2::*, WlSurfaceId, ZwpTabletPadV2Id, ZwpTabletV2Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct ZwpTabletPadV2 {
+    pub id: ZwpTabletPadV2Id,
+    pub client: Rc<Client>,
+    pub seat: Rc<ZwpTabletSeatV2>,
+    pub pad: Rc<TabletPad>,
+    pub groups: CopyHashMap<u32, Rc<ZwpTabletPadGroupV2>>,
+    pub rings: CopyHashMap<u32, Rc<ZwpTabletPadRingV2>>,
+    pub strips: CopyHashMap<u32, Rc<ZwpTabletPadStripV2>>,
+    pub tracker: Tracker<Self>,
+}
+
+impl ZwpTabletPadV2 {
+    pub fn send_group(&self, group: &ZwpTabletPadGroupV2) {
+        self.client.event(Group {
+            self_id: self.id,
+            pad_group: group.id,
+        });
+    }
+
+    pub fn send_path(&self, path: &str) {
+        self.client.event(Path {
+            self_id: self.id,
+            path,
+        });
+    }
+
+    pub fn send_buttons(&self, buttons: u32) {
+        self.client.event(Buttons {
+            self_id: self.id,
+            buttons,
+        });
+    }
+
+    pub fn send_done(&self) {
+        self.client.event(Done { self_id: self.id });
+    }
+
+    pub fn send_button(&self, time: u32, button: u32, state: u32) {
+        self.client.event(Button {
+            self_id: self.id,
+            time,
+            button,
+            state,
+        });
+    }
+
+    pub fn send_enter(&self, serial: u32, tablet: ZwpTabletV2Id, surface: WlSurfaceId) {
+        self.client.event(Enter {
+            self_id: self.id,
+            serial,
+            tablet,
+            surface,
+        });
+    }
+
+    pub fn send_leave(&self, serial: u32, surface: WlSurfaceId) {
+        self.client.event(Leave {
+            self_id: self.id,
+            serial,
+            surface,
+        });
+    }
+
+    pub fn send_removed(&self) {
+        self.client.event(Removed { self_id: self.id });
+    }
+
+    fn remove_from_pad(&self) {
+        self.pad.bindings.remove(&(self.client.id, self.seat.id));
+    }
+
+    fn set_feedback(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpTabletPadV2Error> {
+        let _req: SetFeedback = self.client.parse(self, parser)?;
+        Ok(())
+    }
+
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpTabletPadV2Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.remove_from_pad();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    ZwpTabletPadV2;
+
+    SET_FEEDBACK => set_feedback,
+    DESTROY => destroy,
+}
+
+impl Object for ZwpTabletPadV2 {
+    fn num_requests(&self) -> u32 {
+        DESTROY + 1
+    }
+
+    fn break_loops(&self) {
+        self.remove_from_pad();
+        self.groups.clear();
+        self.rings.clear();
+        self.strips.clear();
+    }
+}
+
+simple_add_obj!(ZwpTabletPadV2);
+
+#[derive(Debug, Error)]
+pub enum ZwpTabletPadV2Error {
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwpTabletPadV2Error, MsgParserError);
+efrom!(ZwpTabletPadV2Error, ClientError);
@@ -0,0 +1,182 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::wl_seat::{
+            tablet::{
+                zwp_tablet_pad_group_v2::ZwpTabletPadGroupV2,
+                zwp_tablet_pad_ring_v2::ZwpTabletPadRingV2,
+                zwp_tablet_pad_strip_v2::ZwpTabletPadStripV2, zwp_tablet_pad_v2::ZwpTabletPadV2,
+                zwp_tablet_tool_v2::ZwpTabletToolV2, zwp_tablet_v2::ZwpTabletV2, Tablet, TabletPad,
+                TabletTool,
+            },
+            WlSeatGlobal,
+        },
+        leaks::Tracker,
+        object::Object,
+        utils::buffd::{MsgParser, MsgParserError},
+        wire::{zwp_tablet_seat_v2::*, ZwpTabletSeatV2Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct ZwpTabletSeatV2 {
+    pub id: ZwpTabletSeatV2Id,
+    pub client: Rc<Client>,
+    pub seat: Rc<WlSeatGlobal>,
+    pub tracker: Tracker<Self>,
+}
+
+impl ZwpTabletSeatV2 {
+    pub fn announce_tablet(self: &Rc<Self>, tablet: &Rc<Tablet>) -> Result<(), ClientError> {
+        let obj = Rc::new(ZwpTabletV2 {
+            id: self.client.new_id()?,
+            client: self.client.clone(),
+            seat: self.clone(),
+            tablet: tablet.clone(),
+            tracker: Default::default(),
+        });
+        track!(self.client, obj);
+        self.client.add_server_obj(&obj);
+        self.client.event(TabletAdded {
+            self_id: self.id,
+            id: obj.id,
+        });
+        obj.send_name(&tablet.init.name);
+        obj.send_id(tablet.init.vid, tablet.init.pid);
+        obj.send_path(&tablet.init.path);
+        obj.send_done();
+        tablet.bindings.set((self.client.id, self.id), obj);
+        Ok(())
+    }
+
+    pub fn announce_tool(
+        self: &Rc<Self>,
+        tool: &Rc<TabletTool>,
+    ) -> Result<Rc<ZwpTabletToolV2>, ClientError> {
+        let obj = Rc::new(ZwpTabletToolV2 {
+            id: self.client.new_id()?,
+            client: self.client.clone(),
+            seat: self.clone(),
+            tool: tool.clone(),
+            tracker: Default::default(),
+        });
+        track!(self.client, obj);
+        self.client.add_server_obj(&obj);
+        self.client.event(ToolAdded {
+            self_id: self.id,
+            id: obj.id,
+        });
+        obj.send_type(tool.init.type_);
+        obj.send_hardware_serial(tool.init.hardware_serial);
+        obj.send_hardware_id_wacom(tool.init.hardware_id_wacom);
+        obj.send_capabilities(&tool.init.capabilities);
+        obj.send_done();
+        tool.bindings.set((self.client.id, self.id), obj.clone());
+        Ok(obj)
+    }
+
+    pub fn announce_pad(
+        self: &Rc<Self>,
+        pad: &Rc<TabletPad>,
+    ) -> Result<Rc<ZwpTabletPadV2>, ClientError> {
+        let obj = Rc::new(ZwpTabletPadV2 {
+            id: self.client.new_id()?,
+            client: self.client.clone(),
+            seat: self.clone(),
+            pad: pad.clone(),
+            groups: Default::default(),
+            rings: Default::default(),
+            strips: Default::default(),
+            tracker: Default::default(),
+        });
+        track!(self.client, obj);
+        self.client.add_server_obj(&obj);
+        self.client.event(PadAdded {
+            self_id: self.id,
+            id: obj.id,
+        });
+        for (idx, init) in pad.init.groups.iter().enumerate() {
+            let group = Rc::new(ZwpTabletPadGroupV2 {
+                id: self.client.new_id()?,
+                client: self.client.clone(),
+                pad: obj.clone(),
+                index: idx as u32,
+                tracker: Default::default(),
+            });
+            track!(self.client, group);
+            self.client.add_server_obj(&group);
+            obj.send_group(&group);
+            group.send_buttons(&init.buttons);
+            for &ring in &init.rings {
+                let ring = Rc::new(ZwpTabletPadRingV2 {
+                    id: self.client.new_id()?,
+                    client: self.client.clone(),
+                    pad: obj.clone(),
+                    index: ring,
+                    tracker: Default::default(),
+                });
+                track!(self.client, ring);
+                self.client.add_server_obj(&ring);
+                group.send_ring(&ring);
+                obj.rings.set(ring.index, ring);
+            }
+            for &strip in &init.strips {
+                let strip = Rc::new(ZwpTabletPadStripV2 {
+                    id: self.client.new_id()?,
+                    client: self.client.clone(),
+                    pad: obj.clone(),
+                    index: strip,
+                    tracker: Default::default(),
+                });
+                track!(self.client, strip);
+                self.client.add_server_obj(&strip);
+                group.send_strip(&strip);
+                obj.strips.set(strip.index, strip);
+            }
+            group.send_modes(init.modes);
+            group.send_done();
+            obj.groups.set(group.index, group);
+        }
+        obj.send_path(&pad.init.path);
+        obj.send_buttons(pad.init.buttons);
+        obj.send_done();
+        pad.bindings.set((self.client.id, self.id), obj.clone());
+        Ok(obj)
+    }
+
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpTabletSeatV2Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.seat.tablet.seats.remove(&(self.client.id, self.id));
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    ZwpTabletSeatV2;
+
+    DESTROY => destroy,
+}
+
+impl Object for ZwpTabletSeatV2 {
+    fn num_requests(&self) -> u32 {
+        DESTROY + 1
+    }
+
+    fn break_loops(&self) {
+        self.seat.tablet.seats.remove(&(self.client.id, self.id));
+    }
+}
+
+simple_add_obj!(ZwpTabletSeatV2);
+
+#[derive(Debug, Error)]
+pub enum ZwpTabletSeatV2Error {
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwpTabletSeatV2Error, MsgParserError);
+efrom!(ZwpTabletSeatV2Error, ClientError);
@@ -0,0 +1,239 @@
+use {
+    crate::{
+        backend::{TabletToolCapabilities, TabletToolType},
+        client::{Client, ClientError},
+        fixed::Fixed,
+        ifs::wl_seat::tablet::{zwp_tablet_seat_v2::ZwpTabletSeatV2, TabletTool},
+        leaks::Tracker,
+        object::Object,
+        utils::buffd::{MsgParser, MsgParserError},
+        wire::{zwp_tablet_tool_v2::*, WlSurfaceId, ZwpTabletToolV2Id, ZwpTabletV2Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+const TYPE_PEN: u32 = 0x140;
+const TYPE_ERASER: u32 = 0x141;
+const TYPE_BRUSH: u32 = 0x142;
+const TYPE_PENCIL: u32 = 0x143;
+const TYPE_AIRBRUSH: u32 = 0x144;
+const TYPE_FINGER: u32 = 0x145;
+const TYPE_MOUSE: u32 = 0x146;
+const TYPE_LENS: u32 = 0x147;
+
+const CAPABILITY_TILT: u32 = 1;
+const CAPABILITY_PRESSURE: u32 = 2;
+const CAPABILITY_DISTANCE: u32 = 3;
+const CAPABILITY_ROTATION: u32 = 4;
+const CAPABILITY_SLIDER: u32 = 5;
+const CAPABILITY_WHEEL: u32 = 6;
+
+pub struct ZwpTabletToolV2 {
+    pub id: ZwpTabletToolV2Id,
+    pub client: Rc<Client>,
+    pub seat: Rc<ZwpTabletSeatV2>,
+    pub tool: Rc<TabletTool>,
+    pub tracker: Tracker<Self>,
+}
+
+impl ZwpTabletToolV2 {
+    pub fn send_type(&self, ty: TabletToolType) {
+        let tool_type = match ty {
+            TabletToolType::Pen => TYPE_PEN,
+            TabletToolType::Eraser => TYPE_ERASER,
+            TabletToolType::Brush => TYPE_BRUSH,
+            TabletToolType::Pencil => TYPE_PENCIL,
+            TabletToolType::Airbrush => TYPE_AIRBRUSH,
+            TabletToolType::Finger => TYPE_FINGER,
+            TabletToolType::Mouse => TYPE_MOUSE,
+            TabletToolType::Lens => TYPE_LENS,
+        };
+        self.client.event(Type {
+            self_id: self.id,
+            tool_type,
+        });
+    }
+
+    pub fn send_hardware_serial(&self, serial: u64) {
+        self.client.event(HardwareSerial {
+            self_id: self.id,
+            hardware_serial_hi: (serial >> 32) as u32,
+            hardware_serial_lo: serial as u32,
+        });
+    }
+
+    pub fn send_hardware_id_wacom(&self, id: u64) {
+        self.client.event(HardwareIdWacom {
+            self_id: self.id,
+            hardware_id_hi: (id >> 32) as u32,
+            hardware_id_lo: id as u32,
+        });
+    }
+
+    pub fn send_capabilities(&self, caps: &TabletToolCapabilities) {
+        let caps = [
+            (caps.tilt, CAPABILITY_TILT),
+            (caps.pressure, CAPABILITY_PRESSURE),
+            (caps.distance, CAPABILITY_DISTANCE),
+            (caps.rotation, CAPABILITY_ROTATION),
+            (caps.slider, CAPABILITY_SLIDER),
+            (caps.wheel, CAPABILITY_WHEEL),
+        ];
+        for (has, capability) in caps {
+            if has {
+                self.client.event(Capability {
+                    self_id: self.id,
+                    capability,
+                });
+            }
+        }
+    }
+
+    pub fn send_done(&self) {
+        self.client.event(Done { self_id: self.id });
+    }
+
+    pub fn send_removed(&self) {
+        self.client.event(Removed { self_id: self.id });
+    }
+
+    pub fn send_proximity_in(&self, serial: u32, tablet: ZwpTabletV2Id, surface: WlSurfaceId) {
+        self.client.event(ProximityIn {
+            self_id: self.id,
+            serial,
+            tablet,
+            surface,
+        });
+    }
+
+    pub fn send_proximity_out(&self) {
+        self.client.event(ProximityOut { self_id: self.id });
+    }
+
+    pub fn send_down(&self, serial: u32) {
+        self.client.event(Down {
+            self_id: self.id,
+            serial,
+        });
+    }
+
+    pub fn send_up(&self) {
+        self.client.event(Up { self_id: self.id });
+    }
+
+    pub fn send_motion(&self, x: Fixed, y: Fixed) {
+        self.client.event(Motion {
+            self_id: self.id,
+            x,
+            y,
+        });
+    }
+
+    pub fn send_pressure(&self, pressure: u32) {
+        self.client.event(Pressure {
+            self_id: self.id,
+            pressure,
+        });
+    }
+
+    pub fn send_distance(&self, distance: u32) {
+        self.client.event(Distance {
+            self_id: self.id,
+            distance,
+        });
+    }
+
+    pub fn send_tilt(&self, tilt_x: Fixed, tilt_y: Fixed) {
+        self.client.event(Tilt {
+            self_id: self.id,
+            tilt_x,
+            tilt_y,
+        });
+    }
+
+    pub fn send_rotation(&self, degrees: Fixed) {
+        self.client.event(Rotation {
+            self_id: self.id,
+            degrees,
+        });
+    }
+
+    pub fn send_slider(&self, position: i32) {
+        self.client.event(Slider {
+            self_id: self.id,
+            position,
+        });
+    }
+
+    pub fn send_wheel(&self, degrees: Fixed, clicks: i32) {
+        self.client.event(Wheel {
+            self_id: self.id,
+            degrees,
+            clicks,
+        });
+    }
+
+    pub fn send_button(&self, serial: u32, button: u32, state: u32) {
+        self.client.event(Button {
+            self_id: self.id,
+            serial,
+            button,
+            state,
+        });
+    }
+
+    pub fn send_frame(&self, time: u32) {
+        self.client.event(Frame {
+            self_id: self.id,
+            time,
+        });
+    }
+
+    fn remove_from_tool(&self) {
+        self.tool.bindings.remove(&(self.client.id, self.seat.id));
+    }
+
+    fn set_cursor(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpTabletToolV2Error> {
+        let _req: SetCursor = self.client.parse(self, parser)?;
+        // Tablet tools do not have a cursor of their own. The cursor of the seat is used
+        // instead.
+        Ok(())
+    }
+
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpTabletToolV2Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.remove_from_tool();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    ZwpTabletToolV2;
+
+    SET_CURSOR => set_cursor,
+    DESTROY => destroy,
+}
+
+impl Object for ZwpTabletToolV2 {
+    fn num_requests(&self) -> u32 {
+        DESTROY + 1
+    }
+
+    fn break_loops(&self) {
+        self.remove_from_tool();
+    }
+}
+
+simple_add_obj!(ZwpTabletToolV2);
+
+#[derive(Debug, Error)]
+pub enum ZwpTabletToolV2Error {
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwpTabletToolV2Error, MsgParserError);
+efrom!(ZwpTabletToolV2Error, ClientError);
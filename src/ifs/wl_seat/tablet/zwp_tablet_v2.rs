@@ -0,0 +1,91 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::wl_seat::tablet::{zwp_tablet_seat_v2::ZwpTabletSeatV2, Tablet},
+        leaks::Tracker,
+        object::Object,
+        utils::buffd::{MsgParser, MsgParserError},
+        wire::{zwp_tablet_v2::*, ZwpTabletV2Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct ZwpTabletV2 {
+    pub id: ZwpTabletV2Id,
+    pub client: Rc<Client>,
+    pub seat: Rc<ZwpTabletSeatV2>,
+    pub tablet: Rc<Tablet>,
+    pub tracker: Tracker<Self>,
+}
+
+impl ZwpTabletV2 {
+    pub fn send_name(&self, name: &str) {
+        self.client.event(Name {
+            self_id: self.id,
+            name,
+        });
+    }
+
+    pub fn send_id(&self, vid: u32, pid: u32) {
+        self.client.event(Id {
+            self_id: self.id,
+            vid,
+            pid,
+        });
+    }
+
+    pub fn send_path(&self, path: &str) {
+        self.client.event(Path {
+            self_id: self.id,
+            path,
+        });
+    }
+
+    pub fn send_done(&self) {
+        self.client.event(Done { self_id: self.id });
+    }
+
+    pub fn send_removed(&self) {
+        self.client.event(Removed { self_id: self.id });
+    }
+
+    fn remove_from_tablet(&self) {
+        self.tablet.bindings.remove(&(self.client.id, self.seat.id));
+    }
+
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpTabletV2Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.remove_from_tablet();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    ZwpTabletV2;
+
+    DESTROY => destroy,
+}
+
+impl Object for ZwpTabletV2 {
+    fn num_requests(&self) -> u32 {
+        DESTROY + 1
+    }
+
+    fn break_loops(&self) {
+        self.remove_from_tablet();
+    }
+}
+
+simple_add_obj!(ZwpTabletV2);
+
+#[derive(Debug, Error)]
+pub enum ZwpTabletV2Error {
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwpTabletV2Error, MsgParserError);
+efrom!(ZwpTabletV2Error, ClientError);
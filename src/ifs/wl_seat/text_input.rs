@@ -0,0 +1,225 @@
+pub mod zwp_input_method_keyboard_grab_v2;
+pub mod zwp_input_method_manager_v2;
+pub mod zwp_input_method_v2;
+pub mod zwp_text_input_manager_v3;
+pub mod zwp_text_input_v3;
+
+use {
+    crate::{
+        client::ClientId,
+        ifs::{
+            wl_seat::{
+                text_input::{
+                    zwp_input_method_keyboard_grab_v2::ZwpInputMethodKeyboardGrabV2,
+                    zwp_input_method_v2::ZwpInputMethodV2, zwp_text_input_v3::ZwpTextInputV3,
+                },
+                wl_keyboard, WlSeatGlobal,
+            },
+            wl_surface::WlSurface,
+        },
+        tree::Node,
+        utils::{clonecell::CloneCell, copyhashmap::CopyHashMap, rc_eq::rc_eq},
+        wire::ZwpTextInputV3Id,
+        xkbcommon::{ModifierState, XkbKeymap},
+    },
+    std::rc::Rc,
+};
+
+type TextInputKey = (ClientId, ZwpTextInputV3Id);
+
+#[derive(Default)]
+pub struct TextInputSeatData {
+    text_inputs: CopyHashMap<TextInputKey, Rc<ZwpTextInputV3>>,
+    active: CloneCell<Option<Rc<ZwpTextInputV3>>>,
+    input_method: CloneCell<Option<Rc<ZwpInputMethodV2>>>,
+}
+
+impl TextInputSeatData {
+    pub fn clear(&self) {
+        self.text_inputs.clear();
+        self.active.take();
+        self.input_method.take();
+    }
+}
+
+impl WlSeatGlobal {
+    fn text_input_focus(&self) -> Option<Rc<WlSurface>> {
+        self.keyboard_node.get().node_into_surface()
+    }
+
+    pub fn text_input_add(&self, ti: &Rc<ZwpTextInputV3>) {
+        self.text_input
+            .text_inputs
+            .set((ti.client.id, ti.id), ti.clone());
+        if let Some(surface) = self.text_input_focus() {
+            if surface.client.id == ti.client.id {
+                ti.enter(&surface);
+            }
+        }
+    }
+
+    pub fn text_input_remove(&self, ti: &ZwpTextInputV3) {
+        self.text_input.text_inputs.remove(&(ti.client.id, ti.id));
+        if let Some(active) = self.text_input.active.get() {
+            if active.id == ti.id && active.client.id == ti.client.id {
+                self.text_input_deactivate();
+            }
+        }
+    }
+
+    pub(super) fn text_input_focus_surface(&self, surface: &Rc<WlSurface>) {
+        for ti in self.text_input.text_inputs.lock().values() {
+            if ti.client.id == surface.client.id {
+                ti.enter(surface);
+            }
+        }
+    }
+
+    pub(super) fn text_input_unfocus_surface(&self, surface: &WlSurface) {
+        if let Some(active) = self.text_input.active.get() {
+            if let Some(s) = active.surface.get() {
+                if s.id == surface.id && s.client.id == surface.client.id {
+                    self.text_input_deactivate();
+                }
+            }
+        }
+        for ti in self.text_input.text_inputs.lock().values() {
+            if let Some(s) = ti.surface.get() {
+                if s.id == surface.id && s.client.id == surface.client.id {
+                    ti.leave();
+                }
+            }
+        }
+    }
+
+    pub fn text_input_active(&self) -> Option<Rc<ZwpTextInputV3>> {
+        self.text_input.active.get()
+    }
+
+    pub fn text_input_commit(&self, ti: &Rc<ZwpTextInputV3>) {
+        let enabled = ti.current.borrow().enabled;
+        let is_active = match self.text_input.active.get() {
+            Some(active) => rc_eq(&active, ti),
+            _ => false,
+        };
+        if !enabled {
+            if is_active {
+                self.text_input_deactivate();
+            }
+            return;
+        }
+        if !is_active {
+            self.text_input_deactivate();
+            self.text_input.active.set(Some(ti.clone()));
+            if let Some(im) = self.text_input.input_method.get() {
+                im.send_activate();
+            }
+        }
+        if let Some(im) = self.text_input.input_method.get() {
+            im.send_state(&ti.current.borrow());
+            im.send_done();
+            im.update_popups();
+        }
+    }
+
+    fn text_input_deactivate(&self) {
+        if self.text_input.active.take().is_none() {
+            return;
+        }
+        if let Some(im) = self.text_input.input_method.get() {
+            im.send_deactivate();
+            im.send_done();
+            im.update_popups();
+        }
+    }
+
+    /// Returns `false` if the seat already has an input method.
+    pub fn input_method_add(&self, im: &Rc<ZwpInputMethodV2>) -> bool {
+        if self.text_input.input_method.get().is_some() {
+            return false;
+        }
+        self.text_input.input_method.set(Some(im.clone()));
+        if let Some(ti) = self.text_input.active.get() {
+            im.send_activate();
+            im.send_state(&ti.current.borrow());
+            im.send_done();
+        }
+        true
+    }
+
+    pub fn input_method_remove(&self, im: &ZwpInputMethodV2) {
+        if let Some(old) = self.text_input.input_method.get() {
+            if old.id == im.id && old.client.id == im.client.id {
+                self.text_input.input_method.take();
+            }
+        }
+    }
+
+    pub fn for_each_input_popup<F: FnMut(&Rc<WlSurface>)>(&self, mut f: F) {
+        if let Some(im) = self.text_input.input_method.get() {
+            for popup in im.popups.lock().values() {
+                if popup.surface.node_visible() {
+                    f(&popup.surface);
+                }
+            }
+        }
+    }
+
+    pub(super) fn is_input_method_client(&self, client: ClientId) -> bool {
+        match self.text_input.input_method.get() {
+            Some(im) => im.client.id == client,
+            _ => false,
+        }
+    }
+
+    fn input_method_grab(&self) -> Option<Rc<ZwpInputMethodKeyboardGrabV2>> {
+        self.text_input.input_method.get()?.grab.get()
+    }
+
+    pub(super) fn input_method_keymap_changed(&self, keymap: &XkbKeymap) {
+        if let Some(grab) = self.input_method_grab() {
+            grab.send_keymap(keymap);
+        }
+    }
+
+    pub(super) fn input_method_repeat_info_changed(&self, rate: i32, delay: i32) {
+        if let Some(grab) = self.input_method_grab() {
+            grab.send_repeat_info(rate, delay);
+        }
+    }
+
+    /// Returns `true` if the key was consumed by the grab.
+    pub(super) fn input_method_key(&self, time_usec: u64, key: u32, state: u32) -> bool {
+        let grab = match self.input_method_grab() {
+            Some(grab) => grab,
+            _ => return false,
+        };
+        if state == wl_keyboard::RELEASED {
+            // Keys that were pressed before the grab was established are released
+            // to the client that received the press.
+            if !grab.pressed_keys.borrow_mut().remove(&key) {
+                return false;
+            }
+        } else {
+            if self.text_input.active.get().is_none() {
+                return false;
+            }
+            grab.pressed_keys.borrow_mut().insert(key);
+        }
+        grab.send_key(time_usec, key, state);
+        true
+    }
+
+    pub(super) fn input_method_mods(&self, mods: ModifierState) -> bool {
+        if self.text_input.active.get().is_none() {
+            return false;
+        }
+        match self.input_method_grab() {
+            Some(grab) => {
+                grab.send_modifiers(mods);
+                true
+            }
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::wl_seat::{text_input::zwp_input_method_v2::ZwpInputMethodV2, wl_keyboard},
+        leaks::Tracker,
+        object::Object,
+        utils::buffd::{MsgParser, MsgParserError},
+        wire::{zwp_input_method_keyboard_grab_v2::*, ZwpInputMethodKeyboardGrabV2Id},
+        xkbcommon::{ModifierState, XkbKeymap},
+    },
+    ahash::AHashSet,
+    std::{cell::RefCell, rc::Rc},
+    thiserror::Error,
+};
+
+pub struct ZwpInputMethodKeyboardGrabV2 {
+    pub id: ZwpInputMethodKeyboardGrabV2Id,
+    pub client: Rc<Client>,
+    pub input_method: Rc<ZwpInputMethodV2>,
+    pub tracker: Tracker<Self>,
+    /// The keys whose press events were sent to this grab.
+    pub pressed_keys: RefCell<AHashSet<u32>>,
+}
+
+impl ZwpInputMethodKeyboardGrabV2 {
+    pub fn new(id: ZwpInputMethodKeyboardGrabV2Id, input_method: &Rc<ZwpInputMethodV2>) -> Self {
+        Self {
+            id,
+            client: input_method.client.clone(),
+            input_method: input_method.clone(),
+            tracker: Default::default(),
+            pressed_keys: Default::default(),
+        }
+    }
+
+    pub fn send_keymap(&self, keymap: &XkbKeymap) {
+        self.client.event(Keymap {
+            self_id: self.id,
+            format: wl_keyboard::XKB_V1,
+            fd: keymap.map.clone(),
+            size: keymap.map_len as _,
+        });
+    }
+
+    pub fn send_key(&self, time_usec: u64, key: u32, state: u32) {
+        self.client.event(Key {
+            self_id: self.id,
+            serial: self.client.next_serial(),
+            time: (time_usec / 1000) as _,
+            key,
+            state,
+        });
+    }
+
+    pub fn send_modifiers(&self, mods: ModifierState) {
+        self.client.event(Modifiers {
+            self_id: self.id,
+            serial: self.client.next_serial(),
+            mods_depressed: mods.mods_depressed,
+            mods_latched: mods.mods_latched,
+            mods_locked: mods.mods_locked,
+            group: mods.group,
+        });
+    }
+
+    pub fn send_repeat_info(&self, rate: i32, delay: i32) {
+        self.client.event(RepeatInfo {
+            self_id: self.id,
+            rate,
+            delay,
+        });
+    }
+
+    fn detach(&self) {
+        if let Some(grab) = self.input_method.grab.get() {
+            if grab.id == self.id {
+                self.input_method.grab.take();
+            }
+        }
+    }
+
+    fn release(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpInputMethodKeyboardGrabV2Error> {
+        let _req: Release = self.client.parse(self, parser)?;
+        self.detach();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    ZwpInputMethodKeyboardGrabV2;
+
+    RELEASE => release,
+}
+
+impl Object for ZwpInputMethodKeyboardGrabV2 {
+    fn num_requests(&self) -> u32 {
+        RELEASE + 1
+    }
+
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
+
+simple_add_obj!(ZwpInputMethodKeyboardGrabV2);
+
+#[derive(Debug, Error)]
+pub enum ZwpInputMethodKeyboardGrabV2Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("Parsing failed")]
+    MsgParserError(Box<MsgParserError>),
+}
+efrom!(ZwpInputMethodKeyboardGrabV2Error, ClientError);
+efrom!(ZwpInputMethodKeyboardGrabV2Error, MsgParserError);
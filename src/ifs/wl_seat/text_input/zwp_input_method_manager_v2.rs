@@ -0,0 +1,119 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        globals::{Global, GlobalName},
+        ifs::wl_seat::text_input::zwp_input_method_v2::ZwpInputMethodV2,
+        leaks::Tracker,
+        object::Object,
+        utils::buffd::{MsgParser, MsgParserError},
+        wire::{zwp_input_method_manager_v2::*, ZwpInputMethodManagerV2Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct ZwpInputMethodManagerV2Global {
+    pub name: GlobalName,
+}
+
+pub struct ZwpInputMethodManagerV2 {
+    pub id: ZwpInputMethodManagerV2Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+}
+
+impl ZwpInputMethodManagerV2Global {
+    pub fn new(name: GlobalName) -> Self {
+        Self { name }
+    }
+
+    fn bind_(
+        self: Rc<Self>,
+        id: ZwpInputMethodManagerV2Id,
+        client: &Rc<Client>,
+        _version: u32,
+    ) -> Result<(), ZwpInputMethodManagerV2Error> {
+        let obj = Rc::new(ZwpInputMethodManagerV2 {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+        });
+        track!(client, obj);
+        client.add_client_obj(&obj)?;
+        Ok(())
+    }
+}
+
+global_base!(
+    ZwpInputMethodManagerV2Global,
+    ZwpInputMethodManagerV2,
+    ZwpInputMethodManagerV2Error
+);
+
+impl Global for ZwpInputMethodManagerV2Global {
+    fn singleton(&self) -> bool {
+        true
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn secure(&self) -> bool {
+        true
+    }
+}
+
+simple_add_global!(ZwpInputMethodManagerV2Global);
+
+impl ZwpInputMethodManagerV2 {
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpInputMethodManagerV2Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn get_input_method(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), ZwpInputMethodManagerV2Error> {
+        let req: GetInputMethod = self.client.parse(self, parser)?;
+        let seat = self.client.lookup(req.seat)?;
+        let im = Rc::new(ZwpInputMethodV2::new(
+            req.input_method,
+            &self.client,
+            &seat.global,
+        ));
+        track!(self.client, im);
+        self.client.add_client_obj(&im)?;
+        if !seat.global.input_method_add(&im) {
+            im.send_unavailable();
+        }
+        Ok(())
+    }
+}
+
+object_base! {
+    ZwpInputMethodManagerV2;
+
+    GET_INPUT_METHOD => get_input_method,
+    DESTROY => destroy,
+}
+
+impl Object for ZwpInputMethodManagerV2 {
+    fn num_requests(&self) -> u32 {
+        DESTROY + 1
+    }
+}
+
+simple_add_obj!(ZwpInputMethodManagerV2);
+
+#[derive(Debug, Error)]
+pub enum ZwpInputMethodManagerV2Error {
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwpInputMethodManagerV2Error, MsgParserError);
+efrom!(ZwpInputMethodManagerV2Error, ClientError);
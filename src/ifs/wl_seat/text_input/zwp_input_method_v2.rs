@@ -0,0 +1,242 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::{
+            wl_seat::{
+                text_input::{
+                    zwp_input_method_keyboard_grab_v2::ZwpInputMethodKeyboardGrabV2,
+                    zwp_text_input_v3::TextInputState,
+                },
+                WlSeatGlobal,
+            },
+            wl_surface::zwp_input_popup_surface_v2::{
+                ZwpInputPopupSurfaceV2, ZwpInputPopupSurfaceV2Error,
+            },
+        },
+        leaks::Tracker,
+        object::Object,
+        utils::{
+            buffd::{MsgParser, MsgParserError},
+            clonecell::CloneCell,
+            copyhashmap::CopyHashMap,
+            numcell::NumCell,
+        },
+        wire::{zwp_input_method_v2::*, ZwpInputMethodV2Id, ZwpInputPopupSurfaceV2Id},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        mem,
+        rc::Rc,
+    },
+    thiserror::Error,
+};
+
+#[derive(Default)]
+struct PendingState {
+    commit_string: Option<String>,
+    preedit_string: Option<(String, i32, i32)>,
+    delete_surrounding_text: Option<(u32, u32)>,
+}
+
+pub struct ZwpInputMethodV2 {
+    pub id: ZwpInputMethodV2Id,
+    pub client: Rc<Client>,
+    pub seat: Rc<WlSeatGlobal>,
+    pub tracker: Tracker<Self>,
+    pub popups: CopyHashMap<ZwpInputPopupSurfaceV2Id, Rc<ZwpInputPopupSurfaceV2>>,
+    pub grab: CloneCell<Option<Rc<ZwpInputMethodKeyboardGrabV2>>>,
+    /// Set if the seat already had an input method when this object was created.
+    pub inert: Cell<bool>,
+    pending: RefCell<PendingState>,
+    num_done: NumCell<u32>,
+}
+
+impl ZwpInputMethodV2 {
+    pub fn new(id: ZwpInputMethodV2Id, client: &Rc<Client>, seat: &Rc<WlSeatGlobal>) -> Self {
+        Self {
+            id,
+            client: client.clone(),
+            seat: seat.clone(),
+            tracker: Default::default(),
+            popups: Default::default(),
+            grab: Default::default(),
+            inert: Cell::new(false),
+            pending: Default::default(),
+            num_done: NumCell::new(0),
+        }
+    }
+
+    pub fn send_activate(&self) {
+        self.client.event(Activate { self_id: self.id });
+    }
+
+    pub fn send_deactivate(&self) {
+        self.client.event(Deactivate { self_id: self.id });
+    }
+
+    pub fn send_state(&self, state: &TextInputState) {
+        if let Some(st) = &state.surrounding_text {
+            self.client.event(SurroundingText {
+                self_id: self.id,
+                text: &st.text,
+                cursor: st.cursor as _,
+                anchor: st.anchor as _,
+            });
+        }
+        self.client.event(TextChangeCause {
+            self_id: self.id,
+            cause: state.text_change_cause,
+        });
+        self.client.event(ContentType {
+            self_id: self.id,
+            hint: state.content_hint,
+            purpose: state.content_purpose,
+        });
+    }
+
+    pub fn send_done(&self) {
+        self.num_done.fetch_add(1);
+        self.client.event(Done { self_id: self.id });
+    }
+
+    pub fn send_unavailable(&self) {
+        self.inert.set(true);
+        self.client.event(Unavailable { self_id: self.id });
+    }
+
+    pub fn update_popups(&self) {
+        for popup in self.popups.lock().values() {
+            popup.update_position();
+        }
+    }
+
+    fn commit_string(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpInputMethodV2Error> {
+        let req: CommitString = self.client.parse(self, parser)?;
+        self.pending.borrow_mut().commit_string = Some(req.text.to_string());
+        Ok(())
+    }
+
+    fn set_preedit_string(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpInputMethodV2Error> {
+        let req: SetPreeditString = self.client.parse(self, parser)?;
+        self.pending.borrow_mut().preedit_string =
+            Some((req.text.to_string(), req.cursor_begin, req.cursor_end));
+        Ok(())
+    }
+
+    fn delete_surrounding_text(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), ZwpInputMethodV2Error> {
+        let req: DeleteSurroundingText = self.client.parse(self, parser)?;
+        self.pending.borrow_mut().delete_surrounding_text =
+            Some((req.before_length, req.after_length));
+        Ok(())
+    }
+
+    fn commit(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpInputMethodV2Error> {
+        let req: Commit = self.client.parse(self, parser)?;
+        let pending = mem::take(&mut *self.pending.borrow_mut());
+        // State that is based on outdated information from the text input is dropped.
+        if self.inert.get() || req.serial != self.num_done.get() {
+            return Ok(());
+        }
+        let ti = match self.seat.text_input_active() {
+            Some(ti) => ti,
+            _ => return Ok(()),
+        };
+        if let Some((text, begin, end)) = &pending.preedit_string {
+            ti.send_preedit_string(Some(text), *begin, *end);
+        }
+        if let Some(text) = &pending.commit_string {
+            ti.send_commit_string(Some(text));
+        }
+        if let Some((before, after)) = pending.delete_surrounding_text {
+            ti.send_delete_surrounding_text(before, after);
+        }
+        ti.send_done();
+        Ok(())
+    }
+
+    fn get_input_popup_surface(
+        self: &Rc<Self>,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), ZwpInputMethodV2Error> {
+        let req: GetInputPopupSurface = self.client.parse(&**self, parser)?;
+        let surface = self.client.lookup(req.surface)?;
+        let popup = Rc::new(ZwpInputPopupSurfaceV2::new(req.id, self, &surface));
+        track!(self.client, popup);
+        self.client.add_client_obj(&popup)?;
+        popup.install()?;
+        self.popups.set(req.id, popup);
+        Ok(())
+    }
+
+    fn grab_keyboard(
+        self: &Rc<Self>,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), ZwpInputMethodV2Error> {
+        let req: GrabKeyboard = self.client.parse(&**self, parser)?;
+        let grab = Rc::new(ZwpInputMethodKeyboardGrabV2::new(req.keyboard, self));
+        track!(self.client, grab);
+        self.client.add_client_obj(&grab)?;
+        grab.send_keymap(self.seat.kb_source.get().borrow().map());
+        let (rate, delay) = self.seat.repeat_rate.get();
+        grab.send_repeat_info(rate, delay);
+        if !self.inert.get() {
+            self.grab.set(Some(grab));
+        }
+        Ok(())
+    }
+
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpInputMethodV2Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.detach();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn detach(&self) {
+        self.seat.input_method_remove(self);
+        self.inert.set(true);
+        self.grab.take();
+        self.update_popups();
+    }
+}
+
+object_base! {
+    ZwpInputMethodV2;
+
+    COMMIT_STRING => commit_string,
+    SET_PREEDIT_STRING => set_preedit_string,
+    DELETE_SURROUNDING_TEXT => delete_surrounding_text,
+    COMMIT => commit,
+    GET_INPUT_POPUP_SURFACE => get_input_popup_surface,
+    GRAB_KEYBOARD => grab_keyboard,
+    DESTROY => destroy,
+}
+
+impl Object for ZwpInputMethodV2 {
+    fn num_requests(&self) -> u32 {
+        DESTROY + 1
+    }
+
+    fn break_loops(&self) {
+        self.detach();
+        self.popups.clear();
+    }
+}
+
+simple_add_obj!(ZwpInputMethodV2);
+
+#[derive(Debug, Error)]
+pub enum ZwpInputMethodV2Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("Parsing failed")]
+    MsgParserError(Box<MsgParserError>),
+    #[error(transparent)]
+    ZwpInputPopupSurfaceV2Error(Box<ZwpInputPopupSurfaceV2Error>),
+}
+efrom!(ZwpInputMethodV2Error, ClientError);
+efrom!(ZwpInputMethodV2Error, MsgParserError);
+efrom!(ZwpInputMethodV2Error, ZwpInputPopupSurfaceV2Error);
@@ -0,0 +1,106 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        globals::{Global, GlobalName},
+        ifs::wl_seat::text_input::zwp_text_input_v3::ZwpTextInputV3,
+        leaks::Tracker,
+        object::Object,
+        utils::buffd::{MsgParser, MsgParserError},
+        wire::{zwp_text_input_manager_v3::*, ZwpTextInputManagerV3Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct ZwpTextInputManagerV3Global {
+    pub name: GlobalName,
+}
+
+pub struct ZwpTextInputManagerV3 {
+    pub id: ZwpTextInputManagerV3Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+}
+
+impl ZwpTextInputManagerV3Global {
+    pub fn new(name: GlobalName) -> Self {
+        Self { name }
+    }
+
+    fn bind_(
+        self: Rc<Self>,
+        id: ZwpTextInputManagerV3Id,
+        client: &Rc<Client>,
+        _version: u32,
+    ) -> Result<(), ZwpTextInputManagerV3Error> {
+        let obj = Rc::new(ZwpTextInputManagerV3 {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+        });
+        track!(client, obj);
+        client.add_client_obj(&obj)?;
+        Ok(())
+    }
+}
+
+global_base!(
+    ZwpTextInputManagerV3Global,
+    ZwpTextInputManagerV3,
+    ZwpTextInputManagerV3Error
+);
+
+impl Global for ZwpTextInputManagerV3Global {
+    fn singleton(&self) -> bool {
+        true
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+}
+
+simple_add_global!(ZwpTextInputManagerV3Global);
+
+impl ZwpTextInputManagerV3 {
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpTextInputManagerV3Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn get_text_input(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpTextInputManagerV3Error> {
+        let req: GetTextInput = self.client.parse(self, parser)?;
+        let seat = self.client.lookup(req.seat)?;
+        let ti = Rc::new(ZwpTextInputV3::new(req.id, &self.client, &seat.global));
+        track!(self.client, ti);
+        self.client.add_client_obj(&ti)?;
+        seat.global.text_input_add(&ti);
+        Ok(())
+    }
+}
+
+object_base! {
+    ZwpTextInputManagerV3;
+
+    DESTROY => destroy,
+    GET_TEXT_INPUT => get_text_input,
+}
+
+impl Object for ZwpTextInputManagerV3 {
+    fn num_requests(&self) -> u32 {
+        GET_TEXT_INPUT + 1
+    }
+}
+
+simple_add_obj!(ZwpTextInputManagerV3);
+
+#[derive(Debug, Error)]
+pub enum ZwpTextInputManagerV3Error {
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwpTextInputManagerV3Error, MsgParserError);
+efrom!(ZwpTextInputManagerV3Error, ClientError);
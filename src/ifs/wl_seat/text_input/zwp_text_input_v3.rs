@@ -0,0 +1,217 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::{wl_seat::WlSeatGlobal, wl_surface::WlSurface},
+        leaks::Tracker,
+        object::Object,
+        rect::Rect,
+        utils::{
+            buffd::{MsgParser, MsgParserError},
+            clonecell::CloneCell,
+            numcell::NumCell,
+        },
+        wire::{zwp_text_input_v3::*, ZwpTextInputV3Id},
+    },
+    std::{cell::RefCell, rc::Rc},
+    thiserror::Error,
+};
+
+#[derive(Clone, Default)]
+pub struct TextInputState {
+    pub enabled: bool,
+    pub surrounding_text: Option<SurroundingText>,
+    pub text_change_cause: u32,
+    pub content_hint: u32,
+    pub content_purpose: u32,
+    /// The cursor rectangle in surface coordinates.
+    pub cursor_rectangle: Option<Rect>,
+}
+
+#[derive(Clone)]
+pub struct SurroundingText {
+    pub text: String,
+    pub cursor: i32,
+    pub anchor: i32,
+}
+
+pub struct ZwpTextInputV3 {
+    pub id: ZwpTextInputV3Id,
+    pub client: Rc<Client>,
+    pub seat: Rc<WlSeatGlobal>,
+    pub tracker: Tracker<Self>,
+    pub surface: CloneCell<Option<Rc<WlSurface>>>,
+    pending: RefCell<TextInputState>,
+    pub current: RefCell<TextInputState>,
+    num_commits: NumCell<u32>,
+}
+
+impl ZwpTextInputV3 {
+    pub fn new(id: ZwpTextInputV3Id, client: &Rc<Client>, seat: &Rc<WlSeatGlobal>) -> Self {
+        Self {
+            id,
+            client: client.clone(),
+            seat: seat.clone(),
+            tracker: Default::default(),
+            surface: Default::default(),
+            pending: Default::default(),
+            current: Default::default(),
+            num_commits: NumCell::new(0),
+        }
+    }
+
+    pub fn enter(&self, surface: &Rc<WlSurface>) {
+        if let Some(old) = self.surface.get() {
+            if old.id == surface.id {
+                return;
+            }
+            self.leave();
+        }
+        self.surface.set(Some(surface.clone()));
+        self.client.event(Enter {
+            self_id: self.id,
+            surface: surface.id,
+        });
+    }
+
+    pub fn leave(&self) {
+        if let Some(surface) = self.surface.take() {
+            self.current.borrow_mut().enabled = false;
+            self.client.event(Leave {
+                self_id: self.id,
+                surface: surface.id,
+            });
+        }
+    }
+
+    pub fn send_preedit_string(&self, text: Option<&str>, cursor_begin: i32, cursor_end: i32) {
+        self.client.event(PreeditString {
+            self_id: self.id,
+            text,
+            cursor_begin,
+            cursor_end,
+        });
+    }
+
+    pub fn send_commit_string(&self, text: Option<&str>) {
+        self.client.event(CommitString {
+            self_id: self.id,
+            text,
+        });
+    }
+
+    pub fn send_delete_surrounding_text(&self, before_length: u32, after_length: u32) {
+        self.client.event(DeleteSurroundingText {
+            self_id: self.id,
+            before_length,
+            after_length,
+        });
+    }
+
+    pub fn send_done(&self) {
+        self.client.event(Done {
+            self_id: self.id,
+            serial: self.num_commits.get(),
+        });
+    }
+
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpTextInputV3Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.seat.text_input_remove(self);
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn enable(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpTextInputV3Error> {
+        let _req: Enable = self.client.parse(self, parser)?;
+        *self.pending.borrow_mut() = TextInputState {
+            enabled: true,
+            ..Default::default()
+        };
+        Ok(())
+    }
+
+    fn disable(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpTextInputV3Error> {
+        let _req: Disable = self.client.parse(self, parser)?;
+        self.pending.borrow_mut().enabled = false;
+        Ok(())
+    }
+
+    fn set_surrounding_text(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpTextInputV3Error> {
+        let req: SetSurroundingText = self.client.parse(self, parser)?;
+        self.pending.borrow_mut().surrounding_text = Some(SurroundingText {
+            text: req.text.to_string(),
+            cursor: req.cursor,
+            anchor: req.anchor,
+        });
+        Ok(())
+    }
+
+    fn set_text_change_cause(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpTextInputV3Error> {
+        let req: SetTextChangeCause = self.client.parse(self, parser)?;
+        self.pending.borrow_mut().text_change_cause = req.cause;
+        Ok(())
+    }
+
+    fn set_content_type(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpTextInputV3Error> {
+        let req: SetContentType = self.client.parse(self, parser)?;
+        let mut pending = self.pending.borrow_mut();
+        pending.content_hint = req.hint;
+        pending.content_purpose = req.purpose;
+        Ok(())
+    }
+
+    fn set_cursor_rectangle(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpTextInputV3Error> {
+        let req: SetCursorRectangle = self.client.parse(self, parser)?;
+        self.pending.borrow_mut().cursor_rectangle =
+            Rect::new_sized(req.x, req.y, req.width, req.height);
+        Ok(())
+    }
+
+    fn commit(self: &Rc<Self>, parser: MsgParser<'_, '_>) -> Result<(), ZwpTextInputV3Error> {
+        let _req: Commit = self.client.parse(&**self, parser)?;
+        self.num_commits.fetch_add(1);
+        // Requests are ignored while the text input does not have focus.
+        if self.surface.get().is_none() {
+            return Ok(());
+        }
+        *self.current.borrow_mut() = self.pending.borrow().clone();
+        self.seat.text_input_commit(self);
+        Ok(())
+    }
+}
+
+object_base! {
+    ZwpTextInputV3;
+
+    DESTROY => destroy,
+    ENABLE => enable,
+    DISABLE => disable,
+    SET_SURROUNDING_TEXT => set_surrounding_text,
+    SET_TEXT_CHANGE_CAUSE => set_text_change_cause,
+    SET_CONTENT_TYPE => set_content_type,
+    SET_CURSOR_RECTANGLE => set_cursor_rectangle,
+    COMMIT => commit,
+}
+
+impl Object for ZwpTextInputV3 {
+    fn num_requests(&self) -> u32 {
+        COMMIT + 1
+    }
+
+    fn break_loops(&self) {
+        self.seat.text_input_remove(self);
+        self.surface.take();
+    }
+}
+
+simple_add_obj!(ZwpTextInputV3);
+
+#[derive(Debug, Error)]
+pub enum ZwpTextInputV3Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("Parsing failed")]
+    MsgParserError(Box<MsgParserError>),
+}
+efrom!(ZwpTextInputV3Error, ClientError);
+efrom!(ZwpTextInputV3Error, MsgParserError);
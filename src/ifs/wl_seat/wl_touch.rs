@@ -1,11 +1,12 @@
 use {
     crate::{
         client::ClientError,
+        fixed::Fixed,
         ifs::wl_seat::WlSeat,
         leaks::Tracker,
         object::Object,
         utils::buffd::{MsgParser, MsgParserError},
-        wire::{wl_touch::*, WlTouchId},
+        wire::{wl_touch::*, WlSurfaceId, WlTouchId},
     },
     std::rc::Rc,
     thiserror::Error,
@@ -41,8 +42,56 @@ impl WlTouch {
         }
     }
 
+    pub fn send_down(
+        &self,
+        serial: u32,
+        time: u32,
+        surface: WlSurfaceId,
+        id: i32,
+        x: Fixed,
+        y: Fixed,
+    ) {
+        self.seat.client.event(Down {
+            self_id: self.id,
+            serial,
+            time,
+            surface,
+            id,
+            x,
+            y,
+        })
+    }
+
+    pub fn send_up(&self, serial: u32, time: u32, id: i32) {
+        self.seat.client.event(Up {
+            self_id: self.id,
+            serial,
+            time,
+            id,
+        })
+    }
+
+    pub fn send_motion(&self, time: u32, id: i32, x: Fixed, y: Fixed) {
+        self.seat.client.event(Motion {
+            self_id: self.id,
+            time,
+            id,
+            x,
+            y,
+        })
+    }
+
+    pub fn send_frame(&self) {
+        self.seat.client.event(Frame { self_id: self.id })
+    }
+
+    pub fn send_cancel(&self) {
+        self.seat.client.event(Cancel { self_id: self.id })
+    }
+
     fn release(&self, parser: MsgParser<'_, '_>) -> Result<(), WlTouchError> {
         let _req: Release = self.seat.client.parse(self, parser)?;
+        self.seat.touches.remove(&self.id);
         self.seat.client.remove_obj(self)?;
         Ok(())
     }
@@ -0,0 +1,134 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        cursor::KnownCursor,
+        ifs::wl_seat::WlSeatGlobal,
+        leaks::Tracker,
+        object::Object,
+        utils::buffd::{MsgParser, MsgParserError},
+        wire::{wp_cursor_shape_device_v1::*, WpCursorShapeDeviceV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+const INVALID_SHAPE: u32 = 1;
+
+pub struct WpCursorShapeDeviceV1 {
+    pub id: WpCursorShapeDeviceV1Id,
+    pub client: Rc<Client>,
+    /// The seat of the pointer or `None` if this device belongs to a tablet tool.
+    pub seat: Option<Rc<WlSeatGlobal>>,
+    pub tracker: Tracker<Self>,
+}
+
+fn shape_to_cursor(shape: u32) -> Option<KnownCursor> {
+    let cursor = match shape {
+        1 => KnownCursor::Default,
+        2 => KnownCursor::ContextMenu,
+        3 => KnownCursor::Help,
+        4 => KnownCursor::Pointer,
+        5 => KnownCursor::Progress,
+        6 => KnownCursor::Wait,
+        7 => KnownCursor::Cell,
+        8 => KnownCursor::Crosshair,
+        9 => KnownCursor::Text,
+        10 => KnownCursor::VerticalText,
+        11 => KnownCursor::Alias,
+        12 => KnownCursor::Copy,
+        13 => KnownCursor::Move,
+        14 => KnownCursor::NoDrop,
+        15 => KnownCursor::NotAllowed,
+        16 => KnownCursor::Grab,
+        17 => KnownCursor::Grabbing,
+        18 => KnownCursor::ResizeRight,
+        19 => KnownCursor::ResizeTop,
+        20 => KnownCursor::ResizeTopRight,
+        21 => KnownCursor::ResizeTopLeft,
+        22 => KnownCursor::ResizeBottom,
+        23 => KnownCursor::ResizeBottomRight,
+        24 => KnownCursor::ResizeBottomLeft,
+        25 => KnownCursor::ResizeLeft,
+        26 => KnownCursor::ResizeLeftRight,
+        27 => KnownCursor::ResizeTopBottom,
+        28 => KnownCursor::ResizeTopRightBottomLeft,
+        29 => KnownCursor::ResizeTopLeftBottomRight,
+        30 => KnownCursor::ColResize,
+        31 => KnownCursor::RowResize,
+        32 => KnownCursor::AllScroll,
+        33 => KnownCursor::ZoomIn,
+        34 => KnownCursor::ZoomOut,
+        _ => return None,
+    };
+    Some(cursor)
+}
+
+impl WpCursorShapeDeviceV1 {
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), WpCursorShapeDeviceV1Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn set_shape(&self, parser: MsgParser<'_, '_>) -> Result<(), WpCursorShapeDeviceV1Error> {
+        let req: SetShape = self.client.parse(self, parser)?;
+        let cursor = match shape_to_cursor(req.shape) {
+            Some(c) => c,
+            _ => {
+                self.client.protocol_error(
+                    self,
+                    INVALID_SHAPE,
+                    &format!("Unknown cursor shape {}", req.shape),
+                );
+                return Err(WpCursorShapeDeviceV1Error::InvalidShape(req.shape));
+            }
+        };
+        let seat = match &self.seat {
+            Some(s) => s,
+            _ => return Ok(()),
+        };
+        if !self.client.valid_serial(req.serial) {
+            log::warn!("Client tried to set_shape with an invalid serial");
+            return Ok(());
+        }
+        let pointer_node = match seat.pointer_node() {
+            Some(n) => n,
+            _ => return Ok(()),
+        };
+        if pointer_node.node_client_id() != Some(self.client.id) {
+            return Ok(());
+        }
+        if req.serial != self.client.last_enter_serial.get() {
+            return Ok(());
+        }
+        seat.set_known_cursor(cursor);
+        Ok(())
+    }
+}
+
+object_base! {
+    WpCursorShapeDeviceV1;
+
+    DESTROY => destroy,
+    SET_SHAPE => set_shape,
+}
+
+impl Object for WpCursorShapeDeviceV1 {
+    fn num_requests(&self) -> u32 {
+        SET_SHAPE + 1
+    }
+}
+
+simple_add_obj!(WpCursorShapeDeviceV1);
+
+#[derive(Debug, Error)]
+pub enum WpCursorShapeDeviceV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error("Unknown cursor shape {0}")]
+    InvalidShape(u32),
+}
+efrom!(WpCursorShapeDeviceV1Error, ClientError);
+efrom!(WpCursorShapeDeviceV1Error, MsgParserError);
@@ -0,0 +1,129 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        globals::{Global, GlobalName},
+        ifs::wl_seat::wp_cursor_shape_device_v1::WpCursorShapeDeviceV1,
+        leaks::Tracker,
+        object::Object,
+        utils::buffd::{MsgParser, MsgParserError},
+        wire::{wp_cursor_shape_manager_v1::*, WpCursorShapeManagerV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct WpCursorShapeManagerV1Global {
+    pub name: GlobalName,
+}
+
+pub struct WpCursorShapeManagerV1 {
+    pub id: WpCursorShapeManagerV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+}
+
+impl WpCursorShapeManagerV1Global {
+    pub fn new(name: GlobalName) -> Self {
+        Self { name }
+    }
+
+    fn bind_(
+        self: Rc<Self>,
+        id: WpCursorShapeManagerV1Id,
+        client: &Rc<Client>,
+        _version: u32,
+    ) -> Result<(), WpCursorShapeManagerV1Error> {
+        let obj = Rc::new(WpCursorShapeManagerV1 {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+        });
+        track!(client, obj);
+        client.add_client_obj(&obj)?;
+        Ok(())
+    }
+}
+
+global_base!(
+    WpCursorShapeManagerV1Global,
+    WpCursorShapeManagerV1,
+    WpCursorShapeManagerV1Error
+);
+
+impl Global for WpCursorShapeManagerV1Global {
+    fn singleton(&self) -> bool {
+        true
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+}
+
+simple_add_global!(WpCursorShapeManagerV1Global);
+
+impl WpCursorShapeManagerV1 {
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), WpCursorShapeManagerV1Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn get_pointer(&self, parser: MsgParser<'_, '_>) -> Result<(), WpCursorShapeManagerV1Error> {
+        let req: GetPointer = self.client.parse(self, parser)?;
+        let pointer = self.client.lookup(req.pointer)?;
+        let device = Rc::new(WpCursorShapeDeviceV1 {
+            id: req.cursor_shape_device,
+            client: self.client.clone(),
+            seat: Some(pointer.seat.global.clone()),
+            tracker: Default::default(),
+        });
+        track!(self.client, device);
+        self.client.add_client_obj(&device)?;
+        Ok(())
+    }
+
+    fn get_tablet_tool_v2(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), WpCursorShapeManagerV1Error> {
+        let req: GetTabletToolV2 = self.client.parse(self, parser)?;
+        // Tablet tools do not have a cursor of their own. Shapes set through this device
+        // are ignored.
+        let device = Rc::new(WpCursorShapeDeviceV1 {
+            id: req.cursor_shape_device,
+            client: self.client.clone(),
+            seat: None,
+            tracker: Default::default(),
+        });
+        track!(self.client, device);
+        self.client.add_client_obj(&device)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    WpCursorShapeManagerV1;
+
+    DESTROY => destroy,
+    GET_POINTER => get_pointer,
+    GET_TABLET_TOOL_V2 => get_tablet_tool_v2,
+}
+
+impl Object for WpCursorShapeManagerV1 {
+    fn num_requests(&self) -> u32 {
+        GET_TABLET_TOOL_V2 + 1
+    }
+}
+
+simple_add_obj!(WpCursorShapeManagerV1);
+
+#[derive(Debug, Error)]
+pub enum WpCursorShapeManagerV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+}
+efrom!(WpCursorShapeManagerV1Error, ClientError);
+efrom!(WpCursorShapeManagerV1Error, MsgParserError);
@@ -0,0 +1,163 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        globals::{Global, GlobalName},
+        ifs::wl_seat::{zwlr_virtual_pointer_v1::ZwlrVirtualPointerV1, WlSeatGlobal},
+        leaks::Tracker,
+        object::Object,
+        utils::buffd::{MsgParser, MsgParserError},
+        wire::{
+            zwlr_virtual_pointer_manager_v1::*, WlOutputId, WlSeatId,
+            ZwlrVirtualPointerManagerV1Id, ZwlrVirtualPointerV1Id,
+        },
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct ZwlrVirtualPointerManagerV1Global {
+    pub name: GlobalName,
+}
+
+pub struct ZwlrVirtualPointerManagerV1 {
+    pub id: ZwlrVirtualPointerManagerV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+}
+
+impl ZwlrVirtualPointerManagerV1Global {
+    pub fn new(name: GlobalName) -> Self {
+        Self { name }
+    }
+
+    fn bind_(
+        self: Rc<Self>,
+        id: ZwlrVirtualPointerManagerV1Id,
+        client: &Rc<Client>,
+        _version: u32,
+    ) -> Result<(), ZwlrVirtualPointerManagerV1Error> {
+        let obj = Rc::new(ZwlrVirtualPointerManagerV1 {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+        });
+        track!(client, obj);
+        client.add_client_obj(&obj)?;
+        Ok(())
+    }
+}
+
+global_base!(
+    ZwlrVirtualPointerManagerV1Global,
+    ZwlrVirtualPointerManagerV1,
+    ZwlrVirtualPointerManagerV1Error
+);
+
+impl Global for ZwlrVirtualPointerManagerV1Global {
+    fn singleton(&self) -> bool {
+        true
+    }
+
+    fn version(&self) -> u32 {
+        2
+    }
+
+    fn secure(&self) -> bool {
+        true
+    }
+}
+
+simple_add_global!(ZwlrVirtualPointerManagerV1Global);
+
+impl ZwlrVirtualPointerManagerV1 {
+    fn create(
+        &self,
+        id: ZwlrVirtualPointerV1Id,
+        seat: WlSeatId,
+        output: WlOutputId,
+    ) -> Result<(), ZwlrVirtualPointerManagerV1Error> {
+        let seat = if seat.is_some() {
+            self.client.lookup(seat)?.global.clone()
+        } else {
+            self.default_seat()?
+        };
+        let mut mapped_output = None;
+        if output.is_some() {
+            mapped_output = Some(self.client.lookup(output)?.global.clone());
+        }
+        let pointer = Rc::new(ZwlrVirtualPointerV1::new(
+            id,
+            &self.client,
+            &seat,
+            mapped_output,
+        ));
+        track!(self.client, pointer);
+        self.client.add_client_obj(&pointer)?;
+        Ok(())
+    }
+
+    /// Returns the seat used when the client does not specify one.
+    fn default_seat(&self) -> Result<Rc<WlSeatGlobal>, ZwlrVirtualPointerManagerV1Error> {
+        let seats = self.client.state.globals.seats.lock();
+        let mut res = None;
+        for seat in seats.values() {
+            if seat.seat_name() == "default" {
+                return Ok(seat.clone());
+            }
+            if res.is_none() {
+                res = Some(seat.clone());
+            }
+        }
+        res.ok_or(ZwlrVirtualPointerManagerV1Error::NoSeat)
+    }
+
+    fn create_virtual_pointer(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), ZwlrVirtualPointerManagerV1Error> {
+        let req: CreateVirtualPointer = self.client.parse(self, parser)?;
+        self.create(req.id, req.seat, WlOutputId::NONE)
+    }
+
+    fn create_virtual_pointer_with_output(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), ZwlrVirtualPointerManagerV1Error> {
+        let req: CreateVirtualPointerWithOutput = self.client.parse(self, parser)?;
+        self.create(req.id, req.seat, req.output)
+    }
+
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwlrVirtualPointerManagerV1Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    ZwlrVirtualPointerManagerV1;
+
+    CREATE_VIRTUAL_POINTER => create_virtual_pointer,
+    DESTROY => destroy,
+    CREATE_VIRTUAL_POINTER_WITH_OUTPUT => create_virtual_pointer_with_output,
+}
+
+impl Object for ZwlrVirtualPointerManagerV1 {
+    fn num_requests(&self) -> u32 {
+        CREATE_VIRTUAL_POINTER_WITH_OUTPUT + 1
+    }
+}
+
+simple_add_obj!(ZwlrVirtualPointerManagerV1);
+
+#[derive(Debug, Error)]
+pub enum ZwlrVirtualPointerManagerV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error("There are no seats")]
+    NoSeat,
+}
+efrom!(ZwlrVirtualPointerManagerV1Error, ClientError);
+efrom!(ZwlrVirtualPointerManagerV1Error, MsgParserError);
@@ -0,0 +1,233 @@
+use {
+    crate::{
+        backend::{self, KeyState, ScrollAxis, AXIS_120},
+        client::{Client, ClientError},
+        fixed::Fixed,
+        ifs::{
+            wl_output::WlOutputGlobal,
+            wl_seat::{
+                wl_pointer::{
+                    CONTINUOUS, FINGER, HORIZONTAL_SCROLL, PRESSED, VERTICAL_SCROLL, WHEEL,
+                    WHEEL_TILT,
+                },
+                WlSeatGlobal, PX_PER_SCROLL,
+            },
+        },
+        leaks::Tracker,
+        object::Object,
+        rect::Rect,
+        utils::buffd::{MsgParser, MsgParserError},
+        wire::{zwlr_virtual_pointer_v1::*, ZwlrVirtualPointerV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+const INVALID_AXIS: u32 = 0;
+const INVALID_AXIS_SOURCE: u32 = 1;
+
+pub struct ZwlrVirtualPointerV1 {
+    pub id: ZwlrVirtualPointerV1Id,
+    pub client: Rc<Client>,
+    pub seat: Rc<WlSeatGlobal>,
+    pub output: Option<Rc<WlOutputGlobal>>,
+    pub tracker: Tracker<Self>,
+    time_usec: Cell<u64>,
+}
+
+impl ZwlrVirtualPointerV1 {
+    pub fn new(
+        id: ZwlrVirtualPointerV1Id,
+        client: &Rc<Client>,
+        seat: &Rc<WlSeatGlobal>,
+        output: Option<Rc<WlOutputGlobal>>,
+    ) -> Self {
+        Self {
+            id,
+            client: client.clone(),
+            seat: seat.clone(),
+            output,
+            tracker: Default::default(),
+            time_usec: Cell::new(0),
+        }
+    }
+
+    fn set_time(&self, time: u32) -> u64 {
+        let time_usec = time as u64 * 1000;
+        self.time_usec.set(time_usec);
+        time_usec
+    }
+
+    fn axis(&self, axis: u32) -> Result<ScrollAxis, ZwlrVirtualPointerV1Error> {
+        match axis {
+            VERTICAL_SCROLL => Ok(ScrollAxis::Vertical),
+            HORIZONTAL_SCROLL => Ok(ScrollAxis::Horizontal),
+            _ => {
+                self.client
+                    .protocol_error(self, INVALID_AXIS, &format!("Unknown axis {}", axis));
+                Err(ZwlrVirtualPointerV1Error::InvalidAxis(axis))
+            }
+        }
+    }
+
+    /// Returns the area that absolute motion events are mapped to.
+    fn mapped_area(&self) -> Option<Rect> {
+        if let Some(output) = &self.output {
+            return Some(output.pos.get());
+        }
+        let mut area: Option<Rect> = None;
+        for output in self.client.state.outputs.lock().values() {
+            let pos = output.node.global.pos.get();
+            area = Some(match area {
+                Some(area) => area.union(pos),
+                _ => pos,
+            });
+        }
+        area
+    }
+
+    fn motion(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwlrVirtualPointerV1Error> {
+        let req: Motion = self.client.parse(self, parser)?;
+        let time_usec = self.set_time(req.time);
+        self.seat
+            .motion_event(time_usec, req.dx, req.dy, req.dx, req.dy);
+        Ok(())
+    }
+
+    fn motion_absolute(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwlrVirtualPointerV1Error> {
+        let req: MotionAbsolute = self.client.parse(self, parser)?;
+        let time_usec = self.set_time(req.time);
+        if req.x_extent == 0 || req.y_extent == 0 {
+            return Ok(());
+        }
+        let area = match self.mapped_area() {
+            Some(a) => a,
+            _ => return Ok(()),
+        };
+        let x = area.x1() as f64 + req.x as f64 / req.x_extent as f64 * area.width() as f64;
+        let y = area.y1() as f64 + req.y as f64 / req.y_extent as f64 * area.height() as f64;
+        let (x_int, y_int) = (x as i32, y as i32);
+        for output in self.client.state.outputs.lock().values() {
+            let pos = output.node.global.pos.get();
+            if pos.contains(x_int, y_int) {
+                self.seat.connector_position_event(
+                    time_usec,
+                    output.connector.connector.id(),
+                    Fixed::from_f64(x - pos.x1() as f64),
+                    Fixed::from_f64(y - pos.y1() as f64),
+                );
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn button(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwlrVirtualPointerV1Error> {
+        let req: Button = self.client.parse(self, parser)?;
+        let time_usec = self.set_time(req.time);
+        let state = match req.state {
+            PRESSED => KeyState::Pressed,
+            _ => KeyState::Released,
+        };
+        self.seat.button_event(time_usec, req.button, state);
+        Ok(())
+    }
+
+    fn axis_(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwlrVirtualPointerV1Error> {
+        let req: Axis = self.client.parse(self, parser)?;
+        self.set_time(req.time);
+        let axis = self.axis(req.axis)?;
+        self.seat.pointer_owner.axis_px(req.value, axis);
+        Ok(())
+    }
+
+    fn frame(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwlrVirtualPointerV1Error> {
+        let _req: Frame = self.client.parse(self, parser)?;
+        self.seat
+            .pointer_owner
+            .frame(PX_PER_SCROLL, &self.seat, self.time_usec.get());
+        Ok(())
+    }
+
+    fn axis_source(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwlrVirtualPointerV1Error> {
+        let req: AxisSource = self.client.parse(self, parser)?;
+        let source = match req.axis_source {
+            WHEEL | WHEEL_TILT => backend::AxisSource::Wheel,
+            FINGER => backend::AxisSource::Finger,
+            CONTINUOUS => backend::AxisSource::Continuous,
+            _ => {
+                self.client.protocol_error(
+                    self,
+                    INVALID_AXIS_SOURCE,
+                    &format!("Unknown axis source {}", req.axis_source),
+                );
+                return Err(ZwlrVirtualPointerV1Error::InvalidAxisSource(
+                    req.axis_source,
+                ));
+            }
+        };
+        self.seat.pointer_owner.axis_source(source);
+        Ok(())
+    }
+
+    fn axis_stop(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwlrVirtualPointerV1Error> {
+        let req: AxisStop = self.client.parse(self, parser)?;
+        self.set_time(req.time);
+        let axis = self.axis(req.axis)?;
+        self.seat.pointer_owner.axis_stop(axis);
+        Ok(())
+    }
+
+    fn axis_discrete(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwlrVirtualPointerV1Error> {
+        let req: AxisDiscrete = self.client.parse(self, parser)?;
+        self.set_time(req.time);
+        let axis = self.axis(req.axis)?;
+        self.seat.pointer_owner.axis_px(req.value, axis);
+        self.seat
+            .pointer_owner
+            .axis_120(req.discrete * AXIS_120, axis);
+        Ok(())
+    }
+
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwlrVirtualPointerV1Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    ZwlrVirtualPointerV1;
+
+    MOTION => motion,
+    MOTION_ABSOLUTE => motion_absolute,
+    BUTTON => button,
+    AXIS => axis_,
+    FRAME => frame,
+    AXIS_SOURCE => axis_source,
+    AXIS_STOP => axis_stop,
+    AXIS_DISCRETE => axis_discrete,
+    DESTROY => destroy,
+}
+
+impl Object for ZwlrVirtualPointerV1 {
+    fn num_requests(&self) -> u32 {
+        DESTROY + 1
+    }
+}
+
+simple_add_obj!(ZwlrVirtualPointerV1);
+
+#[derive(Debug, Error)]
+pub enum ZwlrVirtualPointerV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error("Unknown axis {0}")]
+    InvalidAxis(u32),
+    #[error("Unknown axis source {0}")]
+    InvalidAxisSource(u32),
+}
+efrom!(ZwlrVirtualPointerV1Error, ClientError);
+efrom!(ZwlrVirtualPointerV1Error, MsgParserError);
@@ -0,0 +1,132 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        globals::{Global, GlobalName},
+        ifs::wl_seat::zwp_keyboard_shortcuts_inhibitor_v1::ZwpKeyboardShortcutsInhibitorV1,
+        leaks::Tracker,
+        object::Object,
+        utils::buffd::{MsgParser, MsgParserError},
+        wire::{
+            zwp_keyboard_shortcuts_inhibit_manager_v1::*, ZwpKeyboardShortcutsInhibitManagerV1Id,
+        },
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+const ALREADY_INHIBITED: u32 = 0;
+
+pub struct ZwpKeyboardShortcutsInhibitManagerV1Global {
+    pub name: GlobalName,
+}
+
+pub struct ZwpKeyboardShortcutsInhibitManagerV1 {
+    pub id: ZwpKeyboardShortcutsInhibitManagerV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+}
+
+impl ZwpKeyboardShortcutsInhibitManagerV1Global {
+    pub fn new(name: GlobalName) -> Self {
+        Self { name }
+    }
+
+    fn bind_(
+        self: Rc<Self>,
+        id: ZwpKeyboardShortcutsInhibitManagerV1Id,
+        client: &Rc<Client>,
+        _version: u32,
+    ) -> Result<(), ZwpKeyboardShortcutsInhibitManagerV1Error> {
+        let obj = Rc::new(ZwpKeyboardShortcutsInhibitManagerV1 {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+        });
+        track!(client, obj);
+        client.add_client_obj(&obj)?;
+        Ok(())
+    }
+}
+
+global_base!(
+    ZwpKeyboardShortcutsInhibitManagerV1Global,
+    ZwpKeyboardShortcutsInhibitManagerV1,
+    ZwpKeyboardShortcutsInhibitManagerV1Error
+);
+
+impl Global for ZwpKeyboardShortcutsInhibitManagerV1Global {
+    fn singleton(&self) -> bool {
+        true
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+}
+
+simple_add_global!(ZwpKeyboardShortcutsInhibitManagerV1Global);
+
+impl ZwpKeyboardShortcutsInhibitManagerV1 {
+    fn destroy(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), ZwpKeyboardShortcutsInhibitManagerV1Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn inhibit_shortcuts(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), ZwpKeyboardShortcutsInhibitManagerV1Error> {
+        let req: InhibitShortcuts = self.client.parse(self, parser)?;
+        let surface = self.client.lookup(req.surface)?;
+        let seat = self.client.lookup(req.seat)?;
+        if surface.shortcuts_inhibitors.contains(&seat.global.id()) {
+            self.client.protocol_error(
+                self,
+                ALREADY_INHIBITED,
+                "The surface already inhibits the shortcuts of this seat",
+            );
+            return Err(ZwpKeyboardShortcutsInhibitManagerV1Error::AlreadyInhibited);
+        }
+        let inhibitor = Rc::new(ZwpKeyboardShortcutsInhibitorV1::new(
+            req.id,
+            &self.client,
+            &seat.global,
+            &surface,
+        ));
+        track!(self.client, inhibitor);
+        self.client.add_client_obj(&inhibitor)?;
+        inhibitor.install();
+        Ok(())
+    }
+}
+
+object_base! {
+    ZwpKeyboardShortcutsInhibitManagerV1;
+
+    DESTROY => destroy,
+    INHIBIT_SHORTCUTS => inhibit_shortcuts,
+}
+
+impl Object for ZwpKeyboardShortcutsInhibitManagerV1 {
+    fn num_requests(&self) -> u32 {
+        INHIBIT_SHORTCUTS + 1
+    }
+}
+
+simple_add_obj!(ZwpKeyboardShortcutsInhibitManagerV1);
+
+#[derive(Debug, Error)]
+pub enum ZwpKeyboardShortcutsInhibitManagerV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error("The surface already inhibits the shortcuts of this seat")]
+    AlreadyInhibited,
+}
+efrom!(ZwpKeyboardShortcutsInhibitManagerV1Error, ClientError);
+efrom!(ZwpKeyboardShortcutsInhibitManagerV1Error, MsgParserError);
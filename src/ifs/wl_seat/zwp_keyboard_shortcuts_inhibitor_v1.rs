@@ -0,0 +1,122 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::{wl_seat::WlSeatGlobal, wl_surface::WlSurface},
+        leaks::Tracker,
+        object::Object,
+        utils::buffd::{MsgParser, MsgParserError},
+        wire::{zwp_keyboard_shortcuts_inhibitor_v1::*, ZwpKeyboardShortcutsInhibitorV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+pub struct ZwpKeyboardShortcutsInhibitorV1 {
+    pub id: ZwpKeyboardShortcutsInhibitorV1Id,
+    pub client: Rc<Client>,
+    pub seat: Rc<WlSeatGlobal>,
+    pub surface: Rc<WlSurface>,
+    pub tracker: Tracker<Self>,
+    /// Whether the config allowed this inhibitor.
+    allowed: Cell<bool>,
+    active: Cell<bool>,
+}
+
+impl ZwpKeyboardShortcutsInhibitorV1 {
+    pub fn new(
+        id: ZwpKeyboardShortcutsInhibitorV1Id,
+        client: &Rc<Client>,
+        seat: &Rc<WlSeatGlobal>,
+        surface: &Rc<WlSurface>,
+    ) -> Self {
+        Self {
+            id,
+            client: client.clone(),
+            seat: seat.clone(),
+            surface: surface.clone(),
+            tracker: Default::default(),
+            allowed: Cell::new(false),
+            active: Cell::new(false),
+        }
+    }
+
+    pub fn install(self: &Rc<Self>) {
+        let app_id = match self.surface.get_toplevel() {
+            Some(tl) => tl.tl_data().app_id.borrow().clone(),
+            _ => String::new(),
+        };
+        let allowed = match self.client.state.config.get() {
+            Some(config) => {
+                config.shortcuts_inhibit_allowed(self.seat.id(), &app_id, &self.client.pid_info)
+            }
+            _ => true,
+        };
+        self.allowed.set(allowed);
+        self.surface
+            .shortcuts_inhibitors
+            .insert(self.seat.id(), self.clone());
+        if self.seat.keyboard_node.get().node_id() == self.surface.node_id {
+            self.activate();
+        }
+    }
+
+    pub fn activate(self: &Rc<Self>) {
+        if !self.allowed.get() || self.active.replace(true) {
+            return;
+        }
+        self.seat.shortcuts_inhibitor.set(Some(self.clone()));
+        self.client.event(Active { self_id: self.id });
+    }
+
+    pub fn deactivate(&self) {
+        if self.active.replace(false) {
+            self.seat.shortcuts_inhibitor.take();
+            self.client.event(Inactive { self_id: self.id });
+        }
+    }
+
+    fn detach(&self) {
+        self.surface.shortcuts_inhibitors.remove(&self.seat.id());
+        if self.active.replace(false) {
+            self.seat.shortcuts_inhibitor.take();
+        }
+    }
+
+    fn destroy(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), ZwpKeyboardShortcutsInhibitorV1Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.detach();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    ZwpKeyboardShortcutsInhibitorV1;
+
+    DESTROY => destroy,
+}
+
+impl Object for ZwpKeyboardShortcutsInhibitorV1 {
+    fn num_requests(&self) -> u32 {
+        DESTROY + 1
+    }
+
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
+
+simple_add_obj!(ZwpKeyboardShortcutsInhibitorV1);
+
+#[derive(Debug, Error)]
+pub enum ZwpKeyboardShortcutsInhibitorV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+}
+efrom!(ZwpKeyboardShortcutsInhibitorV1Error, ClientError);
+efrom!(ZwpKeyboardShortcutsInhibitorV1Error, MsgParserError);
@@ -0,0 +1,71 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::{wl_seat::WlSeat, wl_surface::WlSurface},
+        leaks::Tracker,
+        object::Object,
+        utils::buffd::{MsgParser, MsgParserError},
+        wire::{zwp_pointer_gesture_hold_v1::*, ZwpPointerGestureHoldV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct ZwpPointerGestureHoldV1 {
+    pub id: ZwpPointerGestureHoldV1Id,
+    pub client: Rc<Client>,
+    pub seat: Rc<WlSeat>,
+    pub tracker: Tracker<Self>,
+}
+
+impl ZwpPointerGestureHoldV1 {
+    pub fn send_begin(&self, serial: u32, time: u32, surface: &WlSurface, fingers: u32) {
+        self.client.event(Begin {
+            self_id: self.id,
+            serial,
+            time,
+            surface: surface.id,
+            fingers,
+        });
+    }
+
+    pub fn send_end(&self, serial: u32, time: u32, cancelled: bool) {
+        self.client.event(End {
+            self_id: self.id,
+            serial,
+            time,
+            cancelled: cancelled as i32,
+        });
+    }
+
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpPointerGestureHoldV1Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.seat.hold_gestures.remove(&self.id);
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    ZwpPointerGestureHoldV1;
+
+    DESTROY => destroy,
+}
+
+impl Object for ZwpPointerGestureHoldV1 {
+    fn num_requests(&self) -> u32 {
+        DESTROY + 1
+    }
+}
+
+simple_add_obj!(ZwpPointerGestureHoldV1);
+
+#[derive(Debug, Error)]
+pub enum ZwpPointerGestureHoldV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("Parsing failed")]
+    MsgParserError(Box<MsgParserError>),
+}
+efrom!(ZwpPointerGestureHoldV1Error, ClientError);
+efrom!(ZwpPointerGestureHoldV1Error, MsgParserError);
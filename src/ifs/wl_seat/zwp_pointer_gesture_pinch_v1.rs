@@ -0,0 +1,83 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        fixed::Fixed,
+        ifs::{wl_seat::WlSeat, wl_surface::WlSurface},
+        leaks::Tracker,
+        object::Object,
+        utils::buffd::{MsgParser, MsgParserError},
+        wire::{zwp_pointer_gesture_pinch_v1::*, ZwpPointerGesturePinchV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct ZwpPointerGesturePinchV1 {
+    pub id: ZwpPointerGesturePinchV1Id,
+    pub client: Rc<Client>,
+    pub seat: Rc<WlSeat>,
+    pub tracker: Tracker<Self>,
+}
+
+impl ZwpPointerGesturePinchV1 {
+    pub fn send_begin(&self, serial: u32, time: u32, surface: &WlSurface, fingers: u32) {
+        self.client.event(Begin {
+            self_id: self.id,
+            serial,
+            time,
+            surface: surface.id,
+            fingers,
+        });
+    }
+
+    pub fn send_update(&self, time: u32, dx: Fixed, dy: Fixed, scale: Fixed, rotation: Fixed) {
+        self.client.event(Update {
+            self_id: self.id,
+            time,
+            dx,
+            dy,
+            scale,
+            rotation,
+        });
+    }
+
+    pub fn send_end(&self, serial: u32, time: u32, cancelled: bool) {
+        self.client.event(End {
+            self_id: self.id,
+            serial,
+            time,
+            cancelled: cancelled as i32,
+        });
+    }
+
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpPointerGesturePinchV1Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.seat.pinch_gestures.remove(&self.id);
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    ZwpPointerGesturePinchV1;
+
+    DESTROY => destroy,
+}
+
+impl Object for ZwpPointerGesturePinchV1 {
+    fn num_requests(&self) -> u32 {
+        DESTROY + 1
+    }
+}
+
+simple_add_obj!(ZwpPointerGesturePinchV1);
+
+#[derive(Debug, Error)]
+pub enum ZwpPointerGesturePinchV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("Parsing failed")]
+    MsgParserError(Box<MsgParserError>),
+}
+efrom!(ZwpPointerGesturePinchV1Error, ClientError);
+efrom!(ZwpPointerGesturePinchV1Error, MsgParserError);
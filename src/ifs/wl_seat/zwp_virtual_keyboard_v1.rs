@@ -0,0 +1,206 @@
+use {
+    crate::{
+        backend::KeyState,
+        client::{Client, ClientError},
+        ifs::wl_seat::{wl_keyboard, WlSeatGlobal},
+        leaks::Tracker,
+        object::Object,
+        time::now_usec,
+        utils::{
+            buffd::{MsgParser, MsgParserError},
+            clonecell::CloneCell,
+            oserror::OsError,
+            rc_eq::rc_eq,
+        },
+        wire::{zwp_virtual_keyboard_v1::*, ZwpVirtualKeyboardV1Id},
+        xkbcommon::{XkbCommonError, XkbState},
+    },
+    ahash::AHashSet,
+    std::{cell::RefCell, rc::Rc},
+    thiserror::Error,
+    uapi::c,
+};
+
+const NO_KEYMAP: u32 = 0;
+
+pub struct ZwpVirtualKeyboardV1 {
+    pub id: ZwpVirtualKeyboardV1Id,
+    pub client: Rc<Client>,
+    pub seat: Rc<WlSeatGlobal>,
+    pub tracker: Tracker<Self>,
+    kb_state: CloneCell<Option<Rc<RefCell<XkbState>>>>,
+    pressed_keys: RefCell<AHashSet<u32>>,
+}
+
+impl ZwpVirtualKeyboardV1 {
+    pub fn new(id: ZwpVirtualKeyboardV1Id, client: &Rc<Client>, seat: &Rc<WlSeatGlobal>) -> Self {
+        Self {
+            id,
+            client: client.clone(),
+            seat: seat.clone(),
+            tracker: Default::default(),
+            kb_state: Default::default(),
+            pressed_keys: Default::default(),
+        }
+    }
+
+    fn get_kb_state(&self) -> Result<Rc<RefCell<XkbState>>, ZwpVirtualKeyboardV1Error> {
+        match self.kb_state.get() {
+            Some(s) => Ok(s),
+            _ => {
+                self.client
+                    .protocol_error(self, NO_KEYMAP, "No keymap has been set");
+                Err(ZwpVirtualKeyboardV1Error::NoKeymap)
+            }
+        }
+    }
+
+    /// Keys injected by the input method itself must not be sent back to its
+    /// keyboard grab.
+    fn to_input_method(&self) -> bool {
+        !self.seat.is_input_method_client(self.client.id)
+    }
+
+    fn detach(&self) {
+        let kb_state = match self.kb_state.take() {
+            Some(s) => s,
+            _ => return,
+        };
+        let keys: Vec<_> = self.pressed_keys.borrow_mut().drain().collect();
+        let time_usec = now_usec();
+        for key in keys {
+            self.seat.key_event_with_state(
+                time_usec,
+                key,
+                KeyState::Released,
+                &kb_state,
+                self.to_input_method(),
+            );
+        }
+        if rc_eq(&self.seat.kb_source.get(), &kb_state) {
+            let seat_kb_state = self.seat.kb_state.clone();
+            self.seat.set_kb_source(&seat_kb_state);
+        }
+    }
+
+    /// Reads the keymap with `pread` since a mapping of the client's file could be
+    /// truncated while it is being parsed.
+    fn read_keymap(&self, fd: c::c_int, size: usize) -> Result<Vec<u8>, ZwpVirtualKeyboardV1Error> {
+        let mut buf = vec![0u8; size];
+        let mut pos = 0;
+        while pos < buf.len() {
+            match uapi::pread(fd, &mut buf[pos..], pos as _) {
+                Ok([]) => break,
+                Ok(n) => pos += n.len(),
+                Err(e) => return Err(ZwpVirtualKeyboardV1Error::ReadKeymap(e.into())),
+            }
+        }
+        if pos != buf.len() {
+            return Err(ZwpVirtualKeyboardV1Error::KeymapTooShort);
+        }
+        Ok(buf)
+    }
+
+    fn keymap(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpVirtualKeyboardV1Error> {
+        let req: Keymap = self.client.parse(self, parser)?;
+        if req.format != wl_keyboard::XKB_V1 {
+            return Err(ZwpVirtualKeyboardV1Error::UnsupportedFormat(req.format));
+        }
+        let map = self.read_keymap(req.fd.raw(), req.size as _)?;
+        let keymap = self.client.state.xkb_ctx.keymap_from_buffer(&map)?;
+        let kb_state = keymap.state()?;
+        self.kb_state.set(Some(Rc::new(RefCell::new(kb_state))));
+        Ok(())
+    }
+
+    fn key(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpVirtualKeyboardV1Error> {
+        let req: Key = self.client.parse(self, parser)?;
+        let kb_state = self.get_kb_state()?;
+        let state = match req.state {
+            wl_keyboard::RELEASED => {
+                self.pressed_keys.borrow_mut().remove(&req.key);
+                KeyState::Released
+            }
+            wl_keyboard::PRESSED => {
+                self.pressed_keys.borrow_mut().insert(req.key);
+                KeyState::Pressed
+            }
+            _ => return Err(ZwpVirtualKeyboardV1Error::UnknownKeyState(req.state)),
+        };
+        self.seat.key_event_with_state(
+            req.time as u64 * 1000,
+            req.key,
+            state,
+            &kb_state,
+            self.to_input_method(),
+        );
+        Ok(())
+    }
+
+    fn modifiers(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpVirtualKeyboardV1Error> {
+        let req: Modifiers = self.client.parse(self, parser)?;
+        let kb_state = self.get_kb_state()?;
+        let mods = kb_state.borrow_mut().set_mods(
+            req.mods_depressed,
+            req.mods_latched,
+            req.mods_locked,
+            req.group,
+        );
+        if !rc_eq(&self.seat.kb_source.get(), &kb_state) {
+            self.seat.set_kb_source(&kb_state);
+        } else if let Some(mods) = mods {
+            self.seat.mods_event(mods);
+        }
+        Ok(())
+    }
+
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpVirtualKeyboardV1Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.detach();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    ZwpVirtualKeyboardV1;
+
+    KEYMAP => keymap,
+    KEY => key,
+    MODIFIERS => modifiers,
+    DESTROY => destroy,
+}
+
+impl Object for ZwpVirtualKeyboardV1 {
+    fn num_requests(&self) -> u32 {
+        DESTROY + 1
+    }
+
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
+
+simple_add_obj!(ZwpVirtualKeyboardV1);
+
+#[derive(Debug, Error)]
+pub enum ZwpVirtualKeyboardV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error("Keymap format {0} is not supported")]
+    UnsupportedFormat(u32),
+    #[error("Could not read the keymap")]
+    ReadKeymap(#[source] OsError),
+    #[error("The keymap is smaller than the advertised size")]
+    KeymapTooShort,
+    #[error("Could not parse the keymap")]
+    ParseKeymap(#[from] XkbCommonError),
+    #[error("No keymap has been set")]
+    NoKeymap,
+    #[error("Unknown key state {0}")]
+    UnknownKeyState(u32),
+}
+efrom!(ZwpVirtualKeyboardV1Error, ClientError);
+efrom!(ZwpVirtualKeyboardV1Error, MsgParserError);
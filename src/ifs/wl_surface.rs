@@ -9,6 +9,7 @@ pub mod xdg_surface;
 pub mod xwayland_shell_v1;
 pub mod zwlr_layer_surface_v1;
 pub mod zwp_idle_inhibitor_v1;
+pub mod zwp_input_popup_surface_v2;
 
 use {
     crate::{
@@ -23,8 +24,10 @@ use {
                 TF_NORMAL,
             },
             wl_seat::{
-                wl_pointer::PendingScroll, zwp_pointer_constraints_v1::SeatConstraint, Dnd,
-                NodeSeatState, SeatId, WlSeatGlobal,
+                wl_pointer::PendingScroll,
+                zwp_keyboard_shortcuts_inhibitor_v1::ZwpKeyboardShortcutsInhibitorV1,
+                zwp_pointer_constraints_v1::SeatConstraint, Dnd, NodeSeatState, SeatId,
+                WlSeatGlobal,
             },
             wl_surface::{
                 cursor::CursorSurface, wl_subsurface::WlSubsurface,
@@ -202,6 +205,7 @@ pub enum SurfaceRole {
     ZwlrLayerSurface,
     XSurface,
     ExtSessionLockSurface,
+    InputPopup,
 }
 
 impl SurfaceRole {
@@ -215,6 +219,7 @@ impl SurfaceRole {
             SurfaceRole::ZwlrLayerSurface => "zwlr_layer_surface",
             SurfaceRole::XSurface => "xwayland surface",
             SurfaceRole::ExtSessionLockSurface => "ext_session_lock_surface",
+            SurfaceRole::InputPopup => "input_popup_surface",
         }
     }
 }
@@ -262,9 +267,11 @@ pub struct WlSurface {
     output: CloneCell<Rc<OutputNode>>,
     fractional_scale: CloneCell<Option<Rc<WpFractionalScaleV1>>>,
     pub constraints: SmallMap<SeatId, Rc<SeatConstraint>, 1>,
+    pub shortcuts_inhibitors: SmallMap<SeatId, Rc<ZwpKeyboardShortcutsInhibitorV1>, 1>,
     xwayland_serial: Cell<Option<u64>>,
     tearing_control: CloneCell<Option<Rc<WpTearingControlV1>>>,
     tearing: Cell<bool>,
+    damage_full: Cell<bool>,
 }
 
 impl Debug for WlSurface {
@@ -350,7 +357,8 @@ struct PendingState {
     opaque_region: Cell<Option<Option<Rc<Region>>>>,
     input_region: Cell<Option<Option<Rc<Region>>>>,
     frame_request: RefCell<Vec<Rc<WlCallback>>>,
-    damage: Cell<bool>,
+    damage: RefCell<Vec<Rect>>,
+    buffer_damage: RefCell<Vec<Rect>>,
     presentation_feedback: RefCell<Vec<Rc<WpPresentationFeedback>>>,
     src_rect: Cell<Option<Option<[Fixed; 4]>>>,
     dst_size: Cell<Option<Option<(i32, i32)>>>,
@@ -409,9 +417,11 @@ impl WlSurface {
             output: CloneCell::new(client.state.dummy_output.get().unwrap()),
             fractional_scale: Default::default(),
             constraints: Default::default(),
+            shortcuts_inhibitors: Default::default(),
             xwayland_serial: Default::default(),
             tearing_control: Default::default(),
             tearing: Cell::new(false),
+            damage_full: Cell::new(false),
         }
     }
 
@@ -485,6 +495,26 @@ impl WlSurface {
             .push(fb.clone());
     }
 
+    pub fn direct_scanout_buffer(&self) -> Option<Rc<WlBuffer>> {
+        if let Some(children) = self.children.borrow().deref() {
+            if !children.below.is_empty() || !children.above.is_empty() {
+                return None;
+            }
+        }
+        if !self.buffer_untransformed() || self.dst_size.get().is_some() {
+            return None;
+        }
+        self.buffer.get()
+    }
+
+    pub fn buffer_untransformed(&self) -> bool {
+        self.buffer_transform.get() == Transform::Normal && self.src_rect.get().is_none()
+    }
+
+    pub fn tearing(&self) -> bool {
+        self.tearing.get()
+    }
+
     pub fn is_cursor(&self) -> bool {
         self.role.get() == SurfaceRole::Cursor
     }
@@ -634,9 +664,7 @@ impl WlSurface {
             *children = None;
         }
         if let Some(buffer) = self.buffer.set(None) {
-            if !buffer.destroyed() {
-                buffer.send_release();
-            }
+            buffer.release();
         }
         if let Some(xwayland_serial) = self.xwayland_serial.get() {
             self.client
@@ -648,6 +676,7 @@ impl WlSurface {
         self.client.remove_obj(self)?;
         self.idle_inhibitors.clear();
         self.constraints.take();
+        self.shortcuts_inhibitors.take();
         Ok(())
     }
 
@@ -663,8 +692,10 @@ impl WlSurface {
     }
 
     fn damage(&self, parser: MsgParser<'_, '_>) -> Result<(), WlSurfaceError> {
-        let _req: Damage = self.parse(parser)?;
-        self.pending.damage.set(true);
+        let req: Damage = self.parse(parser)?;
+        if let Some(rect) = Rect::new_sized(req.x, req.y, req.width, req.height) {
+            self.pending.damage.borrow_mut().push(rect);
+        }
         Ok(())
     }
 
@@ -704,6 +735,8 @@ impl WlSurface {
         if ext.clone().pre_commit(ctx)? == CommitAction::AbortCommit {
             return Ok(());
         }
+        let old_size = self.buffer_abs_pos.get().size();
+        let old_extents = self.extents.get();
         {
             let children = self.children.borrow();
             if let Some(children) = children.deref() {
@@ -746,11 +779,10 @@ impl WlSurface {
             buffer_changed = true;
             if let Some(buffer) = self.buffer.take() {
                 old_raw_size = Some(buffer.rect);
-                if !buffer.destroyed() {
-                    buffer.send_release();
-                }
+                buffer.release();
             }
             if let Some((dx, dy, buffer)) = buffer_change {
+                buffer.cancel_release();
                 let _ = buffer.update_texture();
                 self.buffer.set(Some(buffer));
                 self.buf_x.fetch_add(dx);
@@ -879,7 +911,9 @@ impl WlSurface {
             }
         }
         if let Some(tearing) = self.pending.tearing.take() {
-            self.tearing.set(tearing);
+            if self.tearing.replace(tearing) != tearing {
+                self.output.get().update_tearing();
+            }
         }
         if let Some(xwayland_serial) = self.pending.xwayland_serial.take() {
             self.xwayland_serial.set(Some(xwayland_serial));
@@ -902,10 +936,108 @@ impl WlSurface {
             }
         }
         ext.post_commit();
-        self.client.state.damage();
+        {
+            let mut damage_full = self.damage_full.take()
+                || transform_changed
+                || old_size != self.buffer_abs_pos.get().size()
+                || old_extents != self.extents.get();
+            let mut damage = self.pending.damage.take();
+            let buffer_damage = self.pending.buffer_damage.take();
+            if buffer_changed && damage.is_empty() && buffer_damage.is_empty() {
+                damage_full = true;
+            }
+            if !damage_full && !buffer_damage.is_empty() {
+                match self.buffer_damage_to_surface(&buffer_damage) {
+                    Some(rects) => damage.extend(rects),
+                    _ => damage_full = true,
+                }
+            }
+            if self.visible.get() {
+                self.apply_damage(old_extents, damage_full, &damage);
+            }
+        }
+        for (_, seat) in &self.dnd_icons {
+            seat.damage_cursor();
+        }
         Ok(())
     }
 
+    /// Returns `None` if a viewport or buffer transform is in effect.
+    fn buffer_damage_to_surface(&self, damage: &[Rect]) -> Option<Vec<Rect>> {
+        if self.buffer_transform.get() != Transform::Normal
+            || self.src_rect.get().is_some()
+            || self.dst_size.get().is_some()
+        {
+            return None;
+        }
+        let scale = self.buffer_scale.get();
+        let rects = damage
+            .iter()
+            .map(|rect| {
+                Rect::new(
+                    rect.x1() / scale,
+                    rect.y1() / scale,
+                    (rect.x2() + scale - 1) / scale,
+                    (rect.y2() + scale - 1) / scale,
+                )
+                .unwrap()
+            })
+            .collect();
+        Some(rects)
+    }
+
+    /// Returns the position of the surface in compositor coordinates.
+    fn damage_origin(&self) -> (i32, i32) {
+        match self.ext.get().into_subsurface() {
+            Some(ss) => {
+                let (x, y) = ss.parent.damage_origin();
+                let pos = ss.position.get();
+                (x + pos.x1(), y + pos.y1())
+            }
+            _ => self.buffer_abs_pos.get().position(),
+        }
+    }
+
+    fn apply_damage(&self, old_extents: Rect, full: bool, damage: &[Rect]) {
+        let state = &self.client.state;
+        let (x, y) = self.damage_origin();
+        let mut damaged = false;
+        if full {
+            for extents in [old_extents, self.extents.get()] {
+                if !extents.is_empty() {
+                    state.damage_rect(extents.move_(x, y));
+                    damaged = true;
+                }
+            }
+        } else {
+            let bounds = self.buffer_abs_pos.get().at_point(0, 0);
+            for rect in damage {
+                let rect = rect.intersect(bounds);
+                if !rect.is_empty() {
+                    state.damage_rect(rect.move_(x, y));
+                    damaged = true;
+                }
+            }
+        }
+        if !damaged
+            && (!self.frame_requests.borrow().is_empty()
+                || !self.presentation_feedback.borrow().is_empty())
+        {
+            // Schedule a frame anyway so that the client receives its frame callbacks.
+            self.output.get().global.connector.connector.damage();
+        }
+    }
+
+    /// Damages the current extents of the surface, e.g. because it is about to be unmapped.
+    pub fn damage_extents(&self) {
+        if self.visible.get() {
+            let (x, y) = self.damage_origin();
+            self.client
+                .state
+                .damage_rect(self.extents.get().move_(x, y));
+        }
+    }
+
     fn commit(self: &Rc<Self>, parser: MsgParser<'_, '_>) -> Result<(), WlSurfaceError> {
         let _req: Commit = self.parse(parser)?;
         self.do_commit(CommitContext::RootCommit)?;
@@ -940,8 +1072,10 @@ impl WlSurface {
     }
 
     fn damage_buffer(&self, parser: MsgParser<'_, '_>) -> Result<(), WlSurfaceError> {
-        let _req: DamageBuffer = self.parse(parser)?;
-        self.pending.damage.set(true);
+        let req: DamageBuffer = self.parse(parser)?;
+        if let Some(rect) = Rect::new_sized(req.x, req.y, req.width, req.height) {
+            self.pending.buffer_damage.borrow_mut().push(rect);
+        }
         Ok(())
     }
 
@@ -1027,6 +1161,9 @@ impl WlSurface {
         for (_, constraint) in &self.constraints {
             constraint.deactivate();
         }
+        for (_, inhibitor) in &self.shortcuts_inhibitors {
+            inhibitor.deactivate();
+        }
         for (_, inhibitor) in self.idle_inhibitors.lock().drain() {
             inhibitor.deactivate();
         }
@@ -1092,6 +1229,7 @@ impl Object for WlSurface {
         self.fractional_scale.take();
         self.tearing_control.take();
         self.constraints.clear();
+        self.shortcuts_inhibitors.clear();
     }
 }
 
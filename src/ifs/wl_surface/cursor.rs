@@ -60,6 +60,7 @@ impl CursorSurface {
         if self.seat.hardware_cursor() {
             self.seat.update_hardware_cursor();
         }
+        self.seat.damage_cursor();
     }
 }
 
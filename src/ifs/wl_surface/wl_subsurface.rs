@@ -139,6 +139,7 @@ impl WlSubsurface {
 
     fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), WlSubsurfaceError> {
         let _req: Destroy = self.surface.client.parse(self, parser)?;
+        self.surface.damage_extents();
         self.surface.unset_ext();
         *self.pending.node.borrow_mut() = None;
         *self.node.borrow_mut() = None;
@@ -280,11 +281,13 @@ impl SurfaceExt for WlSubsurface {
         if let Some(v) = self.pending.node.take() {
             v.pending.set(false);
             self.node.borrow_mut().replace(v);
+            self.parent.damage_full.set(true);
         }
         if let Some((x, y)) = self.pending.position.take() {
             self.position
                 .set(self.surface.buffer_abs_pos.get().at_point(x, y));
             self.parent.need_extents_update.set(true);
+            self.parent.damage_full.set(true);
         }
     }
 
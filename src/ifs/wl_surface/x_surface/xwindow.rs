@@ -212,6 +212,9 @@ impl Xwindow {
             Some(surface.client.clone()),
         );
         tld.pos.set(surface.extents.get());
+        if let Some(class) = data.info.class.borrow().as_ref() {
+            *tld.app_id.borrow_mut() = class.to_string();
+        }
         let slf = Rc::new(Self {
             id: data.state.node_ids.next(),
             seat_state: Default::default(),
@@ -270,22 +273,29 @@ impl Xwindow {
                     Some(self.data.state.root.stacked.add_last(self.clone()));
                 self.data.state.tree_changed();
             }
-            Change::Map if self.data.info.wants_floating.get() => {
-                let ws = self.data.state.float_map_ws();
-                let ext = self.data.info.pending_extents.get();
+            Change::Map => {
+                let rules = self.toplevel_data.run_new_window_hook(self.clone());
+                let float_size = match self.data.info.wants_floating.get() {
+                    true => {
+                        let ext = self.data.info.pending_extents.get();
+                        Some((ext.width(), ext.height()))
+                    }
+                    false => None,
+                };
                 self.data
                     .state
-                    .map_floating(self.clone(), ext.width(), ext.height(), &ws);
-                self.data.title_changed();
-            }
-            Change::Map => {
-                self.data.state.map_tiled(self.clone());
+                    .map_new_toplevel(self.clone(), rules, float_size, None);
                 self.data.title_changed();
             }
         }
         match map_change {
             Change::Unmap => self.tl_set_visible(false),
-            Change::Map => self.tl_set_visible(true),
+            Change::Map => {
+                self.tl_set_visible(true);
+                if !self.data.info.override_redirect.get() {
+                    self.toplevel_data.publish(self.clone());
+                }
+            }
             Change::None => {}
         }
         self.data.state.tree_changed();
@@ -376,6 +386,14 @@ impl ToplevelNode for Xwindow {
             .push(XWaylandEvent::Activate(self.data.clone()));
     }
 
+    fn tl_set_minimized(&self, minimized: bool) {
+        self.data
+            .state
+            .xwayland
+            .queue
+            .push(XWaylandEvent::SetMinimized(self.data.clone(), minimized));
+    }
+
     fn tl_focus_child(&self, _seat: SeatId) -> Option<Rc<dyn Node>> {
         Some(self.x.surface.clone())
     }
@@ -384,6 +402,10 @@ impl ToplevelNode for Xwindow {
         self.x.surface.set_output(&ws.output.get());
     }
 
+    fn tl_xwindow_info(&self) -> Option<&XwindowInfo> {
+        Some(&self.data.info)
+    }
+
     fn tl_change_extents(self: Rc<Self>, rect: &Rect) {
         // log::info!("xwin {} change_extents {:?}", self.data.window_id, rect);
         let old = self.data.info.extents.replace(*rect);
@@ -423,6 +445,10 @@ impl ToplevelNode for Xwindow {
         self.display_link.borrow_mut().take();
         self.x.surface.destroy_node();
     }
+
+    fn tl_scanout_surface(&self) -> Option<Rc<WlSurface>> {
+        Some(self.x.surface.clone())
+    }
 }
 
 impl StackedNode for Xwindow {
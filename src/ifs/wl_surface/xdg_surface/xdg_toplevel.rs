@@ -7,7 +7,10 @@ use {
         fixed::Fixed,
         ifs::{
             wl_seat::{NodeSeatState, SeatId, WlSeatGlobal},
-            wl_surface::xdg_surface::{XdgSurface, XdgSurfaceError, XdgSurfaceExt},
+            wl_surface::{
+                xdg_surface::{XdgSurface, XdgSurfaceError, XdgSurfaceExt},
+                WlSurface,
+            },
         },
         leaks::Tracker,
         object::Object,
@@ -16,7 +19,7 @@ use {
         state::State,
         tree::{
             Direction, FindTreeResult, FoundNode, Node, NodeId, NodeVisitor, ToplevelData,
-            ToplevelNode, ToplevelNodeId, WorkspaceNode,
+            ToplevelNode, ToplevelNodeId, WindowRules, WorkspaceNode,
         },
         utils::{
             buffd::{MsgParser, MsgParserError},
@@ -66,7 +69,6 @@ const CAP_WINDOW_MENU: u32 = 1;
 #[allow(dead_code)]
 const CAP_MAXIMIZE: u32 = 2;
 const CAP_FULLSCREEN: u32 = 3;
-#[allow(dead_code)]
 const CAP_MINIMIZE: u32 = 4;
 
 pub const WM_CAPABILITIES_SINCE: u32 = 5;
@@ -177,7 +179,7 @@ impl XdgToplevel {
     pub fn send_wm_capabilities(&self) {
         self.xdg.surface.client.event(WmCapabilities {
             self_id: self.id,
-            capabilities: &[CAP_FULLSCREEN],
+            capabilities: &[CAP_FULLSCREEN, CAP_MINIMIZE],
         })
     }
 
@@ -229,6 +231,7 @@ impl XdgToplevel {
     fn set_app_id(&self, parser: MsgParser<'_, '_>) -> Result<(), XdgToplevelError> {
         let req: SetAppId = self.xdg.surface.client.parse(self, parser)?;
         self.bugs.set(bugs::get(req.app_id));
+        self.toplevel_data.set_app_id(req.app_id);
         Ok(())
     }
 
@@ -336,26 +339,19 @@ impl XdgToplevel {
         Ok(())
     }
 
-    fn set_minimized(&self, parser: MsgParser<'_, '_>) -> Result<(), XdgToplevelError> {
-        let _req: SetMinimized = self.xdg.surface.client.parse(self, parser)?;
+    fn set_minimized(self: &Rc<Self>, parser: MsgParser<'_, '_>) -> Result<(), XdgToplevelError> {
+        let _req: SetMinimized = self.xdg.surface.client.parse(self.deref(), parser)?;
+        if self.toplevel_data.parent.get().is_some() {
+            self.state.hide_toplevel(self.clone());
+        }
         Ok(())
     }
 
-    fn map_floating(self: &Rc<Self>, workspace: &Rc<WorkspaceNode>) {
-        let (width, height) = self.toplevel_data.float_size(workspace);
+    fn map_child(self: &Rc<Self>, parent: &XdgToplevel, rules: WindowRules) {
+        let ws = parent.xdg.workspace.get();
+        let float_size = ws.as_ref().map(|ws| self.toplevel_data.float_size(ws));
         self.state
-            .map_floating(self.clone(), width, height, workspace);
-    }
-
-    fn map_child(self: &Rc<Self>, parent: &XdgToplevel) {
-        match parent.xdg.workspace.get() {
-            Some(w) => self.map_floating(&w),
-            _ => self.map_tiled(),
-        }
-    }
-
-    fn map_tiled(self: &Rc<Self>) {
-        self.state.map_tiled(self.clone());
+            .map_new_toplevel(self.clone(), rules, float_size, ws);
     }
 }
 
@@ -511,6 +507,10 @@ impl ToplevelNode for XdgToplevel {
         self.xdg.destroy_node();
     }
 
+    fn tl_scanout_surface(&self) -> Option<Rc<WlSurface>> {
+        Some(self.xdg.surface.clone())
+    }
+
     // fn move_to_workspace(self: &Rc<Self>, workspace: &Rc<WorkspaceNode>) {
     //     let parent = match self.parent_node.get() {
     //         Some(p) => p,
@@ -556,16 +556,18 @@ impl XdgSurfaceExt for XdgToplevel {
                 self.state.tree_changed();
             }
         } else if surface.buffer.get().is_some() {
+            let rules = self.toplevel_data.run_new_window_hook(self.clone());
             if let Some(parent) = self.parent.get() {
-                self.map_child(&parent);
+                self.map_child(&parent, rules);
             } else {
-                self.map_tiled();
+                self.state.map_new_toplevel(self.clone(), rules, None, None);
             }
             self.extents_changed();
             if let Some(workspace) = self.xdg.workspace.get() {
                 let output = workspace.output.get();
                 surface.set_output(&output);
             }
+            self.toplevel_data.publish(self.clone());
             // {
             //     let seats = surface.client.state.globals.lock_seats();
             //     for seat in seats.values() {
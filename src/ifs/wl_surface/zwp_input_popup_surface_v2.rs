@@ -0,0 +1,165 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::{
+            wl_seat::text_input::zwp_input_method_v2::ZwpInputMethodV2,
+            wl_surface::{SurfaceExt, SurfaceRole, WlSurface, WlSurfaceError},
+        },
+        leaks::Tracker,
+        object::Object,
+        rect::Rect,
+        tree::Node,
+        utils::buffd::{MsgParser, MsgParserError},
+        wire::{zwp_input_popup_surface_v2::*, WlSurfaceId, ZwpInputPopupSurfaceV2Id},
+    },
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+pub struct ZwpInputPopupSurfaceV2 {
+    pub id: ZwpInputPopupSurfaceV2Id,
+    pub client: Rc<Client>,
+    pub input_method: Rc<ZwpInputMethodV2>,
+    pub surface: Rc<WlSurface>,
+    pub tracker: Tracker<Self>,
+    text_input_rectangle: Cell<Option<Rect>>,
+}
+
+impl ZwpInputPopupSurfaceV2 {
+    pub fn new(
+        id: ZwpInputPopupSurfaceV2Id,
+        input_method: &Rc<ZwpInputMethodV2>,
+        surface: &Rc<WlSurface>,
+    ) -> Self {
+        Self {
+            id,
+            client: input_method.client.clone(),
+            input_method: input_method.clone(),
+            surface: surface.clone(),
+            tracker: Default::default(),
+            text_input_rectangle: Cell::new(None),
+        }
+    }
+
+    pub fn install(self: &Rc<Self>) -> Result<(), ZwpInputPopupSurfaceV2Error> {
+        self.surface.set_role(SurfaceRole::InputPopup)?;
+        if self.surface.ext.get().is_some() {
+            return Err(ZwpInputPopupSurfaceV2Error::AlreadyAttached(
+                self.surface.id,
+            ));
+        }
+        self.surface.ext.set(self.clone());
+        self.update_position();
+        Ok(())
+    }
+
+    pub fn update_position(&self) {
+        let target = match self.input_method.inert.get() {
+            true => None,
+            false => self.input_method.seat.text_input_active().and_then(|ti| {
+                let surface = ti.surface.get()?;
+                let rect = ti.current.borrow().cursor_rectangle;
+                Some((surface, rect))
+            }),
+        };
+        let (ti_surface, rect) = match target {
+            Some(t) if self.surface.buffer.get().is_some() => t,
+            _ => {
+                self.hide();
+                return;
+            }
+        };
+        let ti_pos = ti_surface.buffer_abs_pos.get();
+        let rect = match rect {
+            Some(r) => r.move_(ti_pos.x1(), ti_pos.y1()),
+            _ => Rect::new_empty(ti_pos.x1(), ti_pos.y2()),
+        };
+        let output = ti_surface.output.get();
+        let output_rect = output.global.pos.get();
+        let (width, height) = self.surface.buffer_abs_pos.get().size();
+        let mut x1 = rect.x1();
+        let mut y1 = rect.y2();
+        if y1 + height > output_rect.y2() && rect.y1() - height >= output_rect.y1() {
+            y1 = rect.y1() - height;
+        }
+        if x1 + width > output_rect.x2() {
+            x1 = (output_rect.x2() - width).max(output_rect.x1());
+        }
+        let old_pos = self.surface.buffer_abs_pos.get();
+        if !self.surface.visible.get() || (old_pos.x1(), old_pos.y1()) != (x1, y1) {
+            self.surface.damage_extents();
+            self.surface.set_absolute_position(x1, y1);
+            self.surface.set_output(&output);
+            self.surface.set_visible(true);
+            self.surface.damage_extents();
+        }
+        let relative = rect.move_(-x1, -y1);
+        if self.text_input_rectangle.replace(Some(relative)) != Some(relative) {
+            self.client.event(TextInputRectangle {
+                self_id: self.id,
+                x: relative.x1(),
+                y: relative.y1(),
+                width: relative.width(),
+                height: relative.height(),
+            });
+        }
+    }
+
+    fn hide(&self) {
+        if self.surface.node_visible() {
+            self.surface.damage_extents();
+            self.surface.set_visible(false);
+        }
+    }
+
+    fn detach(&self) {
+        self.hide();
+        self.input_method.popups.remove(&self.id);
+    }
+
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpInputPopupSurfaceV2Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.detach();
+        self.surface.unset_ext();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+impl SurfaceExt for ZwpInputPopupSurfaceV2 {
+    fn post_commit(self: Rc<Self>) {
+        self.update_position();
+    }
+}
+
+object_base! {
+    ZwpInputPopupSurfaceV2;
+
+    DESTROY => destroy,
+}
+
+impl Object for ZwpInputPopupSurfaceV2 {
+    fn num_requests(&self) -> u32 {
+        DESTROY + 1
+    }
+
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
+
+simple_add_obj!(ZwpInputPopupSurfaceV2);
+
+#[derive(Debug, Error)]
+pub enum ZwpInputPopupSurfaceV2Error {
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error(transparent)]
+    WlSurfaceError(#[from] WlSurfaceError),
+    #[error("Surface {0} cannot be turned into an input popup surface because it already has an attached role object")]
+    AlreadyAttached(WlSurfaceId),
+}
+efrom!(ZwpInputPopupSurfaceV2Error, MsgParserError);
+efrom!(ZwpInputPopupSurfaceV2Error, ClientError);
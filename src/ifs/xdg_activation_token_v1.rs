@@ -0,0 +1,129 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::wl_seat::WlSeatGlobal,
+        leaks::Tracker,
+        object::Object,
+        utils::{
+            buffd::{MsgParser, MsgParserError},
+            clonecell::CloneCell,
+        },
+        wire::{xdg_activation_token_v1::*, XdgActivationTokenV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+const ALREADY_USED: u32 = 0;
+
+/// The environment variable that passes an activation token to spawned programs.
+pub const XDG_ACTIVATION_TOKEN: &str = "XDG_ACTIVATION_TOKEN";
+
+/// Returns a new random activation token.
+pub fn random_activation_token() -> String {
+    format!("{:032x}", rand::random::<u128>())
+}
+
+pub struct XdgActivationTokenV1 {
+    pub id: XdgActivationTokenV1Id,
+    pub client: Rc<Client>,
+    pub serial: Cell<Option<u32>>,
+    pub seat: CloneCell<Option<Rc<WlSeatGlobal>>>,
+    pub committed: Cell<bool>,
+    pub tracker: Tracker<Self>,
+}
+
+impl XdgActivationTokenV1 {
+    fn set_serial(&self, parser: MsgParser<'_, '_>) -> Result<(), XdgActivationTokenV1Error> {
+        let req: SetSerial = self.client.parse(self, parser)?;
+        let seat = self.client.lookup(req.seat)?;
+        self.serial.set(Some(req.serial));
+        self.seat.set(Some(seat.global.clone()));
+        Ok(())
+    }
+
+    fn set_app_id(&self, parser: MsgParser<'_, '_>) -> Result<(), XdgActivationTokenV1Error> {
+        let _req: SetAppId = self.client.parse(self, parser)?;
+        Ok(())
+    }
+
+    fn set_surface(&self, parser: MsgParser<'_, '_>) -> Result<(), XdgActivationTokenV1Error> {
+        let req: SetSurface = self.client.parse(self, parser)?;
+        self.client.lookup(req.surface)?;
+        Ok(())
+    }
+
+    fn commit(&self, parser: MsgParser<'_, '_>) -> Result<(), XdgActivationTokenV1Error> {
+        let _req: Commit = self.client.parse(self, parser)?;
+        if self.committed.replace(true) {
+            self.client.protocol_error(
+                self,
+                ALREADY_USED,
+                "The activation token has already been committed",
+            );
+            return Err(XdgActivationTokenV1Error::AlreadyUsed);
+        }
+        // Only clients that recently received input may pass the focus on. Other
+        // clients still receive a token but activating a window with it only marks
+        // the window as urgent.
+        let mut seat = None;
+        if let (Some(serial), Some(s)) = (self.serial.get(), self.seat.take()) {
+            let focused = s.keyboard_node_client() == Some(self.client.id);
+            if focused && self.client.valid_serial(serial) {
+                seat = Some(s);
+            }
+        }
+        let token = match seat {
+            Some(seat) => self
+                .client
+                .state
+                .create_activation_token(Some(seat), Some(self.client.id)),
+            _ => random_activation_token(),
+        };
+        self.client.event(Done {
+            self_id: self.id,
+            token: &token,
+        });
+        Ok(())
+    }
+
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), XdgActivationTokenV1Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    XdgActivationTokenV1;
+
+    SET_SERIAL => set_serial,
+    SET_APP_ID => set_app_id,
+    SET_SURFACE => set_surface,
+    COMMIT => commit,
+    DESTROY => destroy,
+}
+
+impl Object for XdgActivationTokenV1 {
+    fn num_requests(&self) -> u32 {
+        DESTROY + 1
+    }
+
+    fn break_loops(&self) {
+        self.seat.take();
+    }
+}
+
+simple_add_obj!(XdgActivationTokenV1);
+
+#[derive(Debug, Error)]
+pub enum XdgActivationTokenV1Error {
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("The activation token has already been committed")]
+    AlreadyUsed,
+}
+efrom!(XdgActivationTokenV1Error, MsgParserError);
+efrom!(XdgActivationTokenV1Error, ClientError);
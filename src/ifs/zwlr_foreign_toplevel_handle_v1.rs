@@ -0,0 +1,267 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::wl_output::WlOutputGlobal,
+        leaks::Tracker,
+        object::Object,
+        tree::{OutputNode, ToplevelData, ToplevelNode},
+        utils::{
+            buffd::{MsgParser, MsgParserError},
+            clonecell::CloneCell,
+        },
+        wire::{zwlr_foreign_toplevel_handle_v1::*, ZwlrForeignToplevelHandleV1Id},
+    },
+    std::rc::{Rc, Weak},
+    thiserror::Error,
+};
+
+#[allow(dead_code)]
+const STATE_MAXIMIZED: u32 = 0;
+const STATE_MINIMIZED: u32 = 1;
+const STATE_ACTIVATED: u32 = 2;
+const STATE_FULLSCREEN: u32 = 3;
+
+pub struct ZwlrForeignToplevelHandleV1 {
+    pub id: ZwlrForeignToplevelHandleV1Id,
+    pub client: Rc<Client>,
+    pub version: u32,
+    pub toplevel: Weak<dyn ToplevelNode>,
+    pub output: CloneCell<Option<Rc<WlOutputGlobal>>>,
+    pub tracker: Tracker<Self>,
+}
+
+impl ZwlrForeignToplevelHandleV1 {
+    pub fn send_title(&self, title: &str) {
+        self.client.event(Title {
+            self_id: self.id,
+            title,
+        });
+    }
+
+    pub fn send_app_id(&self, app_id: &str) {
+        self.client.event(AppId {
+            self_id: self.id,
+            app_id,
+        });
+    }
+
+    pub fn send_state(&self, data: &ToplevelData) {
+        let mut state = vec![];
+        if data.active_surfaces.get() > 0 {
+            state.push(STATE_ACTIVATED);
+        }
+        if data.is_fullscreen.get() {
+            state.push(STATE_FULLSCREEN);
+        }
+        if data.is_hidden() {
+            state.push(STATE_MINIMIZED);
+        }
+        self.client.event(State {
+            self_id: self.id,
+            state: &state,
+        });
+    }
+
+    pub fn send_done(&self) {
+        self.client.event(Done { self_id: self.id });
+    }
+
+    pub fn send_closed(&self) {
+        self.output.take();
+        self.client.event(Closed { self_id: self.id });
+    }
+
+    fn send_output(&self, output: &WlOutputGlobal, enter: bool) {
+        let bindings = output.bindings.borrow_mut();
+        if let Some(bindings) = bindings.get(&self.client.id) {
+            for binding in bindings.values() {
+                if enter {
+                    self.client.event(OutputEnter {
+                        self_id: self.id,
+                        output: binding.id,
+                    });
+                } else {
+                    self.client.event(OutputLeave {
+                        self_id: self.id,
+                        output: binding.id,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Moves the handle to the output. Returns whether the output changed.
+    pub fn update_output(&self, output: &Rc<OutputNode>) -> bool {
+        if let Some(old) = self.output.get() {
+            if old.name == output.global.name {
+                return false;
+            }
+            self.send_output(&old, false);
+        }
+        self.send_output(&output.global, true);
+        self.output.set(Some(output.global.clone()));
+        true
+    }
+
+    fn detach(&self) {
+        self.output.take();
+        if let Some(tl) = self.toplevel.upgrade() {
+            tl.tl_data()
+                .foreign_handles
+                .remove(&(self.client.id, self.id));
+        }
+    }
+
+    fn set_maximized(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), ZwlrForeignToplevelHandleV1Error> {
+        let _req: SetMaximized = self.client.parse(self, parser)?;
+        Ok(())
+    }
+
+    fn unset_maximized(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), ZwlrForeignToplevelHandleV1Error> {
+        let _req: UnsetMaximized = self.client.parse(self, parser)?;
+        Ok(())
+    }
+
+    fn set_minimized(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), ZwlrForeignToplevelHandleV1Error> {
+        let _req: SetMinimized = self.client.parse(self, parser)?;
+        if let Some(tl) = self.toplevel.upgrade() {
+            self.client.state.hide_toplevel(tl);
+        }
+        Ok(())
+    }
+
+    fn unset_minimized(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), ZwlrForeignToplevelHandleV1Error> {
+        let _req: UnsetMinimized = self.client.parse(self, parser)?;
+        if let Some(tl) = self.toplevel.upgrade() {
+            if tl.tl_data().is_hidden() {
+                if let Some(seat) = self.client.state.seat_queue.last() {
+                    self.client.state.show_hidden_toplevel(&seat, tl);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn activate(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwlrForeignToplevelHandleV1Error> {
+        let req: Activate = self.client.parse(self, parser)?;
+        let seat = self.client.lookup(req.seat)?.global.clone();
+        if let Some(tl) = self.toplevel.upgrade() {
+            if let Some(ws) = tl.tl_data().workspace.get() {
+                if !ws.visible.get() {
+                    self.client.state.show_workspace(&seat, &ws.name);
+                }
+            }
+            seat.focus_toplevel(tl);
+        }
+        Ok(())
+    }
+
+    fn close(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwlrForeignToplevelHandleV1Error> {
+        let _req: Close = self.client.parse(self, parser)?;
+        if let Some(tl) = self.toplevel.upgrade() {
+            tl.tl_close();
+        }
+        Ok(())
+    }
+
+    fn set_rectangle(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), ZwlrForeignToplevelHandleV1Error> {
+        let _req: SetRectangle = self.client.parse(self, parser)?;
+        Ok(())
+    }
+
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwlrForeignToplevelHandleV1Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.detach();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn set_fullscreen(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), ZwlrForeignToplevelHandleV1Error> {
+        let req: SetFullscreen = self.client.parse(self, parser)?;
+        let output = if req.output.is_some() {
+            self.client.lookup(req.output)?.global.node.get()
+        } else {
+            None
+        };
+        if let Some(tl) = self.toplevel.upgrade() {
+            match output {
+                Some(output) => {
+                    let data = tl.tl_data();
+                    data.set_fullscreen(&data.state, tl.clone(), &output);
+                }
+                _ => tl.tl_set_fullscreen(true),
+            }
+        }
+        Ok(())
+    }
+
+    fn unset_fullscreen(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), ZwlrForeignToplevelHandleV1Error> {
+        let _req: UnsetFullscreen = self.client.parse(self, parser)?;
+        if let Some(tl) = self.toplevel.upgrade() {
+            tl.tl_set_fullscreen(false);
+        }
+        Ok(())
+    }
+}
+
+object_base! {
+    ZwlrForeignToplevelHandleV1;
+
+    SET_MAXIMIZED => set_maximized,
+    UNSET_MAXIMIZED => unset_maximized,
+    SET_MINIMIZED => set_minimized,
+    UNSET_MINIMIZED => unset_minimized,
+    ACTIVATE => activate,
+    CLOSE => close,
+    SET_RECTANGLE => set_rectangle,
+    DESTROY => destroy,
+    SET_FULLSCREEN => set_fullscreen,
+    UNSET_FULLSCREEN => unset_fullscreen,
+}
+
+impl Object for ZwlrForeignToplevelHandleV1 {
+    fn num_requests(&self) -> u32 {
+        if self.version < 2 {
+            DESTROY + 1
+        } else {
+            UNSET_FULLSCREEN + 1
+        }
+    }
+
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
+
+simple_add_obj!(ZwlrForeignToplevelHandleV1);
+
+#[derive(Debug, Error)]
+pub enum ZwlrForeignToplevelHandleV1Error {
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwlrForeignToplevelHandleV1Error, MsgParserError);
+efrom!(ZwlrForeignToplevelHandleV1Error, ClientError);
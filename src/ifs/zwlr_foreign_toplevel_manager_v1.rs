@@ -0,0 +1,175 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        globals::{Global, GlobalName},
+        ifs::zwlr_foreign_toplevel_handle_v1::ZwlrForeignToplevelHandleV1,
+        leaks::Tracker,
+        object::Object,
+        tree::ToplevelNode,
+        utils::buffd::{MsgParser, MsgParserError},
+        wire::{zwlr_foreign_toplevel_manager_v1::*, ZwlrForeignToplevelManagerV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+pub struct ZwlrForeignToplevelManagerV1Global {
+    pub name: GlobalName,
+}
+
+impl ZwlrForeignToplevelManagerV1Global {
+    pub fn new(name: GlobalName) -> Self {
+        Self { name }
+    }
+
+    fn bind_(
+        self: Rc<Self>,
+        id: ZwlrForeignToplevelManagerV1Id,
+        client: &Rc<Client>,
+        version: u32,
+    ) -> Result<(), ZwlrForeignToplevelManagerV1Error> {
+        let obj = Rc::new(ZwlrForeignToplevelManagerV1 {
+            id,
+            client: client.clone(),
+            version,
+            stopped: Cell::new(false),
+            tracker: Default::default(),
+        });
+        track!(client, obj);
+        client.add_client_obj(&obj)?;
+        client
+            .state
+            .foreign_toplevel_managers
+            .set((client.id, id), obj.clone());
+        let toplevels: Vec<_> = client
+            .state
+            .toplevels
+            .lock()
+            .values()
+            .filter_map(|tl| tl.upgrade())
+            .collect();
+        for tl in toplevels {
+            obj.announce(&tl);
+        }
+        Ok(())
+    }
+}
+
+global_base!(
+    ZwlrForeignToplevelManagerV1Global,
+    ZwlrForeignToplevelManagerV1,
+    ZwlrForeignToplevelManagerV1Error
+);
+
+impl Global for ZwlrForeignToplevelManagerV1Global {
+    fn singleton(&self) -> bool {
+        true
+    }
+
+    fn version(&self) -> u32 {
+        2
+    }
+
+    fn secure(&self) -> bool {
+        true
+    }
+}
+
+simple_add_global!(ZwlrForeignToplevelManagerV1Global);
+
+pub struct ZwlrForeignToplevelManagerV1 {
+    pub id: ZwlrForeignToplevelManagerV1Id,
+    pub client: Rc<Client>,
+    pub version: u32,
+    pub stopped: Cell<bool>,
+    pub tracker: Tracker<Self>,
+}
+
+impl ZwlrForeignToplevelManagerV1 {
+    /// Creates a handle for the toplevel and sends its initial state.
+    pub fn announce(&self, tl: &Rc<dyn ToplevelNode>) {
+        if let Err(e) = self.announce_(tl) {
+            self.client.error(e);
+        }
+    }
+
+    fn announce_(&self, tl: &Rc<dyn ToplevelNode>) -> Result<(), ClientError> {
+        if self.stopped.get() {
+            return Ok(());
+        }
+        let data = tl.tl_data();
+        let handle = Rc::new(ZwlrForeignToplevelHandleV1 {
+            id: self.client.new_id()?,
+            client: self.client.clone(),
+            version: self.version,
+            toplevel: Rc::downgrade(tl),
+            output: Default::default(),
+            tracker: Default::default(),
+        });
+        track!(self.client, handle);
+        self.client.add_server_obj(&handle);
+        self.client.event(Toplevel {
+            self_id: self.id,
+            toplevel: handle.id,
+        });
+        handle.send_title(&data.title.borrow());
+        handle.send_app_id(&data.app_id.borrow());
+        if let Some(ws) = data.workspace.get() {
+            handle.update_output(&ws.output.get());
+        }
+        handle.send_state(data);
+        handle.send_done();
+        data.foreign_handles
+            .set((self.client.id, handle.id), handle.clone());
+        Ok(())
+    }
+
+    fn send_finished(&self) {
+        self.client.event(Finished { self_id: self.id });
+    }
+
+    fn detach(&self) {
+        self.client
+            .state
+            .foreign_toplevel_managers
+            .remove(&(self.client.id, self.id));
+    }
+
+    fn stop(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwlrForeignToplevelManagerV1Error> {
+        let _req: Stop = self.client.parse(self, parser)?;
+        if !self.stopped.replace(true) {
+            self.detach();
+            self.send_finished();
+            self.client.remove_obj(self)?;
+        }
+        Ok(())
+    }
+}
+
+object_base! {
+    ZwlrForeignToplevelManagerV1;
+
+    STOP => stop,
+}
+
+impl Object for ZwlrForeignToplevelManagerV1 {
+    fn num_requests(&self) -> u32 {
+        STOP + 1
+    }
+
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
+
+simple_add_obj!(ZwlrForeignToplevelManagerV1);
+
+#[derive(Debug, Error)]
+pub enum ZwlrForeignToplevelManagerV1Error {
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwlrForeignToplevelManagerV1Error, MsgParserError);
+efrom!(ZwlrForeignToplevelManagerV1Error, ClientError);
@@ -0,0 +1,131 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        globals::{Global, GlobalName},
+        ifs::zwlr_gamma_control_v1::ZwlrGammaControlV1,
+        leaks::Tracker,
+        object::Object,
+        utils::buffd::{MsgParser, MsgParserError},
+        wire::{zwlr_gamma_control_manager_v1::*, ZwlrGammaControlManagerV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+pub struct ZwlrGammaControlManagerV1Global {
+    pub name: GlobalName,
+}
+
+impl ZwlrGammaControlManagerV1Global {
+    pub fn new(name: GlobalName) -> Self {
+        Self { name }
+    }
+
+    fn bind_(
+        self: Rc<Self>,
+        id: ZwlrGammaControlManagerV1Id,
+        client: &Rc<Client>,
+        _version: u32,
+    ) -> Result<(), ZwlrGammaControlManagerV1Error> {
+        let obj = Rc::new(ZwlrGammaControlManagerV1 {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+        });
+        track!(client, obj);
+        client.add_client_obj(&obj)?;
+        Ok(())
+    }
+}
+
+global_base!(
+    ZwlrGammaControlManagerV1Global,
+    ZwlrGammaControlManagerV1,
+    ZwlrGammaControlManagerV1Error
+);
+
+impl Global for ZwlrGammaControlManagerV1Global {
+    fn singleton(&self) -> bool {
+        true
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn secure(&self) -> bool {
+        true
+    }
+}
+
+simple_add_global!(ZwlrGammaControlManagerV1Global);
+
+pub struct ZwlrGammaControlManagerV1 {
+    pub id: ZwlrGammaControlManagerV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+}
+
+impl ZwlrGammaControlManagerV1 {
+    fn get_gamma_control(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), ZwlrGammaControlManagerV1Error> {
+        let req: GetGammaControl = self.client.parse(self, parser)?;
+        let output = self.client.lookup(req.output)?;
+        let connector = output.global.connector.clone();
+        let obj = Rc::new(ZwlrGammaControlV1 {
+            id: req.id,
+            client: self.client.clone(),
+            tracker: Default::default(),
+            connector: connector.clone(),
+            gamma_size: connector.connector.gamma_size(),
+            failed: Cell::new(false),
+            lut: Default::default(),
+        });
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        // Only one client can control the gamma of an output at a time.
+        if obj.gamma_size == 0
+            || output.global.destroyed.get()
+            || connector.gamma_control.get().is_some()
+        {
+            obj.send_failed();
+            return Ok(());
+        }
+        connector.gamma_control.set(Some(obj.clone()));
+        obj.send_gamma_size();
+        Ok(())
+    }
+
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwlrGammaControlManagerV1Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    ZwlrGammaControlManagerV1;
+
+    GET_GAMMA_CONTROL => get_gamma_control,
+    DESTROY => destroy,
+}
+
+impl Object for ZwlrGammaControlManagerV1 {
+    fn num_requests(&self) -> u32 {
+        DESTROY + 1
+    }
+}
+
+simple_add_obj!(ZwlrGammaControlManagerV1);
+
+#[derive(Debug, Error)]
+pub enum ZwlrGammaControlManagerV1Error {
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwlrGammaControlManagerV1Error, MsgParserError);
+efrom!(ZwlrGammaControlManagerV1Error, ClientError);
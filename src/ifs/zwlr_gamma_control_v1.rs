@@ -0,0 +1,140 @@
+use {
+    crate::{
+        backend::GammaLut,
+        client::{Client, ClientError},
+        leaks::Tracker,
+        object::Object,
+        state::ConnectorData,
+        utils::{
+            buffd::{MsgParser, MsgParserError},
+            clonecell::CloneCell,
+            errorfmt::ErrorFmt,
+            nonblock::set_nonblock,
+            oserror::OsError,
+        },
+        wire::{zwlr_gamma_control_v1::*, ZwlrGammaControlV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+    uapi::c,
+};
+
+pub struct ZwlrGammaControlV1 {
+    pub id: ZwlrGammaControlV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub connector: Rc<ConnectorData>,
+    pub gamma_size: u32,
+    pub failed: Cell<bool>,
+    pub lut: CloneCell<Option<Rc<GammaLut>>>,
+}
+
+impl ZwlrGammaControlV1 {
+    pub fn send_gamma_size(&self) {
+        self.client.event(GammaSize {
+            self_id: self.id,
+            size: self.gamma_size,
+        });
+    }
+
+    /// Sends the failed event and makes the object inert.
+    pub fn send_failed(&self) {
+        if !self.failed.replace(true) {
+            self.detach();
+            self.client.event(Failed { self_id: self.id });
+        }
+    }
+
+    fn detach(&self) {
+        if let Some(gc) = self.connector.gamma_control.get() {
+            if gc.id == self.id && gc.client.id == self.client.id {
+                self.connector.gamma_control.take();
+                self.connector.update_gamma();
+            }
+        }
+    }
+
+    fn read_lut(&self, fd: c::c_int) -> Result<GammaLut, ZwlrGammaControlV1Error> {
+        let size = self.gamma_size as usize;
+        let mut buf = vec![0u8; 3 * size * 2];
+        set_nonblock(fd).map_err(ZwlrGammaControlV1Error::Read)?;
+        let mut pos = 0;
+        while pos < buf.len() {
+            match uapi::read(fd, &mut buf[pos..]) {
+                Ok([]) => break,
+                Ok(n) => pos += n.len(),
+                Err(e) => return Err(ZwlrGammaControlV1Error::Read(e.into())),
+            }
+        }
+        if pos != buf.len() {
+            return Err(ZwlrGammaControlV1Error::InvalidSize);
+        }
+        let values: Vec<_> = buf
+            .chunks_exact(2)
+            .map(|c| u16::from_ne_bytes([c[0], c[1]]))
+            .collect();
+        Ok(GammaLut {
+            red: values[..size].to_vec(),
+            green: values[size..2 * size].to_vec(),
+            blue: values[2 * size..].to_vec(),
+        })
+    }
+
+    fn set_gamma(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwlrGammaControlV1Error> {
+        let req: SetGamma = self.client.parse(self, parser)?;
+        if self.failed.get() {
+            return Ok(());
+        }
+        match self.read_lut(req.fd.raw()) {
+            Ok(lut) => {
+                self.lut.set(Some(Rc::new(lut)));
+                self.connector.update_gamma();
+            }
+            Err(e) => {
+                log::warn!("Could not read gamma table: {}", ErrorFmt(e));
+                self.send_failed();
+            }
+        }
+        Ok(())
+    }
+
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwlrGammaControlV1Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.detach();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    ZwlrGammaControlV1;
+
+    SET_GAMMA => set_gamma,
+    DESTROY => destroy,
+}
+
+impl Object for ZwlrGammaControlV1 {
+    fn num_requests(&self) -> u32 {
+        DESTROY + 1
+    }
+
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
+
+simple_add_obj!(ZwlrGammaControlV1);
+
+#[derive(Debug, Error)]
+pub enum ZwlrGammaControlV1Error {
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("Could not read from the file descriptor")]
+    Read(#[source] OsError),
+    #[error("The gamma table has the wrong size")]
+    InvalidSize,
+}
+efrom!(ZwlrGammaControlV1Error, MsgParserError);
+efrom!(ZwlrGammaControlV1Error, ClientError);
@@ -0,0 +1,178 @@
+use {
+    crate::{
+        backend::Mode,
+        client::{Client, ClientError},
+        ifs::zwlr_output_head_v1::ZwlrOutputHeadV1,
+        leaks::Tracker,
+        object::Object,
+        scale::Scale,
+        utils::buffd::{MsgParser, MsgParserError},
+        wire::{zwlr_output_configuration_head_v1::*, ZwlrOutputConfigurationHeadV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+pub const TF_NORMAL: i32 = 0;
+const TF_FLIPPED_270: i32 = 7;
+
+#[derive(Copy, Clone)]
+pub enum RequestedMode {
+    Existing(Mode),
+    /// A mode requested via `set_custom_mode`. A refresh rate of 0 selects the highest
+    /// refresh rate available for the size.
+    Custom(Mode),
+}
+
+pub struct ZwlrOutputConfigurationHeadV1 {
+    pub id: ZwlrOutputConfigurationHeadV1Id,
+    pub client: Rc<Client>,
+    pub head: Rc<ZwlrOutputHeadV1>,
+    pub mode: Cell<Option<RequestedMode>>,
+    pub position: Cell<Option<(i32, i32)>>,
+    pub transform: Cell<Option<i32>>,
+    pub scale: Cell<Option<Scale>>,
+    pub tracker: Tracker<Self>,
+}
+
+impl ZwlrOutputConfigurationHeadV1 {
+    /// Returns the mode that should be used for the head or `None` if the requested
+    /// custom mode is not supported.
+    pub fn resolve_mode(&self) -> Option<Mode> {
+        let mode = match self.mode.get() {
+            None => return Some(self.head.current_state().mode),
+            Some(RequestedMode::Existing(mode)) => return Some(mode),
+            Some(RequestedMode::Custom(mode)) => mode,
+        };
+        self.head
+            .monitor_info
+            .modes
+            .iter()
+            .filter(|m| (m.width, m.height) == (mode.width, mode.height))
+            .filter(|m| {
+                mode.refresh_rate_millihz == 0
+                    || m.refresh_rate_millihz == mode.refresh_rate_millihz
+            })
+            .max_by_key(|m| m.refresh_rate_millihz)
+            .copied()
+    }
+
+    fn set_mode(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), ZwlrOutputConfigurationHeadV1Error> {
+        let req: SetMode = self.client.parse(self, parser)?;
+        if self.mode.get().is_some() {
+            return Err(ZwlrOutputConfigurationHeadV1Error::AlreadySet("mode"));
+        }
+        let mode = match self.head.find_mode(req.mode) {
+            Some(m) => m,
+            _ => return Err(ZwlrOutputConfigurationHeadV1Error::InvalidMode),
+        };
+        self.mode.set(Some(RequestedMode::Existing(mode)));
+        Ok(())
+    }
+
+    fn set_custom_mode(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), ZwlrOutputConfigurationHeadV1Error> {
+        let req: SetCustomMode = self.client.parse(self, parser)?;
+        if self.mode.get().is_some() {
+            return Err(ZwlrOutputConfigurationHeadV1Error::AlreadySet("mode"));
+        }
+        if req.width <= 0 || req.height <= 0 || req.refresh < 0 {
+            return Err(ZwlrOutputConfigurationHeadV1Error::InvalidCustomMode(
+                req.width,
+                req.height,
+                req.refresh,
+            ));
+        }
+        self.mode.set(Some(RequestedMode::Custom(Mode {
+            width: req.width,
+            height: req.height,
+            refresh_rate_millihz: req.refresh as _,
+        })));
+        Ok(())
+    }
+
+    fn set_position(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), ZwlrOutputConfigurationHeadV1Error> {
+        let req: SetPosition = self.client.parse(self, parser)?;
+        if self.position.replace(Some((req.x, req.y))).is_some() {
+            return Err(ZwlrOutputConfigurationHeadV1Error::AlreadySet("position"));
+        }
+        Ok(())
+    }
+
+    fn set_transform(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), ZwlrOutputConfigurationHeadV1Error> {
+        let req: SetTransform = self.client.parse(self, parser)?;
+        if req.transform < TF_NORMAL || req.transform > TF_FLIPPED_270 {
+            return Err(ZwlrOutputConfigurationHeadV1Error::InvalidTransform(
+                req.transform,
+            ));
+        }
+        if self.transform.replace(Some(req.transform)).is_some() {
+            return Err(ZwlrOutputConfigurationHeadV1Error::AlreadySet("transform"));
+        }
+        Ok(())
+    }
+
+    fn set_scale(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), ZwlrOutputConfigurationHeadV1Error> {
+        let req: SetScale = self.client.parse(self, parser)?;
+        let scale = req.scale.to_f64();
+        if scale <= 0.0 {
+            return Err(ZwlrOutputConfigurationHeadV1Error::InvalidScale(scale));
+        }
+        if self.scale.replace(Some(Scale::from_f64(scale))).is_some() {
+            return Err(ZwlrOutputConfigurationHeadV1Error::AlreadySet("scale"));
+        }
+        Ok(())
+    }
+}
+
+object_base! {
+    ZwlrOutputConfigurationHeadV1;
+
+    SET_MODE => set_mode,
+    SET_CUSTOM_MODE => set_custom_mode,
+    SET_POSITION => set_position,
+    SET_TRANSFORM => set_transform,
+    SET_SCALE => set_scale,
+}
+
+impl Object for ZwlrOutputConfigurationHeadV1 {
+    fn num_requests(&self) -> u32 {
+        SET_SCALE + 1
+    }
+}
+
+simple_add_obj!(ZwlrOutputConfigurationHeadV1);
+
+#[derive(Debug, Error)]
+pub enum ZwlrOutputConfigurationHeadV1Error {
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("The {0} has already been set")]
+    AlreadySet(&'static str),
+    #[error("The mode does not belong to the head")]
+    InvalidMode,
+    #[error("The custom mode {0}x{1}@{2} is invalid")]
+    InvalidCustomMode(i32, i32, i32),
+    #[error("Unknown transform {0}")]
+    InvalidTransform(i32),
+    #[error("The scale {0} is not positive")]
+    InvalidScale(f64),
+}
+efrom!(ZwlrOutputConfigurationHeadV1Error, MsgParserError);
+efrom!(ZwlrOutputConfigurationHeadV1Error, ClientError);
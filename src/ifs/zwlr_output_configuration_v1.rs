@@ -0,0 +1,351 @@
+use {
+    crate::{
+        backend::{ConnectorConfig, ConnectorError, ConnectorId, Mode},
+        client::{Client, ClientError},
+        compositor::MAX_EXTENTS,
+        ifs::{
+            zwlr_output_configuration_head_v1::{ZwlrOutputConfigurationHeadV1, TF_NORMAL},
+            zwlr_output_head_v1::ZwlrOutputHeadV1,
+            zwlr_output_manager_v1::ZwlrOutputManagerV1,
+        },
+        leaks::Tracker,
+        object::Object,
+        scale::Scale,
+        state::ConnectorData,
+        utils::{
+            buffd::{MsgParser, MsgParserError},
+            copyhashmap::CopyHashMap,
+            errorfmt::ErrorFmt,
+        },
+        wire::{zwlr_output_configuration_v1::*, ZwlrOutputConfigurationV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+pub struct ZwlrOutputConfigurationV1 {
+    pub id: ZwlrOutputConfigurationV1Id,
+    pub client: Rc<Client>,
+    pub manager: Rc<ZwlrOutputManagerV1>,
+    pub serial: u32,
+    pub heads: CopyHashMap<ConnectorId, Option<Rc<ZwlrOutputConfigurationHeadV1>>>,
+    pub used: Cell<bool>,
+    pub tracker: Tracker<Self>,
+}
+
+struct HeadConfig {
+    head: Rc<ZwlrOutputHeadV1>,
+    mode: Mode,
+    position: Option<(i32, i32)>,
+    scale: Option<Scale>,
+}
+
+/// A backend change that has to be reverted if a later change fails.
+enum Undo {
+    Enabled(Rc<ConnectorData>, bool),
+    Mode(Rc<ConnectorData>, Mode),
+    Pending(Rc<ConnectorData>, Option<(i32, i32)>, Option<Scale>),
+}
+
+impl ZwlrOutputConfigurationV1 {
+    fn send_succeeded(&self) {
+        self.client.event(Succeeded { self_id: self.id });
+    }
+
+    fn send_failed(&self) {
+        self.client.event(Failed { self_id: self.id });
+    }
+
+    fn send_cancelled(&self) {
+        self.client.event(Cancelled { self_id: self.id });
+    }
+
+    fn configure_head(
+        &self,
+        head: &ZwlrOutputHeadV1,
+        config: Option<Rc<ZwlrOutputConfigurationHeadV1>>,
+    ) -> Result<(), ZwlrOutputConfigurationV1Error> {
+        if self.used.get() {
+            return Err(ZwlrOutputConfigurationV1Error::AlreadyUsed);
+        }
+        let id = head.connector.connector.id();
+        if self.heads.set(id, config).is_some() {
+            return Err(ZwlrOutputConfigurationV1Error::AlreadyConfiguredHead(
+                head.connector.name.clone(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn enable_head(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwlrOutputConfigurationV1Error> {
+        let req: EnableHead = self.client.parse(self, parser)?;
+        let head = self.client.lookup(req.head)?;
+        let obj = Rc::new(ZwlrOutputConfigurationHeadV1 {
+            id: req.id,
+            client: self.client.clone(),
+            head: head.clone(),
+            mode: Cell::new(None),
+            position: Cell::new(None),
+            transform: Cell::new(None),
+            scale: Cell::new(None),
+            tracker: Default::default(),
+        });
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        self.configure_head(&head, Some(obj))
+    }
+
+    fn disable_head(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), ZwlrOutputConfigurationV1Error> {
+        let req: DisableHead = self.client.parse(self, parser)?;
+        let head = self.client.lookup(req.head)?;
+        self.configure_head(&head, None)
+    }
+
+    /// Validates the configuration. Returns `None` if the configuration cannot be
+    /// applied.
+    fn validate(&self) -> Option<(Vec<Rc<ZwlrOutputHeadV1>>, Vec<HeadConfig>)> {
+        let mut disabled = vec![];
+        let mut enabled = vec![];
+        for head in self.manager.heads.lock().values() {
+            let config = match self.heads.get(&head.connector.connector.id()) {
+                Some(Some(c)) => c,
+                _ => {
+                    disabled.push(head.clone());
+                    continue;
+                }
+            };
+            let mode = match config.resolve_mode() {
+                Some(m) => m,
+                _ => {
+                    log::warn!("Client requested an unsupported mode");
+                    return None;
+                }
+            };
+            if config.transform.get().unwrap_or(TF_NORMAL) != TF_NORMAL {
+                log::warn!("Client requested an unsupported transform");
+                return None;
+            }
+            if let Some(scale) = config.scale.get() {
+                let scale = scale.to_f64();
+                if !(0.1..=1000.0).contains(&scale) {
+                    log::warn!("Client requested an unsupported scale: {}", scale);
+                    return None;
+                }
+            }
+            if let Some((x, y)) = config.position.get() {
+                if x < 0 || y < 0 || x > MAX_EXTENTS || y > MAX_EXTENTS {
+                    log::warn!("Client requested an invalid position: {}x{}", x, y);
+                    return None;
+                }
+            }
+            enabled.push(HeadConfig {
+                head: head.clone(),
+                mode,
+                position: config.position.get(),
+                scale: config.scale.get(),
+            });
+        }
+        Some((disabled, enabled))
+    }
+
+    fn test_config(
+        &self,
+        disabled: &[Rc<ZwlrOutputHeadV1>],
+        enabled: &[HeadConfig],
+    ) -> Result<(), ConnectorError> {
+        let mut configs = vec![];
+        for head in disabled {
+            configs.push(ConnectorConfig {
+                connector: head.connector.connector.id(),
+                enabled: false,
+                mode: None,
+            });
+        }
+        for config in enabled {
+            configs.push(ConnectorConfig {
+                connector: config.head.connector.connector.id(),
+                enabled: true,
+                mode: Some(config.mode),
+            });
+        }
+        self.client
+            .state
+            .backend
+            .get()
+            .test_connector_configs(&configs)
+    }
+
+    fn apply_connectors(
+        &self,
+        disabled: &[Rc<ZwlrOutputHeadV1>],
+        enabled: &[HeadConfig],
+        undo: &mut Vec<Undo>,
+    ) -> Result<(), ConnectorError> {
+        let state = &self.client.state;
+        for head in disabled {
+            if head.connector.enabled.get() {
+                head.connector.set_enabled(false)?;
+                undo.push(Undo::Enabled(head.connector.clone(), true));
+            }
+        }
+        for config in enabled {
+            let connector = &config.head.connector;
+            let old_mode = config.head.current_state().mode;
+            if config.mode != old_mode {
+                connector.connector.set_mode(config.mode)?;
+                undo.push(Undo::Mode(connector.clone(), old_mode));
+            }
+            if !state.outputs.contains(&connector.connector.id()) {
+                undo.push(Undo::Pending(
+                    connector.clone(),
+                    connector.pending_position.replace(config.position),
+                    connector.pending_scale.replace(config.scale),
+                ));
+                if !connector.enabled.get() {
+                    connector.set_enabled(true)?;
+                    undo.push(Undo::Enabled(connector.clone(), false));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn apply_config(
+        &self,
+        disabled: Vec<Rc<ZwlrOutputHeadV1>>,
+        enabled: Vec<HeadConfig>,
+    ) -> Result<(), ConnectorError> {
+        let state = &self.client.state;
+        let mut undo = vec![];
+        if let Err(e) = self.apply_connectors(&disabled, &enabled, &mut undo) {
+            for undo in undo.into_iter().rev() {
+                let res = match undo {
+                    Undo::Enabled(connector, enabled) => connector.set_enabled(enabled),
+                    Undo::Mode(connector, mode) => connector.connector.set_mode(mode),
+                    Undo::Pending(connector, position, scale) => {
+                        connector.pending_position.set(position);
+                        connector.pending_scale.set(scale);
+                        Ok(())
+                    }
+                };
+                if let Err(e) = res {
+                    log::error!(
+                        "Could not restore the output configuration: {}",
+                        ErrorFmt(e)
+                    );
+                }
+            }
+            return Err(e);
+        }
+        for config in enabled {
+            if let Some(output) = state.outputs.get(&config.head.connector.connector.id()) {
+                if let Some((x, y)) = config.position {
+                    output.node.set_position(x, y);
+                }
+                if let Some(scale) = config.scale {
+                    output.node.set_preferred_scale(scale);
+                }
+            }
+        }
+        state.damage();
+        Ok(())
+    }
+
+    fn test_or_apply(&self, apply: bool) -> Result<(), ZwlrOutputConfigurationV1Error> {
+        if self.used.replace(true) {
+            return Err(ZwlrOutputConfigurationV1Error::AlreadyUsed);
+        }
+        if self.serial != self.manager.serial.get() {
+            self.send_cancelled();
+            return Ok(());
+        }
+        for head in self.manager.heads.lock().values() {
+            if !self.heads.contains(&head.connector.connector.id()) {
+                return Err(ZwlrOutputConfigurationV1Error::UnconfiguredHead(
+                    head.connector.name.clone(),
+                ));
+            }
+        }
+        let (disabled, enabled) = match self.validate() {
+            Some(c) => c,
+            _ => {
+                self.send_failed();
+                return Ok(());
+            }
+        };
+        if let Err(e) = self.test_config(&disabled, &enabled) {
+            log::warn!(
+                "The backend rejected the output configuration: {}",
+                ErrorFmt(e)
+            );
+            self.send_failed();
+            return Ok(());
+        }
+        if apply {
+            if let Err(e) = self.apply_config(disabled, enabled) {
+                log::warn!("Could not apply the output configuration: {}", ErrorFmt(e));
+                self.send_failed();
+                return Ok(());
+            }
+        }
+        self.send_succeeded();
+        Ok(())
+    }
+
+    fn apply(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwlrOutputConfigurationV1Error> {
+        let _req: Apply = self.client.parse(self, parser)?;
+        self.test_or_apply(true)
+    }
+
+    fn test(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwlrOutputConfigurationV1Error> {
+        let _req: Test = self.client.parse(self, parser)?;
+        self.test_or_apply(false)
+    }
+
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwlrOutputConfigurationV1Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    ZwlrOutputConfigurationV1;
+
+    ENABLE_HEAD => enable_head,
+    DISABLE_HEAD => disable_head,
+    APPLY => apply,
+    TEST => test,
+    DESTROY => destroy,
+}
+
+impl Object for ZwlrOutputConfigurationV1 {
+    fn num_requests(&self) -> u32 {
+        DESTROY + 1
+    }
+
+    fn break_loops(&self) {
+        self.heads.clear();
+    }
+}
+
+simple_add_obj!(ZwlrOutputConfigurationV1);
+
+#[derive(Debug, Error)]
+pub enum ZwlrOutputConfigurationV1Error {
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("Head {0} has already been configured")]
+    AlreadyConfiguredHead(String),
+    #[error("Head {0} has not been configured")]
+    UnconfiguredHead(String),
+    #[error("The configuration has already been applied or tested")]
+    AlreadyUsed,
+}
+efrom!(ZwlrOutputConfigurationV1Error, MsgParserError);
+efrom!(ZwlrOutputConfigurationV1Error, ClientError);
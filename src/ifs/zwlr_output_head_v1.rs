@@ -0,0 +1,272 @@
+use {
+    crate::{
+        backend::{self, MonitorInfo},
+        client::{Client, ClientError},
+        fixed::Fixed,
+        ifs::zwlr_output_mode_v1::ZwlrOutputModeV1,
+        leaks::Tracker,
+        object::Object,
+        scale,
+        state::ConnectorData,
+        utils::{
+            buffd::{MsgParser, MsgParserError},
+            clonecell::CloneCell,
+        },
+        wire::{zwlr_output_head_v1::*, ZwlrOutputHeadV1Id, ZwlrOutputModeV1Id},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+    thiserror::Error,
+};
+
+const TF_NORMAL: i32 = 0;
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub struct HeadState {
+    pub enabled: bool,
+    pub mode: backend::Mode,
+    pub pos: (i32, i32),
+    pub scale: scale::Scale,
+}
+
+pub struct ZwlrOutputHeadV1 {
+    pub id: ZwlrOutputHeadV1Id,
+    pub client: Rc<Client>,
+    pub connector: Rc<ConnectorData>,
+    pub monitor_info: Rc<MonitorInfo>,
+    pub modes: RefCell<Vec<Rc<ZwlrOutputModeV1>>>,
+    pub current_mode: CloneCell<Option<Rc<ZwlrOutputModeV1>>>,
+    pub sent_state: Cell<Option<HeadState>>,
+    pub released: Cell<bool>,
+    pub tracker: Tracker<Self>,
+}
+
+impl ZwlrOutputHeadV1 {
+    pub fn current_state(&self) -> HeadState {
+        let output = self
+            .client
+            .state
+            .outputs
+            .get(&self.connector.connector.id());
+        match output {
+            Some(output) => {
+                let global = &output.node.global;
+                let pos = global.pos.get();
+                HeadState {
+                    enabled: true,
+                    mode: global.mode.get(),
+                    pos: (pos.x1(), pos.y1()),
+                    scale: output.node.preferred_scale.get(),
+                }
+            }
+            _ => HeadState {
+                enabled: false,
+                mode: self.monitor_info.initial_mode,
+                pos: (0, 0),
+                scale: scale::Scale::from_int(1),
+            },
+        }
+    }
+
+    pub fn announce_mode(
+        self: &Rc<Self>,
+        mode: &backend::Mode,
+    ) -> Result<Rc<ZwlrOutputModeV1>, ClientError> {
+        let obj = Rc::new(ZwlrOutputModeV1 {
+            id: self.client.new_id()?,
+            client: self.client.clone(),
+            mode: *mode,
+            released: Cell::new(false),
+            tracker: Default::default(),
+        });
+        track!(self.client, obj);
+        self.client.add_server_obj(&obj);
+        self.client.event(Mode {
+            self_id: self.id,
+            mode: obj.id,
+        });
+        obj.send_size(mode.width, mode.height);
+        if mode.refresh_rate_millihz > 0 {
+            obj.send_refresh(mode.refresh_rate_millihz as _);
+        }
+        self.modes.borrow_mut().push(obj.clone());
+        Ok(obj)
+    }
+
+    /// Returns whether any events were sent.
+    pub fn update_state(self: &Rc<Self>) -> Result<bool, ClientError> {
+        if self.released.get() {
+            return Ok(false);
+        }
+        let state = self.current_state();
+        let old = self.sent_state.replace(Some(state));
+        if old == Some(state) {
+            return Ok(false);
+        }
+        if old.map(|o| o.enabled) != Some(state.enabled) {
+            self.send_enabled(state.enabled);
+        }
+        if state.enabled {
+            let mode_changed = old.map(|o| (o.enabled, o.mode)) != Some((true, state.mode));
+            if mode_changed {
+                let existing = self
+                    .modes
+                    .borrow()
+                    .iter()
+                    .find(|m| m.mode == state.mode)
+                    .cloned();
+                let mode = match existing {
+                    Some(m) => m,
+                    _ => self.announce_mode(&state.mode)?,
+                };
+                self.send_current_mode(&mode);
+                self.current_mode.set(Some(mode));
+            }
+            if old.map(|o| (o.enabled, o.pos)) != Some((true, state.pos)) {
+                self.send_position(state.pos.0, state.pos.1);
+            }
+            if old.map(|o| o.enabled) != Some(true) {
+                self.send_transform(TF_NORMAL);
+            }
+            if old.map(|o| (o.enabled, o.scale)) != Some((true, state.scale)) {
+                self.send_scale(Fixed::from_f64(state.scale.to_f64()));
+            }
+        }
+        Ok(true)
+    }
+
+    pub fn find_mode(&self, id: ZwlrOutputModeV1Id) -> Option<backend::Mode> {
+        self.modes
+            .borrow()
+            .iter()
+            .find(|m| m.id == id)
+            .map(|m| m.mode)
+    }
+
+    pub fn send_name(&self, name: &str) {
+        self.client.event(Name {
+            self_id: self.id,
+            name,
+        });
+    }
+
+    pub fn send_description(&self, description: &str) {
+        self.client.event(Description {
+            self_id: self.id,
+            description,
+        });
+    }
+
+    pub fn send_physical_size(&self, width: i32, height: i32) {
+        self.client.event(PhysicalSize {
+            self_id: self.id,
+            width,
+            height,
+        });
+    }
+
+    fn send_enabled(&self, enabled: bool) {
+        self.client.event(Enabled {
+            self_id: self.id,
+            enabled: enabled as _,
+        });
+    }
+
+    fn send_current_mode(&self, mode: &ZwlrOutputModeV1) {
+        self.client.event(CurrentMode {
+            self_id: self.id,
+            mode: mode.id,
+        });
+    }
+
+    fn send_position(&self, x: i32, y: i32) {
+        self.client.event(Position {
+            self_id: self.id,
+            x,
+            y,
+        });
+    }
+
+    fn send_transform(&self, transform: i32) {
+        self.client.event(Transform {
+            self_id: self.id,
+            transform,
+        });
+    }
+
+    fn send_scale(&self, scale: Fixed) {
+        self.client.event(Scale {
+            self_id: self.id,
+            scale,
+        });
+    }
+
+    pub fn send_make(&self, make: &str) {
+        self.client.event(Make {
+            self_id: self.id,
+            make,
+        });
+    }
+
+    pub fn send_model(&self, model: &str) {
+        self.client.event(Model {
+            self_id: self.id,
+            model,
+        });
+    }
+
+    pub fn send_serial_number(&self, serial_number: &str) {
+        self.client.event(SerialNumber {
+            self_id: self.id,
+            serial_number,
+        });
+    }
+
+    pub fn send_finished(&self) {
+        for mode in self.modes.borrow_mut().drain(..) {
+            mode.send_finished();
+        }
+        self.current_mode.take();
+        if !self.released.get() {
+            self.client.event(Finished { self_id: self.id });
+        }
+    }
+
+    fn release(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwlrOutputHeadV1Error> {
+        let _req: Release = self.client.parse(self, parser)?;
+        self.released.set(true);
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    ZwlrOutputHeadV1;
+
+    RELEASE => release,
+}
+
+impl Object for ZwlrOutputHeadV1 {
+    fn num_requests(&self) -> u32 {
+        RELEASE + 1
+    }
+
+    fn break_loops(&self) {
+        self.modes.borrow_mut().clear();
+        self.current_mode.take();
+    }
+}
+
+dedicated_add_obj!(ZwlrOutputHeadV1, ZwlrOutputHeadV1Id, output_heads);
+
+#[derive(Debug, Error)]
+pub enum ZwlrOutputHeadV1Error {
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwlrOutputHeadV1Error, MsgParserError);
+efrom!(ZwlrOutputHeadV1Error, ClientError);
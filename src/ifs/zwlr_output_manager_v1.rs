@@ -0,0 +1,265 @@
+use {
+    crate::{
+        backend::{ConnectorId, MonitorInfo},
+        client::{Client, ClientError},
+        globals::{Global, GlobalName},
+        ifs::{
+            zwlr_output_configuration_v1::ZwlrOutputConfigurationV1,
+            zwlr_output_head_v1::ZwlrOutputHeadV1,
+        },
+        leaks::Tracker,
+        object::Object,
+        state::ConnectorData,
+        utils::{
+            buffd::{MsgParser, MsgParserError},
+            copyhashmap::CopyHashMap,
+            numcell::NumCell,
+            rc_eq::rc_eq,
+        },
+        wire::{zwlr_output_manager_v1::*, ZwlrOutputManagerV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+pub struct ZwlrOutputManagerV1Global {
+    pub name: GlobalName,
+}
+
+impl ZwlrOutputManagerV1Global {
+    pub fn new(name: GlobalName) -> Self {
+        Self { name }
+    }
+
+    fn bind_(
+        self: Rc<Self>,
+        id: ZwlrOutputManagerV1Id,
+        client: &Rc<Client>,
+        version: u32,
+    ) -> Result<(), ZwlrOutputManagerV1Error> {
+        let obj = Rc::new(ZwlrOutputManagerV1 {
+            id,
+            client: client.clone(),
+            version,
+            heads: Default::default(),
+            serial: Default::default(),
+            stopped: Cell::new(false),
+            tracker: Default::default(),
+        });
+        track!(client, obj);
+        client.add_client_obj(&obj)?;
+        client
+            .state
+            .output_managers
+            .set((client.id, id), obj.clone());
+        obj.update_()?;
+        Ok(())
+    }
+}
+
+global_base!(
+    ZwlrOutputManagerV1Global,
+    ZwlrOutputManagerV1,
+    ZwlrOutputManagerV1Error
+);
+
+impl Global for ZwlrOutputManagerV1Global {
+    fn singleton(&self) -> bool {
+        true
+    }
+
+    fn version(&self) -> u32 {
+        3
+    }
+
+    fn secure(&self) -> bool {
+        true
+    }
+}
+
+simple_add_global!(ZwlrOutputManagerV1Global);
+
+pub struct ZwlrOutputManagerV1 {
+    pub id: ZwlrOutputManagerV1Id,
+    pub client: Rc<Client>,
+    pub version: u32,
+    pub heads: CopyHashMap<ConnectorId, Rc<ZwlrOutputHeadV1>>,
+    pub serial: NumCell<u32>,
+    pub stopped: Cell<bool>,
+    pub tracker: Tracker<Self>,
+}
+
+impl ZwlrOutputManagerV1 {
+    /// Sends the changes of the output configuration to the client.
+    pub fn update(self: &Rc<Self>) {
+        if let Err(e) = self.update_() {
+            self.client.error(e);
+        }
+    }
+
+    fn update_(self: &Rc<Self>) -> Result<(), ClientError> {
+        if self.stopped.get() {
+            return Ok(());
+        }
+        let state = &self.client.state;
+        let mut changed = false;
+        let heads: Vec<_> = self.heads.lock().values().cloned().collect();
+        for head in heads {
+            let mi = state
+                .connectors
+                .get(&head.connector.connector.id())
+                .and_then(|c| c.monitor_info.get());
+            let still_exists = match mi {
+                Some(mi) => rc_eq(&mi, &head.monitor_info),
+                _ => false,
+            };
+            if !still_exists {
+                self.heads.remove(&head.connector.connector.id());
+                head.send_finished();
+                changed = true;
+            }
+        }
+        let connectors: Vec<_> = state.connectors.lock().values().cloned().collect();
+        for connector in connectors {
+            let mi = match connector.monitor_info.get() {
+                Some(mi) => mi,
+                _ => continue,
+            };
+            let head = match self.heads.get(&connector.connector.id()) {
+                Some(h) => h,
+                _ => {
+                    changed = true;
+                    self.announce_head(&connector, &mi)?
+                }
+            };
+            changed |= head.update_state()?;
+        }
+        if changed {
+            self.serial.fetch_add(1);
+            self.send_done();
+        }
+        Ok(())
+    }
+
+    fn announce_head(
+        self: &Rc<Self>,
+        connector: &Rc<ConnectorData>,
+        mi: &Rc<MonitorInfo>,
+    ) -> Result<Rc<ZwlrOutputHeadV1>, ClientError> {
+        let head = Rc::new(ZwlrOutputHeadV1 {
+            id: self.client.new_id()?,
+            client: self.client.clone(),
+            connector: connector.clone(),
+            monitor_info: mi.clone(),
+            modes: Default::default(),
+            current_mode: Default::default(),
+            sent_state: Cell::new(None),
+            released: Cell::new(false),
+            tracker: Default::default(),
+        });
+        track!(self.client, head);
+        self.client.add_server_obj(&head);
+        self.client.event(Head {
+            self_id: self.id,
+            head: head.id,
+        });
+        head.send_name(&connector.name);
+        head.send_description(&format!(
+            "{} {} {}",
+            mi.manufacturer, mi.product, mi.serial_number
+        ));
+        head.send_physical_size(mi.width_mm, mi.height_mm);
+        for (idx, mode) in mi.modes.iter().enumerate() {
+            let mode = head.announce_mode(mode)?;
+            if idx == 0 {
+                mode.send_preferred();
+            }
+        }
+        if self.version >= MAKE_SINCE {
+            head.send_make(&mi.manufacturer);
+            head.send_model(&mi.product);
+            head.send_serial_number(&mi.serial_number);
+        }
+        self.heads.set(connector.connector.id(), head.clone());
+        Ok(head)
+    }
+
+    fn send_done(&self) {
+        self.client.event(Done {
+            self_id: self.id,
+            serial: self.serial.get(),
+        });
+    }
+
+    fn send_finished(&self) {
+        self.client.event(Finished { self_id: self.id });
+    }
+
+    fn detach(&self) {
+        self.client
+            .state
+            .output_managers
+            .remove(&(self.client.id, self.id));
+    }
+
+    fn create_configuration(
+        self: &Rc<Self>,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), ZwlrOutputManagerV1Error> {
+        let req: CreateConfiguration = self.client.parse(&**self, parser)?;
+        let obj = Rc::new(ZwlrOutputConfigurationV1 {
+            id: req.id,
+            client: self.client.clone(),
+            manager: self.clone(),
+            serial: req.serial,
+            heads: Default::default(),
+            used: Cell::new(false),
+            tracker: Default::default(),
+        });
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        Ok(())
+    }
+
+    fn stop(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwlrOutputManagerV1Error> {
+        let _req: Stop = self.client.parse(self, parser)?;
+        if !self.stopped.replace(true) {
+            self.detach();
+            self.send_finished();
+            self.client.remove_obj(self)?;
+        }
+        Ok(())
+    }
+}
+
+const MAKE_SINCE: u32 = 2;
+
+object_base! {
+    ZwlrOutputManagerV1;
+
+    CREATE_CONFIGURATION => create_configuration,
+    STOP => stop,
+}
+
+impl Object for ZwlrOutputManagerV1 {
+    fn num_requests(&self) -> u32 {
+        STOP + 1
+    }
+
+    fn break_loops(&self) {
+        self.detach();
+        self.heads.clear();
+    }
+}
+
+simple_add_obj!(ZwlrOutputManagerV1);
+
+#[derive(Debug, Error)]
+pub enum ZwlrOutputManagerV1Error {
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwlrOutputManagerV1Error, MsgParserError);
+efrom!(ZwlrOutputManagerV1Error, ClientError);
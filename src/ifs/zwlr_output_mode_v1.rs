@@ -0,0 +1,78 @@
+use {
+    crate::{
+        backend::Mode,
+        client::{Client, ClientError},
+        leaks::Tracker,
+        object::Object,
+        utils::buffd::{MsgParser, MsgParserError},
+        wire::{zwlr_output_mode_v1::*, ZwlrOutputModeV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+pub struct ZwlrOutputModeV1 {
+    pub id: ZwlrOutputModeV1Id,
+    pub client: Rc<Client>,
+    pub mode: Mode,
+    pub released: Cell<bool>,
+    pub tracker: Tracker<Self>,
+}
+
+impl ZwlrOutputModeV1 {
+    pub fn send_size(&self, width: i32, height: i32) {
+        self.client.event(Size {
+            self_id: self.id,
+            width,
+            height,
+        });
+    }
+
+    pub fn send_refresh(&self, refresh: i32) {
+        self.client.event(Refresh {
+            self_id: self.id,
+            refresh,
+        });
+    }
+
+    pub fn send_preferred(&self) {
+        self.client.event(Preferred { self_id: self.id });
+    }
+
+    pub fn send_finished(&self) {
+        if !self.released.get() {
+            self.client.event(Finished { self_id: self.id });
+        }
+    }
+
+    fn release(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwlrOutputModeV1Error> {
+        let _req: Release = self.client.parse(self, parser)?;
+        self.released.set(true);
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    ZwlrOutputModeV1;
+
+    RELEASE => release,
+}
+
+impl Object for ZwlrOutputModeV1 {
+    fn num_requests(&self) -> u32 {
+        RELEASE + 1
+    }
+}
+
+simple_add_obj!(ZwlrOutputModeV1);
+
+#[derive(Debug, Error)]
+pub enum ZwlrOutputModeV1Error {
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwlrOutputModeV1Error, MsgParserError);
+efrom!(ZwlrOutputModeV1Error, ClientError);
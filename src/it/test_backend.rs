@@ -2,10 +2,10 @@ use {
     crate::{
         async_engine::SpawnedFuture,
         backend::{
-            AxisSource, Backend, BackendEvent, Connector, ConnectorEvent, ConnectorId,
-            ConnectorKernelId, DrmDeviceId, InputDevice, InputDeviceAccelProfile,
-            InputDeviceCapability, InputDeviceId, InputEvent, KeyState, Mode, MonitorInfo,
-            ScrollAxis, TransformMatrix,
+            AxisSource, Backend, BackendEvent, Connector, ConnectorError, ConnectorEvent,
+            ConnectorId, ConnectorKernelId, DrmDeviceId, GammaLut, InputDevice,
+            InputDeviceAccelProfile, InputDeviceCapability, InputDeviceId, InputEvent, KeyState,
+            Mode, MonitorInfo, ScrollAxis, TransformMatrix,
         },
         compositor::TestFuture,
         fixed::Fixed,
@@ -42,20 +42,13 @@ pub struct TestBackend {
     pub default_connector: Rc<TestConnector>,
     pub default_mouse: Rc<TestBackendMouse>,
     pub default_kb: Rc<TestBackendKb>,
+    pub default_touch: Rc<TestBackendTouch>,
     pub render_context_installed: Cell<bool>,
 }
 
 impl TestBackend {
     pub fn new(state: &Rc<State>, future: TestFuture) -> Self {
-        let default_connector = Rc::new(TestConnector {
-            id: state.connector_ids.next(),
-            kernel_id: ConnectorKernelId {
-                ty: ConnectorType::VGA,
-                idx: 1,
-            },
-            events: Default::default(),
-            on_change: Default::default(),
-        });
+        let default_connector = Rc::new(TestConnector::new(state, 1));
         let default_mouse = Rc::new(TestBackendMouse {
             common: TestInputDeviceCommon {
                 id: state.input_device_ids.next(),
@@ -88,12 +81,27 @@ impl TestBackend {
                 name: Rc::new("default-keyboard".to_string()),
             },
         });
+        let default_touch = Rc::new(TestBackendTouch {
+            common: TestInputDeviceCommon {
+                id: state.input_device_ids.next(),
+                removed: Cell::new(false),
+                events: Default::default(),
+                on_change: Default::default(),
+                capabilities: {
+                    let chm = CopyHashMap::new();
+                    chm.set(InputDeviceCapability::Touch, ());
+                    chm
+                },
+                name: Rc::new("default-touch".to_string()),
+            },
+        });
         Self {
             state: state.clone(),
             test_future: future,
             default_connector,
             default_mouse,
             default_kb,
+            default_touch,
             render_context_installed: Cell::new(false),
         }
     }
@@ -112,31 +120,28 @@ impl TestBackend {
         self.state
             .backend_events
             .push(BackendEvent::NewConnector(self.default_connector.clone()));
-        let mode = Mode {
-            width: 800,
-            height: 600,
-            refresh_rate_millihz: 60_000,
-        };
-        self.default_connector
-            .events
-            .push(ConnectorEvent::Connected(MonitorInfo {
-                modes: vec![mode],
-                manufacturer: "jay".to_string(),
-                product: "TestConnector".to_string(),
-                serial_number: self.default_connector.id.to_string(),
-                initial_mode: mode,
-                width_mm: 80,
-                height_mm: 60,
-            }));
+        self.default_connector.connect();
         self.state
             .backend_events
             .push(BackendEvent::NewInputDevice(self.default_kb.clone()));
         self.state
             .backend_events
             .push(BackendEvent::NewInputDevice(self.default_mouse.clone()));
+        self.state
+            .backend_events
+            .push(BackendEvent::NewInputDevice(self.default_touch.clone()));
         Ok(())
     }
 
+    pub fn add_connector(&self, idx: u32) -> Rc<TestConnector> {
+        let connector = Rc::new(TestConnector::new(&self.state, idx));
+        self.state
+            .backend_events
+            .push(BackendEvent::NewConnector(connector.clone()));
+        connector.connect();
+        connector
+    }
+
     fn create_render_context(&self) -> Result<(), TestBackendError> {
         let dri = match std::fs::read_dir("/dev/dri") {
             Ok(d) => d,
@@ -220,6 +225,73 @@ pub struct TestConnector {
     pub kernel_id: ConnectorKernelId,
     pub events: SyncQueue<ConnectorEvent>,
     pub on_change: CloneCell<Option<Rc<dyn Fn()>>>,
+    pub damaged: Cell<bool>,
+    pub vrr_enabled: Cell<bool>,
+    pub tearing_allowed: Cell<bool>,
+    pub gamma_lut: CloneCell<Option<Rc<GammaLut>>>,
+    /// A mode that the connector fails to apply.
+    pub rejected_mode: Cell<Option<Mode>>,
+}
+
+pub const TEST_GAMMA_SIZE: u32 = 16;
+
+impl TestConnector {
+    fn new(state: &State, idx: u32) -> Self {
+        Self {
+            id: state.connector_ids.next(),
+            kernel_id: ConnectorKernelId {
+                ty: ConnectorType::VGA,
+                idx,
+            },
+            events: Default::default(),
+            on_change: Default::default(),
+            damaged: Cell::new(false),
+            vrr_enabled: Cell::new(false),
+            tearing_allowed: Cell::new(false),
+            gamma_lut: Default::default(),
+            rejected_mode: Cell::new(None),
+        }
+    }
+
+    pub fn connect(&self) {
+        let mode = Mode {
+            width: 800,
+            height: 600,
+            refresh_rate_millihz: 60_000,
+        };
+        let modes = vec![
+            mode,
+            Mode {
+                width: 800,
+                height: 600,
+                refresh_rate_millihz: 120_000,
+            },
+            Mode {
+                width: 1024,
+                height: 768,
+                refresh_rate_millihz: 60_000,
+            },
+        ];
+        self.events.push(ConnectorEvent::Connected(MonitorInfo {
+            modes,
+            manufacturer: "jay".to_string(),
+            product: "TestConnector".to_string(),
+            serial_number: self.id.to_string(),
+            initial_mode: mode,
+            width_mm: 80,
+            height_mm: 60,
+        }));
+        if let Some(oc) = self.on_change.get() {
+            oc();
+        }
+    }
+
+    pub fn disconnect(&self) {
+        self.events.push(ConnectorEvent::Disconnected);
+        if let Some(oc) = self.on_change.get() {
+            oc();
+        }
+    }
 }
 
 impl Connector for TestConnector {
@@ -240,15 +312,43 @@ impl Connector for TestConnector {
     }
 
     fn damage(&self) {
-        // nothing
+        self.damaged.set(true);
     }
 
     fn drm_dev(&self) -> Option<DrmDeviceId> {
         None
     }
 
-    fn set_enabled(&self, _enabled: bool) {
+    fn set_enabled(&self, _enabled: bool) -> Result<(), ConnectorError> {
         // todo
+        Ok(())
+    }
+
+    fn set_mode(&self, mode: Mode) -> Result<(), ConnectorError> {
+        if self.rejected_mode.get() == Some(mode) {
+            return Err(ConnectorError::UnsupportedMode(mode));
+        }
+        self.events.push(ConnectorEvent::ModeChanged(mode));
+        if let Some(oc) = self.on_change.get() {
+            oc();
+        }
+        Ok(())
+    }
+
+    fn set_vrr_enabled(&self, enabled: bool) {
+        self.vrr_enabled.set(enabled);
+    }
+
+    fn set_tearing_allowed(&self, allowed: bool) {
+        self.tearing_allowed.set(allowed);
+    }
+
+    fn gamma_size(&self) -> u32 {
+        TEST_GAMMA_SIZE
+    }
+
+    fn set_gamma_lut(&self, lut: Option<Rc<GammaLut>>) {
+        self.gamma_lut.set(lut);
     }
 }
 
@@ -307,6 +407,22 @@ impl TestBackendMouse {
         }
     }
 
+    pub fn swipe(&self, fingers: u32, dx: f64, dy: f64) {
+        self.common.event(InputEvent::SwipeBegin {
+            time_usec: now_usec(),
+            finger_count: fingers,
+        });
+        self.common.event(InputEvent::SwipeUpdate {
+            time_usec: now_usec(),
+            dx: Fixed::from_f64(dx),
+            dy: Fixed::from_f64(dy),
+        });
+        self.common.event(InputEvent::SwipeEnd {
+            time_usec: now_usec(),
+            cancelled: false,
+        });
+    }
+
     pub fn scroll(&self, dy: i32) {
         self.common.event(InputEvent::AxisSource {
             source: AxisSource::Wheel,
@@ -367,6 +483,47 @@ impl TestBackendKb {
     }
 }
 
+pub struct TestBackendTouch {
+    pub common: TestInputDeviceCommon,
+}
+
+impl TestBackendTouch {
+    pub fn down(&self, id: i32, x_normed: f64, y_normed: f64) {
+        self.common.event(InputEvent::TouchDown {
+            time_usec: now_usec(),
+            id,
+            x_normed,
+            y_normed,
+        });
+    }
+
+    pub fn motion(&self, id: i32, x_normed: f64, y_normed: f64) {
+        self.common.event(InputEvent::TouchMotion {
+            time_usec: now_usec(),
+            id,
+            x_normed,
+            y_normed,
+        });
+    }
+
+    pub fn up(&self, id: i32) {
+        self.common.event(InputEvent::TouchUp {
+            time_usec: now_usec(),
+            id,
+        });
+    }
+
+    pub fn frame(&self) {
+        self.common.event(InputEvent::TouchFrame);
+    }
+}
+
+impl TestInputDevice for TestBackendTouch {
+    fn common(&self) -> &TestInputDeviceCommon {
+        &self.common
+    }
+}
+
 impl TestInputDevice for TestBackendKb {
     fn common(&self) -> &TestInputDeviceCommon {
         &self.common
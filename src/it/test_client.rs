@@ -10,7 +10,8 @@ use {
                 test_compositor::TestCompositor, test_jay_compositor::TestJayCompositor,
                 test_keyboard::TestKeyboard, test_pointer::TestPointer,
                 test_registry::TestRegistry, test_seat::TestSeat, test_shm::TestShm,
-                test_subcompositor::TestSubcompositor, test_xdg_base::TestXdgWmBase,
+                test_subcompositor::TestSubcompositor, test_touch::TestTouch,
+                test_xdg_base::TestXdgWmBase,
             },
             test_transport::TestTransport,
             test_utils::test_window::TestWindow,
@@ -38,6 +39,7 @@ pub struct DefaultSeat {
     pub seat: Rc<TestSeat>,
     pub kb: Rc<TestKeyboard>,
     pub pointer: Rc<TestPointer>,
+    pub touch: Rc<TestTouch>,
 }
 
 impl TestClient {
@@ -71,10 +73,12 @@ impl TestClient {
         tseat.server.set(Some(server));
         let pointer = tseat.get_pointer().await?;
         let tkb = tseat.get_keyboard().await?;
+        let touch = tseat.get_touch().await?;
         Ok(DefaultSeat {
             seat: tseat,
             kb: tkb,
             pointer,
+            touch,
         })
     }
 
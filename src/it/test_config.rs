@@ -1,6 +1,6 @@
 use {
     crate::{
-        backend::InputDeviceId,
+        backend::{ConnectorId, InputDeviceId},
         ifs::wl_seat::SeatId,
         it::test_error::{TestError, TestResult},
         utils::{copyhashmap::CopyHashMap, stack::Stack},
@@ -12,11 +12,18 @@ use {
             ipc::{ClientMessage, Response, ServerMessage},
             ConfigEntry, VERSION,
         },
-        input::{InputDevice, Seat},
+        input::{InputDevice, Seat, ShortcutsInhibitRequest},
         keyboard::{Keymap, ModifiedKeySym},
-        Axis, Direction,
+        video::{Connector, Mode, VrrMode},
+        window::Window,
+        Axis, Direction, Workspace,
+    },
+    std::{
+        cell::{Cell, RefCell},
+        ops::Deref,
+        ptr,
+        rc::Rc,
     },
-    std::{cell::Cell, ops::Deref, ptr, rc::Rc},
 };
 
 pub static TEST_CONFIG_ENTRY: ConfigEntry = ConfigEntry {
@@ -38,7 +45,21 @@ where
             srv: Cell::new(None),
             responses: Default::default(),
             invoked_shortcuts: Default::default(),
+            invoked_swipes: Default::default(),
             graphics_initialized: Cell::new(false),
+            shortcuts_inhibit_requests: Default::default(),
+            allow_shortcuts_inhibit: Cell::new(true),
+            new_windows: Default::default(),
+            float_new_windows: Cell::new(None),
+            new_window_workspace: Cell::new(None),
+            focused_windows: Default::default(),
+            title_changes: Default::default(),
+            fullscreen_changes: Default::default(),
+            created_workspaces: Default::default(),
+            destroyed_workspaces: Default::default(),
+            shown_workspaces: Default::default(),
+            activation_requests: Default::default(),
+            allow_activation: Cell::new(true),
         });
         let old = CONFIG;
         CONFIG = tc.deref();
@@ -106,6 +127,61 @@ unsafe extern "C" fn handle_msg(data: *const u8, msg: *const u8, size: usize) {
         ServerMessage::DelDrmDev { .. } => {}
         ServerMessage::Idle => {}
         ServerMessage::DevicesEnumerated => {}
+        ServerMessage::InvokeSwipe {
+            seat,
+            fingers,
+            direction,
+        } => {
+            tc.invoked_swipes
+                .set((SeatId::from_raw(seat.0 as _), fingers, direction), ());
+        }
+        ServerMessage::ShortcutsInhibitRequest { request } => {
+            tc.shortcuts_inhibit_requests.borrow_mut().push(request);
+            let allow = tc.allow_shortcuts_inhibit.get();
+            let _ = tc.send(ClientMessage::ShortcutsInhibitDecision { allow });
+        }
+        ServerMessage::NewWindow { window } => {
+            tc.new_windows.borrow_mut().push(window);
+            if let Some((width, height)) = tc.float_new_windows.get() {
+                let _ = tc.send(ClientMessage::SetWindowFloating {
+                    window,
+                    floating: true,
+                });
+                let _ = tc.send(ClientMessage::SetWindowFloatSize {
+                    window,
+                    width,
+                    height,
+                });
+            }
+            if let Some(workspace) = tc.new_window_workspace.get() {
+                let _ = tc.send(ClientMessage::SetWindowWorkspace { window, workspace });
+            }
+        }
+        ServerMessage::WindowFocused { window, .. } => {
+            tc.focused_windows.borrow_mut().push(window);
+        }
+        ServerMessage::WindowTitleChanged { window } => {
+            tc.title_changes.borrow_mut().push(window);
+        }
+        ServerMessage::WindowFullscreenChanged { window, fullscreen } => {
+            tc.fullscreen_changes
+                .borrow_mut()
+                .push((window, fullscreen));
+        }
+        ServerMessage::WorkspaceCreated { workspace, .. } => {
+            tc.created_workspaces.borrow_mut().push(workspace);
+        }
+        ServerMessage::WorkspaceDestroyed { workspace, .. } => {
+            tc.destroyed_workspaces.borrow_mut().push(workspace);
+        }
+        ServerMessage::WorkspaceShown { workspace, .. } => {
+            tc.shown_workspaces.borrow_mut().push(workspace);
+        }
+        ServerMessage::ActivationRequest { window } => {
+            tc.activation_requests.borrow_mut().push(window);
+            let allow = tc.allow_activation.get();
+            let _ = tc.send(ClientMessage::ActivationDecision { allow });
+        }
     }
 }
 
@@ -120,7 +196,21 @@ pub struct TestConfig {
     srv: Cell<Option<ServerData>>,
     responses: Stack<Response>,
     pub invoked_shortcuts: CopyHashMap<(SeatId, ModifiedKeySym), ()>,
+    pub invoked_swipes: CopyHashMap<(SeatId, u32, Direction), ()>,
     pub graphics_initialized: Cell<bool>,
+    pub shortcuts_inhibit_requests: RefCell<Vec<ShortcutsInhibitRequest>>,
+    pub allow_shortcuts_inhibit: Cell<bool>,
+    pub new_windows: RefCell<Vec<Window>>,
+    pub float_new_windows: Cell<Option<(i32, i32)>>,
+    pub new_window_workspace: Cell<Option<Workspace>>,
+    pub focused_windows: RefCell<Vec<Option<Window>>>,
+    pub title_changes: RefCell<Vec<Window>>,
+    pub fullscreen_changes: RefCell<Vec<(Window, bool)>>,
+    pub created_workspaces: RefCell<Vec<Workspace>>,
+    pub destroyed_workspaces: RefCell<Vec<Workspace>>,
+    pub shown_workspaces: RefCell<Vec<Workspace>>,
+    pub activation_requests: RefCell<Vec<Window>>,
+    pub allow_activation: Cell<bool>,
 }
 
 macro_rules! get_response {
@@ -222,6 +312,60 @@ impl TestConfig {
         })
     }
 
+    pub fn set_shortcuts_inhibit_escape<T: Into<ModifiedKeySym>>(
+        &self,
+        seat: SeatId,
+        key: T,
+    ) -> TestResult {
+        self.send(ClientMessage::SeatSetShortcutsInhibitEscape {
+            seat: Seat(seat.raw() as _),
+            escape: Some(key.into()),
+        })
+    }
+
+    pub fn set_shortcuts_inhibit_hook(&self) -> TestResult {
+        self.send(ClientMessage::SetShortcutsInhibitHook { enabled: true })
+    }
+
+    pub fn set_activation_hook(&self) -> TestResult {
+        self.send(ClientMessage::SetActivationHook { enabled: true })
+    }
+
+    pub fn add_swipe_binding(
+        &self,
+        seat: SeatId,
+        fingers: u32,
+        direction: Direction,
+    ) -> TestResult {
+        self.send(ClientMessage::AddSwipeBinding {
+            seat: Seat(seat.raw() as _),
+            fingers,
+            direction,
+        })
+    }
+
+    pub fn connector_modes(&self, connector: ConnectorId) -> TestResult<Vec<Mode>> {
+        let reply = self.send_with_reply(ClientMessage::ConnectorModes {
+            connector: Connector(connector.raw() as _),
+        })?;
+        get_response!(reply, ConnectorModes { modes });
+        Ok(modes)
+    }
+
+    pub fn set_connector_mode(&self, connector: ConnectorId, mode: Mode) -> TestResult {
+        self.send(ClientMessage::ConnectorSetMode {
+            connector: Connector(connector.raw() as _),
+            mode,
+        })
+    }
+
+    pub fn set_connector_vrr_mode(&self, connector: ConnectorId, mode: VrrMode) -> TestResult {
+        self.send(ClientMessage::ConnectorSetVrrMode {
+            connector: Connector(connector.raw() as _),
+            mode,
+        })
+    }
+
     pub fn set_input_device_seat(&self, id: InputDeviceId, seat: SeatId) -> Result<(), TestError> {
         self.send(ClientMessage::SetSeat {
             device: InputDevice(id.raw() as _),
@@ -243,6 +387,92 @@ impl TestConfig {
         })
     }
 
+    pub fn window_app_id(&self, window: Window) -> TestResult<String> {
+        let reply = self.send_with_reply(ClientMessage::GetWindowAppId { window })?;
+        get_response!(reply, GetWindowAppId { app_id });
+        Ok(app_id)
+    }
+
+    pub fn set_window_workspace(&self, window: Window, name: &str) -> TestResult {
+        let reply = self.send_with_reply(ClientMessage::GetWorkspace { name })?;
+        get_response!(reply, GetWorkspace { workspace });
+        self.send(ClientMessage::SetWindowWorkspace { window, workspace })
+    }
+
+    pub fn get_workspace(&self, name: &str) -> TestResult<Workspace> {
+        let reply = self.send_with_reply(ClientMessage::GetWorkspace { name })?;
+        get_response!(reply, GetWorkspace { workspace });
+        Ok(workspace)
+    }
+
+    pub fn set_workspace_output(&self, name: &str, connector: ConnectorId) -> TestResult {
+        let workspace = self.get_workspace(name)?;
+        self.send(ClientMessage::SetWorkspaceOutput {
+            workspace,
+            connector: Connector(connector.raw() as _),
+        })
+    }
+
+    pub fn move_workspace_to_output(&self, name: &str, direction: Direction) -> TestResult {
+        let workspace = self.get_workspace(name)?;
+        self.send(ClientMessage::MoveWorkspaceToOutput {
+            workspace,
+            direction,
+        })
+    }
+
+    pub fn hide_window(&self, window: Window) -> TestResult {
+        self.send(ClientMessage::HideWindow { window })
+    }
+
+    pub fn window_hidden(&self, window: Window) -> TestResult<bool> {
+        let reply = self.send_with_reply(ClientMessage::GetWindowHidden { window })?;
+        get_response!(reply, GetWindowHidden { hidden });
+        Ok(hidden)
+    }
+
+    pub fn toggle_scratchpad(&self, seat: SeatId, name: &str) -> TestResult {
+        self.send(ClientMessage::ToggleScratchpad {
+            seat: Seat(seat.raw() as _),
+            name,
+        })
+    }
+
+    pub fn set_window_fullscreen(&self, window: Window, fullscreen: bool) -> TestResult {
+        self.send(ClientMessage::SetWindowFullscreen { window, fullscreen })
+    }
+
+    pub fn set_window_sticky(&self, window: Window, sticky: bool) -> TestResult {
+        self.send(ClientMessage::SetWindowSticky { window, sticky })
+    }
+
+    pub fn workspace_windows(&self, name: &str) -> TestResult<Vec<Window>> {
+        let reply = self.send_with_reply(ClientMessage::GetWorkspace { name })?;
+        get_response!(reply, GetWorkspace { workspace });
+        let reply = self.send_with_reply(ClientMessage::GetWorkspaceWindows { workspace })?;
+        get_response!(reply, GetWindows { windows });
+        Ok(windows)
+    }
+
+    pub fn focused_window(&self, seat: SeatId) -> TestResult<Option<Window>> {
+        let reply = self.send_with_reply(ClientMessage::GetFocusedWindow {
+            seat: Seat(seat.raw() as _),
+        })?;
+        get_response!(reply, GetFocusedWindow { window });
+        Ok(window)
+    }
+
+    pub fn focus_window(&self, seat: SeatId, window: Window) -> TestResult {
+        self.send(ClientMessage::FocusWindow {
+            window,
+            seat: Seat(seat.raw() as _),
+        })
+    }
+
+    pub fn close_window(&self, window: Window) -> TestResult {
+        self.send(ClientMessage::CloseWindow { window })
+    }
+
     fn clear(&self) {
         unsafe {
             if let Some(srv) = self.srv.take() {
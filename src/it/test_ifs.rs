@@ -1,8 +1,33 @@
+pub mod test_activation;
+pub mod test_activation_token;
 pub mod test_callback;
 pub mod test_compositor;
+pub mod test_cursor_shape_device;
+pub mod test_cursor_shape_manager;
+pub mod test_data_control_device;
+pub mod test_data_control_manager;
+pub mod test_data_control_offer;
+pub mod test_data_control_source;
 pub mod test_display;
+pub mod test_foreign_toplevel_handle;
+pub mod test_foreign_toplevel_manager;
+pub mod test_gamma_control;
+pub mod test_gamma_control_manager;
+pub mod test_idle_notification;
+pub mod test_idle_notifier;
+pub mod test_input_method;
+pub mod test_input_method_keyboard_grab;
+pub mod test_input_method_manager;
 pub mod test_jay_compositor;
 pub mod test_keyboard;
+pub mod test_keyboard_shortcuts_inhibit_manager;
+pub mod test_keyboard_shortcuts_inhibitor;
+pub mod test_output;
+pub mod test_output_configuration;
+pub mod test_output_configuration_head;
+pub mod test_output_head;
+pub mod test_output_manager;
+pub mod test_output_mode;
 pub mod test_pointer;
 pub mod test_region;
 pub mod test_registry;
@@ -14,6 +39,18 @@ pub mod test_shm_pool;
 pub mod test_subcompositor;
 pub mod test_subsurface;
 pub mod test_surface;
+pub mod test_tearing_control;
+pub mod test_tearing_control_manager;
+pub mod test_text_input;
+pub mod test_text_input_manager;
+pub mod test_touch;
+pub mod test_virtual_keyboard;
+pub mod test_virtual_keyboard_manager;
+pub mod test_virtual_pointer;
+pub mod test_virtual_pointer_manager;
+pub mod test_workspace;
+pub mod test_workspace_group;
+pub mod test_workspace_manager;
 pub mod test_xdg_base;
 pub mod test_xdg_surface;
 pub mod test_xdg_toplevel;
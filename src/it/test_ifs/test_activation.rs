@@ -0,0 +1,64 @@
+use {
+    crate::{
+        it::{
+            test_error::TestError, test_ifs::test_activation_token::TestActivationToken,
+            test_object::TestObject, test_transport::TestTransport,
+        },
+        wire::{xdg_activation_v1::*, WlSurfaceId, XdgActivationV1Id},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+};
+
+pub struct TestActivation {
+    pub id: XdgActivationV1Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+}
+
+impl TestActivation {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn get_activation_token(&self) -> Result<Rc<TestActivationToken>, TestError> {
+        let obj = Rc::new(TestActivationToken {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+            token: RefCell::new(None),
+        });
+        self.tran.send(GetActivationToken {
+            self_id: self.id,
+            id: obj.id,
+        })?;
+        self.tran.add_obj(obj.clone())?;
+        Ok(obj)
+    }
+
+    pub fn activate(&self, token: &str, surface: WlSurfaceId) -> Result<(), TestError> {
+        self.tran.send(Activate {
+            self_id: self.id,
+            token,
+            surface,
+        })?;
+        Ok(())
+    }
+}
+
+impl Drop for TestActivation {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestActivation, XdgActivationV1;
+}
+
+impl TestObject for TestActivation {}
@@ -0,0 +1,57 @@
+use {
+    crate::{
+        it::{
+            test_error::{TestError, TestResult},
+            test_object::TestObject,
+            test_transport::TestTransport,
+            testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{xdg_activation_token_v1::*, XdgActivationTokenV1Id},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+};
+
+pub struct TestActivationToken {
+    pub id: XdgActivationTokenV1Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+    pub token: RefCell<Option<String>>,
+}
+
+impl TestActivationToken {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn commit(&self) -> Result<(), TestError> {
+        self.tran.send(Commit { self_id: self.id })?;
+        Ok(())
+    }
+
+    fn handle_done(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Done::parse_full(parser)?;
+        *self.token.borrow_mut() = Some(ev.token.to_string());
+        Ok(())
+    }
+}
+
+impl Drop for TestActivationToken {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestActivationToken, XdgActivationTokenV1;
+
+    DONE => handle_done,
+}
+
+impl TestObject for TestActivationToken {}
@@ -0,0 +1,55 @@
+use {
+    crate::{
+        it::{
+            test_error::TestError, test_ifs::test_cursor_shape_device::TestCursorShapeDevice,
+            test_object::TestObject, test_transport::TestTransport,
+        },
+        wire::{wp_cursor_shape_manager_v1::*, WlPointerId, WpCursorShapeManagerV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestCursorShapeManager {
+    pub id: WpCursorShapeManagerV1Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+}
+
+impl TestCursorShapeManager {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn get_pointer(
+        &self,
+        pointer: WlPointerId,
+    ) -> Result<Rc<TestCursorShapeDevice>, TestError> {
+        let obj = Rc::new(TestCursorShapeDevice {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+        });
+        self.tran.send(GetPointer {
+            self_id: self.id,
+            cursor_shape_device: obj.id,
+            pointer,
+        })?;
+        self.tran.add_obj(obj.clone())?;
+        Ok(obj)
+    }
+}
+
+impl Drop for TestCursorShapeManager {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestCursorShapeManager, WpCursorShapeManagerV1;
+}
+
+impl TestObject for TestCursorShapeManager {}
@@ -0,0 +1,126 @@
+use {
+    crate::{
+        it::{
+            test_error::{TestError, TestResult},
+            test_ifs::{
+                test_data_control_offer::TestDataControlOffer,
+                test_data_control_source::TestDataControlSource,
+            },
+            test_object::TestObject,
+            test_transport::TestTransport,
+            testrun::ParseFull,
+        },
+        utils::{buffd::MsgParser, clonecell::CloneCell, copyhashmap::CopyHashMap},
+        wire::{
+            zwlr_data_control_device_v1::*, ZwlrDataControlDeviceV1Id, ZwlrDataControlOfferV1Id,
+            ZwlrDataControlSourceV1Id,
+        },
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+};
+
+pub struct TestDataControlDevice {
+    pub id: ZwlrDataControlDeviceV1Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+    pub pending: CopyHashMap<ZwlrDataControlOfferV1Id, Rc<TestDataControlOffer>>,
+    pub selection: CloneCell<Option<Rc<TestDataControlOffer>>>,
+    pub primary_selection: CloneCell<Option<Rc<TestDataControlOffer>>>,
+    pub finished: Cell<bool>,
+}
+
+impl TestDataControlDevice {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn set_selection(&self, source: Option<&TestDataControlSource>) -> Result<(), TestError> {
+        self.tran.send(SetSelection {
+            self_id: self.id,
+            source: source
+                .map(|s| s.id)
+                .unwrap_or(ZwlrDataControlSourceV1Id::NONE),
+        })?;
+        Ok(())
+    }
+
+    pub fn set_primary_selection(
+        &self,
+        source: Option<&TestDataControlSource>,
+    ) -> Result<(), TestError> {
+        self.tran.send(SetPrimarySelection {
+            self_id: self.id,
+            source: source
+                .map(|s| s.id)
+                .unwrap_or(ZwlrDataControlSourceV1Id::NONE),
+        })?;
+        Ok(())
+    }
+
+    fn take_offer(
+        &self,
+        id: ZwlrDataControlOfferV1Id,
+    ) -> TestResult<Option<Rc<TestDataControlOffer>>> {
+        if id.is_none() {
+            return Ok(None);
+        }
+        match self.pending.remove(&id) {
+            Some(offer) => Ok(Some(offer)),
+            _ => bail!("Compositor sent an unknown offer"),
+        }
+    }
+
+    fn handle_data_offer(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = DataOffer::parse_full(parser)?;
+        let offer = Rc::new(TestDataControlOffer {
+            id: ev.id,
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+            mime_types: RefCell::new(vec![]),
+        });
+        self.tran.add_obj(offer.clone())?;
+        self.pending.set(ev.id, offer);
+        Ok(())
+    }
+
+    fn handle_selection(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Selection::parse_full(parser)?;
+        self.selection.set(self.take_offer(ev.id)?);
+        Ok(())
+    }
+
+    fn handle_finished(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Finished::parse_full(parser)?;
+        self.finished.set(true);
+        Ok(())
+    }
+
+    fn handle_primary_selection(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = PrimarySelection::parse_full(parser)?;
+        self.primary_selection.set(self.take_offer(ev.id)?);
+        Ok(())
+    }
+}
+
+impl Drop for TestDataControlDevice {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestDataControlDevice, ZwlrDataControlDeviceV1;
+
+    DATA_OFFER => handle_data_offer,
+    SELECTION => handle_selection,
+    FINISHED => handle_finished,
+    PRIMARY_SELECTION => handle_primary_selection,
+}
+
+impl TestObject for TestDataControlDevice {}
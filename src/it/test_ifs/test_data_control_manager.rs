@@ -0,0 +1,77 @@
+use {
+    crate::{
+        it::{
+            test_error::TestError,
+            test_ifs::{
+                test_data_control_device::TestDataControlDevice,
+                test_data_control_source::TestDataControlSource, test_seat::TestSeat,
+            },
+            test_object::TestObject,
+            test_transport::TestTransport,
+        },
+        wire::{zwlr_data_control_manager_v1::*, ZwlrDataControlManagerV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestDataControlManager {
+    pub id: ZwlrDataControlManagerV1Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+}
+
+impl TestDataControlManager {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn create_data_source(&self) -> Result<Rc<TestDataControlSource>, TestError> {
+        let obj = Rc::new(TestDataControlSource {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+            sends: Default::default(),
+            cancelled: Cell::new(false),
+        });
+        self.tran.send(CreateDataSource {
+            self_id: self.id,
+            id: obj.id,
+        })?;
+        self.tran.add_obj(obj.clone())?;
+        Ok(obj)
+    }
+
+    pub fn get_data_device(&self, seat: &TestSeat) -> Result<Rc<TestDataControlDevice>, TestError> {
+        let obj = Rc::new(TestDataControlDevice {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+            pending: Default::default(),
+            selection: Default::default(),
+            primary_selection: Default::default(),
+            finished: Cell::new(false),
+        });
+        self.tran.send(GetDataDevice {
+            self_id: self.id,
+            id: obj.id,
+            seat: seat.id,
+        })?;
+        self.tran.add_obj(obj.clone())?;
+        Ok(obj)
+    }
+}
+
+impl Drop for TestDataControlManager {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestDataControlManager, ZwlrDataControlManagerV1;
+}
+
+impl TestObject for TestDataControlManager {}
@@ -0,0 +1,64 @@
+use {
+    crate::{
+        it::{
+            test_error::{TestError, TestResult},
+            test_object::TestObject,
+            test_transport::TestTransport,
+            testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{zwlr_data_control_offer_v1::*, ZwlrDataControlOfferV1Id},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+    uapi::{c, OwnedFd},
+};
+
+pub struct TestDataControlOffer {
+    pub id: ZwlrDataControlOfferV1Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+    pub mime_types: RefCell<Vec<String>>,
+}
+
+impl TestDataControlOffer {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    /// Requests the data and returns the read end of the pipe.
+    pub fn receive(&self, mime_type: &str) -> Result<OwnedFd, TestError> {
+        let (read, write) = uapi::pipe2(c::O_CLOEXEC)?;
+        self.tran.send(Receive {
+            self_id: self.id,
+            mime_type,
+            fd: Rc::new(write),
+        })?;
+        Ok(read)
+    }
+
+    fn handle_offer(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Offer::parse_full(parser)?;
+        self.mime_types.borrow_mut().push(ev.mime_type.to_string());
+        Ok(())
+    }
+}
+
+impl Drop for TestDataControlOffer {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestDataControlOffer, ZwlrDataControlOfferV1;
+
+    OFFER => handle_offer,
+}
+
+impl TestObject for TestDataControlOffer {}
@@ -0,0 +1,110 @@
+use {
+    crate::{
+        it::{
+            test_error::{TestError, TestResult},
+            test_object::TestObject,
+            test_transport::TestTransport,
+            test_utils::test_expected_event::TEEH,
+            testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{zwlr_foreign_toplevel_handle_v1::*, WlSeatId, ZwlrForeignToplevelHandleV1Id},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+};
+
+pub struct TestForeignToplevelHandle {
+    pub id: ZwlrForeignToplevelHandleV1Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+    pub title: RefCell<String>,
+    pub app_id: RefCell<String>,
+    pub states: RefCell<Vec<u32>>,
+    pub done: TEEH<()>,
+    pub closed: Cell<bool>,
+}
+
+impl TestForeignToplevelHandle {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn activate(&self, seat: WlSeatId) -> Result<(), TestError> {
+        self.tran.send(Activate {
+            self_id: self.id,
+            seat,
+        })?;
+        Ok(())
+    }
+
+    pub fn close(&self) -> Result<(), TestError> {
+        self.tran.send(Close { self_id: self.id })?;
+        Ok(())
+    }
+
+    fn handle_title(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Title::parse_full(parser)?;
+        *self.title.borrow_mut() = ev.title.to_string();
+        Ok(())
+    }
+
+    fn handle_app_id(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = AppId::parse_full(parser)?;
+        *self.app_id.borrow_mut() = ev.app_id.to_string();
+        Ok(())
+    }
+
+    fn handle_output_enter(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = OutputEnter::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_output_leave(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = OutputLeave::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_state(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = State::parse_full(parser)?;
+        *self.states.borrow_mut() = ev.state.to_vec();
+        Ok(())
+    }
+
+    fn handle_done(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Done::parse_full(parser)?;
+        self.done.push(());
+        Ok(())
+    }
+
+    fn handle_closed(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Closed::parse_full(parser)?;
+        self.closed.set(true);
+        Ok(())
+    }
+}
+
+impl Drop for TestForeignToplevelHandle {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestForeignToplevelHandle, ZwlrForeignToplevelHandleV1;
+
+    TITLE => handle_title,
+    APP_ID => handle_app_id,
+    OUTPUT_ENTER => handle_output_enter,
+    OUTPUT_LEAVE => handle_output_leave,
+    STATE => handle_state,
+    DONE => handle_done,
+    CLOSED => handle_closed,
+}
+
+impl TestObject for TestForeignToplevelHandle {}
@@ -0,0 +1,57 @@
+use {
+    crate::{
+        it::{
+            test_error::TestResult,
+            test_ifs::test_foreign_toplevel_handle::TestForeignToplevelHandle,
+            test_object::TestObject, test_transport::TestTransport, testrun::ParseFull,
+        },
+        utils::{buffd::MsgParser, copyhashmap::CopyHashMap},
+        wire::{
+            zwlr_foreign_toplevel_manager_v1::*, ZwlrForeignToplevelHandleV1Id,
+            ZwlrForeignToplevelManagerV1Id,
+        },
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+};
+
+pub struct TestForeignToplevelManager {
+    pub id: ZwlrForeignToplevelManagerV1Id,
+    pub tran: Rc<TestTransport>,
+    pub toplevels: CopyHashMap<ZwlrForeignToplevelHandleV1Id, Rc<TestForeignToplevelHandle>>,
+}
+
+impl TestForeignToplevelManager {
+    fn handle_toplevel(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Toplevel::parse_full(parser)?;
+        let handle = Rc::new(TestForeignToplevelHandle {
+            id: ev.toplevel,
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+            title: RefCell::new(String::new()),
+            app_id: RefCell::new(String::new()),
+            states: RefCell::new(vec![]),
+            done: Default::default(),
+            closed: Cell::new(false),
+        });
+        self.tran.add_obj(handle.clone())?;
+        self.toplevels.set(ev.toplevel, handle);
+        Ok(())
+    }
+
+    fn handle_finished(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Finished::parse_full(parser)?;
+        Ok(())
+    }
+}
+
+test_object! {
+    TestForeignToplevelManager, ZwlrForeignToplevelManagerV1;
+
+    TOPLEVEL => handle_toplevel,
+    FINISHED => handle_finished,
+}
+
+impl TestObject for TestForeignToplevelManager {}
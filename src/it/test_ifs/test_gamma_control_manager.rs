@@ -0,0 +1,59 @@
+use {
+    crate::{
+        it::{
+            test_error::TestError,
+            test_ifs::{test_gamma_control::TestGammaControl, test_output::TestOutput},
+            test_object::TestObject,
+            test_transport::TestTransport,
+        },
+        wire::{zwlr_gamma_control_manager_v1::*, ZwlrGammaControlManagerV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestGammaControlManager {
+    pub id: ZwlrGammaControlManagerV1Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+}
+
+impl TestGammaControlManager {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn get_gamma_control(
+        &self,
+        output: &TestOutput,
+    ) -> Result<Rc<TestGammaControl>, TestError> {
+        let obj = Rc::new(TestGammaControl {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+            size: Cell::new(None),
+            failed: Cell::new(false),
+        });
+        self.tran.send(GetGammaControl {
+            self_id: self.id,
+            id: obj.id,
+            output: output.id,
+        })?;
+        self.tran.add_obj(obj.clone())?;
+        Ok(obj)
+    }
+}
+
+impl Drop for TestGammaControlManager {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestGammaControlManager, ZwlrGammaControlManagerV1;
+}
+
+impl TestObject for TestGammaControlManager {}
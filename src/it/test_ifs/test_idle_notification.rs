@@ -0,0 +1,56 @@
+use {
+    crate::{
+        it::{
+            test_error::{TestError, TestResult},
+            test_object::TestObject,
+            test_transport::TestTransport,
+            testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{ext_idle_notification_v1::*, ExtIdleNotificationV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestIdleNotification {
+    pub id: ExtIdleNotificationV1Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+    pub idle: Cell<bool>,
+}
+
+impl TestIdleNotification {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    fn handle_idled(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Idled::parse_full(parser)?;
+        self.idle.set(true);
+        Ok(())
+    }
+
+    fn handle_resumed(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Resumed::parse_full(parser)?;
+        self.idle.set(false);
+        Ok(())
+    }
+}
+
+impl Drop for TestIdleNotification {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestIdleNotification, ExtIdleNotificationV1;
+
+    IDLED => handle_idled,
+    RESUMED => handle_resumed,
+}
+
+impl TestObject for TestIdleNotification {}
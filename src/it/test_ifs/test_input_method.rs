@@ -0,0 +1,127 @@
+use {
+    crate::{
+        it::{
+            test_error::{TestError, TestResult},
+            test_ifs::test_input_method_keyboard_grab::TestInputMethodKeyboardGrab,
+            test_object::TestObject,
+            test_transport::TestTransport,
+            test_utils::test_expected_event::TEEH,
+            testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{zwp_input_method_v2::*, ZwpInputMethodV2Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestInputMethod {
+    pub id: ZwpInputMethodV2Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+    pub activate: TEEH<()>,
+    pub deactivate: TEEH<()>,
+    pub done: TEEH<()>,
+    pub unavailable: TEEH<()>,
+    pub num_done: Cell<u32>,
+}
+
+impl TestInputMethod {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn commit_string(&self, text: &str) -> Result<(), TestError> {
+        self.tran.send(CommitString {
+            self_id: self.id,
+            text,
+        })?;
+        Ok(())
+    }
+
+    /// Commits the pending state using the serial of the last `done` event.
+    pub fn commit(&self) -> Result<(), TestError> {
+        self.tran.send(Commit {
+            self_id: self.id,
+            serial: self.num_done.get(),
+        })?;
+        Ok(())
+    }
+
+    pub fn grab_keyboard(&self) -> Result<Rc<TestInputMethodKeyboardGrab>, TestError> {
+        let obj = Rc::new(TestInputMethodKeyboardGrab {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+            key: Default::default(),
+        });
+        self.tran.send(GrabKeyboard {
+            self_id: self.id,
+            keyboard: obj.id,
+        })?;
+        self.tran.add_obj(obj.clone())?;
+        Ok(obj)
+    }
+
+    fn handle_activate(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Activate::parse_full(parser)?;
+        self.activate.push(());
+        Ok(())
+    }
+
+    fn handle_deactivate(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Deactivate::parse_full(parser)?;
+        self.deactivate.push(());
+        Ok(())
+    }
+
+    fn handle_surrounding_text(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = SurroundingText::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_text_change_cause(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = TextChangeCause::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_content_type(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = ContentType::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_done(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Done::parse_full(parser)?;
+        self.num_done.set(self.num_done.get() + 1);
+        self.done.push(());
+        Ok(())
+    }
+
+    fn handle_unavailable(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Unavailable::parse_full(parser)?;
+        self.unavailable.push(());
+        Ok(())
+    }
+}
+
+impl Drop for TestInputMethod {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestInputMethod, ZwpInputMethodV2;
+
+    ACTIVATE => handle_activate,
+    DEACTIVATE => handle_deactivate,
+    SURROUNDING_TEXT => handle_surrounding_text,
+    TEXT_CHANGE_CAUSE => handle_text_change_cause,
+    CONTENT_TYPE => handle_content_type,
+    DONE => handle_done,
+    UNAVAILABLE => handle_unavailable,
+}
+
+impl TestObject for TestInputMethod {}
@@ -0,0 +1,68 @@
+use {
+    crate::{
+        it::{
+            test_error::{TestError, TestResult},
+            test_object::TestObject,
+            test_transport::TestTransport,
+            test_utils::test_expected_event::TEEH,
+            testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{zwp_input_method_keyboard_grab_v2::*, ZwpInputMethodKeyboardGrabV2Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestInputMethodKeyboardGrab {
+    pub id: ZwpInputMethodKeyboardGrabV2Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+    pub key: TEEH<Key>,
+}
+
+impl TestInputMethodKeyboardGrab {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Release { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    fn handle_keymap(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Keymap::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_key(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Key::parse_full(parser)?;
+        self.key.push(ev);
+        Ok(())
+    }
+
+    fn handle_modifiers(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Modifiers::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_repeat_info(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = RepeatInfo::parse_full(parser)?;
+        Ok(())
+    }
+}
+
+impl Drop for TestInputMethodKeyboardGrab {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestInputMethodKeyboardGrab, ZwpInputMethodKeyboardGrabV2;
+
+    KEYMAP => handle_keymap,
+    KEY => handle_key,
+    MODIFIERS => handle_modifiers,
+    REPEAT_INFO => handle_repeat_info,
+}
+
+impl TestObject for TestInputMethodKeyboardGrab {}
@@ -0,0 +1,57 @@
+use {
+    crate::{
+        it::{
+            test_error::TestError, test_ifs::test_input_method::TestInputMethod,
+            test_object::TestObject, test_transport::TestTransport,
+        },
+        wire::{zwp_input_method_manager_v2::*, WlSeatId, ZwpInputMethodManagerV2Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestInputMethodManager {
+    pub id: ZwpInputMethodManagerV2Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+}
+
+impl TestInputMethodManager {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn get_input_method(&self, seat: WlSeatId) -> Result<Rc<TestInputMethod>, TestError> {
+        let obj = Rc::new(TestInputMethod {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+            activate: Default::default(),
+            deactivate: Default::default(),
+            done: Default::default(),
+            unavailable: Default::default(),
+            num_done: Cell::new(0),
+        });
+        self.tran.send(GetInputMethod {
+            self_id: self.id,
+            seat,
+            input_method: obj.id,
+        })?;
+        self.tran.add_obj(obj.clone())?;
+        Ok(obj)
+    }
+}
+
+impl Drop for TestInputMethodManager {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestInputMethodManager, ZwpInputMethodManagerV2;
+}
+
+impl TestObject for TestInputMethodManager {}
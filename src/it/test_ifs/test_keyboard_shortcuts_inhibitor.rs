@@ -0,0 +1,56 @@
+use {
+    crate::{
+        it::{
+            test_error::{TestError, TestResult},
+            test_object::TestObject,
+            test_transport::TestTransport,
+            testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{zwp_keyboard_shortcuts_inhibitor_v1::*, ZwpKeyboardShortcutsInhibitorV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestKeyboardShortcutsInhibitor {
+    pub id: ZwpKeyboardShortcutsInhibitorV1Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+    pub active: Cell<bool>,
+}
+
+impl TestKeyboardShortcutsInhibitor {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    fn handle_active(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Active::parse_full(parser)?;
+        self.active.set(true);
+        Ok(())
+    }
+
+    fn handle_inactive(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Inactive::parse_full(parser)?;
+        self.active.set(false);
+        Ok(())
+    }
+}
+
+impl Drop for TestKeyboardShortcutsInhibitor {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestKeyboardShortcutsInhibitor, ZwpKeyboardShortcutsInhibitorV1;
+
+    ACTIVE => handle_active,
+    INACTIVE => handle_inactive,
+}
+
+impl TestObject for TestKeyboardShortcutsInhibitor {}
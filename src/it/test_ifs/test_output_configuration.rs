@@ -0,0 +1,84 @@
+use {
+    crate::{
+        it::{
+            test_error::TestResult,
+            test_ifs::{
+                test_output_configuration_head::TestOutputConfigurationHead,
+                test_output_head::TestOutputHead,
+            },
+            test_object::TestObject,
+            test_transport::TestTransport,
+            testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{zwlr_output_configuration_v1::*, ZwlrOutputConfigurationV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TestOutputConfigurationResult {
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+pub struct TestOutputConfiguration {
+    pub id: ZwlrOutputConfigurationV1Id,
+    pub tran: Rc<TestTransport>,
+    pub result: Cell<Option<TestOutputConfigurationResult>>,
+}
+
+impl TestOutputConfiguration {
+    pub fn enable_head(
+        &self,
+        head: &TestOutputHead,
+    ) -> TestResult<Rc<TestOutputConfigurationHead>> {
+        let obj = Rc::new(TestOutputConfigurationHead {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+        });
+        self.tran.send(EnableHead {
+            self_id: self.id,
+            id: obj.id,
+            head: head.id,
+        })?;
+        self.tran.add_obj(obj.clone())?;
+        Ok(obj)
+    }
+
+    pub fn apply(&self) -> TestResult {
+        self.tran.send(Apply { self_id: self.id })?;
+        Ok(())
+    }
+
+    fn handle_succeeded(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Succeeded::parse_full(parser)?;
+        self.result
+            .set(Some(TestOutputConfigurationResult::Succeeded));
+        Ok(())
+    }
+
+    fn handle_failed(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Failed::parse_full(parser)?;
+        self.result.set(Some(TestOutputConfigurationResult::Failed));
+        Ok(())
+    }
+
+    fn handle_cancelled(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Cancelled::parse_full(parser)?;
+        self.result
+            .set(Some(TestOutputConfigurationResult::Cancelled));
+        Ok(())
+    }
+}
+
+test_object! {
+    TestOutputConfiguration, ZwlrOutputConfigurationV1;
+
+    SUCCEEDED => handle_succeeded,
+    FAILED => handle_failed,
+    CANCELLED => handle_cancelled,
+}
+
+impl TestObject for TestOutputConfiguration {}
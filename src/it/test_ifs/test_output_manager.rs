@@ -0,0 +1,78 @@
+use {
+    crate::{
+        it::{
+            test_error::TestResult,
+            test_ifs::{
+                test_output_configuration::TestOutputConfiguration,
+                test_output_head::TestOutputHead,
+            },
+            test_object::TestObject,
+            test_transport::TestTransport,
+            testrun::ParseFull,
+        },
+        utils::{buffd::MsgParser, copyhashmap::CopyHashMap},
+        wire::{zwlr_output_manager_v1::*, ZwlrOutputHeadV1Id, ZwlrOutputManagerV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestOutputManager {
+    pub id: ZwlrOutputManagerV1Id,
+    pub tran: Rc<TestTransport>,
+    pub heads: CopyHashMap<ZwlrOutputHeadV1Id, Rc<TestOutputHead>>,
+    pub serial: Cell<u32>,
+}
+
+impl TestOutputManager {
+    pub fn create_configuration(&self) -> TestResult<Rc<TestOutputConfiguration>> {
+        let obj = Rc::new(TestOutputConfiguration {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            result: Cell::new(None),
+        });
+        self.tran.send(CreateConfiguration {
+            self_id: self.id,
+            id: obj.id,
+            serial: self.serial.get(),
+        })?;
+        self.tran.add_obj(obj.clone())?;
+        Ok(obj)
+    }
+
+    fn handle_head(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Head::parse_full(parser)?;
+        let head = Rc::new(TestOutputHead {
+            id: ev.head,
+            tran: self.tran.clone(),
+            name: Default::default(),
+            modes: Default::default(),
+            current_mode: Cell::new(None),
+            enabled: Cell::new(false),
+            position: Cell::new((0, 0)),
+        });
+        self.tran.add_obj(head.clone())?;
+        self.heads.set(ev.head, head);
+        Ok(())
+    }
+
+    fn handle_done(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Done::parse_full(parser)?;
+        self.serial.set(ev.serial);
+        Ok(())
+    }
+
+    fn handle_finished(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Finished::parse_full(parser)?;
+        Ok(())
+    }
+}
+
+test_object! {
+    TestOutputManager, ZwlrOutputManagerV1;
+
+    HEAD => handle_head,
+    DONE => handle_done,
+    FINISHED => handle_finished,
+}
+
+impl TestObject for TestOutputManager {}
@@ -5,14 +5,28 @@ use {
         it::{
             test_error::TestError,
             test_ifs::{
-                test_compositor::TestCompositor, test_jay_compositor::TestJayCompositor,
-                test_shm::TestShm, test_subcompositor::TestSubcompositor,
-                test_xdg_base::TestXdgWmBase,
+                test_activation::TestActivation, test_compositor::TestCompositor,
+                test_cursor_shape_manager::TestCursorShapeManager,
+                test_data_control_manager::TestDataControlManager,
+                test_foreign_toplevel_manager::TestForeignToplevelManager,
+                test_gamma_control_manager::TestGammaControlManager,
+                test_idle_notifier::TestIdleNotifier,
+                test_input_method_manager::TestInputMethodManager,
+                test_jay_compositor::TestJayCompositor,
+                test_keyboard_shortcuts_inhibit_manager::TestKeyboardShortcutsInhibitManager,
+                test_output::TestOutput, test_output_manager::TestOutputManager, test_shm::TestShm,
+                test_subcompositor::TestSubcompositor,
+                test_tearing_control_manager::TestTearingControlManager,
+                test_text_input_manager::TestTextInputManager,
+                test_virtual_keyboard_manager::TestVirtualKeyboardManager,
+                test_virtual_pointer_manager::TestVirtualPointerManager,
+                test_workspace_manager::TestWorkspaceManager, test_xdg_base::TestXdgWmBase,
             },
             test_object::TestObject,
             test_transport::TestTransport,
             testrun::ParseFull,
         },
+        tree::OutputNode,
         utils::{buffd::MsgParser, clonecell::CloneCell, copyhashmap::CopyHashMap},
         wire::{wl_registry::*, WlRegistryId, WlSeat},
     },
@@ -31,6 +45,20 @@ pub struct TestRegistrySingletons {
     pub wl_subcompositor: u32,
     pub wl_shm: u32,
     pub xdg_wm_base: u32,
+    pub zwlr_output_manager_v1: u32,
+    pub wp_tearing_control_manager_v1: u32,
+    pub zwp_text_input_manager_v3: u32,
+    pub zwp_input_method_manager_v2: u32,
+    pub zwp_virtual_keyboard_manager_v1: u32,
+    pub zwlr_virtual_pointer_manager_v1: u32,
+    pub zwlr_foreign_toplevel_manager_v1: u32,
+    pub ext_workspace_manager_v1: u32,
+    pub xdg_activation_v1: u32,
+    pub zwlr_gamma_control_manager_v1: u32,
+    pub zwlr_data_control_manager_v1: u32,
+    pub zwp_keyboard_shortcuts_inhibit_manager_v1: u32,
+    pub ext_idle_notifier_v1: u32,
+    pub wp_cursor_shape_manager_v1: u32,
 }
 
 pub struct TestRegistry {
@@ -43,6 +71,21 @@ pub struct TestRegistry {
     pub subcompositor: CloneCell<Option<Rc<TestSubcompositor>>>,
     pub shm: CloneCell<Option<Rc<TestShm>>>,
     pub xdg: CloneCell<Option<Rc<TestXdgWmBase>>>,
+    pub output_manager: CloneCell<Option<Rc<TestOutputManager>>>,
+    pub tearing_control_manager: CloneCell<Option<Rc<TestTearingControlManager>>>,
+    pub text_input_manager: CloneCell<Option<Rc<TestTextInputManager>>>,
+    pub input_method_manager: CloneCell<Option<Rc<TestInputMethodManager>>>,
+    pub virtual_keyboard_manager: CloneCell<Option<Rc<TestVirtualKeyboardManager>>>,
+    pub virtual_pointer_manager: CloneCell<Option<Rc<TestVirtualPointerManager>>>,
+    pub foreign_toplevel_manager: CloneCell<Option<Rc<TestForeignToplevelManager>>>,
+    pub workspace_manager: CloneCell<Option<Rc<TestWorkspaceManager>>>,
+    pub activation: CloneCell<Option<Rc<TestActivation>>>,
+    pub gamma_control_manager: CloneCell<Option<Rc<TestGammaControlManager>>>,
+    pub data_control_manager: CloneCell<Option<Rc<TestDataControlManager>>>,
+    pub keyboard_shortcuts_inhibit_manager:
+        CloneCell<Option<Rc<TestKeyboardShortcutsInhibitManager>>>,
+    pub idle_notifier: CloneCell<Option<Rc<TestIdleNotifier>>>,
+    pub cursor_shape_manager: CloneCell<Option<Rc<TestCursorShapeManager>>>,
     pub seats: CopyHashMap<GlobalName, Rc<WlSeatGlobal>>,
 }
 
@@ -90,6 +133,20 @@ impl TestRegistry {
             wl_subcompositor,
             wl_shm,
             xdg_wm_base,
+            zwlr_output_manager_v1,
+            wp_tearing_control_manager_v1,
+            zwp_text_input_manager_v3,
+            zwp_input_method_manager_v2,
+            zwp_virtual_keyboard_manager_v1,
+            zwlr_virtual_pointer_manager_v1,
+            zwlr_foreign_toplevel_manager_v1,
+            ext_workspace_manager_v1,
+            xdg_activation_v1,
+            zwlr_gamma_control_manager_v1,
+            zwlr_data_control_manager_v1,
+            zwp_keyboard_shortcuts_inhibit_manager_v1,
+            ext_idle_notifier_v1,
+            wp_cursor_shape_manager_v1,
         };
         self.singletons.set(Some(singletons.clone()));
         Ok(singletons)
@@ -165,6 +222,227 @@ impl TestRegistry {
         Ok(jc)
     }
 
+    pub async fn get_output_manager(&self) -> Result<Rc<TestOutputManager>, TestError> {
+        singleton!(self.output_manager);
+        let singletons = self.get_singletons().await?;
+        singleton!(self.output_manager);
+        let jc = Rc::new(TestOutputManager {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            heads: Default::default(),
+            serial: Cell::new(0),
+        });
+        self.bind(&jc, singletons.zwlr_output_manager_v1, 3)?;
+        self.output_manager.set(Some(jc.clone()));
+        Ok(jc)
+    }
+
+    pub async fn get_tearing_control_manager(
+        &self,
+    ) -> Result<Rc<TestTearingControlManager>, TestError> {
+        singleton!(self.tearing_control_manager);
+        let singletons = self.get_singletons().await?;
+        singleton!(self.tearing_control_manager);
+        let jc = Rc::new(TestTearingControlManager {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+        });
+        self.bind(&jc, singletons.wp_tearing_control_manager_v1, 1)?;
+        self.tearing_control_manager.set(Some(jc.clone()));
+        Ok(jc)
+    }
+
+    pub async fn get_text_input_manager(&self) -> Result<Rc<TestTextInputManager>, TestError> {
+        singleton!(self.text_input_manager);
+        let singletons = self.get_singletons().await?;
+        singleton!(self.text_input_manager);
+        let jc = Rc::new(TestTextInputManager {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+        });
+        self.bind(&jc, singletons.zwp_text_input_manager_v3, 1)?;
+        self.text_input_manager.set(Some(jc.clone()));
+        Ok(jc)
+    }
+
+    pub async fn get_input_method_manager(&self) -> Result<Rc<TestInputMethodManager>, TestError> {
+        singleton!(self.input_method_manager);
+        let singletons = self.get_singletons().await?;
+        singleton!(self.input_method_manager);
+        let jc = Rc::new(TestInputMethodManager {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+        });
+        self.bind(&jc, singletons.zwp_input_method_manager_v2, 1)?;
+        self.input_method_manager.set(Some(jc.clone()));
+        Ok(jc)
+    }
+
+    pub async fn get_virtual_keyboard_manager(
+        &self,
+    ) -> Result<Rc<TestVirtualKeyboardManager>, TestError> {
+        singleton!(self.virtual_keyboard_manager);
+        let singletons = self.get_singletons().await?;
+        singleton!(self.virtual_keyboard_manager);
+        let jc = Rc::new(TestVirtualKeyboardManager {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+        });
+        self.bind(&jc, singletons.zwp_virtual_keyboard_manager_v1, 1)?;
+        self.virtual_keyboard_manager.set(Some(jc.clone()));
+        Ok(jc)
+    }
+
+    pub async fn get_virtual_pointer_manager(
+        &self,
+    ) -> Result<Rc<TestVirtualPointerManager>, TestError> {
+        singleton!(self.virtual_pointer_manager);
+        let singletons = self.get_singletons().await?;
+        singleton!(self.virtual_pointer_manager);
+        let jc = Rc::new(TestVirtualPointerManager {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+        });
+        self.bind(&jc, singletons.zwlr_virtual_pointer_manager_v1, 2)?;
+        self.virtual_pointer_manager.set(Some(jc.clone()));
+        Ok(jc)
+    }
+
+    pub async fn get_foreign_toplevel_manager(
+        &self,
+    ) -> Result<Rc<TestForeignToplevelManager>, TestError> {
+        singleton!(self.foreign_toplevel_manager);
+        let singletons = self.get_singletons().await?;
+        singleton!(self.foreign_toplevel_manager);
+        let jc = Rc::new(TestForeignToplevelManager {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            toplevels: Default::default(),
+        });
+        self.bind(&jc, singletons.zwlr_foreign_toplevel_manager_v1, 2)?;
+        self.foreign_toplevel_manager.set(Some(jc.clone()));
+        Ok(jc)
+    }
+
+    pub async fn get_workspace_manager(&self) -> Result<Rc<TestWorkspaceManager>, TestError> {
+        singleton!(self.workspace_manager);
+        let singletons = self.get_singletons().await?;
+        singleton!(self.workspace_manager);
+        let jc = Rc::new(TestWorkspaceManager {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            groups: Default::default(),
+            workspaces: Default::default(),
+        });
+        self.bind(&jc, singletons.ext_workspace_manager_v1, 1)?;
+        self.workspace_manager.set(Some(jc.clone()));
+        Ok(jc)
+    }
+
+    pub async fn get_activation(&self) -> Result<Rc<TestActivation>, TestError> {
+        singleton!(self.activation);
+        let singletons = self.get_singletons().await?;
+        singleton!(self.activation);
+        let jc = Rc::new(TestActivation {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+        });
+        self.bind(&jc, singletons.xdg_activation_v1, 1)?;
+        self.activation.set(Some(jc.clone()));
+        Ok(jc)
+    }
+
+    pub async fn get_gamma_control_manager(
+        &self,
+    ) -> Result<Rc<TestGammaControlManager>, TestError> {
+        singleton!(self.gamma_control_manager);
+        let singletons = self.get_singletons().await?;
+        singleton!(self.gamma_control_manager);
+        let jc = Rc::new(TestGammaControlManager {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+        });
+        self.bind(&jc, singletons.zwlr_gamma_control_manager_v1, 1)?;
+        self.gamma_control_manager.set(Some(jc.clone()));
+        Ok(jc)
+    }
+
+    pub async fn get_data_control_manager(&self) -> Result<Rc<TestDataControlManager>, TestError> {
+        singleton!(self.data_control_manager);
+        let singletons = self.get_singletons().await?;
+        singleton!(self.data_control_manager);
+        let jc = Rc::new(TestDataControlManager {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+        });
+        self.bind(&jc, singletons.zwlr_data_control_manager_v1, 2)?;
+        self.data_control_manager.set(Some(jc.clone()));
+        Ok(jc)
+    }
+
+    pub async fn get_keyboard_shortcuts_inhibit_manager(
+        &self,
+    ) -> Result<Rc<TestKeyboardShortcutsInhibitManager>, TestError> {
+        singleton!(self.keyboard_shortcuts_inhibit_manager);
+        let singletons = self.get_singletons().await?;
+        singleton!(self.keyboard_shortcuts_inhibit_manager);
+        let jc = Rc::new(TestKeyboardShortcutsInhibitManager {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+        });
+        self.bind(&jc, singletons.zwp_keyboard_shortcuts_inhibit_manager_v1, 1)?;
+        self.keyboard_shortcuts_inhibit_manager
+            .set(Some(jc.clone()));
+        Ok(jc)
+    }
+
+    pub async fn get_idle_notifier(&self) -> Result<Rc<TestIdleNotifier>, TestError> {
+        singleton!(self.idle_notifier);
+        let singletons = self.get_singletons().await?;
+        singleton!(self.idle_notifier);
+        let jc = Rc::new(TestIdleNotifier {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+        });
+        self.bind(&jc, singletons.ext_idle_notifier_v1, 1)?;
+        self.idle_notifier.set(Some(jc.clone()));
+        Ok(jc)
+    }
+
+    pub async fn get_cursor_shape_manager(&self) -> Result<Rc<TestCursorShapeManager>, TestError> {
+        singleton!(self.cursor_shape_manager);
+        let singletons = self.get_singletons().await?;
+        singleton!(self.cursor_shape_manager);
+        let jc = Rc::new(TestCursorShapeManager {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+        });
+        self.bind(&jc, singletons.wp_cursor_shape_manager_v1, 1)?;
+        self.cursor_shape_manager.set(Some(jc.clone()));
+        Ok(jc)
+    }
+
+    pub fn bind_output(&self, output: &OutputNode) -> Result<Rc<TestOutput>, TestError> {
+        let obj = Rc::new(TestOutput {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            released: Cell::new(false),
+            name: Default::default(),
+        });
+        self.bind(&obj, output.global.name.raw(), 4)?;
+        Ok(obj)
+    }
+
     pub fn bind<O: TestObject>(
         &self,
         obj: &Rc<O>,
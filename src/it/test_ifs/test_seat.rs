@@ -3,7 +3,9 @@ use {
         ifs::wl_seat::WlSeat,
         it::{
             test_error::{TestError, TestResult},
-            test_ifs::{test_keyboard::TestKeyboard, test_pointer::TestPointer},
+            test_ifs::{
+                test_keyboard::TestKeyboard, test_pointer::TestPointer, test_touch::TestTouch,
+            },
             test_object::TestObject,
             test_transport::TestTransport,
             testrun::ParseFull,
@@ -74,6 +76,30 @@ impl TestSeat {
         Ok(pointer)
     }
 
+    pub async fn get_touch(&self) -> TestResult<Rc<TestTouch>> {
+        let id = self.tran.id();
+        self.tran.send(GetTouch {
+            self_id: self.id,
+            id,
+        })?;
+        let touch = Rc::new(TestTouch {
+            id,
+            tran: self.tran.clone(),
+            server: Default::default(),
+            destroyed: Default::default(),
+            down: Rc::new(Default::default()),
+            up: Rc::new(Default::default()),
+            motion: Rc::new(Default::default()),
+            frame: Rc::new(Default::default()),
+            cancel: Rc::new(Default::default()),
+        });
+        self.tran.add_obj(touch.clone())?;
+        self.tran.sync().await;
+        let server = self.tran.get_server_obj(id)?;
+        touch.server.set(Some(server));
+        Ok(touch)
+    }
+
     fn handle_capabilities(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
         let ev = Capabilities::parse_full(parser)?;
         self.caps.set(ev.capabilities);
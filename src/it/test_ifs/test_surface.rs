@@ -5,6 +5,7 @@ use {
             test_error::TestError, test_object::TestObject, test_transport::TestTransport,
             testrun::ParseFull,
         },
+        rect::Rect,
         utils::buffd::MsgParser,
         wire::{wl_surface::*, WlBufferId, WlSurfaceId},
     },
@@ -36,6 +37,17 @@ impl TestSurface {
         Ok(())
     }
 
+    pub fn damage(&self, rect: Rect) -> Result<(), TestError> {
+        self.tran.send(Damage {
+            self_id: self.id,
+            x: rect.x1(),
+            y: rect.y1(),
+            width: rect.width(),
+            height: rect.height(),
+        })?;
+        Ok(())
+    }
+
     pub fn commit(&self) -> Result<(), TestError> {
         self.tran.send(Commit { self_id: self.id })?;
         Ok(())
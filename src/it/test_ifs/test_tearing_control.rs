@@ -0,0 +1,45 @@
+use {
+    crate::{
+        it::{test_error::TestError, test_object::TestObject, test_transport::TestTransport},
+        wire::{wp_tearing_control_v1::*, WpTearingControlV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub const VSYNC: u32 = 0;
+pub const ASYNC: u32 = 1;
+
+pub struct TestTearingControl {
+    pub id: WpTearingControlV1Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+}
+
+impl TestTearingControl {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn set_presentation_hint(&self, hint: u32) -> Result<(), TestError> {
+        self.tran.send(SetPresentationHint {
+            self_id: self.id,
+            hint,
+        })?;
+        Ok(())
+    }
+}
+
+impl Drop for TestTearingControl {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestTearingControl, WpTearingControlV1;
+}
+
+impl TestObject for TestTearingControl {}
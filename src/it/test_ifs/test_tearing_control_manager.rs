@@ -0,0 +1,55 @@
+use {
+    crate::{
+        it::{
+            test_error::TestError, test_ifs::test_tearing_control::TestTearingControl,
+            test_object::TestObject, test_transport::TestTransport,
+        },
+        wire::{wp_tearing_control_manager_v1::*, WlSurfaceId, WpTearingControlManagerV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestTearingControlManager {
+    pub id: WpTearingControlManagerV1Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+}
+
+impl TestTearingControlManager {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn get_tearing_control(
+        &self,
+        surface: WlSurfaceId,
+    ) -> Result<Rc<TestTearingControl>, TestError> {
+        let obj = Rc::new(TestTearingControl {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+        });
+        self.tran.send(GetTearingControl {
+            self_id: self.id,
+            id: obj.id,
+            surface,
+        })?;
+        self.tran.add_obj(obj.clone())?;
+        Ok(obj)
+    }
+}
+
+impl Drop for TestTearingControlManager {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestTearingControlManager, WpTearingControlManagerV1;
+}
+
+impl TestObject for TestTearingControlManager {}
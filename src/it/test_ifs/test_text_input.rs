@@ -0,0 +1,101 @@
+use {
+    crate::{
+        it::{
+            test_error::{TestError, TestResult},
+            test_object::TestObject,
+            test_transport::TestTransport,
+            test_utils::test_expected_event::TEEH,
+            testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{zwp_text_input_v3::*, WlSurfaceId, ZwpTextInputV3Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestTextInput {
+    pub id: ZwpTextInputV3Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+    pub enter: TEEH<WlSurfaceId>,
+    pub leave: TEEH<WlSurfaceId>,
+    pub commit_string: TEEH<Option<String>>,
+    pub done: TEEH<u32>,
+}
+
+impl TestTextInput {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn enable(&self) -> Result<(), TestError> {
+        self.tran.send(Enable { self_id: self.id })?;
+        Ok(())
+    }
+
+    pub fn disable(&self) -> Result<(), TestError> {
+        self.tran.send(Disable { self_id: self.id })?;
+        Ok(())
+    }
+
+    pub fn commit(&self) -> Result<(), TestError> {
+        self.tran.send(Commit { self_id: self.id })?;
+        Ok(())
+    }
+
+    fn handle_enter(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Enter::parse_full(parser)?;
+        self.enter.push(ev.surface);
+        Ok(())
+    }
+
+    fn handle_leave(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Leave::parse_full(parser)?;
+        self.leave.push(ev.surface);
+        Ok(())
+    }
+
+    fn handle_preedit_string(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = PreeditString::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_commit_string(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = CommitString::parse_full(parser)?;
+        self.commit_string.push(ev.text.map(|t| t.to_string()));
+        Ok(())
+    }
+
+    fn handle_delete_surrounding_text(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = DeleteSurroundingText::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_done(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Done::parse_full(parser)?;
+        self.done.push(ev.serial);
+        Ok(())
+    }
+}
+
+impl Drop for TestTextInput {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestTextInput, ZwpTextInputV3;
+
+    ENTER => handle_enter,
+    LEAVE => handle_leave,
+    PREEDIT_STRING => handle_preedit_string,
+    COMMIT_STRING => handle_commit_string,
+    DELETE_SURROUNDING_TEXT => handle_delete_surrounding_text,
+    DONE => handle_done,
+}
+
+impl TestObject for TestTextInput {}
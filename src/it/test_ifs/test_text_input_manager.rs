@@ -0,0 +1,56 @@
+use {
+    crate::{
+        it::{
+            test_error::TestError, test_ifs::test_text_input::TestTextInput,
+            test_object::TestObject, test_transport::TestTransport,
+        },
+        wire::{zwp_text_input_manager_v3::*, WlSeatId, ZwpTextInputManagerV3Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestTextInputManager {
+    pub id: ZwpTextInputManagerV3Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+}
+
+impl TestTextInputManager {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn get_text_input(&self, seat: WlSeatId) -> Result<Rc<TestTextInput>, TestError> {
+        let obj = Rc::new(TestTextInput {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+            enter: Default::default(),
+            leave: Default::default(),
+            commit_string: Default::default(),
+            done: Default::default(),
+        });
+        self.tran.send(GetTextInput {
+            self_id: self.id,
+            id: obj.id,
+            seat,
+        })?;
+        self.tran.add_obj(obj.clone())?;
+        Ok(obj)
+    }
+}
+
+impl Drop for TestTextInputManager {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestTextInputManager, ZwpTextInputManagerV3;
+}
+
+impl TestObject for TestTextInputManager {}
@@ -0,0 +1,81 @@
+use {
+    crate::{
+        ifs::wl_seat::wl_touch::WlTouch,
+        it::{
+            test_error::TestResult, test_object::TestObject, test_transport::TestTransport,
+            test_utils::test_expected_event::TEEH, testrun::ParseFull,
+        },
+        utils::{buffd::MsgParser, clonecell::CloneCell},
+        wire::{wl_touch::*, WlTouchId},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestTouch {
+    pub id: WlTouchId,
+    pub tran: Rc<TestTransport>,
+    pub server: CloneCell<Option<Rc<WlTouch>>>,
+    pub destroyed: Cell<bool>,
+    pub down: TEEH<Down>,
+    pub up: TEEH<Up>,
+    pub motion: TEEH<Motion>,
+    pub frame: TEEH<Frame>,
+    pub cancel: TEEH<Cancel>,
+}
+
+impl TestTouch {
+    pub fn destroy(&self) -> TestResult {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Release { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    fn handle_down(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Down::parse_full(parser)?;
+        self.down.push(ev);
+        Ok(())
+    }
+
+    fn handle_up(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Up::parse_full(parser)?;
+        self.up.push(ev);
+        Ok(())
+    }
+
+    fn handle_motion(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Motion::parse_full(parser)?;
+        self.motion.push(ev);
+        Ok(())
+    }
+
+    fn handle_frame(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Frame::parse_full(parser)?;
+        self.frame.push(ev);
+        Ok(())
+    }
+
+    fn handle_cancel(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Cancel::parse_full(parser)?;
+        self.cancel.push(ev);
+        Ok(())
+    }
+}
+
+impl Drop for TestTouch {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestTouch, WlTouch;
+
+    DOWN => handle_down,
+    UP => handle_up,
+    MOTION => handle_motion,
+    FRAME => handle_frame,
+    CANCEL => handle_cancel,
+}
+
+impl TestObject for TestTouch {}
@@ -0,0 +1,58 @@
+use {
+    crate::{
+        it::{test_error::TestError, test_object::TestObject, test_transport::TestTransport},
+        wire::{zwp_virtual_keyboard_v1::*, ZwpVirtualKeyboardV1Id},
+    },
+    std::{cell::Cell, io::Write, rc::Rc},
+    uapi::c,
+};
+
+pub struct TestVirtualKeyboard {
+    pub id: ZwpVirtualKeyboardV1Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+}
+
+impl TestVirtualKeyboard {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn keymap(&self, keymap: &str) -> Result<(), TestError> {
+        let mut memfd = uapi::memfd_create("keymap", c::MFD_CLOEXEC)?;
+        memfd.write_all(keymap.as_bytes())?;
+        memfd.write_all(&[0])?;
+        self.tran.send(Keymap {
+            self_id: self.id,
+            format: 1,
+            fd: Rc::new(memfd),
+            size: keymap.len() as u32 + 1,
+        })?;
+        Ok(())
+    }
+
+    pub fn key(&self, key: u32, state: u32) -> Result<(), TestError> {
+        self.tran.send(Key {
+            self_id: self.id,
+            time: 0,
+            key,
+            state,
+        })?;
+        Ok(())
+    }
+}
+
+impl Drop for TestVirtualKeyboard {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestVirtualKeyboard, ZwpVirtualKeyboardV1;
+}
+
+impl TestObject for TestVirtualKeyboard {}
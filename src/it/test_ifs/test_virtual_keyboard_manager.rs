@@ -0,0 +1,41 @@
+use {
+    crate::{
+        it::{
+            test_error::TestError, test_ifs::test_virtual_keyboard::TestVirtualKeyboard,
+            test_object::TestObject, test_transport::TestTransport,
+        },
+        wire::{zwp_virtual_keyboard_manager_v1::*, WlSeatId, ZwpVirtualKeyboardManagerV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestVirtualKeyboardManager {
+    pub id: ZwpVirtualKeyboardManagerV1Id,
+    pub tran: Rc<TestTransport>,
+}
+
+impl TestVirtualKeyboardManager {
+    pub fn create_virtual_keyboard(
+        &self,
+        seat: WlSeatId,
+    ) -> Result<Rc<TestVirtualKeyboard>, TestError> {
+        let obj = Rc::new(TestVirtualKeyboard {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+        });
+        self.tran.send(CreateVirtualKeyboard {
+            self_id: self.id,
+            seat,
+            id: obj.id,
+        })?;
+        self.tran.add_obj(obj.clone())?;
+        Ok(obj)
+    }
+}
+
+test_object! {
+    TestVirtualKeyboardManager, ZwpVirtualKeyboardManagerV1;
+}
+
+impl TestObject for TestVirtualKeyboardManager {}
@@ -0,0 +1,63 @@
+use {
+    crate::{
+        fixed::Fixed,
+        it::{test_error::TestError, test_object::TestObject, test_transport::TestTransport},
+        wire::{zwlr_virtual_pointer_v1::*, ZwlrVirtualPointerV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestVirtualPointer {
+    pub id: ZwlrVirtualPointerV1Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+}
+
+impl TestVirtualPointer {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn motion(&self, dx: f64, dy: f64) -> Result<(), TestError> {
+        self.tran.send(Motion {
+            self_id: self.id,
+            time: 0,
+            dx: Fixed::from_f64(dx),
+            dy: Fixed::from_f64(dy),
+        })?;
+        Ok(())
+    }
+
+    pub fn motion_absolute(
+        &self,
+        x: u32,
+        y: u32,
+        x_extent: u32,
+        y_extent: u32,
+    ) -> Result<(), TestError> {
+        self.tran.send(MotionAbsolute {
+            self_id: self.id,
+            time: 0,
+            x,
+            y,
+            x_extent,
+            y_extent,
+        })?;
+        Ok(())
+    }
+}
+
+impl Drop for TestVirtualPointer {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestVirtualPointer, ZwlrVirtualPointerV1;
+}
+
+impl TestObject for TestVirtualPointer {}
@@ -0,0 +1,55 @@
+use {
+    crate::{
+        it::{
+            test_error::TestError, test_ifs::test_virtual_pointer::TestVirtualPointer,
+            test_object::TestObject, test_transport::TestTransport,
+        },
+        wire::{zwlr_virtual_pointer_manager_v1::*, WlSeatId, ZwlrVirtualPointerManagerV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestVirtualPointerManager {
+    pub id: ZwlrVirtualPointerManagerV1Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+}
+
+impl TestVirtualPointerManager {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn create_virtual_pointer(
+        &self,
+        seat: WlSeatId,
+    ) -> Result<Rc<TestVirtualPointer>, TestError> {
+        let obj = Rc::new(TestVirtualPointer {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+        });
+        self.tran.send(CreateVirtualPointer {
+            self_id: self.id,
+            seat,
+            id: obj.id,
+        })?;
+        self.tran.add_obj(obj.clone())?;
+        Ok(obj)
+    }
+}
+
+impl Drop for TestVirtualPointerManager {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestVirtualPointerManager, ZwlrVirtualPointerManagerV1;
+}
+
+impl TestObject for TestVirtualPointerManager {}
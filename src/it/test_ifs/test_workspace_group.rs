@@ -0,0 +1,87 @@
+use {
+    crate::{
+        it::{
+            test_error::{TestError, TestResult},
+            test_object::TestObject,
+            test_transport::TestTransport,
+            testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{ext_workspace_group_handle_v1::*, ExtWorkspaceGroupHandleV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestWorkspaceGroup {
+    pub id: ExtWorkspaceGroupHandleV1Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+    pub removed: Cell<bool>,
+}
+
+impl TestWorkspaceGroup {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn create_workspace(&self, workspace: &str) -> Result<(), TestError> {
+        self.tran.send(CreateWorkspace {
+            self_id: self.id,
+            workspace,
+        })?;
+        Ok(())
+    }
+
+    fn handle_capabilities(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Capabilities::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_output_enter(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = OutputEnter::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_output_leave(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = OutputLeave::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_workspace_enter(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = WorkspaceEnter::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_workspace_leave(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = WorkspaceLeave::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_removed(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Removed::parse_full(parser)?;
+        self.removed.set(true);
+        Ok(())
+    }
+}
+
+impl Drop for TestWorkspaceGroup {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestWorkspaceGroup, ExtWorkspaceGroupHandleV1;
+
+    CAPABILITIES => handle_capabilities,
+    OUTPUT_ENTER => handle_output_enter,
+    OUTPUT_LEAVE => handle_output_leave,
+    WORKSPACE_ENTER => handle_workspace_enter,
+    WORKSPACE_LEAVE => handle_workspace_leave,
+    REMOVED => handle_removed,
+}
+
+impl TestObject for TestWorkspaceGroup {}
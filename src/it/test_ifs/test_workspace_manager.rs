@@ -0,0 +1,93 @@
+use {
+    crate::{
+        it::{
+            test_error::{TestError, TestResult},
+            test_ifs::{test_workspace::TestWorkspace, test_workspace_group::TestWorkspaceGroup},
+            test_object::TestObject,
+            test_transport::TestTransport,
+            testrun::ParseFull,
+        },
+        utils::{buffd::MsgParser, copyhashmap::CopyHashMap},
+        wire::{
+            ext_workspace_manager_v1::*, ExtWorkspaceGroupHandleV1Id, ExtWorkspaceHandleV1Id,
+            ExtWorkspaceManagerV1Id,
+        },
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+};
+
+pub struct TestWorkspaceManager {
+    pub id: ExtWorkspaceManagerV1Id,
+    pub tran: Rc<TestTransport>,
+    pub groups: CopyHashMap<ExtWorkspaceGroupHandleV1Id, Rc<TestWorkspaceGroup>>,
+    pub workspaces: CopyHashMap<ExtWorkspaceHandleV1Id, Rc<TestWorkspace>>,
+}
+
+impl TestWorkspaceManager {
+    pub fn commit(&self) -> Result<(), TestError> {
+        self.tran.send(Commit { self_id: self.id })?;
+        Ok(())
+    }
+
+    pub fn find_workspace(&self, name: &str) -> TestResult<Rc<TestWorkspace>> {
+        for ws in self.workspaces.lock().values() {
+            if *ws.name.borrow() == name {
+                return Ok(ws.clone());
+            }
+        }
+        bail!("There is no workspace named {}", name);
+    }
+
+    fn handle_workspace_group(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = WorkspaceGroup::parse_full(parser)?;
+        let group = Rc::new(TestWorkspaceGroup {
+            id: ev.workspace_group,
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+            removed: Cell::new(false),
+        });
+        self.tran.add_obj(group.clone())?;
+        self.groups.set(ev.workspace_group, group);
+        Ok(())
+    }
+
+    fn handle_workspace(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Workspace::parse_full(parser)?;
+        let ws = Rc::new(TestWorkspace {
+            id: ev.workspace,
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+            name: RefCell::new(String::new()),
+            state: Cell::new(0),
+            capabilities: Cell::new(0),
+            removed: Cell::new(false),
+        });
+        self.tran.add_obj(ws.clone())?;
+        self.workspaces.set(ev.workspace, ws);
+        Ok(())
+    }
+
+    fn handle_done(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Done::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_finished(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Finished::parse_full(parser)?;
+        Ok(())
+    }
+}
+
+test_object! {
+    TestWorkspaceManager, ExtWorkspaceManagerV1;
+
+    WORKSPACE_GROUP => handle_workspace_group,
+    WORKSPACE => handle_workspace,
+    DONE => handle_done,
+    FINISHED => handle_finished,
+}
+
+impl TestObject for TestWorkspaceManager {}
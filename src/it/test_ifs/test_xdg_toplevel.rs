@@ -39,6 +39,35 @@ impl TestXdgToplevel {
         Ok(())
     }
 
+    pub fn set_parent(&self, parent: &TestXdgToplevel) -> Result<(), TestError> {
+        self.tran.send(SetParent {
+            self_id: self.id,
+            parent: parent.id,
+        })?;
+        Ok(())
+    }
+
+    pub fn set_title(&self, title: &str) -> Result<(), TestError> {
+        self.tran.send(SetTitle {
+            self_id: self.id,
+            title,
+        })?;
+        Ok(())
+    }
+
+    pub fn set_app_id(&self, app_id: &str) -> Result<(), TestError> {
+        self.tran.send(SetAppId {
+            self_id: self.id,
+            app_id,
+        })?;
+        Ok(())
+    }
+
+    pub fn set_minimized(&self) -> Result<(), TestError> {
+        self.tran.send(SetMinimized { self_id: self.id })?;
+        Ok(())
+    }
+
     pub fn container_parent(&self) -> TestResult<Rc<ContainerNode>> {
         let parent = match self.server.tl_data().parent.get() {
             Some(p) => p,
@@ -57,6 +57,20 @@ impl TestTransport {
             subcompositor: Default::default(),
             shm: Default::default(),
             xdg: Default::default(),
+            output_manager: Default::default(),
+            tearing_control_manager: Default::default(),
+            text_input_manager: Default::default(),
+            input_method_manager: Default::default(),
+            virtual_keyboard_manager: Default::default(),
+            virtual_pointer_manager: Default::default(),
+            foreign_toplevel_manager: Default::default(),
+            workspace_manager: Default::default(),
+            activation: Default::default(),
+            gamma_control_manager: Default::default(),
+            data_control_manager: Default::default(),
+            keyboard_shortcuts_inhibit_manager: Default::default(),
+            idle_notifier: Default::default(),
+            cursor_shape_manager: Default::default(),
             seats: Default::default(),
         });
         self.send(wl_display::GetRegistry {
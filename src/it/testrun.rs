@@ -3,7 +3,9 @@ use {
         client::{ClientId, RequestParser},
         ifs::wl_seat::WlSeatGlobal,
         it::{
-            test_backend::{TestBackend, TestBackendKb, TestBackendMouse, TestConnector},
+            test_backend::{
+                TestBackend, TestBackendKb, TestBackendMouse, TestBackendTouch, TestConnector,
+            },
             test_client::TestClient,
             test_config::TestConfig,
             test_error::{TestError, TestErrorExt},
@@ -110,12 +112,15 @@ impl TestRun {
             .set_input_device_seat(self.backend.default_kb.common.id, seat.id())?;
         self.cfg
             .set_input_device_seat(self.backend.default_mouse.common.id, seat.id())?;
+        self.cfg
+            .set_input_device_seat(self.backend.default_touch.common.id, seat.id())?;
         self.state.eng.yield_now().await;
         Ok(DefaultSetup {
             connector: self.backend.default_connector.clone(),
             output,
             kb: self.backend.default_kb.clone(),
             mouse: self.backend.default_mouse.clone(),
+            touch: self.backend.default_touch.clone(),
             seat,
         })
     }
@@ -142,5 +147,6 @@ pub struct DefaultSetup {
     pub output: Rc<OutputNode>,
     pub kb: Rc<TestBackendKb>,
     pub mouse: Rc<TestBackendMouse>,
+    pub touch: Rc<TestBackendTouch>,
     pub seat: Rc<WlSeatGlobal>,
 }
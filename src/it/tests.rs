@@ -44,6 +44,31 @@ mod t0015_scroll_partial;
 mod t0016_scroll_ws;
 mod t0017_remove_unused_ws;
 mod t0018_click_to_active_ws;
+mod t0019_damage;
+mod t0020_touch;
+mod t0021_swipe_binding;
+mod t0022_output_management;
+mod t0023_connector_mode;
+mod t0024_vrr;
+mod t0025_tearing;
+mod t0026_direct_scanout;
+mod t0027_overlay_candidates;
+mod t0028_text_input;
+mod t0029_virtual_input;
+mod t0030_foreign_toplevel;
+mod t0031_ext_workspace;
+mod t0032_xdg_activation;
+mod t0033_gamma_control;
+mod t0034_data_control;
+mod t0035_shortcuts_inhibit;
+mod t0036_idle_notify;
+mod t0037_cursor_shape;
+mod t0038_window_rules;
+mod t0039_window_handle;
+mod t0040_config_events;
+mod t0041_workspace_output;
+mod t0042_scratchpad;
+mod t0043_child_window_rules;
 
 pub trait TestCase: Sync {
     fn name(&self) -> &'static str;
@@ -80,5 +105,30 @@ pub fn tests() -> Vec<&'static dyn TestCase> {
         t0016_scroll_ws,
         t0017_remove_unused_ws,
         t0018_click_to_active_ws,
+        t0019_damage,
+        t0020_touch,
+        t0021_swipe_binding,
+        t0022_output_management,
+        t0023_connector_mode,
+        t0024_vrr,
+        t0025_tearing,
+        t0026_direct_scanout,
+        t0027_overlay_candidates,
+        t0028_text_input,
+        t0029_virtual_input,
+        t0030_foreign_toplevel,
+        t0031_ext_workspace,
+        t0032_xdg_activation,
+        t0033_gamma_control,
+        t0034_data_control,
+        t0035_shortcuts_inhibit,
+        t0036_idle_notify,
+        t0037_cursor_shape,
+        t0038_window_rules,
+        t0039_window_handle,
+        t0040_config_events,
+        t0041_workspace_output,
+        t0042_scratchpad,
+        t0043_child_window_rules,
     }
 }
@@ -0,0 +1,51 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        rect::Rect,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that surface commits only damage the damaged part of the output
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let window = client.create_window().await?;
+    window.map2().await?;
+    client.sync().await;
+
+    ds.output.take_damage();
+    ds.connector.damaged.set(false);
+
+    let pos = window.surface.server.buffer_abs_pos.get();
+
+    window
+        .surface
+        .damage(Rect::new_sized(10, 20, 30, 40).unwrap())?;
+    window.surface.commit()?;
+    client.sync().await;
+
+    tassert!(ds.connector.damaged.get());
+    let damage = ds.output.take_damage();
+    tassert_eq!(
+        damage.extents(),
+        Rect::new_sized(pos.x1() + 10, pos.y1() + 20, 30, 40).unwrap()
+    );
+
+    window
+        .surface
+        .damage(Rect::new_sized(-10, -10, 20, 20).unwrap())?;
+    window.surface.commit()?;
+    client.sync().await;
+
+    let damage = ds.output.take_damage();
+    tassert_eq!(
+        damage.extents(),
+        Rect::new_sized(pos.x1(), pos.y1(), 10, 10).unwrap()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that touch points are delivered to the touched surface
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let seat = client.get_default_seat().await?;
+    let window = client.create_window().await?;
+    window.map2().await?;
+    client.sync().await;
+
+    let pos = window.surface.server.buffer_abs_pos.get();
+    let opos = ds.output.global.pos.get();
+    let normed = |x: i32, y: i32| {
+        (
+            (x as f64 + 0.5 - opos.x1() as f64) / opos.width() as f64,
+            (y as f64 + 0.5 - opos.y1() as f64) / opos.height() as f64,
+        )
+    };
+
+    let down = seat.touch.down.expect()?;
+    let frame = seat.touch.frame.expect()?;
+    let (x, y) = normed(pos.x1() + 10, pos.y1() + 20);
+    ds.touch.down(3, x, y);
+    ds.touch.frame();
+    client.sync().await;
+
+    let ev = down.next()?;
+    tassert_eq!(ev.surface, window.surface.id);
+    tassert_eq!(ev.id, 3);
+    tassert_eq!((ev.x.round_down(), ev.y.round_down()), (10, 20));
+    frame.next()?;
+
+    let motion = seat.touch.motion.expect()?;
+    let (x, y) = normed(pos.x1() + 30, pos.y1() + 40);
+    ds.touch.motion(3, x, y);
+    ds.touch.frame();
+    client.sync().await;
+
+    let ev = motion.next()?;
+    tassert_eq!(ev.id, 3);
+    tassert_eq!((ev.x.round_down(), ev.y.round_down()), (30, 40));
+    frame.next()?;
+
+    let up = seat.touch.up.expect()?;
+    ds.touch.up(3);
+    ds.touch.frame();
+    client.sync().await;
+
+    tassert_eq!(up.next()?.id, 3);
+    frame.next()?;
+
+    ds.touch.motion(3, x, y);
+    ds.touch.frame();
+    client.sync().await;
+
+    motion.none()?;
+    frame.none()?;
+
+    Ok(())
+}
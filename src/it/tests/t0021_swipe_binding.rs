@@ -0,0 +1,36 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    jay_config::Direction,
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    run.cfg
+        .add_swipe_binding(ds.seat.id(), 3, Direction::Left)?;
+    run.sync().await;
+
+    ds.mouse.swipe(3, 100.0, 0.0);
+    run.sync().await;
+    tassert!(run.cfg.invoked_swipes.is_empty());
+
+    ds.mouse.swipe(4, -100.0, 0.0);
+    run.sync().await;
+    tassert!(run.cfg.invoked_swipes.is_empty());
+
+    ds.mouse.swipe(3, -10.0, 0.0);
+    run.sync().await;
+    tassert!(run.cfg.invoked_swipes.is_empty());
+
+    ds.mouse.swipe(3, -100.0, 20.0);
+    run.sync().await;
+    tassert!(run
+        .cfg
+        .invoked_swipes
+        .contains(&(ds.seat.id(), 3, Direction::Left)));
+
+    Ok(())
+}
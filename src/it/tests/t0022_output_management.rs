@@ -0,0 +1,97 @@
+use {
+    crate::{
+        backend::Mode,
+        it::{
+            test_error::TestResult,
+            test_ifs::test_output_configuration::TestOutputConfigurationResult, testrun::TestRun,
+        },
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that a client can change the mode and position of an output
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let om = client.registry.get_output_manager().await?;
+    client.sync().await;
+
+    let heads: Vec<_> = om.heads.lock().values().cloned().collect();
+    tassert_eq!(heads.len(), 1);
+    let head = &heads[0];
+    tassert!(head.enabled.get());
+    tassert_eq!(head.modes.borrow().len(), 3);
+    let current = match head.find_mode(800, 600, 60000) {
+        Some(m) => m,
+        _ => bail!("Compositor did not send the 800x600@60 mode"),
+    };
+    tassert_eq!(head.current_mode.get(), Some(current.id));
+    let target = match head.find_mode(1024, 768, 60000) {
+        Some(m) => m,
+        _ => bail!("Compositor did not send the 1024x768@60 mode"),
+    };
+
+    let config = om.create_configuration()?;
+    let config_head = config.enable_head(head)?;
+    config_head.set_mode(&target)?;
+    config_head.set_position(100, 50)?;
+    config.apply()?;
+    client.sync().await;
+
+    tassert_eq!(
+        config.result.get(),
+        Some(TestOutputConfigurationResult::Succeeded)
+    );
+    let mode = ds.output.global.mode.get();
+    tassert_eq!((mode.width, mode.height), (1024, 768));
+    let pos = ds.output.global.pos.get();
+    tassert_eq!((pos.x1(), pos.y1()), (100, 50));
+    tassert_eq!(head.current_mode.get(), Some(target.id));
+    tassert_eq!(head.position.get(), (100, 50));
+
+    let config = om.create_configuration()?;
+    config.enable_head(head)?;
+    om.serial.set(om.serial.get().wrapping_sub(1));
+    let stale = om.create_configuration()?;
+    stale.enable_head(head)?;
+    stale.apply()?;
+    config.apply()?;
+    client.sync().await;
+
+    tassert_eq!(
+        stale.result.get(),
+        Some(TestOutputConfigurationResult::Cancelled)
+    );
+    tassert_eq!(
+        config.result.get(),
+        Some(TestOutputConfigurationResult::Succeeded)
+    );
+
+    let rejected = match head.find_mode(800, 600, 120000) {
+        Some(m) => m,
+        _ => bail!("Compositor did not send the 800x600@120 mode"),
+    };
+    run.backend.default_connector.rejected_mode.set(Some(Mode {
+        width: 800,
+        height: 600,
+        refresh_rate_millihz: 120000,
+    }));
+    let config = om.create_configuration()?;
+    let config_head = config.enable_head(head)?;
+    config_head.set_mode(&rejected)?;
+    config.apply()?;
+    client.sync().await;
+
+    tassert_eq!(
+        config.result.get(),
+        Some(TestOutputConfigurationResult::Failed)
+    );
+    let mode = ds.output.global.mode.get();
+    tassert_eq!((mode.width, mode.height), (1024, 768));
+    tassert_eq!(head.current_mode.get(), Some(target.id));
+
+    Ok(())
+}
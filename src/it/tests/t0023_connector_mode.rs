@@ -0,0 +1,29 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that the config can list the modes of a connector and change the mode
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let modes = run.cfg.connector_modes(ds.connector.id)?;
+    tassert_eq!(modes.len(), 3);
+    let mode = match modes.iter().find(|m| m.refresh_rate() == 120000) {
+        Some(m) => *m,
+        _ => bail!("Connector does not have a 120hz mode"),
+    };
+
+    run.cfg.set_connector_mode(ds.connector.id, mode)?;
+    run.sync().await;
+
+    let current = ds.output.global.mode.get();
+    tassert_eq!(
+        (current.width, current.height, current.refresh_rate_millihz),
+        (mode.width(), mode.height(), mode.refresh_rate())
+    );
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    jay_config::video::VrrMode,
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that VRR is enabled according to the VRR mode of the connector
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    let id = ds.connector.id;
+
+    tassert!(!ds.connector.vrr_enabled.get());
+
+    run.cfg.set_connector_vrr_mode(id, VrrMode::Always)?;
+    tassert!(ds.connector.vrr_enabled.get());
+
+    run.cfg
+        .set_connector_vrr_mode(id, VrrMode::FullscreenOnly)?;
+    tassert!(!ds.connector.vrr_enabled.get());
+
+    let client = run.create_client().await?;
+    let window = client.create_window().await?;
+    window.map().await?;
+
+    run.cfg.set_fullscreen(ds.seat.id(), true)?;
+    tassert!(ds.connector.vrr_enabled.get());
+
+    run.cfg.set_fullscreen(ds.seat.id(), false)?;
+    tassert!(!ds.connector.vrr_enabled.get());
+
+    run.cfg.set_connector_vrr_mode(id, VrrMode::Never)?;
+    run.cfg.set_fullscreen(ds.seat.id(), true)?;
+    tassert!(!ds.connector.vrr_enabled.get());
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use {
+    crate::it::{
+        test_error::TestResult,
+        test_ifs::test_tearing_control::{ASYNC, VSYNC},
+        testrun::TestRun,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that tearing is only allowed for fullscreen surfaces that request it
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let tcm = client.registry.get_tearing_control_manager().await?;
+    let window = client.create_window().await?;
+    window.map().await?;
+    let tc = tcm.get_tearing_control(window.surface.id)?;
+
+    tc.set_presentation_hint(ASYNC)?;
+    window.surface.commit()?;
+    client.sync().await;
+    tassert!(!ds.connector.tearing_allowed.get());
+
+    run.cfg.set_fullscreen(ds.seat.id(), true)?;
+    tassert!(ds.connector.tearing_allowed.get());
+
+    tc.set_presentation_hint(VSYNC)?;
+    window.surface.commit()?;
+    client.sync().await;
+    tassert!(!ds.connector.tearing_allowed.get());
+
+    tc.set_presentation_hint(ASYNC)?;
+    window.surface.commit()?;
+    client.sync().await;
+    tassert!(ds.connector.tearing_allowed.get());
+
+    run.cfg.set_fullscreen(ds.seat.id(), false)?;
+    tassert!(!ds.connector.tearing_allowed.get());
+
+    Ok(())
+}
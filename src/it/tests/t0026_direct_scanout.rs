@@ -0,0 +1,46 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        utils::rc_eq::rc_eq,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test the selection of buffers for direct scanout and the delayed release of
+/// buffers that are being scanned out
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    ds.seat.set_hardware_cursor(true);
+
+    let client = run.create_client().await?;
+    let window = client.create_window().await?;
+    window.map2().await?;
+    tassert!(ds.output.direct_scanout_candidate().is_none());
+
+    run.cfg.set_fullscreen(ds.seat.id(), true)?;
+    client.sync().await;
+    window.map().await?;
+    let (surface, buffer) = match ds.output.direct_scanout_candidate() {
+        Some(c) => c,
+        _ => bail!("Fullscreen surface is not a direct scanout candidate"),
+    };
+    tassert!(rc_eq(&surface, &window.surface.server));
+
+    buffer.scanout_begin();
+    let old = window.buffer.get();
+    window.map().await?;
+    client.sync().await;
+    tassert!(!old.released.get());
+    buffer.scanout_end();
+    client.sync().await;
+    tassert!(old.released.get());
+
+    run.cfg.set_fullscreen(ds.seat.id(), false)?;
+    client.sync().await;
+    window.map().await?;
+    tassert!(ds.output.direct_scanout_candidate().is_none());
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use {
+    crate::{
+        format::XRGB8888,
+        it::{test_error::TestResult, testrun::TestRun},
+        render::DrawOp,
+        scale::Scale,
+        video::{
+            gbm::{GBM_BO_USE_LINEAR, GBM_BO_USE_RENDERING},
+            ModifiedFormat, INVALID_MODIFIER,
+        },
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that the draw operations recorded for the plane assignment describe
+/// unobstructed surfaces
+async fn test(run: Rc<TestRun>) -> TestResult {
+    run.backend.install_render_context()?;
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let window = client.create_window().await?;
+    window.map2().await?;
+    run.cfg.set_fullscreen(ds.seat.id(), true)?;
+    client.sync().await;
+    window.map().await?;
+
+    let ctx = match run.state.render_ctx.get() {
+        Some(ctx) => ctx,
+        _ => bail!("There is no render context"),
+    };
+    let pos = ds.output.global.pos.get();
+    let format = ModifiedFormat {
+        format: XRGB8888,
+        modifier: INVALID_MODIFIER,
+    };
+    let bo = ctx.gbm.create_bo(
+        pos.width(),
+        pos.height(),
+        &format,
+        GBM_BO_USE_RENDERING | GBM_BO_USE_LINEAR,
+    )?;
+    let fb = ctx.dmabuf_fb(bo.dmabuf())?;
+    let ops = fb.record(&*ds.output, &run.state, Some(pos), Scale::from_int(1), true);
+
+    let node_id = window.surface.server.node_id;
+    let idx = ops.iter().position(|op| match op {
+        DrawOp::Surface(d) => d.surface == node_id,
+        _ => false,
+    });
+    let draw = match idx.map(|idx| &ops[idx]) {
+        Some(DrawOp::Surface(d)) => d,
+        _ => bail!("The fullscreen surface was not recorded"),
+    };
+    tassert!(draw.plain);
+    tassert_eq!(draw.rect, pos.at_point(0, 0));
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that text committed by an input method arrives at the focused text input
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let seat = client.get_default_seat().await?;
+    let tim = client.registry.get_text_input_manager().await?;
+    let ti = tim.get_text_input(seat.seat.id)?;
+    let enter = ti.enter.expect()?;
+    let window = client.create_window().await?;
+    window.map().await?;
+    client.sync().await;
+    tassert_eq!(enter.next()?, window.surface.id);
+
+    let ime = run.create_client().await?;
+    let ime_seat = ime.get_default_seat().await?;
+    let imm = ime.registry.get_input_method_manager().await?;
+    let im = imm.get_input_method(ime_seat.seat.id)?;
+    let activate = im.activate.expect()?;
+    let im_done = im.done.expect()?;
+    ime.sync().await;
+    activate.none()?;
+
+    ti.enable()?;
+    ti.commit()?;
+    client.sync().await;
+    ime.sync().await;
+    activate.next()?;
+    im_done.next()?;
+
+    let commit_string = ti.commit_string.expect()?;
+    let ti_done = ti.done.expect()?;
+    im.commit_string("hello")?;
+    im.commit()?;
+    ime.sync().await;
+    client.sync().await;
+    tassert_eq!(commit_string.next()?, Some("hello".to_string()));
+    tassert_eq!(ti_done.next()?, 1);
+
+    let grab = im.grab_keyboard()?;
+    let key = grab.key.expect()?;
+    ime.sync().await;
+    ds.kb.press(1);
+    run.sync().await;
+    ime.sync().await;
+    let ev = key.next()?;
+    tassert_eq!(ev.key, 1);
+    tassert_eq!(ev.state, 1);
+
+    let im2 = imm.get_input_method(ime_seat.seat.id)?;
+    let unavailable = im2.unavailable.expect()?;
+    ime.sync().await;
+    unavailable.next()?;
+
+    let deactivate = im.deactivate.expect()?;
+    ti.disable()?;
+    ti.commit()?;
+    client.sync().await;
+    ime.sync().await;
+    deactivate.next()?;
+
+    Ok(())
+}
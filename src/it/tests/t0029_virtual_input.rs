@@ -0,0 +1,72 @@
+use {
+    crate::{
+        fixed::Fixed,
+        it::{test_error::TestResult, testrun::TestRun},
+    },
+    jay_config::keyboard::syms::SYM_F13,
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that virtual keyboards use their own keymap and that virtual pointers move the
+/// cursor
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let seat = client.get_default_seat().await?;
+
+    run.cfg.add_shortcut(ds.seat.id(), SYM_F13)?;
+    run.sync().await;
+
+    let keymap = r#"
+xkb_keymap {
+    xkb_keycodes {
+          <1> = 9; # ESC
+    };
+    xkb_types {
+    };
+    xkb_compatibility {
+    };
+    xkb_symbols {
+        key <1> { [ F13 ] };
+    };
+};
+    "#;
+
+    let vkm = client.registry.get_virtual_keyboard_manager().await?;
+    let vk = vkm.create_virtual_keyboard(seat.seat.id)?;
+    vk.keymap(keymap)?;
+    vk.key(1, 1)?;
+    client.sync().await;
+    tassert!(run
+        .cfg
+        .invoked_shortcuts
+        .contains(&(ds.seat.id(), SYM_F13.into())));
+    vk.key(1, 0)?;
+
+    let pos = ds.output.global.pos.get();
+    let vpm = client.registry.get_virtual_pointer_manager().await?;
+    let vp = vpm.create_virtual_pointer(seat.seat.id)?;
+    vp.motion_absolute(10, 20, pos.width() as u32, pos.height() as u32)?;
+    client.sync().await;
+    tassert_eq!(
+        ds.seat.position(),
+        (
+            Fixed::from_int(pos.x1() + 10),
+            Fixed::from_int(pos.y1() + 20)
+        )
+    );
+    vp.motion(5.0, 5.0)?;
+    client.sync().await;
+    tassert_eq!(
+        ds.seat.position(),
+        (
+            Fixed::from_int(pos.x1() + 15),
+            Fixed::from_int(pos.y1() + 25)
+        )
+    );
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that foreign toplevel managers see mapped windows and can close them
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let _ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let window = client.create_window().await?;
+    window.tl.set_title("title")?;
+    window.tl.set_app_id("app")?;
+    window.map().await?;
+
+    let taskbar = run.create_client().await?;
+    let manager = taskbar.registry.get_foreign_toplevel_manager().await?;
+    taskbar.sync().await;
+    let handles: Vec<_> = manager.toplevels.lock().values().cloned().collect();
+    tassert_eq!(handles.len(), 1);
+    let handle = &handles[0];
+    tassert_eq!(handle.title.borrow().clone(), "title");
+    tassert_eq!(handle.app_id.borrow().clone(), "app");
+
+    let done = handle.done.expect()?;
+    window.tl.set_title("new title")?;
+    client.sync().await;
+    taskbar.sync().await;
+    done.next()?;
+    tassert_eq!(handle.title.borrow().clone(), "new title");
+
+    let window2 = client.create_window().await?;
+    window2.map().await?;
+    taskbar.sync().await;
+    tassert!(!handle.states.borrow().contains(&2));
+    let seat = taskbar.get_default_seat().await?;
+    handle.activate(seat.seat.id)?;
+    taskbar.sync().await;
+    client.sync().await;
+    taskbar.sync().await;
+    tassert!(handle.states.borrow().contains(&2));
+
+    handle.close()?;
+    taskbar.sync().await;
+    client.sync().await;
+    tassert!(window.tl.close_requested.get());
+
+    window.tl.destroy()?;
+    client.sync().await;
+    taskbar.sync().await;
+    tassert!(handle.closed.get());
+
+    Ok(())
+}
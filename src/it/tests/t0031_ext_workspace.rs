@@ -0,0 +1,70 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::ToplevelNode,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that workspaces can be created, activated and removed via ext-workspace
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let _ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let manager = client.registry.get_workspace_manager().await?;
+    client.sync().await;
+    tassert_eq!(manager.groups.len(), 1);
+    let group = manager.groups.lock().values().next().cloned().unwrap();
+
+    group.create_workspace("ext-a")?;
+    manager.commit()?;
+    client.sync().await;
+    let a = manager.find_workspace("ext-a")?;
+    a.activate()?;
+    manager.commit()?;
+    client.sync().await;
+    tassert_eq!(a.state.get(), 1);
+
+    group.create_workspace("ext-b")?;
+    manager.commit()?;
+    client.sync().await;
+    let b = manager.find_workspace("ext-b")?;
+    tassert_eq!(b.state.get(), 0);
+
+    b.activate()?;
+    manager.commit()?;
+    client.sync().await;
+    tassert_eq!(b.state.get(), 1);
+    tassert!(a.removed.get());
+
+    let scratchpad = manager.find_workspace("scratchpad")?;
+    tassert_eq!(scratchpad.state.get(), 4);
+    tassert_eq!(scratchpad.capabilities.get(), 0);
+
+    group.create_workspace("ext-c")?;
+    manager.commit()?;
+    client.sync().await;
+    let c = manager.find_workspace("ext-c")?;
+    tassert_eq!(c.capabilities.get(), 5);
+    c.remove()?;
+    b.remove()?;
+    manager.commit()?;
+    client.sync().await;
+    tassert!(c.removed.get());
+    tassert!(!b.removed.get());
+
+    run.cfg.float_new_windows.set(Some((100, 100)));
+    let window = client.create_window().await?;
+    window.map().await?;
+    tassert!(window.tl.server.tl_data().is_floating.get());
+    window.tl.server.tl_set_urgent(true);
+    client.sync().await;
+    tassert_eq!(b.state.get(), 3);
+    window.tl.server.tl_set_urgent(false);
+    client.sync().await;
+    tassert_eq!(b.state.get(), 1);
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::ToplevelNode,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that activation requests focus windows only with a trusted token
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let w1 = client.create_window().await?;
+    w1.map().await?;
+    let w2 = client.create_window().await?;
+    w2.map().await?;
+    ds.seat.focus_toplevel(w1.tl.server.clone());
+    client.sync().await;
+    tassert!(w2.tl.server.tl_data().active_surfaces.get() == 0);
+
+    let activation = client.registry.get_activation().await?;
+    let token = activation.get_activation_token()?;
+    token.commit()?;
+    client.sync().await;
+    let untrusted = match token.token.borrow_mut().take() {
+        Some(t) => t,
+        _ => bail!("Compositor did not send a token"),
+    };
+    activation.activate(&untrusted, w2.surface.id)?;
+    client.sync().await;
+    tassert!(w2.tl.server.tl_data().urgent.get());
+    tassert!(w2.tl.server.tl_data().active_surfaces.get() == 0);
+
+    let trusted = run.state.create_activation_token(None, None);
+    activation.activate(&trusted, w2.surface.id)?;
+    client.sync().await;
+    tassert!(!w2.tl.server.tl_data().urgent.get());
+    tassert!(w2.tl.server.tl_data().active_surfaces.get() > 0);
+
+    let other = run.create_client().await?;
+    let w3 = other.create_window().await?;
+    w3.map().await?;
+    ds.seat.focus_toplevel(w3.tl.server.clone());
+    client.sync().await;
+    let own = run
+        .state
+        .create_activation_token(Some(ds.seat.clone()), Some(client.server.id));
+    activation.activate(&own, w1.surface.id)?;
+    client.sync().await;
+    tassert!(w1.tl.server.tl_data().urgent.get());
+    tassert!(w1.tl.server.tl_data().active_surfaces.get() == 0);
+
+    run.cfg.set_activation_hook()?;
+    run.cfg.allow_activation.set(false);
+    let denied = run.state.create_activation_token(None, None);
+    activation.activate(&denied, w1.surface.id)?;
+    client.sync().await;
+    tassert_eq!(
+        run.cfg.activation_requests.borrow().last().copied(),
+        Some(run.cfg.new_windows.borrow()[0])
+    );
+    tassert!(w1.tl.server.tl_data().active_surfaces.get() == 0);
+
+    run.cfg.allow_activation.set(true);
+    let allowed = run.state.create_activation_token(None, None);
+    activation.activate(&allowed, w1.surface.id)?;
+    client.sync().await;
+    tassert!(!w1.tl.server.tl_data().urgent.get());
+    tassert!(w1.tl.server.tl_data().active_surfaces.get() > 0);
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use {
+    crate::{
+        backend::GammaLut,
+        it::{test_backend::TEST_GAMMA_SIZE, test_error::TestResult, testrun::TestRun},
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that gamma tables are applied and restored when the control is destroyed
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let output = client.registry.bind_output(&ds.output)?;
+    let manager = client.registry.get_gamma_control_manager().await?;
+
+    let gc = manager.get_gamma_control(&output)?;
+    client.sync().await;
+    tassert_eq!(gc.size.get(), Some(TEST_GAMMA_SIZE));
+    tassert!(!gc.failed.get());
+
+    let gc2 = manager.get_gamma_control(&output)?;
+    client.sync().await;
+    tassert!(gc2.failed.get());
+
+    let size = TEST_GAMMA_SIZE as u16;
+    let lut = GammaLut {
+        red: (0..size).map(|i| i * 0x1000).collect(),
+        green: (0..size).map(|i| i * 0x800).collect(),
+        blue: (0..size).map(|i| i * 0x400).collect(),
+    };
+    gc.set_gamma(&lut.red, &lut.green, &lut.blue)?;
+    client.sync().await;
+    let applied = ds.connector.gamma_lut.get();
+    tassert_eq!(applied.as_deref(), Some(&lut));
+
+    gc.destroy()?;
+    client.sync().await;
+    tassert!(ds.connector.gamma_lut.get().is_none());
+
+    let gc3 = manager.get_gamma_control(&output)?;
+    client.sync().await;
+    tassert!(!gc3.failed.get());
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that clipboard managers can observe and set the selection without focus
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let _ds = run.create_default_setup().await?;
+
+    let owner = run.create_client().await?;
+    let owner_seat = owner.get_default_seat().await?;
+    let owner_dev = owner
+        .registry
+        .get_data_control_manager()
+        .await?
+        .get_data_device(&owner_seat.seat)?;
+
+    let watcher = run.create_client().await?;
+    let watcher_seat = watcher.get_default_seat().await?;
+    let watcher_dev = watcher
+        .registry
+        .get_data_control_manager()
+        .await?
+        .get_data_device(&watcher_seat.seat)?;
+    watcher.sync().await;
+    tassert!(watcher_dev.selection.get().is_none());
+
+    let manager = owner.registry.get_data_control_manager().await?;
+    let source = manager.create_data_source()?;
+    source.offer("text/plain")?;
+    source.offer("text/html")?;
+    owner_dev.set_selection(Some(&source))?;
+    owner.sync().await;
+    watcher.sync().await;
+
+    let offer = match watcher_dev.selection.get() {
+        Some(offer) => offer,
+        _ => bail!("Watcher did not receive the selection"),
+    };
+    let mut mime_types = offer.mime_types.borrow().clone();
+    mime_types.sort();
+    tassert_eq!(mime_types, vec!["text/html", "text/plain"]);
+    tassert!(watcher_dev.primary_selection.get().is_none());
+
+    let read = offer.receive("text/plain")?;
+    watcher.sync().await;
+    owner.sync().await;
+    let (mime_type, fd) = match source.sends.borrow_mut().pop() {
+        Some(send) => send,
+        _ => bail!("Source did not receive a send request"),
+    };
+    tassert_eq!(mime_type, "text/plain");
+    uapi::write(fd.raw(), b"hello")?;
+    drop(fd);
+    let mut buf = [0u8; 16];
+    let n = uapi::read(read.raw(), &mut buf[..])?.len();
+    tassert_eq!(&buf[..n], b"hello");
+
+    let primary = manager.create_data_source()?;
+    primary.offer("text/plain")?;
+    owner_dev.set_primary_selection(Some(&primary))?;
+    owner.sync().await;
+    watcher.sync().await;
+    tassert!(watcher_dev.primary_selection.get().is_some());
+
+    let replacement = manager.create_data_source()?;
+    replacement.offer("text/plain")?;
+    owner_dev.set_selection(Some(&replacement))?;
+    owner.sync().await;
+    watcher.sync().await;
+    tassert!(source.cancelled.get());
+    tassert!(watcher_dev.selection.get().is_some());
+
+    replacement.destroy()?;
+    owner.sync().await;
+    watcher.sync().await;
+    tassert!(watcher_dev.selection.get().is_none());
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    jay_config::keyboard::syms::{SYM_F13, SYM_F14},
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that focused surfaces can inhibit shortcuts and that the escape combination
+/// restores them
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let keymap = r#"
+xkb_keymap {
+    xkb_keycodes {
+          <1> = 9;
+          <2> = 10;
+    };
+    xkb_types {
+    };
+    xkb_compatibility {
+    };
+    xkb_symbols {
+        key <1> { [ F13 ] };
+        key <2> { [ F14 ] };
+    };
+};
+    "#;
+    let keymap = run.cfg.parse_keymap(keymap)?;
+    run.cfg.set_keymap(ds.seat.id(), keymap)?;
+    run.cfg.add_shortcut(ds.seat.id(), SYM_F13)?;
+    run.cfg
+        .set_shortcuts_inhibit_escape(ds.seat.id(), SYM_F14)?;
+
+    let client = run.create_client().await?;
+    let seat = client.get_default_seat().await?;
+    let window = client.create_window().await?;
+    window.map().await?;
+    let manager = client
+        .registry
+        .get_keyboard_shortcuts_inhibit_manager()
+        .await?;
+    let inhibitor = manager.inhibit_shortcuts(window.surface.id, seat.seat.id)?;
+    client.sync().await;
+    tassert!(inhibitor.active.get());
+
+    ds.kb.press(1);
+    run.sync().await;
+    tassert!(run.cfg.invoked_shortcuts.is_empty());
+
+    ds.kb.press(2);
+    client.sync().await;
+    tassert!(!inhibitor.active.get());
+
+    ds.kb.press(1);
+    run.sync().await;
+    tassert!(run
+        .cfg
+        .invoked_shortcuts
+        .contains(&(ds.seat.id(), SYM_F13.into())));
+
+    run.cfg.set_shortcuts_inhibit_hook()?;
+    run.cfg.allow_shortcuts_inhibit.set(false);
+    let window2 = client.create_window().await?;
+    window2.map().await?;
+    let inhibitor2 = manager.inhibit_shortcuts(window2.surface.id, seat.seat.id)?;
+    client.sync().await;
+    tassert_eq!(run.cfg.shortcuts_inhibit_requests.borrow().len(), 1);
+    tassert!(!inhibitor2.active.get());
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that idle notifications are sent after their timeout and resumed on input
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let seat = client.get_default_seat().await?;
+    let notifier = client.registry.get_idle_notifier().await?;
+    let short = notifier.get_idle_notification(50, seat.seat.id)?;
+    let long = notifier.get_idle_notification(60_000, seat.seat.id)?;
+    client.sync().await;
+    tassert!(!short.idle.get());
+
+    run.state.wheel.timeout(100).await?;
+    client.sync().await;
+    tassert!(short.idle.get());
+    tassert!(!long.idle.get());
+
+    ds.kb.press(1);
+    client.sync().await;
+    tassert!(!short.idle.get());
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use {
+    crate::{
+        cursor::KnownCursor,
+        it::{
+            test_error::{TestErrorExt, TestResult},
+            testrun::TestRun,
+        },
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that clients can set the cursor by shape
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let cds = client.get_default_seat().await?;
+    let enters = cds.pointer.enter.expect()?;
+    let window = client.create_window().await?;
+    window.map().await?;
+    let manager = client.registry.get_cursor_shape_manager().await?;
+    let device = manager.get_pointer(cds.pointer.id)?;
+
+    ds.mouse.rel(100.0, 100.0);
+    client.sync().await;
+    let enter = enters.last().with_context(|| "enters")?;
+    tassert_eq!(enter.surface, window.surface.id);
+
+    device.set_shape(enter.serial, 9)?;
+    client.sync().await;
+    tassert_eq!(ds.seat.desired_known_cursor(), Some(KnownCursor::Text));
+
+    device.set_shape(enter.serial, 32)?;
+    client.sync().await;
+    tassert_eq!(ds.seat.desired_known_cursor(), Some(KnownCursor::AllScroll));
+
+    Ok(())
+}
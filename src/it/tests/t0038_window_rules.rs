@@ -0,0 +1,50 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::ToplevelNode,
+        wire::WlBufferId,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that the config can inspect and place new windows
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    run.cfg.float_new_windows.set(Some((300, 200)));
+
+    let client = run.create_client().await?;
+    let window = client.create_window().await?;
+    window.tl.set_app_id("rules")?;
+    window.map().await?;
+
+    let new_windows = run.cfg.new_windows.borrow().clone();
+    tassert_eq!(new_windows.len(), 1);
+    let win = new_windows[0];
+    tassert_eq!(run.cfg.window_app_id(win)?, "rules");
+
+    let data = window.tl.server.tl_data();
+    tassert!(data.is_floating.get());
+    tassert_eq!(data.float_width.get(), 300);
+    tassert_eq!(data.float_height.get(), 200);
+
+    let ws_name = || data.workspace.get().map(|ws| ws.name.clone());
+
+    run.cfg.set_window_sticky(win, true)?;
+    run.cfg.show_workspace(ds.seat.id(), "2")?;
+    tassert_eq!(ws_name(), Some("2".to_string()));
+
+    run.cfg.set_window_sticky(win, false)?;
+    run.cfg.set_window_workspace(win, "3")?;
+    tassert_eq!(ws_name(), Some("3".to_string()));
+
+    window.surface.attach(WlBufferId::NONE)?;
+    window.surface.commit()?;
+    client.sync().await;
+    window.map().await?;
+    tassert_eq!(run.cfg.new_windows.borrow().len(), 1);
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::ToplevelNode,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that the config can enumerate, focus and close windows
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let window1 = client.create_window().await?;
+    window1.map().await?;
+    let window2 = client.create_window().await?;
+    window2.map().await?;
+
+    let new_windows = run.cfg.new_windows.borrow().clone();
+    tassert_eq!(new_windows.len(), 2);
+    let (win1, win2) = (new_windows[0], new_windows[1]);
+
+    let ws_name = window1
+        .tl
+        .server
+        .tl_data()
+        .workspace
+        .get()
+        .map(|ws| ws.name.clone())
+        .unwrap_or_default();
+    let mut windows = run.cfg.workspace_windows(&ws_name)?;
+    windows.sort_by_key(|w| w.0);
+    tassert_eq!(windows, vec![win1, win2]);
+    tassert_eq!(run.cfg.focused_window(ds.seat.id())?, Some(win2));
+
+    run.cfg.set_window_workspace(win1, "2")?;
+    tassert_eq!(run.cfg.workspace_windows(&ws_name)?, vec![win2]);
+    tassert_eq!(run.cfg.workspace_windows("2")?, vec![win1]);
+
+    run.cfg.focus_window(ds.seat.id(), win1)?;
+    tassert_eq!(run.cfg.focused_window(ds.seat.id())?, Some(win1));
+    let ws = window1.tl.server.tl_data().workspace.get();
+    tassert!(ws.map(|ws| ws.visible.get()).unwrap_or(false));
+
+    run.cfg.close_window(win2)?;
+    client.sync().await;
+    tassert!(window2.tl.close_requested.get());
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::ToplevelNode,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that the config is notified about window and workspace changes
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let window = client.create_window().await?;
+    window.map().await?;
+
+    let win = run.cfg.new_windows.borrow()[0];
+    tassert_eq!(
+        run.cfg.focused_windows.borrow().last().copied(),
+        Some(Some(win))
+    );
+
+    window.tl.set_title("events")?;
+    client.sync().await;
+    tassert_eq!(run.cfg.title_changes.borrow().last().copied(), Some(win));
+
+    run.cfg.set_window_fullscreen(win, true)?;
+    run.cfg.set_window_fullscreen(win, false)?;
+    tassert_eq!(
+        run.cfg.fullscreen_changes.borrow().clone(),
+        vec![(win, true), (win, false)]
+    );
+
+    let ws1 = match window.tl.server.tl_data().workspace.get() {
+        Some(ws) => run.cfg.get_workspace(&ws.name)?,
+        _ => bail!("Window has no workspace"),
+    };
+    let ws2 = run.cfg.get_workspace("2")?;
+    run.cfg.show_workspace(ds.seat.id(), "2")?;
+    client.sync().await;
+    tassert!(run.cfg.created_workspaces.borrow().contains(&ws2));
+    tassert_eq!(run.cfg.shown_workspaces.borrow().last().copied(), Some(ws2));
+    tassert_eq!(run.cfg.focused_windows.borrow().last().copied(), Some(None));
+
+    run.cfg.focus_window(ds.seat.id(), win)?;
+    client.sync().await;
+    tassert_eq!(run.cfg.shown_workspaces.borrow().last().copied(), Some(ws1));
+    tassert_eq!(run.cfg.destroyed_workspaces.borrow().clone(), vec![ws2]);
+    tassert_eq!(
+        run.cfg.focused_windows.borrow().last().copied(),
+        Some(Some(win))
+    );
+
+    let focus_events = run.cfg.focused_windows.borrow().len();
+    let window2 = client.create_window().await?;
+    window2.map().await?;
+    window2.tl.destroy()?;
+    window2.xdg.destroy()?;
+    window2.surface.destroy()?;
+    client.sync().await;
+    let events = run.cfg.focused_windows.borrow()[focus_events..].to_vec();
+    tassert!(!events.contains(&None));
+    tassert_eq!(events.last().copied(), Some(Some(win)));
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    jay_config::Direction,
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that workspaces can be moved between outputs and return to their preferred output
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let second = run.backend.add_connector(2);
+    run.sync().await;
+
+    run.cfg.show_workspace(ds.seat.id(), "a")?;
+    let client = run.create_client().await?;
+    let window = client.create_window().await?;
+    window.map().await?;
+
+    let connector = || match run.state.workspaces.get("a") {
+        Some(ws) => Some(ws.output.get().global.connector.connector.id()),
+        _ => None,
+    };
+    tassert_eq!(connector(), Some(ds.connector.id));
+
+    run.cfg.set_workspace_output("a", second.id)?;
+    tassert_eq!(connector(), Some(second.id));
+
+    second.disconnect();
+    run.sync().await;
+    tassert_eq!(connector(), Some(ds.connector.id));
+
+    second.connect();
+    run.sync().await;
+    tassert_eq!(connector(), Some(second.id));
+
+    run.cfg.move_workspace_to_output("a", Direction::Left)?;
+    tassert_eq!(connector(), Some(ds.connector.id));
+
+    Ok(())
+}
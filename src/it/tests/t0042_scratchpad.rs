@@ -0,0 +1,39 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::{Node, ToplevelNode},
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that windows can be hidden in the scratchpad and shown again
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let window = client.create_window().await?;
+    window.tl.set_app_id("term")?;
+    window.map().await?;
+    let win = run.cfg.new_windows.borrow()[0];
+
+    window.tl.set_minimized()?;
+    client.sync().await;
+    tassert!(run.cfg.window_hidden(win)?);
+    tassert!(!window.tl.server.node_visible());
+
+    run.cfg.toggle_scratchpad(ds.seat.id(), "term")?;
+    tassert!(!run.cfg.window_hidden(win)?);
+    tassert!(window.tl.server.tl_data().is_floating.get());
+    tassert_eq!(run.cfg.focused_window(ds.seat.id())?, Some(win));
+
+    run.cfg.toggle_scratchpad(ds.seat.id(), "term")?;
+    tassert!(run.cfg.window_hidden(win)?);
+
+    run.cfg.toggle_scratchpad(ds.seat.id(), "term")?;
+    run.cfg.hide_window(win)?;
+    tassert!(run.cfg.window_hidden(win)?);
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::ToplevelNode,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that window rules also apply to windows with a parent
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let _ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let parent = client.create_window().await?;
+    parent.map().await?;
+
+    let ws_name = |tl: &dyn ToplevelNode| tl.tl_data().workspace.get().map(|ws| ws.name.clone());
+    let parent_ws = ws_name(&*parent.tl.server);
+    tassert!(parent_ws.is_some());
+
+    let dialog = client.create_window().await?;
+    dialog.tl.set_parent(&parent.tl)?;
+    dialog.map().await?;
+    tassert!(dialog.tl.server.tl_data().is_floating.get());
+    tassert_eq!(ws_name(&*dialog.tl.server), parent_ws);
+
+    run.cfg
+        .new_window_workspace
+        .set(Some(run.cfg.get_workspace("3")?));
+    let dialog = client.create_window().await?;
+    dialog.tl.set_parent(&parent.tl)?;
+    dialog.map().await?;
+    tassert_eq!(run.cfg.new_windows.borrow().len(), 3);
+    tassert!(dialog.tl.server.tl_data().is_floating.get());
+    tassert_eq!(ws_name(&*dialog.tl.server), Some("3".to_string()));
+
+    Ok(())
+}
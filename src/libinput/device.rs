@@ -6,6 +6,7 @@ use {
             LIBINPUT_CONFIG_DRAG_LOCK_DISABLED, LIBINPUT_CONFIG_DRAG_LOCK_ENABLED,
             LIBINPUT_CONFIG_TAP_DISABLED, LIBINPUT_CONFIG_TAP_ENABLED,
         },
+        event::LibInputTabletPadModeGroup,
         sys::{
             libinput_device, libinput_device_config_accel_set_profile,
             libinput_device_config_accel_set_speed, libinput_device_config_left_handed_set,
@@ -13,9 +14,14 @@ use {
             libinput_device_config_tap_get_drag_lock_enabled,
             libinput_device_config_tap_get_enabled, libinput_device_config_tap_set_drag_enabled,
             libinput_device_config_tap_set_drag_lock_enabled,
-            libinput_device_config_tap_set_enabled, libinput_device_get_name,
-            libinput_device_get_user_data, libinput_device_has_capability,
-            libinput_device_set_user_data, libinput_device_unref, libinput_path_remove_device,
+            libinput_device_config_tap_set_enabled, libinput_device_get_device_group,
+            libinput_device_get_id_product, libinput_device_get_id_vendor,
+            libinput_device_get_name, libinput_device_get_user_data,
+            libinput_device_has_capability, libinput_device_set_user_data,
+            libinput_device_tablet_pad_get_mode_group, libinput_device_tablet_pad_get_num_buttons,
+            libinput_device_tablet_pad_get_num_mode_groups,
+            libinput_device_tablet_pad_get_num_rings, libinput_device_tablet_pad_get_num_strips,
+            libinput_device_unref, libinput_path_remove_device,
         },
         LibInput,
     },
@@ -87,6 +93,46 @@ impl<'a> LibInputDevice<'a> {
         }
     }
 
+    pub fn vendor(&self) -> u32 {
+        unsafe { libinput_device_get_id_vendor(self.dev) as _ }
+    }
+
+    pub fn product(&self) -> u32 {
+        unsafe { libinput_device_get_id_product(self.dev) as _ }
+    }
+
+    pub fn device_group(&self) -> usize {
+        unsafe { libinput_device_get_device_group(self.dev) as usize }
+    }
+
+    pub fn pad_num_buttons(&self) -> u32 {
+        unsafe { libinput_device_tablet_pad_get_num_buttons(self.dev).max(0) as _ }
+    }
+
+    pub fn pad_num_rings(&self) -> u32 {
+        unsafe { libinput_device_tablet_pad_get_num_rings(self.dev).max(0) as _ }
+    }
+
+    pub fn pad_num_strips(&self) -> u32 {
+        unsafe { libinput_device_tablet_pad_get_num_strips(self.dev).max(0) as _ }
+    }
+
+    pub fn pad_num_mode_groups(&self) -> u32 {
+        unsafe { libinput_device_tablet_pad_get_num_mode_groups(self.dev).max(0) as _ }
+    }
+
+    pub fn pad_mode_group(&self, index: u32) -> Option<LibInputTabletPadModeGroup> {
+        let group = unsafe { libinput_device_tablet_pad_get_mode_group(self.dev, index as _) };
+        if group.is_null() {
+            None
+        } else {
+            Some(LibInputTabletPadModeGroup {
+                group,
+                _phantom: Default::default(),
+            })
+        }
+    }
+
     pub fn set_tap_enabled(&self, enabled: bool) {
         let enabled = match enabled {
             true => LIBINPUT_CONFIG_TAP_ENABLED,
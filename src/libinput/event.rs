@@ -1,10 +1,19 @@
 use {
     crate::libinput::{
-        consts::{ButtonState, EventType, KeyState, PointerAxis},
+        consts::{
+            ButtonState, EventType, KeyState, PointerAxis, TabletPadRingAxisSource,
+            TabletPadStripAxisSource, TabletToolProximityState, TabletToolTipState, TabletToolType,
+        },
         device::LibInputDevice,
         sys::{
-            libinput_event, libinput_event_destroy, libinput_event_get_device,
-            libinput_event_get_keyboard_event, libinput_event_get_pointer_event,
+            libinput_event, libinput_event_destroy, libinput_event_gesture,
+            libinput_event_gesture_get_angle_delta, libinput_event_gesture_get_cancelled,
+            libinput_event_gesture_get_dx, libinput_event_gesture_get_dy,
+            libinput_event_gesture_get_finger_count, libinput_event_gesture_get_scale,
+            libinput_event_gesture_get_time_usec, libinput_event_get_device,
+            libinput_event_get_gesture_event, libinput_event_get_keyboard_event,
+            libinput_event_get_pointer_event, libinput_event_get_tablet_pad_event,
+            libinput_event_get_tablet_tool_event, libinput_event_get_touch_event,
             libinput_event_get_type, libinput_event_keyboard, libinput_event_keyboard_get_key,
             libinput_event_keyboard_get_key_state, libinput_event_keyboard_get_time_usec,
             libinput_event_pointer, libinput_event_pointer_get_button,
@@ -12,7 +21,44 @@ use {
             libinput_event_pointer_get_dx_unaccelerated, libinput_event_pointer_get_dy,
             libinput_event_pointer_get_dy_unaccelerated, libinput_event_pointer_get_scroll_value,
             libinput_event_pointer_get_scroll_value_v120, libinput_event_pointer_get_time_usec,
-            libinput_event_pointer_has_axis,
+            libinput_event_pointer_has_axis, libinput_event_tablet_pad,
+            libinput_event_tablet_pad_get_button_number,
+            libinput_event_tablet_pad_get_button_state, libinput_event_tablet_pad_get_mode,
+            libinput_event_tablet_pad_get_mode_group, libinput_event_tablet_pad_get_ring_number,
+            libinput_event_tablet_pad_get_ring_position, libinput_event_tablet_pad_get_ring_source,
+            libinput_event_tablet_pad_get_strip_number,
+            libinput_event_tablet_pad_get_strip_position,
+            libinput_event_tablet_pad_get_strip_source, libinput_event_tablet_pad_get_time_usec,
+            libinput_event_tablet_tool, libinput_event_tablet_tool_distance_has_changed,
+            libinput_event_tablet_tool_get_button, libinput_event_tablet_tool_get_button_state,
+            libinput_event_tablet_tool_get_distance, libinput_event_tablet_tool_get_pressure,
+            libinput_event_tablet_tool_get_proximity_state,
+            libinput_event_tablet_tool_get_rotation,
+            libinput_event_tablet_tool_get_slider_position, libinput_event_tablet_tool_get_tilt_x,
+            libinput_event_tablet_tool_get_tilt_y, libinput_event_tablet_tool_get_time_usec,
+            libinput_event_tablet_tool_get_tip_state, libinput_event_tablet_tool_get_tool,
+            libinput_event_tablet_tool_get_wheel_delta,
+            libinput_event_tablet_tool_get_wheel_delta_discrete,
+            libinput_event_tablet_tool_get_x_transformed,
+            libinput_event_tablet_tool_get_y_transformed,
+            libinput_event_tablet_tool_pressure_has_changed,
+            libinput_event_tablet_tool_rotation_has_changed,
+            libinput_event_tablet_tool_slider_has_changed,
+            libinput_event_tablet_tool_tilt_x_has_changed,
+            libinput_event_tablet_tool_tilt_y_has_changed,
+            libinput_event_tablet_tool_wheel_has_changed, libinput_event_tablet_tool_x_has_changed,
+            libinput_event_tablet_tool_y_has_changed, libinput_event_touch,
+            libinput_event_touch_get_seat_slot, libinput_event_touch_get_time_usec,
+            libinput_event_touch_get_x_transformed, libinput_event_touch_get_y_transformed,
+            libinput_tablet_pad_mode_group, libinput_tablet_pad_mode_group_get_index,
+            libinput_tablet_pad_mode_group_get_mode, libinput_tablet_pad_mode_group_get_num_modes,
+            libinput_tablet_pad_mode_group_has_button, libinput_tablet_pad_mode_group_has_ring,
+            libinput_tablet_pad_mode_group_has_strip, libinput_tablet_tool,
+            libinput_tablet_tool_get_serial, libinput_tablet_tool_get_tool_id,
+            libinput_tablet_tool_get_type, libinput_tablet_tool_has_distance,
+            libinput_tablet_tool_has_pressure, libinput_tablet_tool_has_rotation,
+            libinput_tablet_tool_has_slider, libinput_tablet_tool_has_tilt,
+            libinput_tablet_tool_has_wheel,
         },
     },
     std::marker::PhantomData,
@@ -33,6 +79,36 @@ pub struct LibInputEventPointer<'a> {
     pub(super) _phantom: PhantomData<&'a ()>,
 }
 
+pub struct LibInputEventTouch<'a> {
+    pub(super) event: *mut libinput_event_touch,
+    pub(super) _phantom: PhantomData<&'a ()>,
+}
+
+pub struct LibInputEventGesture<'a> {
+    pub(super) event: *mut libinput_event_gesture,
+    pub(super) _phantom: PhantomData<&'a ()>,
+}
+
+pub struct LibInputEventTabletTool<'a> {
+    pub(super) event: *mut libinput_event_tablet_tool,
+    pub(super) _phantom: PhantomData<&'a ()>,
+}
+
+pub struct LibInputEventTabletPad<'a> {
+    pub(super) event: *mut libinput_event_tablet_pad,
+    pub(super) _phantom: PhantomData<&'a ()>,
+}
+
+pub struct LibInputTabletTool<'a> {
+    pub(super) tool: *mut libinput_tablet_tool,
+    pub(super) _phantom: PhantomData<&'a ()>,
+}
+
+pub struct LibInputTabletPadModeGroup<'a> {
+    pub(super) group: *mut libinput_tablet_pad_mode_group,
+    pub(super) _phantom: PhantomData<&'a ()>,
+}
+
 impl<'a> Drop for LibInputEvent<'a> {
     fn drop(&mut self) {
         unsafe {
@@ -76,6 +152,54 @@ impl<'a> LibInputEvent<'a> {
             })
         }
     }
+
+    pub fn tablet_tool_event(&self) -> Option<LibInputEventTabletTool> {
+        let res = unsafe { libinput_event_get_tablet_tool_event(self.event) };
+        if res.is_null() {
+            None
+        } else {
+            Some(LibInputEventTabletTool {
+                event: res,
+                _phantom: Default::default(),
+            })
+        }
+    }
+
+    pub fn tablet_pad_event(&self) -> Option<LibInputEventTabletPad> {
+        let res = unsafe { libinput_event_get_tablet_pad_event(self.event) };
+        if res.is_null() {
+            None
+        } else {
+            Some(LibInputEventTabletPad {
+                event: res,
+                _phantom: Default::default(),
+            })
+        }
+    }
+
+    pub fn touch_event(&self) -> Option<LibInputEventTouch> {
+        let res = unsafe { libinput_event_get_touch_event(self.event) };
+        if res.is_null() {
+            None
+        } else {
+            Some(LibInputEventTouch {
+                event: res,
+                _phantom: Default::default(),
+            })
+        }
+    }
+
+    pub fn gesture_event(&self) -> Option<LibInputEventGesture> {
+        let res = unsafe { libinput_event_get_gesture_event(self.event) };
+        if res.is_null() {
+            None
+        } else {
+            Some(LibInputEventGesture {
+                event: res,
+                _phantom: Default::default(),
+            })
+        }
+    }
 }
 
 impl<'a> LibInputEventKeyboard<'a> {
@@ -134,3 +258,279 @@ impl<'a> LibInputEventPointer<'a> {
         unsafe { libinput_event_pointer_get_time_usec(self.event) }
     }
 }
+
+impl<'a> LibInputEventTouch<'a> {
+    pub fn seat_slot(&self) -> i32 {
+        unsafe { libinput_event_touch_get_seat_slot(self.event) }
+    }
+
+    /// Returns the x coordinate normalized to the range `[0, 1]`.
+    pub fn x_normed(&self) -> f64 {
+        unsafe { libinput_event_touch_get_x_transformed(self.event, 1) }
+    }
+
+    /// Returns the y coordinate normalized to the range `[0, 1]`.
+    pub fn y_normed(&self) -> f64 {
+        unsafe { libinput_event_touch_get_y_transformed(self.event, 1) }
+    }
+
+    pub fn time_usec(&self) -> u64 {
+        unsafe { libinput_event_touch_get_time_usec(self.event) }
+    }
+}
+
+impl<'a> LibInputEventGesture<'a> {
+    pub fn time_usec(&self) -> u64 {
+        unsafe { libinput_event_gesture_get_time_usec(self.event) }
+    }
+
+    pub fn finger_count(&self) -> u32 {
+        unsafe { libinput_event_gesture_get_finger_count(self.event) as u32 }
+    }
+
+    pub fn cancelled(&self) -> bool {
+        unsafe { libinput_event_gesture_get_cancelled(self.event) != 0 }
+    }
+
+    pub fn dx(&self) -> f64 {
+        unsafe { libinput_event_gesture_get_dx(self.event) }
+    }
+
+    pub fn dy(&self) -> f64 {
+        unsafe { libinput_event_gesture_get_dy(self.event) }
+    }
+
+    pub fn scale(&self) -> f64 {
+        unsafe { libinput_event_gesture_get_scale(self.event) }
+    }
+
+    pub fn angle_delta(&self) -> f64 {
+        unsafe { libinput_event_gesture_get_angle_delta(self.event) }
+    }
+}
+
+impl<'a> LibInputEventTabletTool<'a> {
+    pub fn tool(&self) -> LibInputTabletTool {
+        LibInputTabletTool {
+            tool: unsafe { libinput_event_tablet_tool_get_tool(self.event) },
+            _phantom: Default::default(),
+        }
+    }
+
+    pub fn time_usec(&self) -> u64 {
+        unsafe { libinput_event_tablet_tool_get_time_usec(self.event) }
+    }
+
+    pub fn x_has_changed(&self) -> bool {
+        unsafe { libinput_event_tablet_tool_x_has_changed(self.event) != 0 }
+    }
+
+    pub fn y_has_changed(&self) -> bool {
+        unsafe { libinput_event_tablet_tool_y_has_changed(self.event) != 0 }
+    }
+
+    pub fn pressure_has_changed(&self) -> bool {
+        unsafe { libinput_event_tablet_tool_pressure_has_changed(self.event) != 0 }
+    }
+
+    pub fn distance_has_changed(&self) -> bool {
+        unsafe { libinput_event_tablet_tool_distance_has_changed(self.event) != 0 }
+    }
+
+    pub fn tilt_x_has_changed(&self) -> bool {
+        unsafe { libinput_event_tablet_tool_tilt_x_has_changed(self.event) != 0 }
+    }
+
+    pub fn tilt_y_has_changed(&self) -> bool {
+        unsafe { libinput_event_tablet_tool_tilt_y_has_changed(self.event) != 0 }
+    }
+
+    pub fn rotation_has_changed(&self) -> bool {
+        unsafe { libinput_event_tablet_tool_rotation_has_changed(self.event) != 0 }
+    }
+
+    pub fn slider_has_changed(&self) -> bool {
+        unsafe { libinput_event_tablet_tool_slider_has_changed(self.event) != 0 }
+    }
+
+    pub fn wheel_has_changed(&self) -> bool {
+        unsafe { libinput_event_tablet_tool_wheel_has_changed(self.event) != 0 }
+    }
+
+    /// Returns the x coordinate normalized to the range `[0, 1]`.
+    pub fn x_normed(&self) -> f64 {
+        unsafe { libinput_event_tablet_tool_get_x_transformed(self.event, 1) }
+    }
+
+    /// Returns the y coordinate normalized to the range `[0, 1]`.
+    pub fn y_normed(&self) -> f64 {
+        unsafe { libinput_event_tablet_tool_get_y_transformed(self.event, 1) }
+    }
+
+    pub fn pressure(&self) -> f64 {
+        unsafe { libinput_event_tablet_tool_get_pressure(self.event) }
+    }
+
+    pub fn distance(&self) -> f64 {
+        unsafe { libinput_event_tablet_tool_get_distance(self.event) }
+    }
+
+    pub fn tilt_x(&self) -> f64 {
+        unsafe { libinput_event_tablet_tool_get_tilt_x(self.event) }
+    }
+
+    pub fn tilt_y(&self) -> f64 {
+        unsafe { libinput_event_tablet_tool_get_tilt_y(self.event) }
+    }
+
+    pub fn rotation(&self) -> f64 {
+        unsafe { libinput_event_tablet_tool_get_rotation(self.event) }
+    }
+
+    pub fn slider_position(&self) -> f64 {
+        unsafe { libinput_event_tablet_tool_get_slider_position(self.event) }
+    }
+
+    pub fn wheel_delta(&self) -> f64 {
+        unsafe { libinput_event_tablet_tool_get_wheel_delta(self.event) }
+    }
+
+    pub fn wheel_delta_discrete(&self) -> i32 {
+        unsafe { libinput_event_tablet_tool_get_wheel_delta_discrete(self.event) as _ }
+    }
+
+    pub fn proximity_state(&self) -> TabletToolProximityState {
+        unsafe {
+            TabletToolProximityState(libinput_event_tablet_tool_get_proximity_state(self.event))
+        }
+    }
+
+    pub fn tip_state(&self) -> TabletToolTipState {
+        unsafe { TabletToolTipState(libinput_event_tablet_tool_get_tip_state(self.event)) }
+    }
+
+    pub fn button(&self) -> u32 {
+        unsafe { libinput_event_tablet_tool_get_button(self.event) }
+    }
+
+    pub fn button_state(&self) -> ButtonState {
+        unsafe { ButtonState(libinput_event_tablet_tool_get_button_state(self.event)) }
+    }
+}
+
+impl<'a> LibInputTabletTool<'a> {
+    pub fn raw(&self) -> usize {
+        self.tool as usize
+    }
+
+    pub fn ty(&self) -> TabletToolType {
+        unsafe { TabletToolType(libinput_tablet_tool_get_type(self.tool)) }
+    }
+
+    pub fn tool_id(&self) -> u64 {
+        unsafe { libinput_tablet_tool_get_tool_id(self.tool) }
+    }
+
+    pub fn serial(&self) -> u64 {
+        unsafe { libinput_tablet_tool_get_serial(self.tool) }
+    }
+
+    pub fn has_pressure(&self) -> bool {
+        unsafe { libinput_tablet_tool_has_pressure(self.tool) != 0 }
+    }
+
+    pub fn has_distance(&self) -> bool {
+        unsafe { libinput_tablet_tool_has_distance(self.tool) != 0 }
+    }
+
+    pub fn has_tilt(&self) -> bool {
+        unsafe { libinput_tablet_tool_has_tilt(self.tool) != 0 }
+    }
+
+    pub fn has_rotation(&self) -> bool {
+        unsafe { libinput_tablet_tool_has_rotation(self.tool) != 0 }
+    }
+
+    pub fn has_slider(&self) -> bool {
+        unsafe { libinput_tablet_tool_has_slider(self.tool) != 0 }
+    }
+
+    pub fn has_wheel(&self) -> bool {
+        unsafe { libinput_tablet_tool_has_wheel(self.tool) != 0 }
+    }
+}
+
+impl<'a> LibInputEventTabletPad<'a> {
+    pub fn time_usec(&self) -> u64 {
+        unsafe { libinput_event_tablet_pad_get_time_usec(self.event) }
+    }
+
+    pub fn ring_number(&self) -> u32 {
+        unsafe { libinput_event_tablet_pad_get_ring_number(self.event) as _ }
+    }
+
+    pub fn ring_position(&self) -> f64 {
+        unsafe { libinput_event_tablet_pad_get_ring_position(self.event) }
+    }
+
+    pub fn ring_source(&self) -> TabletPadRingAxisSource {
+        unsafe { TabletPadRingAxisSource(libinput_event_tablet_pad_get_ring_source(self.event)) }
+    }
+
+    pub fn strip_number(&self) -> u32 {
+        unsafe { libinput_event_tablet_pad_get_strip_number(self.event) as _ }
+    }
+
+    pub fn strip_position(&self) -> f64 {
+        unsafe { libinput_event_tablet_pad_get_strip_position(self.event) }
+    }
+
+    pub fn strip_source(&self) -> TabletPadStripAxisSource {
+        unsafe { TabletPadStripAxisSource(libinput_event_tablet_pad_get_strip_source(self.event)) }
+    }
+
+    pub fn button_number(&self) -> u32 {
+        unsafe { libinput_event_tablet_pad_get_button_number(self.event) }
+    }
+
+    pub fn button_state(&self) -> ButtonState {
+        unsafe { ButtonState(libinput_event_tablet_pad_get_button_state(self.event)) }
+    }
+
+    pub fn mode(&self) -> u32 {
+        unsafe { libinput_event_tablet_pad_get_mode(self.event) as _ }
+    }
+
+    pub fn mode_group(&self) -> LibInputTabletPadModeGroup {
+        LibInputTabletPadModeGroup {
+            group: unsafe { libinput_event_tablet_pad_get_mode_group(self.event) },
+            _phantom: Default::default(),
+        }
+    }
+}
+
+impl<'a> LibInputTabletPadModeGroup<'a> {
+    pub fn index(&self) -> u32 {
+        unsafe { libinput_tablet_pad_mode_group_get_index(self.group) as _ }
+    }
+
+    pub fn num_modes(&self) -> u32 {
+        unsafe { libinput_tablet_pad_mode_group_get_num_modes(self.group) as _ }
+    }
+
+    pub fn mode(&self) -> u32 {
+        unsafe { libinput_tablet_pad_mode_group_get_mode(self.group) as _ }
+    }
+
+    pub fn has_button(&self, button: u32) -> bool {
+        unsafe { libinput_tablet_pad_mode_group_has_button(self.group, button as _) != 0 }
+    }
+
+    pub fn has_ring(&self, ring: u32) -> bool {
+        unsafe { libinput_tablet_pad_mode_group_has_ring(self.group, ring as _) != 0 }
+    }
+
+    pub fn has_strip(&self, strip: u32) -> bool {
+        unsafe { libinput_tablet_pad_mode_group_has_strip(self.group, strip as _) != 0 }
+    }
+}
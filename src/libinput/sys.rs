@@ -16,6 +16,13 @@ extern "C" {
     pub type libinput_event;
     pub type libinput_event_keyboard;
     pub type libinput_event_pointer;
+    pub type libinput_event_touch;
+    pub type libinput_event_gesture;
+    pub type libinput_event_tablet_tool;
+    pub type libinput_event_tablet_pad;
+    pub type libinput_tablet_tool;
+    pub type libinput_tablet_pad_mode_group;
+    pub type libinput_device_group;
 
     pub fn libinput_log_set_handler(libinput: *mut libinput, log_handler: libinput_log_handler);
     pub fn libinput_log_set_priority(libinput: *mut libinput, priority: libinput_log_priority);
@@ -54,6 +61,20 @@ extern "C" {
         speed: f64,
     ) -> libinput_config_status;
     pub fn libinput_device_get_name(device: *mut libinput_device) -> *const c::c_char;
+    pub fn libinput_device_get_id_vendor(device: *mut libinput_device) -> c::c_uint;
+    pub fn libinput_device_get_id_product(device: *mut libinput_device) -> c::c_uint;
+    pub fn libinput_device_get_device_group(
+        device: *mut libinput_device,
+    ) -> *mut libinput_device_group;
+    pub fn libinput_device_tablet_pad_get_num_buttons(device: *mut libinput_device) -> c::c_int;
+    pub fn libinput_device_tablet_pad_get_num_rings(device: *mut libinput_device) -> c::c_int;
+    pub fn libinput_device_tablet_pad_get_num_strips(device: *mut libinput_device) -> c::c_int;
+    pub fn libinput_device_tablet_pad_get_num_mode_groups(device: *mut libinput_device)
+        -> c::c_int;
+    pub fn libinput_device_tablet_pad_get_mode_group(
+        device: *mut libinput_device,
+        index: c::c_uint,
+    ) -> *mut libinput_tablet_pad_mode_group;
     pub fn libinput_device_config_tap_set_enabled(
         device: *mut libinput_device,
         enable: libinput_config_tap_state,
@@ -120,6 +141,163 @@ extern "C" {
     //     event: *mut libinput_event_pointer,
     //     axis: libinput_pointer_axis,
     // ) -> f64;
+
+    pub fn libinput_event_get_tablet_tool_event(
+        event: *mut libinput_event,
+    ) -> *mut libinput_event_tablet_tool;
+    pub fn libinput_event_tablet_tool_get_tool(
+        event: *mut libinput_event_tablet_tool,
+    ) -> *mut libinput_tablet_tool;
+    pub fn libinput_event_tablet_tool_get_time_usec(event: *mut libinput_event_tablet_tool) -> u64;
+    pub fn libinput_event_tablet_tool_x_has_changed(
+        event: *mut libinput_event_tablet_tool,
+    ) -> c::c_int;
+    pub fn libinput_event_tablet_tool_y_has_changed(
+        event: *mut libinput_event_tablet_tool,
+    ) -> c::c_int;
+    pub fn libinput_event_tablet_tool_pressure_has_changed(
+        event: *mut libinput_event_tablet_tool,
+    ) -> c::c_int;
+    pub fn libinput_event_tablet_tool_distance_has_changed(
+        event: *mut libinput_event_tablet_tool,
+    ) -> c::c_int;
+    pub fn libinput_event_tablet_tool_tilt_x_has_changed(
+        event: *mut libinput_event_tablet_tool,
+    ) -> c::c_int;
+    pub fn libinput_event_tablet_tool_tilt_y_has_changed(
+        event: *mut libinput_event_tablet_tool,
+    ) -> c::c_int;
+    pub fn libinput_event_tablet_tool_rotation_has_changed(
+        event: *mut libinput_event_tablet_tool,
+    ) -> c::c_int;
+    pub fn libinput_event_tablet_tool_slider_has_changed(
+        event: *mut libinput_event_tablet_tool,
+    ) -> c::c_int;
+    pub fn libinput_event_tablet_tool_wheel_has_changed(
+        event: *mut libinput_event_tablet_tool,
+    ) -> c::c_int;
+    pub fn libinput_event_tablet_tool_get_x_transformed(
+        event: *mut libinput_event_tablet_tool,
+        width: u32,
+    ) -> f64;
+    pub fn libinput_event_tablet_tool_get_y_transformed(
+        event: *mut libinput_event_tablet_tool,
+        height: u32,
+    ) -> f64;
+    pub fn libinput_event_tablet_tool_get_pressure(event: *mut libinput_event_tablet_tool) -> f64;
+    pub fn libinput_event_tablet_tool_get_distance(event: *mut libinput_event_tablet_tool) -> f64;
+    pub fn libinput_event_tablet_tool_get_tilt_x(event: *mut libinput_event_tablet_tool) -> f64;
+    pub fn libinput_event_tablet_tool_get_tilt_y(event: *mut libinput_event_tablet_tool) -> f64;
+    pub fn libinput_event_tablet_tool_get_rotation(event: *mut libinput_event_tablet_tool) -> f64;
+    pub fn libinput_event_tablet_tool_get_slider_position(
+        event: *mut libinput_event_tablet_tool,
+    ) -> f64;
+    pub fn libinput_event_tablet_tool_get_wheel_delta(
+        event: *mut libinput_event_tablet_tool,
+    ) -> f64;
+    pub fn libinput_event_tablet_tool_get_wheel_delta_discrete(
+        event: *mut libinput_event_tablet_tool,
+    ) -> c::c_int;
+    pub fn libinput_event_tablet_tool_get_proximity_state(
+        event: *mut libinput_event_tablet_tool,
+    ) -> libinput_tablet_tool_proximity_state;
+    pub fn libinput_event_tablet_tool_get_tip_state(
+        event: *mut libinput_event_tablet_tool,
+    ) -> libinput_tablet_tool_tip_state;
+    pub fn libinput_event_tablet_tool_get_button(event: *mut libinput_event_tablet_tool) -> u32;
+    pub fn libinput_event_tablet_tool_get_button_state(
+        event: *mut libinput_event_tablet_tool,
+    ) -> libinput_button_state;
+
+    pub fn libinput_tablet_tool_get_type(
+        tool: *mut libinput_tablet_tool,
+    ) -> libinput_tablet_tool_type;
+    pub fn libinput_tablet_tool_get_tool_id(tool: *mut libinput_tablet_tool) -> u64;
+    pub fn libinput_tablet_tool_get_serial(tool: *mut libinput_tablet_tool) -> u64;
+    pub fn libinput_tablet_tool_has_pressure(tool: *mut libinput_tablet_tool) -> c::c_int;
+    pub fn libinput_tablet_tool_has_distance(tool: *mut libinput_tablet_tool) -> c::c_int;
+    pub fn libinput_tablet_tool_has_tilt(tool: *mut libinput_tablet_tool) -> c::c_int;
+    pub fn libinput_tablet_tool_has_rotation(tool: *mut libinput_tablet_tool) -> c::c_int;
+    pub fn libinput_tablet_tool_has_slider(tool: *mut libinput_tablet_tool) -> c::c_int;
+    pub fn libinput_tablet_tool_has_wheel(tool: *mut libinput_tablet_tool) -> c::c_int;
+
+    pub fn libinput_event_get_tablet_pad_event(
+        event: *mut libinput_event,
+    ) -> *mut libinput_event_tablet_pad;
+    pub fn libinput_event_tablet_pad_get_time_usec(event: *mut libinput_event_tablet_pad) -> u64;
+    pub fn libinput_event_tablet_pad_get_ring_number(
+        event: *mut libinput_event_tablet_pad,
+    ) -> c::c_uint;
+    pub fn libinput_event_tablet_pad_get_ring_position(
+        event: *mut libinput_event_tablet_pad,
+    ) -> f64;
+    pub fn libinput_event_tablet_pad_get_ring_source(
+        event: *mut libinput_event_tablet_pad,
+    ) -> libinput_tablet_pad_ring_axis_source;
+    pub fn libinput_event_tablet_pad_get_strip_number(
+        event: *mut libinput_event_tablet_pad,
+    ) -> c::c_uint;
+    pub fn libinput_event_tablet_pad_get_strip_position(
+        event: *mut libinput_event_tablet_pad,
+    ) -> f64;
+    pub fn libinput_event_tablet_pad_get_strip_source(
+        event: *mut libinput_event_tablet_pad,
+    ) -> libinput_tablet_pad_strip_axis_source;
+    pub fn libinput_event_tablet_pad_get_button_number(
+        event: *mut libinput_event_tablet_pad,
+    ) -> u32;
+    pub fn libinput_event_tablet_pad_get_button_state(
+        event: *mut libinput_event_tablet_pad,
+    ) -> libinput_button_state;
+    pub fn libinput_event_tablet_pad_get_mode(event: *mut libinput_event_tablet_pad) -> c::c_uint;
+    pub fn libinput_event_tablet_pad_get_mode_group(
+        event: *mut libinput_event_tablet_pad,
+    ) -> *mut libinput_tablet_pad_mode_group;
+
+    pub fn libinput_tablet_pad_mode_group_get_index(
+        group: *mut libinput_tablet_pad_mode_group,
+    ) -> c::c_uint;
+    pub fn libinput_tablet_pad_mode_group_get_num_modes(
+        group: *mut libinput_tablet_pad_mode_group,
+    ) -> c::c_uint;
+    pub fn libinput_tablet_pad_mode_group_get_mode(
+        group: *mut libinput_tablet_pad_mode_group,
+    ) -> c::c_uint;
+    pub fn libinput_tablet_pad_mode_group_has_button(
+        group: *mut libinput_tablet_pad_mode_group,
+        button: c::c_uint,
+    ) -> c::c_int;
+    pub fn libinput_tablet_pad_mode_group_has_ring(
+        group: *mut libinput_tablet_pad_mode_group,
+        ring: c::c_uint,
+    ) -> c::c_int;
+    pub fn libinput_tablet_pad_mode_group_has_strip(
+        group: *mut libinput_tablet_pad_mode_group,
+        strip: c::c_uint,
+    ) -> c::c_int;
+
+    pub fn libinput_event_get_touch_event(event: *mut libinput_event) -> *mut libinput_event_touch;
+    pub fn libinput_event_touch_get_time_usec(event: *mut libinput_event_touch) -> u64;
+    pub fn libinput_event_touch_get_seat_slot(event: *mut libinput_event_touch) -> i32;
+    pub fn libinput_event_touch_get_x_transformed(
+        event: *mut libinput_event_touch,
+        width: u32,
+    ) -> f64;
+    pub fn libinput_event_touch_get_y_transformed(
+        event: *mut libinput_event_touch,
+        height: u32,
+    ) -> f64;
+
+    pub fn libinput_event_get_gesture_event(
+        event: *mut libinput_event,
+    ) -> *mut libinput_event_gesture;
+    pub fn libinput_event_gesture_get_time_usec(event: *mut libinput_event_gesture) -> u64;
+    pub fn libinput_event_gesture_get_finger_count(event: *mut libinput_event_gesture) -> c::c_int;
+    pub fn libinput_event_gesture_get_cancelled(event: *mut libinput_event_gesture) -> c::c_int;
+    pub fn libinput_event_gesture_get_dx(event: *mut libinput_event_gesture) -> f64;
+    pub fn libinput_event_gesture_get_dy(event: *mut libinput_event_gesture) -> f64;
+    pub fn libinput_event_gesture_get_scale(event: *mut libinput_event_gesture) -> f64;
+    pub fn libinput_event_gesture_get_angle_delta(event: *mut libinput_event_gesture) -> f64;
 }
 
 #[repr(C)]
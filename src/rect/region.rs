@@ -145,7 +145,6 @@ impl RegionBuilder {
         self.base.clone()
     }
 
-    #[allow(dead_code)]
     pub fn clear(&mut self) {
         self.pending.clear();
         self.base = Region::empty();
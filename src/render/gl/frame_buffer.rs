@@ -40,16 +40,19 @@ pub unsafe fn with_scissor<T, F: FnOnce() -> T>(scissor: &Rect, f: F) -> T {
     static mut SCISSOR: *const Rect = ptr::null();
 
     let prev = SCISSOR;
-    if prev.is_null() {
+    let scissor = if prev.is_null() {
         glEnable(GL_SCISSOR_TEST);
-    }
+        *scissor
+    } else {
+        scissor.intersect(*prev.deref())
+    };
     glScissor(
         scissor.x1(),
         scissor.y1(),
         scissor.width(),
         scissor.height(),
     );
-    SCISSOR = scissor;
+    SCISSOR = &scissor;
     let res = f();
     if prev.is_null() {
         glDisable(GL_SCISSOR_TEST);
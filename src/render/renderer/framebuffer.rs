@@ -3,16 +3,21 @@ use {
         cursor::Cursor,
         fixed::Fixed,
         format::{Format, ARGB8888, XRGB8888},
-        rect::Rect,
+        ifs::wl_surface::SurfaceNodeId,
+        rect::{Rect, Region},
         render::{
             gl::{
-                frame_buffer::GlFrameBuffer,
+                frame_buffer::{with_scissor, GlFrameBuffer},
                 sys::{
                     glBindFramebuffer, glClear, glClearColor, glViewport, GL_COLOR_BUFFER_BIT,
                     GL_FRAMEBUFFER,
                 },
             },
-            renderer::{context::RenderContext, renderer::Renderer, renderer_base::RendererBase},
+            renderer::{
+                context::RenderContext,
+                renderer::{DrawOp, Renderer},
+                renderer_base::RendererBase,
+            },
             sys::{glBlendFunc, glFlush, glReadnPixels, GL_ONE, GL_ONE_MINUS_SRC_ALPHA},
             RenderResult, Texture,
         },
@@ -21,9 +26,10 @@ use {
         tree::Node,
     },
     std::{
-        cell::Cell,
+        cell::{Cell, RefCell},
         fmt::{Debug, Formatter},
         rc::Rc,
+        slice,
     },
 };
 
@@ -74,11 +80,14 @@ impl Framebuffer {
                     scaled: false,
                     scale,
                     scalef: 1.0,
+                    recording: None,
                 },
                 state,
                 on_output: false,
                 result: &mut RenderResult::default(),
                 logical_extents: Rect::new_sized(0, 0, self.gl.width, self.gl.height).unwrap(),
+                offloaded: &[],
+                clip: None,
             };
             let format = match alpha {
                 true => ARGB8888,
@@ -136,6 +145,7 @@ impl Framebuffer {
                 scaled: scale != 1,
                 scale,
                 scalef: scale.to_f64(),
+                recording: None,
             };
             f(&mut renderer);
             unsafe {
@@ -154,6 +164,8 @@ impl Framebuffer {
         result: &mut RenderResult,
         scale: Scale,
         render_hardware_cursor: bool,
+        damage: Option<&Region>,
+        offloaded: &[SurfaceNodeId],
     ) {
         let _ = self.ctx.ctx.with_current(|| {
             let c = state.theme.colors.background.get();
@@ -161,7 +173,6 @@ impl Framebuffer {
                 glBindFramebuffer(GL_FRAMEBUFFER, self.gl.fbo);
                 glViewport(0, 0, self.gl.width, self.gl.height);
                 glClearColor(c.r, c.g, c.b, 1.0);
-                glClear(GL_COLOR_BUFFER_BIT);
                 glBlendFunc(GL_ONE, GL_ONE_MINUS_SRC_ALPHA);
             }
             let mut renderer = Renderer {
@@ -171,36 +182,39 @@ impl Framebuffer {
                     scaled: scale != 1,
                     scale,
                     scalef: scale.to_f64(),
+                    recording: None,
                 },
                 state,
                 on_output,
                 result,
                 logical_extents: node.node_absolute_position().at_point(0, 0),
+                offloaded,
+                clip: None,
             };
-            node.node_render(&mut renderer, 0, 0);
-            if let Some(rect) = cursor_rect {
-                let seats = state.globals.lock_seats();
-                for seat in seats.values() {
-                    if !render_hardware_cursor && seat.hardware_cursor() {
-                        continue;
-                    }
-                    if let Some(cursor) = seat.get_cursor() {
-                        let (mut x, mut y) = seat.get_position();
-                        if let Some(dnd_icon) = seat.dnd_icon() {
-                            let extents = dnd_icon.extents.get().move_(
-                                x.round_down() + dnd_icon.buf_x.get(),
-                                y.round_down() + dnd_icon.buf_y.get(),
-                            );
-                            if extents.intersects(&rect) {
-                                let (x, y) = rect.translate(extents.x1(), extents.y1());
-                                renderer.render_surface(&dnd_icon, x, y);
-                            }
-                        }
-                        cursor.tick();
-                        x -= Fixed::from_int(rect.x1());
-                        y -= Fixed::from_int(rect.y1());
-                        cursor.render(&mut renderer, x, y);
-                    }
+            let full = Rect::new_sized(0, 0, self.gl.width, self.gl.height).unwrap();
+            let empty = Rect::new_empty(0, 0);
+            let extents;
+            let rects = match damage {
+                None => slice::from_ref(&full),
+                Some(damage) if damage.is_empty() => slice::from_ref(&empty),
+                Some(damage) if damage.len() > MAX_DAMAGE_RECTS => {
+                    extents = damage.extents();
+                    slice::from_ref(&extents)
+                }
+                Some(damage) => &damage[..],
+            };
+            for rect in rects {
+                unsafe {
+                    with_scissor(rect, || {
+                        glClear(GL_COLOR_BUFFER_BIT);
+                        render_pass(
+                            &mut renderer,
+                            node,
+                            state,
+                            cursor_rect,
+                            render_hardware_cursor,
+                        );
+                    });
                 }
             }
             unsafe {
@@ -210,6 +224,44 @@ impl Framebuffer {
         });
     }
 
+    pub fn record(
+        &self,
+        node: &dyn Node,
+        state: &State,
+        cursor_rect: Option<Rect>,
+        scale: Scale,
+        render_hardware_cursor: bool,
+    ) -> Vec<DrawOp> {
+        let recording = RefCell::new(vec![]);
+        let _ = self.ctx.ctx.with_current(|| {
+            let mut renderer = Renderer {
+                base: RendererBase {
+                    ctx: &self.ctx,
+                    fb: &self.gl,
+                    scaled: scale != 1,
+                    scale,
+                    scalef: scale.to_f64(),
+                    recording: Some(&recording),
+                },
+                state,
+                on_output: false,
+                result: &mut RenderResult::default(),
+                logical_extents: node.node_absolute_position().at_point(0, 0),
+                offloaded: &[],
+                clip: None,
+            };
+            render_pass(
+                &mut renderer,
+                node,
+                state,
+                cursor_rect,
+                render_hardware_cursor,
+            );
+            Ok(())
+        });
+        recording.into_inner()
+    }
+
     pub fn render_hardware_cursor(&self, cursor: &dyn Cursor, state: &State, scale: Scale) {
         let _ = self.ctx.ctx.with_current(|| {
             unsafe {
@@ -227,11 +279,14 @@ impl Framebuffer {
                     scaled: scale != 1,
                     scale,
                     scalef: scale.to_f64(),
+                    recording: None,
                 },
                 state,
                 on_output: false,
                 result: &mut res,
                 logical_extents: Rect::new_empty(0, 0),
+                offloaded: &[],
+                clip: None,
             };
             cursor.render_hardware_cursor(&mut renderer);
             unsafe {
@@ -241,3 +296,52 @@ impl Framebuffer {
         });
     }
 }
+
+const MAX_DAMAGE_RECTS: usize = 8;
+
+fn render_pass(
+    renderer: &mut Renderer,
+    node: &dyn Node,
+    state: &State,
+    cursor_rect: Option<Rect>,
+    render_hardware_cursor: bool,
+) {
+    node.node_render(renderer, 0, 0);
+    if let Some(rect) = cursor_rect {
+        let seats = state.globals.lock_seats();
+        for seat in seats.values() {
+            seat.for_each_input_popup(|popup| {
+                let pos = popup.buffer_abs_pos.get();
+                let extents = popup.extents.get().move_(pos.x1(), pos.y1());
+                if extents.intersects(&rect) {
+                    let (x, y) = rect.translate(pos.x1(), pos.y1());
+                    renderer.render_surface(popup, x, y);
+                }
+            });
+        }
+        for seat in seats.values() {
+            if !render_hardware_cursor && seat.hardware_cursor() {
+                continue;
+            }
+            if let Some(cursor) = seat.get_cursor() {
+                let (mut x, mut y) = seat.get_position();
+                if let Some(dnd_icon) = seat.dnd_icon() {
+                    let extents = dnd_icon.extents.get().move_(
+                        x.round_down() + dnd_icon.buf_x.get(),
+                        y.round_down() + dnd_icon.buf_y.get(),
+                    );
+                    if extents.intersects(&rect) {
+                        let (x, y) = rect.translate(extents.x1(), extents.y1());
+                        renderer.render_surface(&dnd_icon, x, y);
+                    }
+                }
+                if renderer.base.recording.is_none() {
+                    cursor.tick();
+                }
+                x -= Fixed::from_int(rect.x1());
+                y -= Fixed::from_int(rect.y1());
+                cursor.render(renderer, x, y);
+            }
+        }
+    }
+}
@@ -5,7 +5,8 @@ use {
             wl_buffer::WlBuffer,
             wl_callback::WlCallback,
             wl_surface::{
-                xdg_surface::XdgSurface, zwlr_layer_surface_v1::ZwlrLayerSurfaceV1, WlSurface,
+                xdg_surface::XdgSurface, zwlr_layer_surface_v1::ZwlrLayerSurfaceV1, SurfaceNodeId,
+                WlSurface,
             },
             wp_presentation_feedback::WpPresentationFeedback,
         },
@@ -39,12 +40,28 @@ impl Debug for RenderResult {
     }
 }
 
+/// An element drawn during a recording pass, in framebuffer coordinates.
+pub enum DrawOp {
+    Fill(Rect),
+    Surface(SurfaceDraw),
+}
+
+pub struct SurfaceDraw {
+    pub surface: SurfaceNodeId,
+    pub buffer: Rc<WlBuffer>,
+    pub rect: Rect,
+    pub plain: bool,
+}
+
 pub struct Renderer<'a> {
     pub base: RendererBase<'a>,
     pub(super) state: &'a State,
     pub(super) on_output: bool,
     pub(super) result: &'a mut RenderResult,
     pub(super) logical_extents: Rect,
+    /// Surfaces whose buffers are shown on a separate plane and must not be drawn.
+    pub(super) offloaded: &'a [SurfaceNodeId],
+    pub(super) clip: Option<Rect>,
 }
 
 impl Renderer<'_> {
@@ -188,6 +205,8 @@ impl Renderer<'_> {
             self.base.fill_boxes2(&rd.title_rects, &c, x, y);
             let c = self.state.theme.colors.focused_title_background.get();
             self.base.fill_boxes2(&rd.active_title_rects, &c, x, y);
+            let c = self.state.theme.colors.urgent_title_background.get();
+            self.base.fill_boxes2(&rd.urgent_title_rects, &c, x, y);
             let c = self.state.theme.colors.separator.get();
             self.base.fill_boxes2(&rd.underline_rects, &c, x, y);
             let c = self.state.theme.colors.border.get();
@@ -307,10 +326,10 @@ impl Renderer<'_> {
                 };
             }
             render!(&children.below);
-            self.render_buffer(&buffer, x, y, &tpoints, size);
+            self.render_surface_buffer(surface, &buffer, x, y, &tpoints, size);
             render!(&children.above);
         } else {
-            self.render_buffer(&buffer, x, y, &tpoints, size);
+            self.render_surface_buffer(surface, &buffer, x, y, &tpoints, size);
         }
         if self.on_output {
             {
@@ -324,6 +343,46 @@ impl Renderer<'_> {
         }
     }
 
+    fn push_clip(&mut self, rect: Rect) -> Option<Rect> {
+        let new = match self.clip {
+            Some(clip) => clip.intersect(rect),
+            _ => rect,
+        };
+        self.clip.replace(new)
+    }
+
+    fn render_surface_buffer(
+        &mut self,
+        surface: &WlSurface,
+        buffer: &Rc<WlBuffer>,
+        x: i32,
+        y: i32,
+        tpoints: &[f32; 8],
+        tsize: (i32, i32),
+    ) {
+        let id = surface.node_id;
+        if let Some(recording) = self.base.recording {
+            let rect = match Rect::new_sized(x, y, tsize.0, tsize.1) {
+                Some(r) => r,
+                _ => return,
+            };
+            let plain = surface.buffer_untransformed()
+                && self.base.physical_extents().contains_rect(&rect)
+                && self.clip.map(|c| c.contains_rect(&rect)).unwrap_or(true);
+            recording.borrow_mut().push(DrawOp::Surface(SurfaceDraw {
+                surface: id,
+                buffer: buffer.clone(),
+                rect,
+                plain,
+            }));
+            return;
+        }
+        if self.offloaded.contains(&id) {
+            return;
+        }
+        self.render_buffer(buffer, x, y, tpoints, tsize);
+    }
+
     pub fn render_buffer(
         &mut self,
         buffer: &WlBuffer,
@@ -355,9 +414,12 @@ impl Renderer<'_> {
         let th = theme.sizes.title_height.get();
         let bw = theme.sizes.border_width.get();
         let bc = theme.colors.border.get();
-        let tc = match floating.active.get() {
-            true => theme.colors.focused_title_background.get(),
-            false => theme.colors.unfocused_title_background.get(),
+        let tc = if floating.active.get() {
+            theme.colors.focused_title_background.get()
+        } else if child.tl_data().urgent.get() {
+            theme.colors.urgent_title_background.get()
+        } else {
+            theme.colors.unfocused_title_background.get()
         };
         let uc = theme.colors.separator.get();
         let borders = [
@@ -385,20 +447,24 @@ impl Renderer<'_> {
         )
         .unwrap();
         let scissor_body = self.base.scale_rect(body);
+        let clip = self.push_clip(scissor_body);
         unsafe {
             with_scissor(&scissor_body, || {
                 child.node_render(self, body.x1(), body.y1());
             });
         }
+        self.clip = clip;
     }
 
     pub fn render_layer_surface(&mut self, surface: &ZwlrLayerSurfaceV1, x: i32, y: i32) {
+        let body = surface.position().at_point(x, y);
+        let body = self.base.scale_rect(body);
+        let clip = self.push_clip(body);
         unsafe {
-            let body = surface.position().at_point(x, y);
-            let body = self.base.scale_rect(body);
             with_scissor(&body, || {
                 self.render_surface(&surface.surface, x, y);
             });
         }
+        self.clip = clip;
     }
 }
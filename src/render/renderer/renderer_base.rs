@@ -13,7 +13,7 @@ use {
                 },
                 texture::image_target,
             },
-            renderer::context::RenderContext,
+            renderer::{context::RenderContext, renderer::DrawOp},
             sys::{glClear, glClearColor, glDisable, glEnable, GL_BLEND, GL_COLOR_BUFFER_BIT},
             Texture,
         },
@@ -21,7 +21,7 @@ use {
         theme::Color,
         utils::rc_eq::rc_eq,
     },
-    std::rc::Rc,
+    std::{cell::RefCell, rc::Rc},
 };
 
 pub struct RendererBase<'a> {
@@ -30,6 +30,8 @@ pub struct RendererBase<'a> {
     pub(super) scaled: bool,
     pub(super) scale: Scale,
     pub(super) scalef: f64,
+    /// If set, draw operations are recorded instead of being executed.
+    pub(super) recording: Option<&'a RefCell<Vec<DrawOp>>>,
 }
 
 impl RendererBase<'_> {
@@ -95,7 +97,20 @@ impl RendererBase<'_> {
         2.0 * (y as f32 / self.fb.height as f32) - 1.0
     }
 
+    fn record(&self, rect: Rect) -> bool {
+        match self.recording {
+            Some(recording) => {
+                recording.borrow_mut().push(DrawOp::Fill(rect));
+                true
+            }
+            _ => false,
+        }
+    }
+
     pub fn clear(&self, c: &Color) {
+        if self.record(self.physical_extents()) {
+            return;
+        }
         unsafe {
             glClearColor(c.r, c.g, c.b, c.a);
             glClear(GL_COLOR_BUFFER_BIT);
@@ -111,6 +126,12 @@ impl RendererBase<'_> {
             return;
         }
         let (dx, dy) = self.scale_point(dx, dy);
+        if self.recording.is_some() {
+            for bx in boxes {
+                self.record(self.scale_rect(*bx).move_(dx, dy));
+            }
+            return;
+        }
         let mut pos = Vec::with_capacity(boxes.len() * 12);
         for bx in boxes {
             let bx = self.scale_rect(*bx);
@@ -141,6 +162,21 @@ impl RendererBase<'_> {
             return;
         }
         let (dx, dy) = self.scale_point_f(dx, dy);
+        if self.recording.is_some() {
+            for bx in boxes {
+                let (x1, y1, x2, y2) = self.scale_rect_f(*bx);
+                let rect = Rect::new(
+                    (x1 + dx).floor() as _,
+                    (y1 + dy).floor() as _,
+                    (x2 + dx).ceil() as _,
+                    (y2 + dy).ceil() as _,
+                );
+                if let Some(rect) = rect {
+                    self.record(rect);
+                }
+            }
+            return;
+        }
         let mut pos = Vec::with_capacity(boxes.len() * 12);
         for bx in boxes {
             let (x1, y1, x2, y2) = self.scale_rect_f(*bx);
@@ -191,6 +227,23 @@ impl RendererBase<'_> {
         tscale: Scale,
     ) {
         assert!(rc_eq(&self.ctx.ctx, &texture.ctx.ctx));
+        let (twidth, theight) = if let Some(size) = tsize {
+            size
+        } else {
+            let (mut w, mut h) = (texture.gl.width, texture.gl.height);
+            if tscale != self.scale {
+                let tscale = tscale.to_f64();
+                w = (w as f64 * self.scalef / tscale).round() as _;
+                h = (h as f64 * self.scalef / tscale).round() as _;
+            }
+            (w, h)
+        };
+        if self.recording.is_some() {
+            if let Some(rect) = Rect::new_sized(x, y, twidth, theight) {
+                self.record(rect);
+            }
+            return;
+        }
         unsafe {
             glActiveTexture(GL_TEXTURE0);
 
@@ -234,18 +287,6 @@ impl RendererBase<'_> {
             let f_width = self.fb.width as f32;
             let f_height = self.fb.height as f32;
 
-            let (twidth, theight) = if let Some(size) = tsize {
-                size
-            } else {
-                let (mut w, mut h) = (texture.gl.width, texture.gl.height);
-                if tscale != self.scale {
-                    let tscale = tscale.to_f64();
-                    w = (w as f64 * self.scalef / tscale).round() as _;
-                    h = (h as f64 * self.scalef / tscale).round() as _;
-                }
-                (w, h)
-            };
-
             let x1 = 2.0 * (x as f32 / f_width) - 1.0;
             let y1 = 2.0 * (y as f32 / f_height) - 1.0;
             let x2 = 2.0 * ((x + twidth) as f32 / f_width) - 1.0;
@@ -62,6 +62,8 @@ pub fn take_screenshot(state: &State) -> Result<Screenshot, ScreenshooterError>
         &mut Default::default(),
         Scale::from_int(1),
         true,
+        None,
+        &[],
     );
     let drm = ctx.gbm.drm.dup_render()?.fd().clone();
     Ok(Screenshot { drm, bo })
@@ -3,28 +3,36 @@ use {
         acceptor::Acceptor,
         async_engine::{AsyncEngine, SpawnedFuture},
         backend::{
-            Backend, BackendDrmDevice, BackendEvent, Connector, ConnectorId, ConnectorIds,
-            DrmDeviceId, DrmDeviceIds, InputDevice, InputDeviceId, InputDeviceIds, MonitorInfo,
+            Backend, BackendDrmDevice, BackendEvent, Connector, ConnectorError, ConnectorId,
+            ConnectorIds, DrmDeviceId, DrmDeviceIds, GammaLut, InputDevice, InputDeviceId,
+            InputDeviceIds, InputEvent, MonitorInfo, TabletToolId, TabletToolIds, TabletToolInit,
         },
         backends::dummy::DummyBackend,
         cli::RunArgs,
         client::{Client, ClientId, Clients, SerialRange, NUM_CACHED_SERIAL_RANGES},
-        config::ConfigProxy,
+        config::{ConfigProxy, WorkspaceEvent},
         cursor::{Cursor, ServerCursors},
         dbus::Dbus,
         forker::ForkerProxy,
         globals::{Globals, GlobalsError, WaylandGlobal},
         ifs::{
+            ext_idle_notification_v1::ExtIdleNotificationV1,
             ext_session_lock_v1::ExtSessionLockV1,
+            ext_workspace_manager_v1::ExtWorkspaceManagerV1,
             jay_render_ctx::JayRenderCtx,
             jay_seat_events::JaySeatEvents,
             jay_workspace_watcher::JayWorkspaceWatcher,
             wl_drm::WlDrmGlobal,
-            wl_seat::{SeatIds, WlSeatGlobal},
+            wl_output::OutputId,
+            wl_seat::{collect_kb_foci, SeatIds, WlSeatGlobal},
             wl_surface::{
                 zwp_idle_inhibitor_v1::{IdleInhibitorId, IdleInhibitorIds, ZwpIdleInhibitorV1},
                 NoneSurfaceExt, WlSurface,
             },
+            xdg_activation_token_v1::random_activation_token,
+            zwlr_foreign_toplevel_manager_v1::ZwlrForeignToplevelManagerV1,
+            zwlr_gamma_control_v1::ZwlrGammaControlV1,
+            zwlr_output_manager_v1::ZwlrOutputManagerV1,
             zwp_linux_dmabuf_v1::ZwpLinuxDmabufV1Global,
         },
         io_uring::IoUring,
@@ -35,16 +43,27 @@ use {
         scale::Scale,
         theme::Theme,
         tree::{
-            ContainerNode, ContainerSplit, Direction, DisplayNode, FloatNode, Node, NodeIds,
-            NodeVisitorBase, OutputNode, PlaceholderNode, ToplevelNode, WorkspaceNode,
+            ContainerNode, ContainerSplit, Direction, DisplayNode, FloatNode, Node, NodeId,
+            NodeIds, NodeVisitorBase, OutputNode, PlaceholderNode, ToplevelNode, VrrMode,
+            WindowRules, WorkspaceNode,
         },
         utils::{
-            asyncevent::AsyncEvent, clonecell::CloneCell, copyhashmap::CopyHashMap,
-            errorfmt::ErrorFmt, fdcloser::FdCloser, linkedlist::LinkedList, numcell::NumCell,
-            queue::AsyncQueue, refcounted::RefCounted, run_toplevel::RunToplevel,
+            asyncevent::AsyncEvent,
+            clonecell::CloneCell,
+            copyhashmap::CopyHashMap,
+            errorfmt::ErrorFmt,
+            fdcloser::FdCloser,
+            linkedlist::{LinkedList, NodeRef},
+            numcell::NumCell,
+            queue::AsyncQueue,
+            refcounted::RefCounted,
+            run_toplevel::RunToplevel,
         },
         wheel::Wheel,
-        wire::{JayRenderCtxId, JaySeatEventsId, JayWorkspaceWatcherId},
+        wire::{
+            ExtIdleNotificationV1Id, ExtWorkspaceManagerV1Id, JayRenderCtxId, JaySeatEventsId,
+            JayWorkspaceWatcherId, ZwlrForeignToplevelManagerV1Id, ZwlrOutputManagerV1Id,
+        },
         xkbcommon::{XkbContext, XkbKeymap},
         xwayland::{self, XWaylandEvent},
     },
@@ -53,11 +72,12 @@ use {
     jay_config::PciId,
     std::{
         cell::{Cell, RefCell},
+        collections::VecDeque,
         fmt::{Debug, Formatter},
         mem,
         num::Wrapping,
         ops::DerefMut,
-        rc::Rc,
+        rc::{Rc, Weak},
         sync::Arc,
         time::Duration,
     },
@@ -81,10 +101,13 @@ pub struct State {
     pub seat_ids: SeatIds,
     pub idle_inhibitor_ids: IdleInhibitorIds,
     pub input_device_ids: InputDeviceIds,
+    pub tablet_tool_ids: TabletToolIds,
     pub node_ids: NodeIds,
     pub root: Rc<DisplayNode>,
     pub workspaces: CopyHashMap<String, Rc<WorkspaceNode>>,
+    pub workspace_outputs: CopyHashMap<String, Rc<OutputId>>,
     pub dummy_output: CloneCell<Option<Rc<OutputNode>>>,
+    pub scratchpad: CloneCell<Option<Rc<WorkspaceNode>>>,
     pub backend_events: AsyncQueue<BackendEvent>,
     pub input_device_handlers: RefCell<AHashMap<InputDeviceId, InputDeviceData>>,
     pub seat_queue: LinkedList<Rc<WlSeatGlobal>>,
@@ -98,6 +121,7 @@ pub struct State {
     pub pending_output_render_data: AsyncQueue<Rc<OutputNode>>,
     pub pending_float_layout: AsyncQueue<Rc<FloatNode>>,
     pub pending_float_titles: AsyncQueue<Rc<FloatNode>>,
+    pub pending_workspace_events: AsyncQueue<WorkspaceEvent>,
     pub dbus: Dbus,
     pub fdcloser: Arc<FdCloser>,
     pub logger: Option<Arc<Logger>>,
@@ -123,7 +147,15 @@ pub struct State {
     pub testers: RefCell<AHashMap<(ClientId, JaySeatEventsId), Rc<JaySeatEvents>>>,
     pub render_ctx_watchers: CopyHashMap<(ClientId, JayRenderCtxId), Rc<JayRenderCtx>>,
     pub workspace_watchers: CopyHashMap<(ClientId, JayWorkspaceWatcherId), Rc<JayWorkspaceWatcher>>,
+    pub output_managers: CopyHashMap<(ClientId, ZwlrOutputManagerV1Id), Rc<ZwlrOutputManagerV1>>,
+    pub foreign_toplevel_managers:
+        CopyHashMap<(ClientId, ZwlrForeignToplevelManagerV1Id), Rc<ZwlrForeignToplevelManagerV1>>,
+    pub toplevels: CopyHashMap<NodeId, Weak<dyn ToplevelNode>>,
+    pub workspace_managers:
+        CopyHashMap<(ClientId, ExtWorkspaceManagerV1Id), Rc<ExtWorkspaceManagerV1>>,
     pub default_workspace_capture: Cell<bool>,
+    pub activation_tokens: RefCell<VecDeque<ActivationToken>>,
+    pub activation_switches_workspace: Cell<bool>,
 }
 
 // impl Drop for State {
@@ -138,6 +170,14 @@ impl Debug for State {
     }
 }
 
+const MAX_ACTIVATION_TOKENS: usize = 64;
+
+pub struct ActivationToken {
+    pub token: String,
+    pub seat: Option<Rc<WlSeatGlobal>>,
+    pub client: Option<ClientId>,
+}
+
 pub struct ScreenlockState {
     pub locked: Cell<bool>,
     pub lock: CloneCell<Option<Rc<ExtSessionLockV1>>>,
@@ -156,6 +196,7 @@ pub struct IdleState {
     pub timeout_changed: Cell<bool>,
     pub inhibitors: CopyHashMap<IdleInhibitorId, Rc<ZwpIdleInhibitorV1>>,
     pub inhibitors_changed: Cell<bool>,
+    pub notifications: CopyHashMap<(ClientId, ExtIdleNotificationV1Id), Rc<ExtIdleNotificationV1>>,
 }
 
 impl IdleState {
@@ -169,12 +210,32 @@ impl IdleState {
         self.inhibitors.set(inhibitor.inhibit_id, inhibitor.clone());
         self.inhibitors_changed.set(true);
         self.change.trigger();
+        for notification in self.notifications.lock().values() {
+            notification.change.trigger();
+        }
     }
 
     pub fn remove_inhibitor(&self, inhibitor: &ZwpIdleInhibitorV1) {
         self.inhibitors.remove(&inhibitor.inhibit_id);
         self.inhibitors_changed.set(true);
         self.change.trigger();
+        let uninhibited = self.inhibitors.is_empty();
+        for notification in self.notifications.lock().values() {
+            notification.restart.set(uninhibited);
+            notification.change.trigger();
+        }
+    }
+
+    pub fn add_notification(&self, notification: &Rc<ExtIdleNotificationV1>) {
+        self.notifications.set(
+            (notification.client.id, notification.id),
+            notification.clone(),
+        );
+    }
+
+    pub fn remove_notification(&self, notification: &ExtIdleNotificationV1) {
+        self.notifications
+            .remove(&(notification.client.id, notification.id));
     }
 }
 
@@ -189,6 +250,37 @@ pub struct DeviceHandlerData {
     pub seat: CloneCell<Option<Rc<WlSeatGlobal>>>,
     pub px_per_scroll_wheel: Cell<f64>,
     pub device: Rc<dyn InputDevice>,
+    pub connector: Cell<Option<ConnectorId>>,
+    pub tablet_tools: CopyHashMap<TabletToolId, Rc<TabletToolInit>>,
+}
+
+impl DeviceHandlerData {
+    pub fn set_seat(&self, seat: Option<Rc<WlSeatGlobal>>) {
+        let old = self.seat.get();
+        if old.as_ref().map(|s| s.id()) == seat.as_ref().map(|s| s.id()) {
+            return;
+        }
+        if let Some(old) = old {
+            old.remove_input_device(self);
+        }
+        if let Some(seat) = &seat {
+            seat.add_input_device(self);
+        }
+        self.seat.set(seat);
+    }
+
+    /// Tracks tools while the device is not assigned to a seat.
+    pub fn update_tablet_tools(&self, event: &InputEvent) {
+        match event {
+            InputEvent::TabletToolAdded { init } => {
+                self.tablet_tools.set(init.id, init.clone());
+            }
+            InputEvent::TabletToolRemoved { id } => {
+                self.tablet_tools.remove(id);
+            }
+            _ => {}
+        }
+    }
 }
 
 pub struct ConnectorData {
@@ -198,6 +290,35 @@ pub struct ConnectorData {
     pub name: String,
     pub drm_dev: Option<Rc<DrmDevData>>,
     pub async_event: Rc<AsyncEvent>,
+    pub enabled: Cell<bool>,
+    /// The monitor that is attached to the connector. This is retained while the
+    /// connector is disabled so that it can be re-enabled via output management.
+    pub monitor_info: CloneCell<Option<Rc<MonitorInfo>>>,
+    pub pending_position: Cell<Option<(i32, i32)>>,
+    pub pending_scale: Cell<Option<Scale>>,
+    pub vrr_mode: Cell<VrrMode>,
+    /// The gamma table set by the config.
+    pub gamma_lut: CloneCell<Option<Rc<GammaLut>>>,
+    pub gamma_control: CloneCell<Option<Rc<ZwlrGammaControlV1>>>,
+}
+
+impl ConnectorData {
+    pub fn set_enabled(&self, enabled: bool) -> Result<(), ConnectorError> {
+        self.connector.set_enabled(enabled)?;
+        self.enabled.set(enabled);
+        Ok(())
+    }
+
+    /// Applies the gamma table of the controlling client or, if there is none, the one set
+    /// by the config.
+    pub fn update_gamma(&self) {
+        let lut = self
+            .gamma_control
+            .get()
+            .and_then(|gc| gc.lut.get())
+            .or_else(|| self.gamma_lut.get());
+        self.connector.set_gamma_lut(lut);
+    }
 }
 
 pub struct OutputData {
@@ -277,6 +398,20 @@ impl State {
         self.reload_cursors();
     }
 
+    pub fn output_config_changed(&self) {
+        let managers: Vec<_> = self.output_managers.lock().values().cloned().collect();
+        for manager in managers {
+            manager.update();
+        }
+    }
+
+    pub fn workspaces_changed(&self) {
+        let managers: Vec<_> = self.workspace_managers.lock().values().cloned().collect();
+        for manager in managers {
+            manager.update();
+        }
+    }
+
     fn cursor_sizes_changed(&self) {
         self.reload_cursors();
     }
@@ -483,6 +618,150 @@ impl State {
         FloatNode::new(self, workspace, position, node);
     }
 
+    /// `float_size` is set for toplevels that float unless the config decides otherwise.
+    pub fn map_new_toplevel(
+        self: &Rc<Self>,
+        tl: Rc<dyn ToplevelNode>,
+        rules: WindowRules,
+        float_size: Option<(i32, i32)>,
+        default_ws: Option<Rc<WorkspaceNode>>,
+    ) {
+        let ws = match rules.workspace {
+            Some(name) => Some(self.workspace_for_rule(&name)),
+            _ => default_ws,
+        };
+        if rules.floating.unwrap_or(float_size.is_some()) {
+            let ws = ws.unwrap_or_else(|| self.float_map_ws());
+            let data = tl.tl_data();
+            let (width, height) = match float_size {
+                Some(size) if data.float_width.get() == 0 || data.float_height.get() == 0 => size,
+                _ => data.float_size(&ws),
+            };
+            self.map_floating(tl.clone(), width, height, &ws);
+        } else if let Some(ws) = ws {
+            self.map_tiled_on(tl.clone(), &ws);
+            if let Some(seat) = self.seat_queue.last() {
+                if tl.node_visible() {
+                    tl.clone().node_do_focus(&seat, Direction::Unspecified);
+                }
+            }
+        } else {
+            self.map_tiled(tl.clone());
+        }
+        if rules.fullscreen {
+            tl.tl_set_fullscreen(true);
+        }
+    }
+
+    fn workspace_for_rule(&self, name: &str) -> Rc<WorkspaceNode> {
+        if let Some(ws) = self.workspaces.get(name) {
+            return ws;
+        }
+        let output = self
+            .preferred_output(name)
+            .or_else(|| {
+                self.seat_queue
+                    .last()
+                    .map(|s| s.get_output())
+                    .filter(|o| !o.is_dummy)
+            })
+            .or_else(|| self.root.outputs.lock().values().next().cloned())
+            .or_else(|| self.dummy_output.get())
+            .unwrap();
+        output.create_workspace(name)
+    }
+
+    /// Moves a toplevel to another workspace. Floating toplevels stay floating.
+    pub fn move_toplevel_to_workspace(
+        self: &Rc<Self>,
+        tl: Rc<dyn ToplevelNode>,
+        ws: &Rc<WorkspaceNode>,
+    ) {
+        let data = tl.tl_data();
+        if data.is_fullscreen.get() {
+            return;
+        }
+        let old_ws = match data.workspace.get() {
+            Some(ws) => ws,
+            _ => return,
+        };
+        if old_ws.id == ws.id {
+            return;
+        }
+        let cn = match data
+            .parent
+            .get()
+            .and_then(|p| p.node_into_containing_node())
+        {
+            Some(cn) => cn,
+            _ => return,
+        };
+        let kb_foci = collect_kb_foci(tl.clone().tl_into_node());
+        cn.cnode_remove_child2(tl.tl_as_node(), true);
+        if !ws.visible.get() {
+            for focus in kb_foci {
+                old_ws.clone().node_do_focus(&focus, Direction::Unspecified);
+            }
+        }
+        if data.is_floating.get() {
+            let (width, height) = (data.float_width.get(), data.float_height.get());
+            self.map_floating(tl, width, height, ws);
+        } else {
+            self.map_tiled_on(tl, ws);
+        }
+    }
+
+    pub fn set_toplevel_floating(self: &Rc<Self>, tl: Rc<dyn ToplevelNode>, floating: bool) {
+        let data = tl.tl_data();
+        if data.is_fullscreen.get() {
+            return;
+        }
+        if data.is_floating.get() == floating {
+            return;
+        }
+        let parent = match data.parent.get() {
+            Some(p) => p,
+            _ => return,
+        };
+        if let Some(cn) = parent.node_into_containing_node() {
+            if !floating {
+                cn.cnode_remove_child2(tl.tl_as_node(), true);
+                self.map_tiled(tl);
+            } else if let Some(ws) = data.workspace.get() {
+                cn.cnode_remove_child2(tl.tl_as_node(), true);
+                let (width, height) = data.float_size(&ws);
+                self.map_floating(tl, width, height, &ws);
+            }
+        }
+    }
+
+    /// Floating toplevels are re-centered with the new size.
+    pub fn set_toplevel_float_size(
+        self: &Rc<Self>,
+        tl: Rc<dyn ToplevelNode>,
+        width: i32,
+        height: i32,
+    ) {
+        let data = tl.tl_data();
+        data.float_width.set(width);
+        data.float_height.set(height);
+        if !data.is_floating.get() || data.is_fullscreen.get() {
+            return;
+        }
+        let ws = match data.workspace.get() {
+            Some(ws) => ws,
+            _ => return,
+        };
+        if let Some(cn) = data
+            .parent
+            .get()
+            .and_then(|p| p.node_into_containing_node())
+        {
+            cn.cnode_remove_child2(tl.tl_as_node(), true);
+            self.map_floating(tl, width, height, &ws);
+        }
+    }
+
     pub fn show_workspace(&self, seat: &Rc<WlSeatGlobal>, name: &str) {
         let (output, ws) = match self.workspaces.get(name) {
             Some(ws) => {
@@ -495,13 +774,20 @@ impl State {
                 (output, ws)
             }
             _ => {
-                let output = seat.get_output();
+                let seat_output = seat.get_output();
+                let output = match self.preferred_output(name) {
+                    Some(output) => output,
+                    _ => seat_output.clone(),
+                };
                 if output.is_dummy {
                     log::warn!("Not showing workspace because seat is on dummy output");
                     return;
                 }
                 let ws = output.create_workspace(name);
                 output.show_workspace(&ws);
+                if output.id != seat_output.id {
+                    ws.clone().node_do_focus(seat, Direction::Unspecified);
+                }
                 (output, ws)
             }
         };
@@ -514,6 +800,279 @@ impl State {
         // }
     }
 
+    pub fn preferred_output(&self, name: &str) -> Option<Rc<OutputNode>> {
+        let id = self.workspace_outputs.get(name)?;
+        self.root
+            .outputs
+            .lock()
+            .values()
+            .find(|o| o.global.output_id == id)
+            .cloned()
+    }
+
+    pub fn output_in_direction(
+        &self,
+        output: &OutputNode,
+        direction: Direction,
+    ) -> Option<Rc<OutputNode>> {
+        let pos = output.global.pos.get();
+        let mut best: Option<(i32, Rc<OutputNode>)> = None;
+        for other in self.root.outputs.lock().values() {
+            if other.id == output.id {
+                continue;
+            }
+            let opos = other.global.pos.get();
+            let overlaps_x = opos.x1() < pos.x2() && pos.x1() < opos.x2();
+            let overlaps_y = opos.y1() < pos.y2() && pos.y1() < opos.y2();
+            let distance = match direction {
+                Direction::Left if overlaps_y && opos.x2() <= pos.x1() => pos.x1() - opos.x2(),
+                Direction::Right if overlaps_y && opos.x1() >= pos.x2() => opos.x1() - pos.x2(),
+                Direction::Up if overlaps_x && opos.y2() <= pos.y1() => pos.y1() - opos.y2(),
+                Direction::Down if overlaps_x && opos.y1() >= pos.y2() => opos.y1() - pos.y2(),
+                _ => continue,
+            };
+            if best.as_ref().map(|(d, _)| distance < *d).unwrap_or(true) {
+                best = Some((distance, other.clone()));
+            }
+        }
+        best.map(|(_, o)| o)
+    }
+
+    pub fn move_ws_to_output(
+        &self,
+        ws: &NodeRef<Rc<WorkspaceNode>>,
+        output: &Rc<OutputNode>,
+        show: bool,
+    ) {
+        let source = ws.output.get();
+        if let Some(visible) = source.workspace.get() {
+            if visible.id == ws.id {
+                source.workspace.take();
+            }
+        }
+        ws.set_output(output);
+        output.workspaces.add_last_existing(ws);
+        if show {
+            output.show_workspace(ws);
+        } else if ws.visible.get() {
+            ws.set_visible(false);
+        }
+        ws.flush_jay_workspaces();
+    }
+
+    pub fn move_workspace(&self, ws: &Rc<WorkspaceNode>, output: &Rc<OutputNode>) {
+        if ws.is_dummy || output.is_dummy {
+            return;
+        }
+        let output_id = output.global.output_id.clone();
+        self.workspace_outputs
+            .set(ws.name.clone(), output_id.clone());
+        ws.desired_output.set(output_id);
+        let source = ws.output.get();
+        if source.id == output.id {
+            return;
+        }
+        let link = match source.workspaces.iter().find(|w| w.id == ws.id) {
+            Some(link) => link,
+            _ => return,
+        };
+        let show = ws.visible.get() || output.workspace.get().is_none();
+        self.move_ws_to_output(&link, output, show);
+        if !source.is_dummy && source.workspace.get().is_none() {
+            match source.workspaces.first() {
+                Some(ws) => {
+                    source.show_workspace(&ws);
+                    ws.flush_jay_workspaces();
+                }
+                _ => {
+                    source.ensure_workspace();
+                }
+            }
+        }
+        source.schedule_update_render_data();
+        output.schedule_update_render_data();
+        self.tree_changed();
+        self.damage();
+        self.workspaces_changed();
+    }
+
+    pub fn create_activation_token(
+        &self,
+        seat: Option<Rc<WlSeatGlobal>>,
+        client: Option<ClientId>,
+    ) -> String {
+        let token = random_activation_token();
+        let mut tokens = self.activation_tokens.borrow_mut();
+        if tokens.len() >= MAX_ACTIVATION_TOKENS {
+            tokens.pop_front();
+        }
+        tokens.push_back(ActivationToken {
+            token: token.clone(),
+            seat,
+            client,
+        });
+        token
+    }
+
+    /// Without a valid token the window is only marked as urgent.
+    pub fn activate_toplevel(
+        self: &Rc<Self>,
+        tl: Rc<dyn ToplevelNode>,
+        token: &str,
+        client: ClientId,
+    ) {
+        let data = tl.tl_data();
+        if data.parent.get().is_none() || data.active_surfaces.get() > 0 {
+            return;
+        }
+        let token = {
+            let mut tokens = self.activation_tokens.borrow_mut();
+            match tokens.iter().position(|t| t.token == token) {
+                Some(idx) => tokens.remove(idx),
+                _ => None,
+            }
+        };
+        let seat = match token {
+            Some(t) if t.client == Some(client) => {
+                t.seat.filter(|s| s.keyboard_node_client() == Some(client))
+            }
+            Some(t) => t
+                .seat
+                .or_else(|| self.seat_queue.last().map(|s| (*s).clone())),
+            _ => None,
+        };
+        let seat = match seat {
+            Some(s) => s,
+            _ => {
+                tl.tl_set_urgent(true);
+                return;
+            }
+        };
+        if let Some(config) = self.config.get() {
+            if !config.activation_allowed(tl.node_id()) {
+                tl.tl_set_urgent(true);
+                return;
+            }
+        }
+        if self.is_hidden(&*tl) {
+            self.show_hidden_toplevel(&seat, tl);
+            return;
+        }
+        if let Some(ws) = data.workspace.get() {
+            if !ws.visible.get() {
+                if !self.activation_switches_workspace.get() {
+                    tl.tl_set_urgent(true);
+                    return;
+                }
+                self.show_workspace(&seat, &ws.name);
+            }
+        }
+        seat.focus_toplevel(tl);
+    }
+
+    pub fn is_hidden(&self, tl: &dyn ToplevelNode) -> bool {
+        tl.tl_data().is_hidden()
+    }
+
+    /// Hides a toplevel by moving it to the scratchpad.
+    pub fn hide_toplevel(self: &Rc<Self>, tl: Rc<dyn ToplevelNode>) {
+        let scratchpad = match self.scratchpad.get() {
+            Some(ws) => ws,
+            _ => return,
+        };
+        let data = tl.tl_data();
+        if data.is_fullscreen.get() {
+            data.unset_fullscreen(self, tl.clone());
+        }
+        let old_ws = match data.workspace.get() {
+            Some(ws) => ws,
+            _ => return,
+        };
+        if old_ws.id == scratchpad.id {
+            return;
+        }
+        let cn = match data
+            .parent
+            .get()
+            .and_then(|p| p.node_into_containing_node())
+        {
+            Some(cn) => cn,
+            _ => return,
+        };
+        let kb_foci = collect_kb_foci(tl.clone().tl_into_node());
+        cn.cnode_remove_child2(tl.tl_as_node(), true);
+        for seat in kb_foci {
+            old_ws.clone().node_do_focus(&seat, Direction::Unspecified);
+        }
+        data.scratchpad.set(true);
+        let (width, height) = data.float_size(&old_ws);
+        let position = Rect::new_sized(0, 0, width, height).unwrap();
+        FloatNode::new(self, &scratchpad, position, tl.clone());
+        tl.tl_set_minimized(true);
+        data.send_foreign_state();
+    }
+
+    /// Shows a hidden toplevel floating and centered on the output of the seat.
+    pub fn show_hidden_toplevel(
+        self: &Rc<Self>,
+        seat: &Rc<WlSeatGlobal>,
+        tl: Rc<dyn ToplevelNode>,
+    ) {
+        let output = seat.get_output();
+        if output.is_dummy {
+            return;
+        }
+        let ws = output.ensure_workspace();
+        let data = tl.tl_data();
+        if let Some(cn) = data
+            .parent
+            .get()
+            .and_then(|p| p.node_into_containing_node())
+        {
+            cn.cnode_remove_child2(tl.tl_as_node(), true);
+        }
+        let (width, height) = data.float_size(&ws);
+        self.map_floating(tl.clone(), width, height, &ws);
+        tl.tl_set_minimized(false);
+        data.send_foreign_state();
+        seat.focus_toplevel(tl);
+    }
+
+    /// Hides the visible scratchpad windows with this app id or shows the first hidden one.
+    pub fn toggle_scratchpad(self: &Rc<Self>, seat: &Rc<WlSeatGlobal>, name: &str) {
+        let output = seat.get_output();
+        if output.is_dummy {
+            return;
+        }
+        let current = output.workspace.get().map(|ws| ws.id);
+        let mut visible = vec![];
+        let mut hidden = vec![];
+        for tl in self.toplevels.lock().values() {
+            let Some(tl) = tl.upgrade() else {
+                continue;
+            };
+            let data = tl.tl_data();
+            if !data.scratchpad.get() || *data.app_id.borrow() != name {
+                continue;
+            }
+            if self.is_hidden(&*tl) {
+                hidden.push(tl);
+            } else if data.workspace.get().map(|ws| ws.id) == current {
+                visible.push(tl);
+            }
+        }
+        if !visible.is_empty() {
+            for tl in visible {
+                self.hide_toplevel(tl);
+            }
+            return;
+        }
+        hidden.sort_by_key(|tl| tl.node_id().raw());
+        if let Some(tl) = hidden.into_iter().next() {
+            self.show_hidden_toplevel(seat, tl);
+        }
+    }
+
     pub fn float_map_ws(&self) -> Rc<WorkspaceNode> {
         if let Some(seat) = self.seat_queue.last() {
             let output = seat.get_output();
@@ -534,13 +1093,17 @@ impl State {
         for output in outputs.values() {
             output.set_status(&status);
         }
-        self.damage();
     }
 
     pub fn input_occurred(&self) {
         if !self.idle.input.replace(true) {
             self.idle.change.trigger();
         }
+        for notification in self.idle.notifications.lock().values() {
+            if !notification.input.replace(true) {
+                notification.change.trigger();
+            }
+        }
     }
 
     pub fn start_xwayland(self: &Rc<Self>) {
@@ -577,6 +1140,9 @@ impl State {
     }
 
     pub fn damage(&self) {
+        for output in self.root.outputs.lock().values() {
+            output.damage_all();
+        }
         for connector in self.connectors.lock().values() {
             if connector.connected.get() {
                 connector.connector.damage();
@@ -584,6 +1150,18 @@ impl State {
         }
     }
 
+    pub fn damage_rect(&self, rect: Rect) {
+        if rect.is_empty() {
+            return;
+        }
+        for output in self.root.outputs.lock().values() {
+            if output.global.pos.get().intersects(&rect) {
+                output.add_damage(rect);
+                output.global.connector.connector.damage();
+            }
+        }
+    }
+
     pub fn clear(&self) {
         self.lock.lock.take();
         self.xwayland.handler.borrow_mut().take();
@@ -617,8 +1195,14 @@ impl State {
         self.pending_output_render_data.clear();
         self.pending_float_layout.clear();
         self.pending_float_titles.clear();
+        self.pending_workspace_events.clear();
         self.render_ctx_watchers.clear();
         self.workspace_watchers.clear();
+        self.output_managers.clear();
+        self.foreign_toplevel_managers.clear();
+        self.workspace_managers.clear();
+        self.activation_tokens.borrow_mut().clear();
+        self.toplevels.clear();
         self.slow_clients.clear();
         for (_, h) in self.input_device_handlers.borrow_mut().drain() {
             h.async_event.clear();
@@ -637,6 +1221,9 @@ impl State {
         if let Some(output) = self.dummy_output.set(None) {
             output.clear();
         }
+        if let Some(ws) = self.scratchpad.set(None) {
+            ws.clear();
+        }
         self.wheel.clear();
         self.eng.clear();
     }
@@ -24,3 +24,12 @@ pub async fn handle_slow_clients(state: Rc<State>) {
     let mut sch = SlowClientHandler { state };
     sch.handle_events().await;
 }
+
+pub async fn handle_workspace_events(state: Rc<State>) {
+    loop {
+        let event = state.pending_workspace_events.pop().await;
+        if let Some(config) = state.config.get() {
+            config.workspace_event(&event);
+        }
+    }
+}
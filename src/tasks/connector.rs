@@ -4,7 +4,7 @@ use {
         ifs::wl_output::WlOutputGlobal,
         scale::Scale,
         state::{ConnectorData, OutputData, State},
-        tree::{OutputNode, OutputRenderData},
+        tree::{OutputNode, OutputRenderData, VrrMode},
         utils::{asyncevent::AsyncEvent, clonecell::CloneCell},
     },
     std::{
@@ -29,6 +29,13 @@ pub fn handle(state: &Rc<State>, connector: &Rc<dyn Connector>) {
         name: connector.kernel_id().to_string(),
         drm_dev: drm_dev.clone(),
         async_event: Rc::new(AsyncEvent::default()),
+        enabled: Cell::new(true),
+        monitor_info: Default::default(),
+        pending_position: Cell::new(None),
+        pending_scale: Cell::new(None),
+        vrr_mode: Cell::new(VrrMode::Never),
+        gamma_lut: Default::default(),
+        gamma_control: Default::default(),
     });
     if let Some(dev) = drm_dev {
         dev.connectors.set(id, data.clone());
@@ -75,11 +82,19 @@ impl ConnectorHandler {
         }
         self.data.handler.set(None);
         self.state.connectors.remove(&self.id);
+        self.state.output_config_changed();
     }
 
     async fn handle_connected(&self, info: MonitorInfo) {
         log::info!("Connector {} connected", self.data.connector.kernel_id());
         self.data.connected.set(true);
+        let same_monitor = match self.data.monitor_info.get() {
+            Some(mi) => *mi == info,
+            _ => false,
+        };
+        if !same_monitor {
+            self.data.monitor_info.set(Some(Rc::new(info.clone())));
+        }
         let name = self.state.globals.name();
         let x1 = self
             .state
@@ -128,9 +143,15 @@ impl ConnectorHandler {
             jay_outputs: Default::default(),
             screencasts: Default::default(),
             update_render_data_scheduled: Cell::new(false),
+            damage: Default::default(),
         });
         self.state.add_output_scale(on.preferred_scale.get());
-        let mode = info.initial_mode;
+        if let Some(scale) = self.data.pending_scale.take() {
+            on.set_preferred_scale(scale);
+        }
+        if let Some((x, y)) = self.data.pending_position.take() {
+            on.set_position(x, y);
+        }
         let output_data = Rc::new(OutputData {
             connector: self.data.clone(),
             monitor_info: info,
@@ -138,12 +159,15 @@ impl ConnectorHandler {
         });
         self.state.outputs.set(self.id, output_data);
         if self.state.outputs.len() == 1 {
+            let pos = global.pos.get();
             let seats = self.state.globals.seats.lock();
             for seat in seats.values() {
-                seat.set_position(x1 + mode.width / 2, mode.height / 2);
+                seat.set_position(pos.x1() + pos.width() / 2, pos.y1() + pos.height() / 2);
             }
         }
         global.node.set(Some(on.clone()));
+        on.update_vrr();
+        on.update_tearing();
         if let Some(config) = self.state.config.get() {
             config.connector_connected(self.id);
         }
@@ -162,19 +186,8 @@ impl ConnectorHandler {
                     }
                 }
                 for ws in ws_to_move {
-                    ws.set_output(&on);
-                    on.workspaces.add_last_existing(&ws);
-                    if ws.visible_on_desired_output.get() && on.workspace.get().is_none() {
-                        on.show_workspace(&ws);
-                    } else {
-                        ws.set_visible(false);
-                    }
-                    ws.flush_jay_workspaces();
-                    if let Some(visible) = source.node.workspace.get() {
-                        if visible.id == ws.id {
-                            source.node.workspace.take();
-                        }
-                    }
+                    let show = ws.visible_on_desired_output.get() && on.workspace.get().is_none();
+                    self.state.move_ws_to_output(&ws, &on, show);
                 }
                 if source.node.workspace.get().is_none() {
                     if let Some(ws) = source.node.workspaces.first() {
@@ -195,6 +208,9 @@ impl ConnectorHandler {
         self.state.root.outputs.set(self.id, on.clone());
         self.state.root.update_extents();
         self.state.add_global(&global);
+        self.state.output_config_changed();
+        self.state.workspaces_changed();
+        self.data.update_gamma();
         'outer: loop {
             while let Some(event) = self.data.connector.event() {
                 match event {
@@ -224,9 +240,15 @@ impl ConnectorHandler {
         for sc in screencasts {
             sc.do_destroy();
         }
+        if let Some(gc) = self.data.gamma_control.get() {
+            gc.send_failed();
+        }
         global.destroyed.set(true);
         self.state.root.outputs.remove(&self.id);
         self.data.connected.set(false);
+        if self.data.enabled.get() {
+            self.data.monitor_info.take();
+        }
         self.state.outputs.remove(&self.id);
         on.lock_surface.take();
         {
@@ -252,19 +274,13 @@ impl ConnectorHandler {
                 let is_visible =
                     !target_is_dummy && target.workspaces.is_empty() && ws.visible.get();
                 ws.visible_on_desired_output.set(ws.visible.get());
-                ws.set_output(&target);
-                target.workspaces.add_last_existing(&ws);
-                if is_visible {
-                    target.show_workspace(&ws);
-                } else if ws.visible.get() {
-                    ws.set_visible(false);
-                }
-                ws.flush_jay_workspaces();
+                self.state.move_ws_to_output(&ws, &target, is_visible);
             }
             target.schedule_update_render_data();
             self.state.tree_changed();
             self.state.damage();
         }
+        self.state.workspaces_changed();
         let seats = self.state.globals.seats.lock();
         for seat in seats.values() {
             if seat.get_output().id == on.id {
@@ -278,5 +294,6 @@ impl ConnectorHandler {
         }
         self.state.remove_output_scale(on.preferred_scale.get());
         let _ = self.state.remove_global(&*global);
+        self.state.output_config_changed();
     }
 }
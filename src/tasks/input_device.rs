@@ -13,6 +13,8 @@ pub fn handle(state: &Rc<State>, dev: Rc<dyn InputDevice>) {
         seat: Default::default(),
         px_per_scroll_wheel: Cell::new(PX_PER_SCROLL),
         device: dev.clone(),
+        connector: Default::default(),
+        tablet_tools: Default::default(),
     });
     let ae = Rc::new(AsyncEvent::default());
     let oh = DeviceHandler {
@@ -56,6 +58,7 @@ impl DeviceHandler {
             if let Some(seat) = self.data.seat.get() {
                 let mut any_events = false;
                 while let Some(event) = self.dev.event() {
+                    self.data.update_tablet_tools(&event);
                     seat.event(&self.data, event);
                     any_events = true;
                 }
@@ -64,12 +67,13 @@ impl DeviceHandler {
                     self.state.input_occurred();
                 }
             } else {
-                while self.dev.event().is_some() {
-                    // nothing
+                while let Some(event) = self.dev.event() {
+                    self.data.update_tablet_tools(&event);
                 }
             }
             self.ae.triggered().await;
         }
+        self.data.set_seat(None);
         if let Some(config) = self.state.config.get() {
             config.del_input_device(self.dev.id());
         }
@@ -89,9 +89,11 @@ colors! {
     captured_unfocused_title_background = (0x22, 0x03, 0x03),
     captured_focused_title_background = (0x77, 0x28, 0x31),
     focused_inactive_title_background = (0x5f, 0x67, 0x6a),
+    urgent_title_background = (0x90, 0x00, 0x00),
     unfocused_title_text = (0x88, 0x88, 0x88),
     focused_title_text = (0xff, 0xff, 0xff),
     focused_inactive_title_text = (0xff, 0xff, 0xff),
+    urgent_title_text = (0xff, 0xff, 0xff),
     separator = (0x33, 0x33, 0x33),
     border = (0x3f, 0x47, 0x4a),
     bar_background = (0x00, 0x00, 0x00),
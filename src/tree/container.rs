@@ -83,6 +83,7 @@ pub struct ContainerTitle {
 pub struct ContainerRenderData {
     pub title_rects: Vec<Rect>,
     pub active_title_rects: Vec<Rect>,
+    pub urgent_title_rects: Vec<Rect>,
     pub last_active_rect: Option<Rect>,
     pub border_rects: Vec<Rect>,
     pub underline_rects: Vec<Rect>,
@@ -619,6 +620,12 @@ impl ContainerNode {
         self.tl_title_changed();
     }
 
+    /// Marks the container as urgent if any of its children is urgent.
+    fn update_urgent(&self) {
+        let urgent = self.children.iter().any(|c| c.node.tl_data().urgent.get());
+        self.tl_set_urgent(urgent);
+    }
+
     pub fn schedule_compute_render_data(self: &Rc<Self>) {
         if !self.compute_render_data_scheduled.replace(true) {
             self.state.pending_container_render_data.push(self.clone());
@@ -641,6 +648,7 @@ impl ContainerNode {
         }
         rd.title_rects.clear();
         rd.active_title_rects.clear();
+        rd.urgent_title_rects.clear();
         rd.border_rects.clear();
         rd.underline_rects.clear();
         rd.last_active_rect.take();
@@ -664,6 +672,9 @@ impl ContainerNode {
             let color = if child.active.get() {
                 rd.active_title_rects.push(rect);
                 theme.colors.focused_title_text.get()
+            } else if child.node.tl_data().urgent.get() {
+                rd.urgent_title_rects.push(rect);
+                theme.colors.urgent_title_text.get()
             } else if !have_active && last_active == Some(child.node.node_id()) {
                 rd.last_active_rect = Some(rect);
                 theme.colors.focused_inactive_title_text.get()
@@ -711,6 +722,18 @@ impl ContainerNode {
                 .push(Rect::new_sized(0, th, cwidth, 1).unwrap());
         }
         rd.titles.remove_if(|_, v| v.is_empty());
+        let (x, y) = (self.abs_x1.get(), self.abs_y1.get());
+        let damaged = rd
+            .title_rects
+            .iter()
+            .chain(rd.active_title_rects.iter())
+            .chain(rd.urgent_title_rects.iter())
+            .chain(rd.last_active_rect.iter())
+            .chain(rd.border_rects.iter())
+            .chain(rd.underline_rects.iter());
+        for rect in damaged {
+            self.state.damage_rect(rect.move_(x, y));
+        }
     }
 
     fn activate_child(self: &Rc<Self>, child: &NodeRef<ContainerChild>) {
@@ -1333,6 +1356,7 @@ impl ContainingNode for ContainerNode {
         }
         self.sum_factors.set(sum);
         self.update_title();
+        self.update_urgent();
         // log::info!("cnode_remove_child2");
         self.schedule_layout();
         self.cancel_seat_ops();
@@ -1341,6 +1365,11 @@ impl ContainingNode for ContainerNode {
     fn cnode_accepts_child(&self, _node: &dyn Node) -> bool {
         true
     }
+
+    fn cnode_child_urgent_changed(self: Rc<Self>, _child: &dyn Node, _urgent: bool) {
+        self.update_urgent();
+        self.schedule_compute_render_data();
+    }
 }
 
 impl ToplevelNode for ContainerNode {
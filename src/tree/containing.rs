@@ -14,4 +14,8 @@ pub trait ContainingNode: Node {
     }
     fn cnode_remove_child2(self: Rc<Self>, child: &dyn Node, preserve_focus: bool);
     fn cnode_accepts_child(&self, node: &dyn Node) -> bool;
+    fn cnode_child_urgent_changed(self: Rc<Self>, child: &dyn Node, urgent: bool) {
+        let _ = child;
+        let _ = urgent;
+    }
 }
@@ -171,15 +171,25 @@ impl FloatNode {
         self.render_titles_scheduled.set(false);
         let theme = &self.state.theme;
         let th = theme.sizes.title_height.get();
-        let tc = match self.active.get() {
-            true => theme.colors.focused_title_text.get(),
-            false => theme.colors.unfocused_title_text.get(),
+        let urgent = match self.child.get() {
+            Some(c) => c.tl_data().urgent.get(),
+            _ => false,
+        };
+        let tc = if self.active.get() {
+            theme.colors.focused_title_text.get()
+        } else if urgent {
+            theme.colors.urgent_title_text.get()
+        } else {
+            theme.colors.unfocused_title_text.get()
         };
         let bw = theme.sizes.border_width.get();
         let font = theme.font.borrow_mut();
         let title = self.title.borrow_mut();
         self.title_textures.clear();
         let pos = self.position.get();
+        if let Some(title) = Rect::new_sized(pos.x1(), pos.y1(), pos.width(), bw + th + 1) {
+            self.state.damage_rect(title);
+        }
         if pos.width() <= 2 * bw || title.is_empty() {
             return;
         }
@@ -336,7 +346,7 @@ impl FloatNode {
         }
     }
 
-    fn set_workspace(self: &Rc<Self>, ws: &Rc<WorkspaceNode>) {
+    pub fn set_workspace(self: &Rc<Self>, ws: &Rc<WorkspaceNode>) {
         if let Some(c) = self.child.get() {
             c.tl_set_workspace(ws);
         }
@@ -535,6 +545,11 @@ impl ContainingNode for FloatNode {
     fn cnode_accepts_child(&self, _node: &dyn Node) -> bool {
         true
     }
+
+    fn cnode_child_urgent_changed(self: Rc<Self>, _child: &dyn Node, _urgent: bool) {
+        self.schedule_render_titles();
+        self.state.workspaces_changed();
+    }
 }
 
 impl StackedNode for FloatNode {
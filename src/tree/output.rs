@@ -2,11 +2,13 @@ use {
     crate::{
         backend::{HardwareCursor, KeyState, Mode},
         client::ClientId,
+        config::WorkspaceEvent,
         cursor::KnownCursor,
         fixed::Fixed,
         ifs::{
             jay_output::JayOutput,
             jay_screencast::JayScreencast,
+            wl_buffer::WlBuffer,
             wl_output::WlOutputGlobal,
             wl_seat::{
                 collect_kb_foci2, wl_pointer::PendingScroll, NodeSeatState, SeatId, WlSeatGlobal,
@@ -15,10 +17,11 @@ use {
             wl_surface::{
                 ext_session_lock_surface_v1::ExtSessionLockSurfaceV1,
                 zwlr_layer_surface_v1::ZwlrLayerSurfaceV1, SurfaceSendPreferredScaleVisitor,
+                WlSurface,
             },
             zwlr_layer_shell_v1::{BACKGROUND, BOTTOM, OVERLAY, TOP},
         },
-        rect::Rect,
+        rect::{Rect, Region, RegionBuilder},
         render::{Framebuffer, Renderer, Texture},
         scale::Scale,
         state::State,
@@ -43,6 +46,14 @@ use {
 };
 
 tree_id!(OutputNodeId);
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum VrrMode {
+    Never,
+    Always,
+    Fullscreen,
+}
+
 pub struct OutputNode {
     pub id: OutputNodeId,
     pub global: Rc<WlOutputGlobal>,
@@ -62,6 +73,7 @@ pub struct OutputNode {
     pub hardware_cursor: CloneCell<Option<Rc<dyn HardwareCursor>>>,
     pub update_render_data_scheduled: Cell<bool>,
     pub screencasts: CopyHashMap<(ClientId, JayScreencastId), Rc<JayScreencast>>,
+    pub damage: RefCell<RegionBuilder>,
 }
 
 pub async fn output_render_data(state: Rc<State>) {
@@ -89,6 +101,38 @@ impl OutputNode {
         }
     }
 
+    pub fn add_damage(&self, rect: Rect) {
+        let pos = self.global.pos.get();
+        let rect = rect.intersect(pos);
+        if rect.is_empty() {
+            return;
+        }
+        let mut rect = rect.move_(-pos.x1(), -pos.y1());
+        let scale = self.preferred_scale.get();
+        if scale != 1 {
+            let scale = scale.to_f64();
+            rect = Rect::new(
+                (rect.x1() as f64 * scale).floor() as _,
+                (rect.y1() as f64 * scale).floor() as _,
+                (rect.x2() as f64 * scale).ceil() as _,
+                (rect.y2() as f64 * scale).ceil() as _,
+            )
+            .unwrap();
+        }
+        self.damage.borrow_mut().add(rect);
+    }
+
+    pub fn damage_all(&self) {
+        self.add_damage(self.global.pos.get());
+    }
+
+    pub fn take_damage(&self) -> Rc<Region> {
+        let mut damage = self.damage.borrow_mut();
+        let res = damage.get();
+        damage.clear();
+        res
+    }
+
     pub fn clear(&self) {
         self.global.clear();
         self.workspace.set(None);
@@ -257,6 +301,9 @@ impl OutputNode {
                 tex: title,
             });
         }
+        let opos = self.global.pos.get();
+        let bar = Rect::new_sized(opos.x1(), opos.y1(), output_width, th + 1).unwrap();
+        self.state.damage_rect(bar);
     }
 
     pub fn ensure_workspace(self: &Rc<Self>) -> Rc<WorkspaceNode> {
@@ -282,13 +329,9 @@ impl OutputNode {
                 return false;
             }
             collect_kb_foci2(old.clone(), &mut seats);
+            self.move_sticky_floats(&old, ws);
             if old.is_empty() {
-                for jw in old.jay_workspaces.lock().values() {
-                    jw.send_destroyed();
-                    jw.workspace.set(None);
-                }
-                old.clear();
-                self.state.workspaces.remove(&old.name);
+                self.destroy_workspace(&old);
             } else {
                 old.set_visible(false);
                 old.flush_jay_workspaces();
@@ -302,9 +345,119 @@ impl OutputNode {
         for seat in seats {
             ws.clone().node_do_focus(&seat, Direction::Unspecified);
         }
+        self.update_vrr();
+        self.update_tearing();
+        let connector = self.global.connector.connector.id();
+        self.state
+            .pending_workspace_events
+            .push(WorkspaceEvent::Shown {
+                name: ws.name.clone(),
+                connector,
+            });
         true
     }
 
+    pub fn destroy_workspace(&self, ws: &Rc<WorkspaceNode>) {
+        for jw in ws.jay_workspaces.lock().values() {
+            jw.send_destroyed();
+            jw.workspace.set(None);
+        }
+        ws.clear();
+        self.state.workspaces.remove(&ws.name);
+        self.state
+            .pending_workspace_events
+            .push(WorkspaceEvent::Destroyed {
+                name: ws.name.clone(),
+                connector: self.global.connector.connector.id(),
+            });
+    }
+
+    /// Moves the floating toplevels that are marked as sticky to the newly shown workspace.
+    fn move_sticky_floats(&self, old: &Rc<WorkspaceNode>, new: &Rc<WorkspaceNode>) {
+        let mut sticky = vec![];
+        for stacked in old.stacked.iter() {
+            if let Some(float) = stacked
+                .deref()
+                .clone()
+                .stacked_into_node()
+                .node_into_float()
+            {
+                if let Some(child) = float.child.get() {
+                    if child.tl_data().sticky.get() {
+                        sticky.push(float);
+                    }
+                }
+            }
+        }
+        for float in sticky {
+            float.set_workspace(new);
+        }
+    }
+
+    /// Enables or disables VRR depending on the VRR mode of the connector and the
+    /// contents of the output.
+    pub fn update_vrr(&self) {
+        let enabled = match self.global.connector.vrr_mode.get() {
+            VrrMode::Never => false,
+            VrrMode::Always => true,
+            VrrMode::Fullscreen => match self.workspace.get() {
+                Some(ws) => ws.fullscreen.get().is_some(),
+                _ => false,
+            },
+        };
+        self.global.connector.connector.set_vrr_enabled(enabled);
+    }
+
+    pub fn direct_scanout_candidate(&self) -> Option<(Rc<WlSurface>, Rc<WlBuffer>)> {
+        if self.state.lock.locked.get() {
+            return None;
+        }
+        if !self.screencasts.is_empty() || !self.global.pending_captures.is_empty() {
+            return None;
+        }
+        if !self.layers[TOP as usize].is_empty() || !self.layers[OVERLAY as usize].is_empty() {
+            return None;
+        }
+        let surface = self
+            .workspace
+            .get()?
+            .fullscreen
+            .get()?
+            .tl_scanout_surface()?;
+        let buffer = surface.direct_scanout_buffer()?;
+        let pos = self.global.pos.get();
+        if surface.buffer_abs_pos.get() != pos {
+            return None;
+        }
+        let mode = self.global.mode.get();
+        if buffer.rect.size() != (mode.width, mode.height) {
+            return None;
+        }
+        for seat in self.state.globals.lock_seats().values() {
+            if seat.hardware_cursor() || seat.get_cursor().is_none() {
+                continue;
+            }
+            let (x, y) = seat.get_position();
+            if pos.contains(x.round_down(), y.round_down()) {
+                return None;
+            }
+        }
+        Some((surface, buffer))
+    }
+
+    /// Allows tearing page flips if the visible workspace contains a fullscreen surface
+    /// that requested async presentation.
+    pub fn update_tearing(&self) {
+        let allowed = self
+            .workspace
+            .get()
+            .and_then(|ws| ws.fullscreen.get())
+            .and_then(|fs| fs.tl_scanout_surface())
+            .map(|s| s.tearing())
+            .unwrap_or(false);
+        self.global.connector.connector.set_tearing_allowed(allowed);
+    }
+
     pub fn create_workspace(self: &Rc<Self>, name: &str) -> Rc<WorkspaceNode> {
         let ws = Rc::new(WorkspaceNode {
             id: self.state.node_ids.next(),
@@ -319,13 +472,22 @@ impl OutputNode {
             visible: Cell::new(false),
             fullscreen: Default::default(),
             visible_on_desired_output: Cell::new(false),
-            desired_output: CloneCell::new(self.global.output_id.clone()),
+            desired_output: CloneCell::new(match self.state.workspace_outputs.get(name) {
+                Some(id) => id,
+                _ => self.global.output_id.clone(),
+            }),
             jay_workspaces: Default::default(),
             capture: self.state.default_workspace_capture.clone(),
         });
         ws.output_link
             .set(Some(self.workspaces.add_last(ws.clone())));
         self.state.workspaces.set(name.to_string(), ws.clone());
+        self.state
+            .pending_workspace_events
+            .push(WorkspaceEvent::Created {
+                name: name.to_string(),
+                connector: self.global.connector.connector.id(),
+            });
         if self.workspace.get().is_none() {
             self.show_workspace(&ws);
         }
@@ -338,6 +500,7 @@ impl OutputNode {
         for (client, e) in clients_to_kill.values() {
             client.error(e);
         }
+        self.state.workspaces_changed();
         self.schedule_update_render_data();
         ws
     }
@@ -361,6 +524,16 @@ impl OutputNode {
         }
         let rect = pos.at_point(x, y);
         self.change_extents_(&rect);
+        let seats = self.state.globals.seats.lock();
+        for seat in seats.values() {
+            if seat.get_output().id == self.id {
+                let seat_pos = seat.position();
+                seat.set_position(
+                    seat_pos.0.round_down() + x - pos.x1(),
+                    seat_pos.1.round_down() + y - pos.y1(),
+                );
+            }
+        }
     }
 
     pub fn update_mode(self: &Rc<Self>, mode: Mode) {
@@ -424,6 +597,7 @@ impl OutputNode {
             }
         }
         self.global.send_mode();
+        self.state.output_config_changed();
     }
 
     pub fn find_layer_surface_at(
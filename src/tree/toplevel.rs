@@ -1,11 +1,18 @@
 use {
     crate::{
-        client::Client,
-        ifs::wl_seat::{collect_kb_foci, collect_kb_foci2, NodeSeatState, SeatId},
+        client::{Client, ClientId},
+        ifs::{
+            wl_seat::{collect_kb_foci, collect_kb_foci2, NodeSeatState, SeatId},
+            wl_surface::{x_surface::xwindow::XwindowInfo, WlSurface},
+            zwlr_foreign_toplevel_handle_v1::ZwlrForeignToplevelHandleV1,
+        },
         rect::Rect,
         state::State,
         tree::{ContainingNode, Direction, Node, OutputNode, PlaceholderNode, WorkspaceNode},
-        utils::{clonecell::CloneCell, numcell::NumCell, smallmap::SmallMap},
+        utils::{
+            clonecell::CloneCell, copyhashmap::CopyHashMap, numcell::NumCell, smallmap::SmallMap,
+        },
+        wire::ZwlrForeignToplevelHandleV1Id,
     },
     std::{
         cell::{Cell, RefCell},
@@ -44,9 +51,11 @@ pub trait ToplevelNode: Node {
         if active {
             if data.active_surfaces.fetch_add(1) == 0 {
                 self.tl_set_active(true);
+                self.tl_set_urgent(false);
                 if let Some(parent) = data.parent.get() {
                     parent.node_child_active_changed(self.tl_as_node(), true, 1);
                 }
+                data.send_foreign_state();
             }
         } else {
             if data.active_surfaces.fetch_sub(1) == 1 {
@@ -54,10 +63,22 @@ pub trait ToplevelNode: Node {
                 if let Some(parent) = data.parent.get() {
                     parent.node_child_active_changed(self.tl_as_node(), false, 1);
                 }
+                data.send_foreign_state();
             }
         }
     }
 
+    /// Marks the toplevel as requiring the attention of the user.
+    fn tl_set_urgent(&self, urgent: bool) {
+        let data = self.tl_data();
+        if data.urgent.replace(urgent) == urgent {
+            return;
+        }
+        if let Some(parent) = data.parent.get() {
+            parent.cnode_child_urgent_changed(self.tl_as_node(), urgent);
+        }
+    }
+
     fn tl_focus_child(&self, seat: SeatId) -> Option<Rc<dyn Node>> {
         self.tl_data()
             .focus_node
@@ -65,6 +86,11 @@ pub trait ToplevelNode: Node {
             .or_else(|| self.tl_default_focus_child())
     }
 
+    /// Informs the toplevel that it was hidden in or shown from the scratchpad.
+    fn tl_set_minimized(&self, minimized: bool) {
+        let _ = minimized;
+    }
+
     fn tl_set_fullscreen(self: Rc<Self>, fullscreen: bool) {
         let data = self.tl_data();
         if fullscreen {
@@ -86,6 +112,16 @@ pub trait ToplevelNode: Node {
             *data.placeholder.tl_data().title.borrow_mut() = title.clone();
             data.placeholder.tl_title_changed();
         }
+        for handle in data.foreign_handles.lock().values() {
+            handle.send_title(&title);
+            handle.send_done();
+        }
+        drop(title);
+        if data.state.toplevels.contains(&self.node_id()) {
+            if let Some(config) = data.state.config.get() {
+                config.window_title_changed(self.node_id());
+            }
+        }
     }
 
     fn tl_set_parent(&self, parent: Rc<dyn ContainingNode>) {
@@ -129,6 +165,11 @@ pub trait ToplevelNode: Node {
     fn tl_set_workspace(self: Rc<Self>, ws: &Rc<WorkspaceNode>) {
         let data = self.tl_data();
         data.workspace.set(Some(ws.clone()));
+        for handle in data.foreign_handles.lock().values() {
+            if handle.update_output(&ws.output.get()) {
+                handle.send_done();
+            }
+        }
         self.tl_set_workspace_ext(ws);
     }
 
@@ -150,6 +191,16 @@ pub trait ToplevelNode: Node {
     fn tl_last_active_child(self: Rc<Self>) -> Rc<dyn ToplevelNode> {
         self.tl_into_dyn()
     }
+
+    /// Returns the surface that covers the whole toplevel, if any.
+    fn tl_scanout_surface(&self) -> Option<Rc<WlSurface>> {
+        None
+    }
+
+    /// Returns the X properties of the toplevel if it is an X window.
+    fn tl_xwindow_info(&self) -> Option<&XwindowInfo> {
+        None
+    }
 }
 
 pub struct FullscreenedData {
@@ -157,6 +208,14 @@ pub struct FullscreenedData {
     pub workspace: Rc<WorkspaceNode>,
 }
 
+/// Placement requested by the config before a toplevel is mapped for the first time.
+#[derive(Default)]
+pub struct WindowRules {
+    pub workspace: Option<Rc<String>>,
+    pub floating: Option<bool>,
+    pub fullscreen: bool,
+}
+
 pub struct ToplevelData {
     pub active: Cell<bool>,
     pub client: Option<Rc<Client>>,
@@ -168,12 +227,20 @@ pub struct ToplevelData {
     pub float_width: Cell<i32>,
     pub float_height: Cell<i32>,
     pub is_fullscreen: Cell<bool>,
+    pub urgent: Cell<bool>,
+    pub sticky: Cell<bool>,
+    pub scratchpad: Cell<bool>,
+    pub new_window_sent: Cell<bool>,
+    pub pending_rules: RefCell<Option<WindowRules>>,
     pub fullscrceen_data: RefCell<Option<FullscreenedData>>,
     pub workspace: CloneCell<Option<Rc<WorkspaceNode>>>,
     pub title: RefCell<String>,
+    pub app_id: RefCell<String>,
     pub parent: CloneCell<Option<Rc<dyn ContainingNode>>>,
     pub pos: Cell<Rect>,
     pub seat_state: NodeSeatState,
+    pub foreign_handles:
+        CopyHashMap<(ClientId, ZwlrForeignToplevelHandleV1Id), Rc<ZwlrForeignToplevelHandleV1>>,
 }
 
 impl ToplevelData {
@@ -189,12 +256,90 @@ impl ToplevelData {
             float_width: Default::default(),
             float_height: Default::default(),
             is_fullscreen: Default::default(),
+            urgent: Default::default(),
+            sticky: Default::default(),
+            scratchpad: Default::default(),
+            new_window_sent: Default::default(),
+            pending_rules: Default::default(),
             fullscrceen_data: Default::default(),
             workspace: Default::default(),
             title: RefCell::new(title),
+            app_id: Default::default(),
             parent: Default::default(),
             pos: Default::default(),
             seat_state: Default::default(),
+            foreign_handles: Default::default(),
+        }
+    }
+
+    /// Announces a mapped client window to foreign toplevel managers.
+    pub fn publish(&self, node: Rc<dyn ToplevelNode>) {
+        self.state
+            .toplevels
+            .set(node.node_id(), Rc::downgrade(&node));
+        for manager in self.state.foreign_toplevel_managers.lock().values() {
+            manager.announce(&node);
+        }
+    }
+
+    /// Returns the placement requested by the config while the hook ran.
+    pub fn run_new_window_hook(&self, node: Rc<dyn ToplevelNode>) -> WindowRules {
+        if self.new_window_sent.replace(true) {
+            return WindowRules::default();
+        }
+        let config = match self.state.config.get() {
+            Some(config) => config,
+            _ => return WindowRules::default(),
+        };
+        self.state
+            .toplevels
+            .set(node.node_id(), Rc::downgrade(&node));
+        *self.pending_rules.borrow_mut() = Some(WindowRules::default());
+        config.new_window(node.node_id());
+        self.state.toplevels.remove(&node.node_id());
+        self.pending_rules.take().unwrap_or_default()
+    }
+
+    fn unpublish(&self, node: &dyn Node) {
+        self.state.toplevels.remove(&node.node_id());
+        for (_, handle) in self.foreign_handles.lock().drain() {
+            handle.send_closed();
+        }
+    }
+
+    pub fn set_app_id(&self, app_id: &str) {
+        let mut current = self.app_id.borrow_mut();
+        if *current == app_id {
+            return;
+        }
+        *current = app_id.to_string();
+        for handle in self.foreign_handles.lock().values() {
+            handle.send_app_id(app_id);
+            handle.send_done();
+        }
+    }
+
+    fn fullscreen_changed(&self, node: &dyn ToplevelNode) {
+        let id = node.node_id();
+        if self.state.toplevels.contains(&id) {
+            if let Some(config) = self.state.config.get() {
+                config.window_fullscreen_changed(id, self.is_fullscreen.get());
+            }
+        }
+    }
+
+    /// Returns whether the toplevel is hidden in the scratchpad.
+    pub fn is_hidden(&self) -> bool {
+        match (self.workspace.get(), self.state.scratchpad.get()) {
+            (Some(ws), Some(scratchpad)) => ws.id == scratchpad.id,
+            _ => false,
+        }
+    }
+
+    pub fn send_foreign_state(&self) {
+        for handle in self.foreign_handles.lock().values() {
+            handle.send_state(self);
+            handle.send_done();
         }
     }
 
@@ -221,6 +366,7 @@ impl ToplevelData {
         self.workspace.take();
         self.seat_state.destroy_node(node);
         self.focus_node.clear();
+        self.unpublish(node);
     }
 
     pub fn set_fullscreen(
@@ -277,7 +423,10 @@ impl ToplevelData {
         });
         drop(data);
         self.is_fullscreen.set(true);
+        self.send_foreign_state();
         ws.fullscreen.set(Some(node.clone()));
+        ws.output.get().update_vrr();
+        ws.output.get().update_tearing();
         node.tl_set_parent(ws.clone());
         node.clone().tl_set_workspace(ws);
         node.clone()
@@ -287,6 +436,7 @@ impl ToplevelData {
                 .tl_into_node()
                 .node_do_focus(&seat, Direction::Unspecified);
         }
+        self.fullscreen_changed(node.deref());
     }
 
     pub fn unset_fullscreen(&self, state: &Rc<State>, node: Rc<dyn ToplevelNode>) {
@@ -294,6 +444,13 @@ impl ToplevelData {
             log::warn!("Cannot unset fullscreen on a node that is not fullscreen");
             return;
         }
+        self.unset_fullscreen2(state, node.clone());
+        if !self.is_fullscreen.get() {
+            self.fullscreen_changed(node.deref());
+        }
+    }
+
+    fn unset_fullscreen2(&self, state: &Rc<State>, node: Rc<dyn ToplevelNode>) {
         let fd = match self.fullscrceen_data.borrow_mut().take() {
             Some(fd) => fd,
             _ => {
@@ -302,6 +459,7 @@ impl ToplevelData {
             }
         };
         self.is_fullscreen.set(false);
+        self.send_foreign_state();
         match fd.workspace.fullscreen.get() {
             None => {
                 log::error!("Node is supposed to be fullscreened on a workspace but workspace has not fullscreen node.");
@@ -314,6 +472,8 @@ impl ToplevelData {
             _ => {}
         }
         fd.workspace.fullscreen.take();
+        fd.workspace.output.get().update_vrr();
+        fd.workspace.output.get().update_tearing();
         if node.node_visible() {
             fd.workspace.set_visible(true);
             fd.workspace.flush_jay_workspaces();
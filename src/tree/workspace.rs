@@ -84,6 +84,32 @@ impl WorkspaceNode {
         self.stacked.is_empty() && self.fullscreen.get().is_none() && self.container.get().is_none()
     }
 
+    pub fn is_urgent(&self) -> bool {
+        if let Some(container) = self.container.get() {
+            if container.tl_data().urgent.get() {
+                return true;
+            }
+        }
+        if let Some(fs) = self.fullscreen.get() {
+            if fs.tl_data().urgent.get() {
+                return true;
+            }
+        }
+        for stacked in self.stacked.iter() {
+            let float = stacked
+                .deref()
+                .clone()
+                .stacked_into_node()
+                .node_into_float();
+            if let Some(child) = float.and_then(|f| f.child.get()) {
+                if child.tl_data().urgent.get() {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
     pub fn stacked_visible(&self) -> bool {
         self.visible.get() && self.fullscreen.get().is_none()
     }
@@ -99,6 +125,7 @@ impl WorkspaceNode {
         for jw in self.jay_workspaces.lock().values() {
             jw.send_done();
         }
+        self.output.get().state.workspaces_changed();
     }
 
     pub fn set_visible(&self, visible: bool) {
@@ -222,6 +249,8 @@ impl ContainingNode for WorkspaceNode {
         if let Some(fs) = self.fullscreen.get() {
             if fs.tl_as_node().node_id() == child.node_id() {
                 self.fullscreen.set(None);
+                self.output.get().update_vrr();
+                self.output.get().update_tearing();
                 return;
             }
         }
@@ -231,4 +260,8 @@ impl ContainingNode for WorkspaceNode {
     fn cnode_accepts_child(&self, node: &dyn Node) -> bool {
         node.node_is_container()
     }
+
+    fn cnode_child_urgent_changed(self: Rc<Self>, _child: &dyn Node, _urgent: bool) {
+        self.output.get().state.workspaces_changed();
+    }
 }
@@ -40,8 +40,8 @@ use crate::{
     },
 };
 pub use sys::{
-    drm_mode_modeinfo, DRM_CLIENT_CAP_ATOMIC, DRM_MODE_ATOMIC_ALLOW_MODESET,
-    DRM_MODE_ATOMIC_NONBLOCK, DRM_MODE_PAGE_FLIP_EVENT,
+    drm_color_lut, drm_mode_modeinfo, DRM_CLIENT_CAP_ATOMIC, DRM_MODE_ATOMIC_ALLOW_MODESET,
+    DRM_MODE_ATOMIC_NONBLOCK, DRM_MODE_PAGE_FLIP_ASYNC, DRM_MODE_PAGE_FLIP_EVENT,
 };
 
 #[derive(Debug, Error)]
@@ -277,7 +277,7 @@ impl DrmMaster {
         res
     }
 
-    pub fn create_blob<T>(self: &Rc<Self>, t: &T) -> Result<PropBlob, DrmError> {
+    pub fn create_blob<T: ?Sized>(self: &Rc<Self>, t: &T) -> Result<PropBlob, DrmError> {
         match mode_create_blob(self.raw(), t) {
             Ok(b) => Ok(PropBlob {
                 master: self.clone(),
@@ -687,7 +687,6 @@ pub struct ObjectChange<'a> {
 }
 
 impl Change {
-    #[allow(dead_code)]
     pub fn test(&self, flags: u32) -> Result<(), DrmError> {
         mode_atomic(
             self.master.raw(),
@@ -674,6 +674,17 @@ pub fn mode_getencoder(fd: c::c_int, encoder_id: u32) -> Result<DrmEncoderInfo,
 
 pub const DRM_DISPLAY_MODE_LEN: usize = 32;
 
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct drm_color_lut {
+    pub red: u16,
+    pub green: u16,
+    pub blue: u16,
+    pub reserved: u16,
+}
+
+unsafe impl Pod for drm_color_lut {}
+
 #[repr(C)]
 pub struct drm_mode_modeinfo {
     pub clock: u32,
@@ -846,6 +857,7 @@ struct drm_mode_atomic {
 const DRM_IOCTL_MODE_ATOMIC: u64 = drm_iowr::<drm_mode_atomic>(0xbc);
 
 pub const DRM_MODE_PAGE_FLIP_EVENT: u32 = 0x01;
+pub const DRM_MODE_PAGE_FLIP_ASYNC: u32 = 0x02;
 pub const DRM_MODE_ATOMIC_TEST_ONLY: u32 = 0x0100;
 pub const DRM_MODE_ATOMIC_NONBLOCK: u32 = 0x0200;
 pub const DRM_MODE_ATOMIC_ALLOW_MODESET: u32 = 0x0400;
@@ -898,9 +910,9 @@ struct drm_mode_create_blob {
 
 const DRM_IOCTL_MODE_CREATEPROPBLOB: u64 = drm_iowr::<drm_mode_create_blob>(0xbd);
 
-pub fn mode_create_blob<T>(fd: c::c_int, t: &T) -> Result<DrmBlob, OsError> {
+pub fn mode_create_blob<T: ?Sized>(fd: c::c_int, t: &T) -> Result<DrmBlob, OsError> {
     let mut res = drm_mode_create_blob {
-        data: t as *const T as _,
+        data: t as *const T as *const u8 as _,
         length: mem::size_of_val(t) as _,
         blob_id: 0,
     };
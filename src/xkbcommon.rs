@@ -106,6 +106,15 @@ extern "C" {
         key: u32,
         direction: xkb_key_direction,
     ) -> xkb_state_component;
+    fn xkb_state_update_mask(
+        state: *mut xkb_state,
+        depressed_mods: u32,
+        latched_mods: u32,
+        locked_mods: u32,
+        depressed_layout: u32,
+        latched_layout: u32,
+        locked_layout: u32,
+    ) -> xkb_state_component;
     #[allow(dead_code)]
     fn xkb_state_serialize_mods(state: *mut xkb_state, components: xkb_state_component) -> u32;
     #[allow(dead_code)]
@@ -158,11 +167,15 @@ impl XkbContext {
     }
 
     pub fn keymap_from_str(&self, s: &str) -> Result<Rc<XkbKeymap>, XkbCommonError> {
+        self.keymap_from_buffer(s.as_bytes())
+    }
+
+    pub fn keymap_from_buffer(&self, buf: &[u8]) -> Result<Rc<XkbKeymap>, XkbCommonError> {
         unsafe {
             let keymap = xkb_keymap_new_from_buffer(
                 self.context,
-                s.as_bytes().as_ptr(),
-                s.len(),
+                buf.as_ptr(),
+                buf.len(),
                 XKB_KEYMAP_FORMAT_TEXT_V1.raw(),
                 0,
             );
@@ -250,14 +263,43 @@ pub struct XkbState {
 }
 
 impl XkbState {
+    pub fn map(&self) -> &Rc<XkbKeymap> {
+        &self.map
+    }
+
     pub fn mods(&self) -> ModifierState {
         self.mods
     }
 
     #[allow(dead_code)]
     pub fn update(&mut self, key: u32, direction: XkbKeyDirection) -> Option<ModifierState> {
+        let changes = unsafe { xkb_state_update_key(self.state, key + 8, direction.raw() as _) };
+        self.apply_changes(changes)
+    }
+
+    pub fn set_mods(
+        &mut self,
+        mods_depressed: u32,
+        mods_latched: u32,
+        mods_locked: u32,
+        group: u32,
+    ) -> Option<ModifierState> {
+        let changes = unsafe {
+            xkb_state_update_mask(
+                self.state,
+                mods_depressed,
+                mods_latched,
+                mods_locked,
+                0,
+                0,
+                group,
+            )
+        };
+        self.apply_changes(changes)
+    }
+
+    fn apply_changes(&mut self, changes: xkb_state_component) -> Option<ModifierState> {
         unsafe {
-            let changes = xkb_state_update_key(self.state, key + 8, direction.raw() as _);
             if changes != 0 {
                 self.mods.mods_depressed =
                     xkb_state_serialize_mods(self.state, XKB_STATE_MODS_DEPRESSED.raw() as _);
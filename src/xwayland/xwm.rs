@@ -610,6 +610,11 @@ impl Wm {
             }
             XWaylandEvent::ActivateRoot => self.activate_window(None, Initiator::Wayland).await,
             XWaylandEvent::Close(window) => self.close_window(&window).await,
+            XWaylandEvent::SetMinimized(window, minimized) => {
+                if !window.destroyed.get() {
+                    self.set_minimized(&window, minimized).await;
+                }
+            }
             XWaylandEvent::SeatChanged => self.seats_changed(),
             XWaylandEvent::PrimarySelectionCancelSource(src) => {
                 self.dd_cancel_source(&self.shared.clone().primary_selection, &src)
@@ -1074,7 +1079,13 @@ impl Wm {
         }
         let mut iter = buf.split(|c| *c == 0);
         *data.info.instance.borrow_mut() = Some(iter.next().unwrap_or(&[]).to_vec().into());
-        *data.info.class.borrow_mut() = Some(iter.next().unwrap_or(&[]).to_vec().into());
+        let class = iter.next().unwrap_or(&[]);
+        if let Some(window) = data.window.get() {
+            window
+                .toplevel_data
+                .set_app_id(&class.as_bstr().to_string());
+        }
+        *data.info.class.borrow_mut() = Some(class.to_vec().into());
     }
 
     async fn load_window_wm_name2(&self, data: &Rc<XwindowData>, prop: u32, name: &str) {
@@ -2223,7 +2234,7 @@ impl Wm {
             _ => return Ok(()),
         };
         let minimize = match event.data[0] {
-            ICCCM_WM_STATE_NORMAL => false,
+            ICCCM_WM_STATE_NORMAL => self.handle_unminimize_requested(data).await,
             ICCCM_WM_STATE_ICONIC => self.handle_minimize_requested(data).await,
             _ => return Ok(()),
         };
@@ -2232,13 +2243,23 @@ impl Wm {
         Ok(())
     }
 
-    async fn handle_minimize_requested(&self, data: &Rc<XwindowData>) -> bool {
+    async fn handle_unminimize_requested(&self, data: &Rc<XwindowData>) -> bool {
         if let Some(w) = data.window.get() {
-            if w.toplevel_data.active_surfaces.get() > 0 {
-                self.set_wm_state(data, ICCCM_WM_STATE_NORMAL).await;
-                return false;
+            if self.state.is_hidden(&*w) {
+                match self.state.seat_queue.last() {
+                    Some(seat) => self.state.show_hidden_toplevel(&seat, w),
+                    _ => return true,
+                }
             }
         }
+        self.set_wm_state(data, ICCCM_WM_STATE_NORMAL).await;
+        false
+    }
+
+    async fn handle_minimize_requested(&self, data: &Rc<XwindowData>) -> bool {
+        if let Some(w) = data.window.get() {
+            self.state.hide_toplevel(w);
+        }
         self.set_wm_state(data, ICCCM_WM_STATE_ICONIC).await;
         true
     }
@@ -2340,9 +2361,10 @@ impl Wm {
             return Ok(());
         }
         if minimized != data.info.minimized.get() {
-            if minimized {
-                minimized = self.handle_minimize_requested(data).await;
-            }
+            minimized = match minimized {
+                true => self.handle_minimize_requested(data).await,
+                false => self.handle_unminimize_requested(data).await,
+            };
         }
         if fullscreen != data.info.fullscreen.get() {
             if let Some(w) = data.window.get() {